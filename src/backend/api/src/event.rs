@@ -0,0 +1,50 @@
+use candid::{CandidType, Deserialize};
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct Event {
+    pub id: String,
+    pub name: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub active: bool,
+    /// Whether the players can spin the wheel for themselves with the event's self-spin code.
+    pub has_self_spin_code: bool,
+    /// How many self-spins the event code allows in total, not counting the failed extractions.
+    pub self_spin_code_max_spins: Option<u32>,
+    /// The event code is disabled once this many wrong self-spin codes have been submitted
+    /// during the event.
+    pub self_spin_max_failed_attempts: u32,
+    /// The wrong self-spin codes submitted during the event since its code was set.
+    pub self_spin_failed_attempts: u32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct CreateEventRequest {
+    pub name: String,
+    /// RFC 3339 date time, e.g. `2025-03-01T18:00:00+00:00`.
+    pub start_date: String,
+    /// RFC 3339 date time, e.g. `2025-03-01T22:00:00+00:00`.
+    pub end_date: String,
+}
+
+pub type CreateEventResponse = Event;
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct UpdateEventRequest {
+    pub id: String,
+    pub name: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct DeleteEventRequest {
+    pub id: String,
+}
+
+pub type ListEventsResponse = Vec<Event>;
+
+pub type GetActiveEventResponse = Option<Event>;
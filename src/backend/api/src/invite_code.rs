@@ -0,0 +1,88 @@
+use candid::{CandidType, Deserialize, Principal};
+
+#[derive(Debug, Clone, Copy, CandidType, Deserialize, PartialEq, Eq)]
+pub enum InviteCodePurpose {
+    /// Proves that the winner attended, when an admin or a scanner extracts them.
+    #[serde(rename = "attendance")]
+    Attendance,
+    /// Lets a player spin the wheel for themselves.
+    #[serde(rename = "self_spin")]
+    SelfSpin,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub enum InviteCodeState {
+    #[serde(rename = "available")]
+    Available,
+    #[serde(rename = "used")]
+    Used {
+        principal: Principal,
+        wheel_prize_extraction_id: String,
+        used_at: String,
+    },
+    #[serde(rename = "revoked")]
+    Revoked {
+        revoked_at: String,
+        revoked_by_user_id: String,
+    },
+}
+
+/// A single-use invite code. The code itself is stored hashed, so it can't be retrieved.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct InviteCode {
+    pub id: String,
+    /// If set, only this principal can use the code.
+    pub principal: Option<Principal>,
+    /// If set, the code can be used only during this event.
+    pub event_id: Option<String>,
+    /// The codes issued before the purposes were introduced don't have it,
+    /// and can be used for any purpose.
+    pub purpose: Option<InviteCodePurpose>,
+    pub state: InviteCodeState,
+    pub created_by_user_id: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct GenerateInviteCodesRequest {
+    /// Between 1 and 1000.
+    pub count: u32,
+    /// If set, the codes can be used only during this event.
+    pub event_id: Option<String>,
+    /// If set, only this principal can use the codes.
+    pub principal: Option<Principal>,
+    /// Defaults to attendance.
+    pub purpose: Option<InviteCodePurpose>,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct GeneratedInviteCode {
+    pub invite_code_id: String,
+    pub invite_code: String,
+}
+
+/// The codes to hand out. This is the only time the codes are returned:
+/// they're not stored, so they can't be retrieved again.
+pub type GenerateInviteCodesResponse = Vec<GeneratedInviteCode>;
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct ListInviteCodesRequest {
+    /// If set, only the codes of this event are listed.
+    pub event_id: Option<String>,
+    /// If set, only the codes issued for this purpose are listed.
+    pub purpose: Option<InviteCodePurpose>,
+}
+
+/// Newest first.
+pub type ListInviteCodesResponse = Vec<InviteCode>;
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct RevokeInviteCodeRequest {
+    pub invite_code_id: String,
+}
+
+/// An audit listing of the invite codes as CSV, with a header row, newest first.
+/// The codes themselves are not included, as they're stored hashed:
+/// they're returned only once, by `generate_invite_codes`.
+pub type ExportInviteCodesResponse = String;
@@ -1,13 +1,23 @@
 mod custom_domain_record;
+mod event;
+mod invite_code;
 mod result;
+mod self_spin;
 mod user_profile;
 mod wallet;
 mod wheel_asset;
+mod wheel_prize_draw;
 mod wheel_prize_extraction;
+mod wheel_prize_extraction_policy;
 
 pub use custom_domain_record::*;
+pub use event::*;
+pub use invite_code::*;
 pub use result::*;
+pub use self_spin::*;
 pub use user_profile::*;
 pub use wallet::*;
 pub use wheel_asset::*;
+pub use wheel_prize_draw::*;
 pub use wheel_prize_extraction::*;
+pub use wheel_prize_extraction_policy::*;
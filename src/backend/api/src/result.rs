@@ -1,4 +1,4 @@
-use candid::{CandidType, Deserialize};
+use candid::{CandidType, Deserialize, Nat, Principal};
 use std::fmt::Display;
 
 #[derive(Debug, CandidType, Deserialize)]
@@ -14,6 +14,142 @@ pub enum ApiResult<T = ()> {
 pub struct ApiError {
     code: u16,
     message: String,
+    /// The structured details of the error, for the errors that clients
+    /// or the canister itself need to handle programmatically.
+    kind: Option<ApiErrorKind>,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    /// The ledger executed the call and refused the transfer,
+    /// so the transfer has not been executed.
+    #[serde(rename = "ledger_transfer_rejected")]
+    LedgerTransferRejected {
+        ledger_canister_id: Principal,
+        rejection: LedgerTransferRejection,
+    },
+    /// The call to the ledger failed, so the transfer may or may not have been executed.
+    #[serde(rename = "ledger_call_failed")]
+    LedgerCallFailed { ledger_canister_id: Principal },
+    /// The wheel prize extraction policy doesn't allow extracting the principal.
+    /// `retry_after_seconds` is set if the principal can be extracted again later.
+    #[serde(rename = "extraction_policy_rejected")]
+    ExtractionPolicyRejected {
+        rejection: ExtractionPolicyRejection,
+        retry_after_seconds: Option<u64>,
+    },
+}
+
+/// The rules of the wheel prize extraction policy that can prevent a principal from being extracted.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub enum ExtractionPolicyRejection {
+    #[serde(rename = "principal_denied")]
+    PrincipalDenied,
+    #[serde(rename = "principal_not_allowed")]
+    PrincipalNotAllowed,
+    #[serde(rename = "max_wins_per_principal")]
+    MaxWinsPerPrincipal { max_wins: u32 },
+    #[serde(rename = "max_wins_per_principal_per_day")]
+    MaxWinsPerPrincipalPerDay { max_wins: u32 },
+    #[serde(rename = "max_wins_per_day")]
+    MaxWinsPerDay { max_wins: u32 },
+    #[serde(rename = "spin_cooldown")]
+    SpinCooldown,
+    #[serde(rename = "failed_spin_cooldown")]
+    FailedSpinCooldown,
+}
+
+impl Display for ExtractionPolicyRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtractionPolicyRejection::PrincipalDenied => {
+                write!(f, "the principal is denied from being extracted")
+            }
+            ExtractionPolicyRejection::PrincipalNotAllowed => {
+                write!(f, "the principal is not allowed to be extracted")
+            }
+            ExtractionPolicyRejection::MaxWinsPerPrincipal { max_wins } => write!(
+                f,
+                "the principal has already been extracted the maximum number of times ({})",
+                max_wins
+            ),
+            ExtractionPolicyRejection::MaxWinsPerPrincipalPerDay { max_wins } => write!(
+                f,
+                "the principal has already been extracted the maximum number of times today ({})",
+                max_wins
+            ),
+            ExtractionPolicyRejection::MaxWinsPerDay { max_wins } => write!(
+                f,
+                "the maximum number of extractions for today ({}) has been reached",
+                max_wins
+            ),
+            ExtractionPolicyRejection::SpinCooldown => {
+                write!(f, "the principal has been extracted too recently")
+            }
+            ExtractionPolicyRejection::FailedSpinCooldown => {
+                write!(
+                    f,
+                    "the last extraction of the principal failed too recently"
+                )
+            }
+        }
+    }
+}
+
+/// The reasons why a ledger refuses an ICRC-1 or ICRC-2 transfer,
+/// except for duplicates which are handled as successful transfers.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub enum LedgerTransferRejection {
+    #[serde(rename = "bad_fee")]
+    BadFee { expected_fee: Nat },
+    #[serde(rename = "bad_burn")]
+    BadBurn { min_burn_amount: Nat },
+    #[serde(rename = "insufficient_funds")]
+    InsufficientFunds { balance: Nat },
+    #[serde(rename = "insufficient_allowance")]
+    InsufficientAllowance { allowance: Nat },
+    #[serde(rename = "too_old")]
+    TooOld,
+    #[serde(rename = "created_in_future")]
+    CreatedInFuture { ledger_time: u64 },
+    #[serde(rename = "temporarily_unavailable")]
+    TemporarilyUnavailable,
+    #[serde(rename = "generic_error")]
+    GenericError { error_code: Nat, message: String },
+}
+
+impl Display for LedgerTransferRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LedgerTransferRejection::BadFee { expected_fee } => {
+                write!(f, "bad fee, expected fee: {}", expected_fee)
+            }
+            LedgerTransferRejection::BadBurn { min_burn_amount } => {
+                write!(f, "bad burn, minimum burn amount: {}", min_burn_amount)
+            }
+            LedgerTransferRejection::InsufficientFunds { balance } => {
+                write!(f, "insufficient funds, balance: {}", balance)
+            }
+            LedgerTransferRejection::InsufficientAllowance { allowance } => {
+                write!(f, "insufficient allowance, allowance: {}", allowance)
+            }
+            LedgerTransferRejection::TooOld => write!(f, "transaction too old"),
+            LedgerTransferRejection::CreatedInFuture { ledger_time } => {
+                write!(
+                    f,
+                    "transaction created in the future, ledger time: {}",
+                    ledger_time
+                )
+            }
+            LedgerTransferRejection::TemporarilyUnavailable => {
+                write!(f, "ledger temporarily unavailable")
+            }
+            LedgerTransferRejection::GenericError {
+                error_code,
+                message,
+            } => write!(f, "error {}: {}", error_code, message),
+        }
+    }
 }
 
 impl Display for ApiError {
@@ -27,6 +163,7 @@ impl ApiError {
         Self {
             code: 400,
             message: message.into(),
+            kind: None,
         }
     }
 
@@ -34,6 +171,7 @@ impl ApiError {
         Self {
             code: 401,
             message: "Anonymous principals are not allowed to call this endpoint".to_string(),
+            kind: None,
         }
     }
 
@@ -41,6 +179,7 @@ impl ApiError {
         Self {
             code: 403,
             message: message.into(),
+            kind: None,
         }
     }
 
@@ -48,6 +187,7 @@ impl ApiError {
         Self {
             code: 404,
             message: message.into(),
+            kind: None,
         }
     }
 
@@ -55,6 +195,15 @@ impl ApiError {
         Self {
             code: 409,
             message: message.into(),
+            kind: None,
+        }
+    }
+
+    pub fn too_many_requests(message: &str) -> Self {
+        Self {
+            code: 429,
+            message: message.into(),
+            kind: None,
         }
     }
 
@@ -62,14 +211,124 @@ impl ApiError {
         Self {
             code: 500,
             message: message.into(),
+            kind: None,
+        }
+    }
+
+    pub fn ledger_transfer_rejected(
+        ledger_canister_id: Principal,
+        rejection: LedgerTransferRejection,
+    ) -> Self {
+        let code = match rejection {
+            LedgerTransferRejection::TemporarilyUnavailable => 503,
+            LedgerTransferRejection::GenericError { .. } => 500,
+            _ => 409,
+        };
+        Self {
+            code,
+            message: format!("Transfer failed: {rejection}"),
+            kind: Some(ApiErrorKind::LedgerTransferRejected {
+                ledger_canister_id,
+                rejection,
+            }),
+        }
+    }
+
+    pub fn ledger_call_failed(ledger_canister_id: Principal, message: &str) -> Self {
+        Self {
+            code: 503,
+            message: format!("Call to ledger canister failed: {message}"),
+            kind: Some(ApiErrorKind::LedgerCallFailed { ledger_canister_id }),
+        }
+    }
+
+    pub fn extraction_policy_rejected(
+        rejection: ExtractionPolicyRejection,
+        retry_after_seconds: Option<u64>,
+    ) -> Self {
+        let code = match rejection {
+            ExtractionPolicyRejection::PrincipalDenied
+            | ExtractionPolicyRejection::PrincipalNotAllowed => 403,
+            ExtractionPolicyRejection::MaxWinsPerPrincipal { .. } => 409,
+            _ => 429,
+        };
+        let message = match retry_after_seconds {
+            Some(seconds) => {
+                format!("Extraction not allowed: {rejection}, retry after {seconds} seconds")
+            }
+            None => format!("Extraction not allowed: {rejection}"),
+        };
+        Self {
+            code,
+            message,
+            kind: Some(ApiErrorKind::ExtractionPolicyRejected {
+                rejection,
+                retry_after_seconds,
+            }),
         }
     }
 }
 
 impl ApiError {
+    pub fn code(&self) -> u16 {
+        self.code
+    }
+
     pub fn message(&self) -> &str {
         &self.message
     }
+
+    pub fn kind(&self) -> Option<&ApiErrorKind> {
+        self.kind.as_ref()
+    }
+
+    /// Whether the ledger refused the transfer, which means that it has not been executed.
+    pub fn is_ledger_transfer_rejected(&self) -> bool {
+        matches!(self.kind, Some(ApiErrorKind::LedgerTransferRejected { .. }))
+    }
+
+    /// Whether the outcome of the call to the ledger is unknown,
+    /// so that the transfer may or may not have been executed.
+    pub fn is_ledger_call_failed(&self) -> bool {
+        matches!(self.kind, Some(ApiErrorKind::LedgerCallFailed { .. }))
+    }
+
+    /// Whether the ledger refused the transfer because its `created_at_time` is too old,
+    /// so that the ledger can't tell anymore whether it's a duplicate.
+    pub fn is_ledger_transfer_too_old(&self) -> bool {
+        matches!(
+            self.kind,
+            Some(ApiErrorKind::LedgerTransferRejected {
+                rejection: LedgerTransferRejection::TooOld,
+                ..
+            })
+        )
+    }
+
+    /// Whether the ledger couldn't be reached or couldn't process the transfer for now,
+    /// so that the transfer can be retried later with the same deduplication parameters.
+    pub fn is_ledger_unavailable(&self) -> bool {
+        matches!(
+            self.kind,
+            Some(ApiErrorKind::LedgerCallFailed { .. })
+                | Some(ApiErrorKind::LedgerTransferRejected {
+                    rejection: LedgerTransferRejection::TemporarilyUnavailable,
+                    ..
+                })
+        )
+    }
+
+    /// Whether the account the transfer is paid from doesn't have enough funds or allowance.
+    pub fn is_insufficient_funds(&self) -> bool {
+        matches!(
+            self.kind,
+            Some(ApiErrorKind::LedgerTransferRejected {
+                rejection: LedgerTransferRejection::InsufficientFunds { .. }
+                    | LedgerTransferRejection::InsufficientAllowance { .. },
+                ..
+            })
+        )
+    }
 }
 
 impl<T> From<Result<T, ApiError>> for ApiResult<T> {
@@ -0,0 +1,31 @@
+use candid::{CandidType, Deserialize, Principal};
+
+/// Issues an invite code with which a player can spin the wheel for themselves once.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct CreateSelfSpinInviteRequest {
+    /// If set, only this principal can use the code.
+    pub principal: Option<Principal>,
+    /// If set, the code can be used only during this event.
+    pub event_id: Option<String>,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct CreateSelfSpinInviteResponse {
+    pub invite_code_id: String,
+    /// The code to give to the player. It's not stored, so it can't be retrieved again.
+    pub invite_code: String,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct SetEventSelfSpinCodeRequest {
+    pub event_id: String,
+    /// The code with which each player can spin the wheel for themselves once during the event.
+    /// At least 6 characters, ignoring case, spaces and dashes.
+    /// `None` disables the self-spin with the event code.
+    pub self_spin_code: Option<String>,
+    /// How many self-spins the code allows in total, by all the players. Required with a code.
+    pub max_spins: Option<u32>,
+    /// How many wrong self-spin codes can be submitted during the event, by all the players,
+    /// before the code is disabled. Defaults to 100.
+    pub max_failed_attempts: Option<u32>,
+}
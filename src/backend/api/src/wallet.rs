@@ -2,21 +2,195 @@ use std::fmt;
 
 use candid::{CandidType, Deserialize, Nat, Principal};
 
+use crate::ApiError;
+
+/// An ICRC-1 account.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct Account {
+    pub owner: Principal,
+    /// Must be 32 bytes long, if provided.
+    pub subaccount: Option<Vec<u8>>,
+}
+
+/// The account tokens are sent to.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub enum RecipientAccount {
+    #[serde(rename = "account")]
+    Account(Account),
+    /// The ICRC-1 textual encoding of the account,
+    /// e.g. `<principal>` or `<principal>-<checksum>.<subaccount>`.
+    #[serde(rename = "text")]
+    Text(String),
+}
+
+impl fmt::Display for RecipientAccount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecipientAccount::Account(account) => {
+                write!(f, "{}", account.owner)?;
+                if let Some(subaccount) = &account.subaccount {
+                    write!(f, ", subaccount: ")?;
+                    for byte in subaccount {
+                        write!(f, "{:02x}", byte)?;
+                    }
+                }
+                Ok(())
+            }
+            RecipientAccount::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// Exactly one of `to` and `to_account` must be provided.
 #[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
 pub struct TransferTokenRequest {
     pub ledger_canister_id: Principal,
-    pub to: Principal,
+    /// The owner of the default account to send the tokens to.
+    pub to: Option<Principal>,
+    pub to_account: Option<RecipientAccount>,
     pub amount: Nat,
+    /// Why the transfer is needed, kept in the audit trail. Required.
+    pub reason: Option<String>,
+    /// The subaccount of the canister to send the tokens from, e.g. the one of a token asset.
+    /// Defaults to the canister's default account. Must be 32 bytes long, if provided.
+    pub from_subaccount: Option<Vec<u8>>,
 }
 
 impl fmt::Display for TransferTokenRequest {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let to = match (&self.to, &self.to_account) {
+            (_, Some(to_account)) => to_account.to_string(),
+            (Some(to), None) => to.to_string(),
+            (None, None) => String::new(),
+        };
         write!(
             f,
-            "Amount: {}, To: {}, Ledger canister id: {}",
-            self.amount, self.to, self.ledger_canister_id
+            "Amount: {}, To: {}, Ledger canister id: {}, Reason: {}",
+            self.amount,
+            to,
+            self.ledger_canister_id,
+            self.reason.as_deref().unwrap_or_default()
         )
     }
 }
 
-pub type TransferTokenResponse = Nat;
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub enum TransferTokenResponse {
+    #[serde(rename = "completed")]
+    Completed { block_index: Nat },
+    /// The amount is above the approval threshold of the ledger,
+    /// so the transfer has been stored until a second admin approves it.
+    #[serde(rename = "pending_approval")]
+    PendingApproval { manual_transfer_id: String },
+}
+
+/// An outgoing transfer executed by a ledger.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct LedgerTransfer {
+    pub id: String,
+    pub ledger_canister_id: Principal,
+    pub from: Account,
+    pub to: Account,
+    pub amount: Nat,
+    /// The fee set in the transfer, if the ledger's fee was not used.
+    pub fee: Option<Nat>,
+    pub memo: Option<Vec<u8>>,
+    pub block_index: Nat,
+    pub initiated_by_user_id: Option<String>,
+    pub wheel_prize_extraction_id: Option<String>,
+    pub wheel_asset_id: Option<String>,
+    pub created_at: String,
+}
+
+/// All the provided filters must match.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct ListLedgerTransfersRequest {
+    pub ledger_canister_id: Option<Principal>,
+    /// The owner of either the source or the destination account.
+    pub principal: Option<Principal>,
+    pub wheel_prize_extraction_id: Option<String>,
+    /// The id of the last transfer of the previous page.
+    pub start_after: Option<String>,
+    /// Defaults to 100, and can't be more than 1000.
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct ListLedgerTransfersResponse {
+    /// Oldest first.
+    pub transfers: Vec<LedgerTransfer>,
+    /// The `start_after` of the next page, `None` if there are no more transfers.
+    pub next_start_after: Option<String>,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub enum ManualTransferState {
+    #[serde(rename = "pending_approval")]
+    PendingApproval,
+    #[serde(rename = "processing")]
+    Processing,
+    #[serde(rename = "completed")]
+    Completed { block_index: Nat },
+    #[serde(rename = "failed")]
+    Failed { error: ApiError },
+    #[serde(rename = "denied")]
+    Denied { error: ApiError },
+    #[serde(rename = "rejected")]
+    Rejected { rejected_by_user_id: String },
+}
+
+/// A transfer requested by an admin with `transfer_token`.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct ManualTransfer {
+    pub id: String,
+    pub ledger_canister_id: Principal,
+    pub from_subaccount: Option<Vec<u8>>,
+    pub to: Principal,
+    pub to_subaccount: Option<Vec<u8>>,
+    pub amount: Nat,
+    pub reason: String,
+    /// The `created_at_time` sent to the ledger, set before the transfer is executed.
+    pub created_at_time: Option<u64>,
+    pub requested_by_user_id: String,
+    pub approved_by_user_id: Option<String>,
+    pub state: ManualTransferState,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Newest first.
+pub type ListManualTransfersResponse = Vec<ManualTransfer>;
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct ApproveManualTransferRequest {
+    pub manual_transfer_id: String,
+}
+
+pub type ApproveManualTransferResponse = Nat;
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct RejectManualTransferRequest {
+    pub manual_transfer_id: String,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct ManualTransferLimit {
+    pub ledger_canister_id: Principal,
+    /// The maximum amount that can be transferred per UTC day, in token base units.
+    pub daily_limit: Option<Nat>,
+    /// The transfers above this amount must be approved by a second admin.
+    pub approval_threshold: Option<Nat>,
+    pub updated_at: String,
+}
+
+/// Replaces the limits of the ledger, empty fields remove the corresponding limit.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct SetManualTransferLimitRequest {
+    pub ledger_canister_id: Principal,
+    pub daily_limit: Option<Nat>,
+    pub approval_threshold: Option<Nat>,
+}
+
+pub type SetManualTransferLimitResponse = ManualTransferLimit;
+
+pub type ListManualTransferLimitsResponse = Vec<ManualTransferLimit>;
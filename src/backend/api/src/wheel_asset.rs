@@ -1,4 +1,6 @@
-use candid::{CandidType, Deserialize, Principal};
+use candid::{CandidType, Deserialize, Nat, Principal};
+
+use crate::{Account, ApiError};
 
 #[derive(Debug, Clone, CandidType, Deserialize)]
 pub enum WheelAssetState {
@@ -20,29 +22,108 @@ pub struct WheelAssetTokenBalance {
     pub last_fetched_at: String,
 }
 
+/// The consecutive failures to fetch a token data since the last successful fetch.
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct WheelAssetTokenFetchStatus {
+    pub failed_attempts: u32,
+    pub last_error: ApiError,
+    pub last_failed_at: String,
+}
+
 #[derive(Debug, Clone, CandidType, Deserialize)]
 pub struct WheelAssetTokenLedgerConfig {
     pub ledger_canister_id: Principal,
     pub decimals: u8,
+    /// Fetched from the ledger, `None` until the first fetch.
+    pub symbol: Option<String>,
+    /// Fetched from the ledger, `None` until the first fetch.
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct CreateWheelAssetTokenLedgerConfig {
+    pub ledger_canister_id: Principal,
+    /// If provided, must match the decimals of the ledger.
+    /// The decimals are fetched from the ledger anyway.
+    pub decimals: Option<u8>,
 }
 
+/// Where the prizes of a token asset are paid from.
 #[derive(Debug, Clone, CandidType, Deserialize)]
+pub enum WheelAssetTokenFundingSource {
+    /// The canister's own account, which must be funded by transferring tokens to it.
+    #[serde(rename = "canister_account")]
+    CanisterAccount,
+    /// The sponsor's account, from which the canister pays the prizes
+    /// within the ICRC-2 allowance approved by the sponsor.
+    #[serde(rename = "sponsor_allowance")]
+    SponsorAllowance { sponsor_account: Account },
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize)]
+#[allow(clippy::large_enum_variant)]
 pub enum WheelAssetType {
     #[serde(rename = "token")]
     Token {
         ledger_config: WheelAssetTokenLedgerConfig,
         exchange_rate_symbol: Option<String>,
         usd_price: Option<WheelAssetTokenPrice>,
+        usd_price_fetch_status: Option<WheelAssetTokenFetchStatus>,
+        funding_source: WheelAssetTokenFundingSource,
+        /// The canister's account of the asset, where its funds must be deposited
+        /// or that the sponsor must approve as the spender of the allowance.
+        deposit_account: Account,
+        /// The `deposit_account` in the ICRC-1 textual encoding.
+        deposit_account_text: String,
+        /// The funds available for the prizes: the canister's balance
+        /// or the remaining sponsor's allowance, depending on the funding source.
         balance: Option<WheelAssetTokenBalance>,
+        balance_fetch_status: Option<WheelAssetTokenFetchStatus>,
         available_draws_count: u32,
+        /// Ignored if `prize_token_amount` is set.
         prize_usd_amount: f64,
+        /// The fixed amount of token base units paid per prize, if any.
+        prize_token_amount: Option<u128>,
+        /// The last fetched transfer fee of the ledger, paid on top of each prize.
+        transfer_fee: Option<u128>,
     },
     #[serde(rename = "gadget")]
-    Gadget { article_type: Option<String> },
+    Gadget {
+        article_type: Option<String>,
+        variants: Vec<WheelAssetGadgetVariant>,
+    },
     #[serde(rename = "jackpot")]
     Jackpot { wheel_asset_ids: Vec<String> },
 }
 
+/// A variant of a gadget, e.g. a T-shirt size, with its own stock.
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct WheelAssetGadgetVariant {
+    pub name: String,
+    pub size: Option<String>,
+    pub color: Option<String>,
+    pub total_amount: u32,
+    pub handed_over_amount: u32,
+    pub available_amount: u32,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct WheelAssetGadgetVariantConfig {
+    /// Must be unique within the gadget, used to pick the variant when handing the prize over.
+    pub name: String,
+    pub size: Option<String>,
+    pub color: Option<String>,
+    pub total_amount: u32,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub enum WheelAssetDrawWeight {
+    #[serde(rename = "fixed")]
+    Fixed { weight: u32 },
+    #[serde(rename = "available_quantity")]
+    AvailableQuantity,
+}
+
 #[derive(Debug, CandidType, Deserialize, Clone)]
 pub struct WheelAssetUiSettings {
     pub background_color_hex: String,
@@ -60,18 +141,36 @@ pub struct WheelAsset {
     pub modal_image_path: Option<String>,
     pub state: WheelAssetState,
     pub wheel_ui_settings: WheelAssetUiSettings,
+    pub draw_weight: WheelAssetDrawWeight,
+    pub event_id: Option<String>,
 }
 
 #[derive(Debug, Clone, CandidType, Deserialize)]
 pub enum CreateWheelAssetTypeConfig {
     #[serde(rename = "token")]
     Token {
-        ledger_config: WheelAssetTokenLedgerConfig,
+        ledger_config: CreateWheelAssetTokenLedgerConfig,
         exchange_rate_symbol: Option<String>,
         prize_usd_amount: f64,
+        /// Defaults to the canister account.
+        funding_source: Option<WheelAssetTokenFundingSource>,
+    },
+    /// A token that pays a fixed amount of token base units per prize,
+    /// e.g. exactly 1 ICP. The exchange rate symbol is only used
+    /// to estimate the USD value of the prizes.
+    #[serde(rename = "token_fixed_amount")]
+    TokenFixedAmount {
+        ledger_config: CreateWheelAssetTokenLedgerConfig,
+        exchange_rate_symbol: Option<String>,
+        prize_token_amount: u128,
+        /// Defaults to the canister account.
+        funding_source: Option<WheelAssetTokenFundingSource>,
     },
     #[serde(rename = "gadget")]
-    Gadget { article_type: Option<String> },
+    Gadget {
+        article_type: Option<String>,
+        variants: Option<Vec<WheelAssetGadgetVariantConfig>>,
+    },
     #[serde(rename = "jackpot")]
     Jackpot { wheel_asset_ids: Vec<String> },
 }
@@ -82,6 +181,9 @@ pub struct CreateWheelAssetRequest {
     pub asset_type_config: CreateWheelAssetTypeConfig,
     pub total_amount: u32,
     pub wheel_ui_settings: Option<WheelAssetUiSettings>,
+    pub draw_weight: Option<WheelAssetDrawWeight>,
+    /// The event the asset belongs to. Cannot be changed after creation.
+    pub event_id: Option<String>,
 }
 
 pub type CreateWheelAssetResponse = WheelAsset;
@@ -96,11 +198,21 @@ pub enum UpdateWheelAssetTypeConfig {
     #[serde(rename = "token")]
     Token {
         exchange_rate_symbol: Option<String>,
+        /// Switches the prize to a USD amount.
         prize_usd_amount: Option<f64>,
+        /// Switches the prize to a fixed amount of token base units.
+        /// Cannot be set together with `prize_usd_amount`.
+        prize_token_amount: Option<u128>,
         ledger_config: Option<UpdateWheelAssetTypeLedgerConfig>,
+        funding_source: Option<WheelAssetTokenFundingSource>,
     },
     #[serde(rename = "gadget")]
-    Gadget { article_type: Option<String> },
+    Gadget {
+        article_type: Option<String>,
+        /// Replaces the variants if provided. The variants that keep their name
+        /// keep their handed over amount, which the new total amount can't be lower than.
+        variants: Option<Vec<WheelAssetGadgetVariantConfig>>,
+    },
     #[serde(rename = "jackpot")]
     Jackpot { wheel_asset_ids: Vec<String> },
 }
@@ -114,6 +226,7 @@ pub struct UpdateWheelAssetRequest {
     pub state: Option<WheelAssetState>,
     pub asset_type_config: Option<UpdateWheelAssetTypeConfig>,
     pub wheel_ui_settings: Option<WheelAssetUiSettings>,
+    pub draw_weight: Option<WheelAssetDrawWeight>,
 }
 
 #[derive(Debug, Clone, CandidType, Deserialize)]
@@ -128,6 +241,20 @@ pub struct DeleteWheelAssetRequest {
     pub id: String,
 }
 
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct RetireWheelAssetRequest {
+    pub id: String,
+    /// The account that receives the remaining balance of the token asset.
+    pub to: Account,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct RetireWheelAssetResponse {
+    /// The block index of the transfer of the remaining balance,
+    /// if the balance covered the transfer fee.
+    pub block_index: Option<Nat>,
+}
+
 #[derive(Debug, Clone, CandidType, Deserialize)]
 pub struct WheelAssetImageConfig {
     pub content_type: String,
@@ -157,6 +284,8 @@ pub struct WheelPrize {
     pub modal_image_path: Option<String>,
     pub wheel_ui_settings: WheelAssetUiSettings,
     pub prize_usd_amount: Option<f64>,
+    /// The probability (between 0 and 1) that this prize is drawn in the next extraction.
+    pub draw_probability: f64,
 }
 
 pub type ListWheelPrizesResponse = Vec<WheelPrize>;
@@ -0,0 +1,228 @@
+use candid::{CandidType, Deserialize};
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+
+/// The length in bytes of the seed used to draw a wheel prize.
+pub const WHEEL_PRIZE_DRAW_SEED_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct WheelPrizeDrawCandidate {
+    pub wheel_asset_id: String,
+    pub weight: u32,
+}
+
+/// A draw whose wheel asset has been excluded because a token price of the asset
+/// couldn't be fetched again, after which the prize has been drawn again with the same seed.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct WheelPrizeExcludedDraw {
+    pub candidates: Vec<WheelPrizeDrawCandidate>,
+    pub chosen_index: u32,
+}
+
+impl WheelPrizeExcludedDraw {
+    /// Returns the drawn candidate, if `chosen_index` points to one.
+    pub fn chosen_candidate(&self) -> Option<&WheelPrizeDrawCandidate> {
+        self.candidates.get(self.chosen_index as usize)
+    }
+}
+
+/// Everything needed to replay the draw of a wheel prize extraction.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct WheelPrizeDrawProof {
+    pub seed: Vec<u8>,
+    /// The wheel assets that could be drawn, with their weights at the time of the draw.
+    pub candidates: Vec<WheelPrizeDrawCandidate>,
+    /// The index in `candidates` of the drawn wheel asset.
+    pub chosen_index: u32,
+    /// The draws that preceded this one in the same extraction, in order.
+    pub excluded_draws: Vec<WheelPrizeExcludedDraw>,
+}
+
+impl WheelPrizeDrawProof {
+    /// Returns the drawn candidate, if `chosen_index` points to one.
+    pub fn chosen_candidate(&self) -> Option<&WheelPrizeDrawCandidate> {
+        self.candidates.get(self.chosen_index as usize)
+    }
+
+    /// Replays the excluded draws and the final draw from the seed and the candidates
+    /// and checks that they result in the chosen indexes.
+    /// The wheel assets of the excluded draws must not be candidates of the final draw.
+    pub fn verify(&self) -> bool {
+        let Ok(seed) = <[u8; WHEEL_PRIZE_DRAW_SEED_LENGTH]>::try_from(self.seed.as_slice()) else {
+            return false;
+        };
+        let replays = |candidates: &[WheelPrizeDrawCandidate], chosen_index: u32| {
+            let weights: Vec<u32> = candidates.iter().map(|c| c.weight).collect();
+            draw_wheel_prize_index(seed, &weights) == Some(chosen_index as usize)
+        };
+
+        self.excluded_draws.iter().all(|excluded_draw| {
+            replays(&excluded_draw.candidates, excluded_draw.chosen_index)
+                && excluded_draw
+                    .chosen_candidate()
+                    .is_some_and(|excluded_candidate| {
+                        self.candidates.iter().all(|candidate| {
+                            candidate.wheel_asset_id != excluded_candidate.wheel_asset_id
+                        })
+                    })
+        }) && replays(&self.candidates, self.chosen_index)
+    }
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct GetWheelPrizeDrawProofRequest {
+    pub wheel_prize_extraction_id: String,
+}
+
+pub type GetWheelPrizeDrawProofResponse = WheelPrizeDrawProof;
+
+/// Draws an index from `weights`, where each index has a probability
+/// proportional to its weight of being drawn.
+///
+/// The draw only depends on the seed and the weights, so that anyone can replay it.
+/// Returns `None` if the sum of the weights is zero.
+pub fn draw_wheel_prize_index(
+    seed: [u8; WHEEL_PRIZE_DRAW_SEED_LENGTH],
+    weights: &[u32],
+) -> Option<usize> {
+    let total_weight: u64 = weights.iter().map(|w| *w as u64).sum();
+    if total_weight == 0 {
+        return None;
+    }
+
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    // rejection sampling, to avoid the modulo bias
+    let zone = (u64::MAX / total_weight) * total_weight;
+    let target = loop {
+        let value = rng.next_u64();
+        if value < zone {
+            break value % total_weight;
+        }
+    };
+
+    let mut cumulative_weight = 0u64;
+    weights.iter().position(|weight| {
+        cumulative_weight += *weight as u64;
+        target < cumulative_weight
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proof(seed: [u8; 32], weights: &[u32]) -> WheelPrizeDrawProof {
+        let chosen_index = draw_wheel_prize_index(seed, weights).unwrap();
+        WheelPrizeDrawProof {
+            seed: seed.to_vec(),
+            candidates: weights
+                .iter()
+                .enumerate()
+                .map(|(i, weight)| WheelPrizeDrawCandidate {
+                    wheel_asset_id: format!("asset-{i}"),
+                    weight: *weight,
+                })
+                .collect(),
+            chosen_index: chosen_index as u32,
+            excluded_draws: vec![],
+        }
+    }
+
+    /// Excludes the drawn candidate of the proof and draws again with the same seed.
+    fn redraw(proof: WheelPrizeDrawProof) -> WheelPrizeDrawProof {
+        let seed = proof.seed.clone().try_into().unwrap();
+        let mut candidates = proof.candidates.clone();
+        candidates.remove(proof.chosen_index as usize);
+        let weights: Vec<u32> = candidates.iter().map(|c| c.weight).collect();
+        let chosen_index = draw_wheel_prize_index(seed, &weights).unwrap();
+
+        let mut excluded_draws = proof.excluded_draws;
+        excluded_draws.push(WheelPrizeExcludedDraw {
+            candidates: proof.candidates,
+            chosen_index: proof.chosen_index,
+        });
+        WheelPrizeDrawProof {
+            seed: proof.seed,
+            candidates,
+            chosen_index: chosen_index as u32,
+            excluded_draws,
+        }
+    }
+
+    #[test]
+    fn draw_is_deterministic() {
+        let weights = [1, 5, 0, 10, 3];
+        for i in 0..=u8::MAX {
+            let seed = [i; 32];
+            assert_eq!(
+                draw_wheel_prize_index(seed, &weights),
+                draw_wheel_prize_index(seed, &weights)
+            );
+        }
+    }
+
+    #[test]
+    fn draw_never_picks_zero_weights() {
+        let weights = [0, 2, 0, 1, 0];
+        for i in 0..=u8::MAX {
+            let index = draw_wheel_prize_index([i; 32], &weights).unwrap();
+            assert_ne!(weights[index], 0);
+        }
+    }
+
+    #[test]
+    fn draw_without_weights() {
+        assert_eq!(draw_wheel_prize_index([0; 32], &[]), None);
+        assert_eq!(draw_wheel_prize_index([0; 32], &[0, 0]), None);
+    }
+
+    #[test]
+    fn verify_proof() {
+        for i in 0..=u8::MAX {
+            assert!(proof([i; 32], &[1, 5, 0, 10, 3]).verify());
+        }
+    }
+
+    #[test]
+    fn verify_tampered_proof() {
+        let valid_proof = proof([7; 32], &[1, 1, 1, 1]);
+
+        let mut wrong_index = valid_proof.clone();
+        wrong_index.chosen_index = (valid_proof.chosen_index + 1) % 4;
+        assert!(!wrong_index.verify());
+
+        let mut wrong_seed_length = valid_proof.clone();
+        wrong_seed_length.seed.pop();
+        assert!(!wrong_seed_length.verify());
+
+        let mut out_of_bounds_index = valid_proof;
+        out_of_bounds_index.chosen_index = 4;
+        assert!(!out_of_bounds_index.verify());
+    }
+
+    #[test]
+    fn verify_proof_with_excluded_draws() {
+        for i in 0..=u8::MAX {
+            assert!(redraw(redraw(proof([i; 32], &[1, 5, 2, 10, 3]))).verify());
+        }
+    }
+
+    #[test]
+    fn verify_tampered_proof_with_excluded_draws() {
+        let valid_proof = redraw(proof([7; 32], &[1, 1, 1, 1]));
+
+        let mut wrong_excluded_index = valid_proof.clone();
+        wrong_excluded_index.excluded_draws[0].chosen_index =
+            (valid_proof.excluded_draws[0].chosen_index + 1) % 4;
+        assert!(!wrong_excluded_index.verify());
+
+        let mut excluded_candidate_drawn_again = valid_proof.clone();
+        let excluded_draw = &valid_proof.excluded_draws[0];
+        excluded_candidate_drawn_again
+            .candidates
+            .push(excluded_draw.chosen_candidate().unwrap().clone());
+        assert!(!excluded_candidate_drawn_again.verify());
+    }
+}
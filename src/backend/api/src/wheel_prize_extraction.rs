@@ -1,6 +1,6 @@
-use candid::{CandidType, Deserialize, Principal};
+use candid::{CandidType, Deserialize, Nat, Principal};
 
-use crate::ApiError;
+use crate::{Account, ApiError, RecipientAccount};
 
 #[derive(Debug, Clone, CandidType, Deserialize, PartialEq)]
 pub enum WheelPrizeExtractionState {
@@ -10,15 +10,94 @@ pub enum WheelPrizeExtractionState {
     Completed { prize_usd_amount: Option<f64> },
     #[serde(rename = "failed")]
     Failed { error: ApiError },
+    #[serde(rename = "partially_completed")]
+    PartiallyCompleted {
+        prize_usd_amount: Option<f64>,
+        error: ApiError,
+    },
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq)]
+pub enum WheelPrizePayoutState {
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "completed")]
+    Completed { block_index: Nat },
+    #[serde(rename = "failed")]
+    Failed { error: ApiError },
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq)]
+pub struct WheelPrizePayout {
+    pub wheel_asset_id: String,
+    pub ledger_canister_id: Principal,
+    /// The sponsor's account the payout is transferred from, if any.
+    pub from_account: Option<Account>,
+    pub amount: Nat,
+    pub memo: Vec<u8>,
+    pub created_at_time: u64,
+    pub state: WheelPrizePayoutState,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq)]
+pub enum StaleWheelAssetPriceResolution {
+    #[serde(rename = "refreshed")]
+    Refreshed { usd_price: f64 },
+    /// The asset and the jackpots paying it out have been excluded from the draw.
+    #[serde(rename = "excluded")]
+    Excluded { error: ApiError },
+}
+
+/// A token price that was too old to draw the prize when the extraction started.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq)]
+pub struct StaleWheelAssetPrice {
+    pub wheel_asset_id: String,
+    pub last_fetched_at: String,
+    pub resolution: StaleWheelAssetPriceResolution,
+}
+
+/// The collection of a gadget prize by the winner.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub enum WheelPrizeGadgetClaim {
+    /// The winner redeems the gadget with the QR code returned by `get_my_wheel_prize_redemption_qr_code`.
+    #[serde(rename = "won")]
+    Won,
+    #[serde(rename = "handed_over")]
+    HandedOver {
+        handed_over_at: String,
+        handed_over_by_user_id: String,
+        gadget_variant_name: Option<String>,
+    },
+}
+
+/// The code with which the winner spun the wheel for themselves.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub enum WheelPrizeSelfSpin {
+    #[serde(rename = "event_code")]
+    EventCode,
+    #[serde(rename = "invite_code")]
+    InviteCode { invite_code_id: String },
 }
 
 #[derive(Debug, Clone, CandidType, Deserialize, PartialEq)]
 pub struct WheelPrizeExtraction {
     pub id: String,
     pub extracted_for_principal: Principal,
+    /// Empty if the prize is paid out to the default account of the principal.
+    pub extracted_for_subaccount: Option<Vec<u8>>,
     pub extracted_by_user_id: String,
     pub state: WheelPrizeExtractionState,
     pub wheel_asset_id: Option<String>,
+    pub event_id: Option<String>,
+    pub payouts: Vec<WheelPrizePayout>,
+    pub stale_prices: Vec<StaleWheelAssetPrice>,
+    /// Empty for the prizes other than gadgets,
+    /// and for the gadgets won before claims were tracked.
+    pub gadget_claim: Option<WheelPrizeGadgetClaim>,
+    /// Empty if the winner was extracted by an admin or a scanner.
+    pub self_spin: Option<WheelPrizeSelfSpin>,
+    /// The invite code with which an admin or a scanner extracted the winner.
+    pub invite_code_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -32,13 +111,135 @@ pub type GetWheelPrizeExtractionResponse = WheelPrizeExtraction;
 
 pub type GetLastWheelPrizeExtractionResponse = Option<WheelPrizeExtraction>;
 
+/// Exactly one of `extract_for_principal` and `extract_for_account` must be provided.
 #[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
 pub struct CreateWheelPrizeExtractionRequest {
-    pub extract_for_principal: Principal,
+    /// The owner of the default account to pay the prize out to.
+    pub extract_for_principal: Option<Principal>,
+    /// The account to pay the prize out to. The extraction policy
+    /// is checked against the owner of the account.
+    pub extract_for_account: Option<RecipientAccount>,
+    /// The invite code of the winner, consumed by the extraction.
+    /// Required if the extraction policy requires invite codes.
+    pub invite_code: Option<String>,
+}
+
+/// Spins the wheel for the caller, paying the prize out to their default account.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct CreateMyWheelPrizeExtractionRequest {
+    /// The self-spin code of the active event, or an invite code.
+    /// Must be an invite code if the extraction policy requires invite codes.
+    pub self_spin_code: String,
 }
 
 pub type ListWheelPrizeExtractionsResponse = Vec<WheelPrizeExtraction>;
 
+/// The state of the extractions to list, regardless of its details.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub enum WheelPrizeExtractionStateFilter {
+    #[serde(rename = "processing")]
+    Processing,
+    #[serde(rename = "completed")]
+    Completed,
+    #[serde(rename = "failed")]
+    Failed,
+    #[serde(rename = "partially_completed")]
+    PartiallyCompleted,
+}
+
+/// All the provided filters must match.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct ListWheelPrizeExtractionsPageRequest {
+    pub state: Option<WheelPrizeExtractionStateFilter>,
+    pub wheel_asset_id: Option<String>,
+    pub extracted_by_user_id: Option<String>,
+    /// RFC 3339 date time, included.
+    pub created_from: Option<String>,
+    /// RFC 3339 date time, excluded.
+    pub created_before: Option<String>,
+    /// The id of the last extraction of the previous page.
+    pub start_after: Option<String>,
+    /// Defaults to 100, and can't be more than 500.
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq)]
+pub struct ListWheelPrizeExtractionsPageResponse {
+    /// Newest first.
+    pub wheel_prize_extractions: Vec<WheelPrizeExtraction>,
+    /// The `start_after` of the next page, `None` if there are no more extractions.
+    pub next_start_after: Option<String>,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq)]
+pub struct MyWheelPrizePayout {
+    pub ledger_canister_id: Principal,
+    pub amount: Nat,
+    /// The block index of the transfer, once completed.
+    pub block_index: Option<Nat>,
+}
+
+/// An extraction of the caller, without the details reserved to the admins and scanners.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq)]
+pub struct MyWheelPrizeExtraction {
+    pub id: String,
+    pub state: WheelPrizeExtractionState,
+    pub wheel_asset_id: Option<String>,
+    /// Empty if no prize has been drawn, or if the prize has been deleted since.
+    pub prize_name: Option<String>,
+    pub extracted_for_subaccount: Option<Vec<u8>>,
+    pub payouts: Vec<MyWheelPrizePayout>,
+    pub gadget_claim: Option<WheelPrizeGadgetClaim>,
+    pub created_at: String,
+}
+
+/// Newest first.
+pub type GetMyWheelPrizeExtractionsResponse = Vec<MyWheelPrizeExtraction>;
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct HandOverWheelPrizeGadgetRequest {
+    pub wheel_prize_extraction_id: String,
+    /// Required if the gadget has variants.
+    pub gadget_variant_name: Option<String>,
+}
+
+/// The content of the redemption QR code, plus the variant chosen at the booth.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct RedeemPrizeRequest {
+    pub wheel_prize_extraction_id: String,
+    pub redemption_code: String,
+    /// Required if the gadget has variants.
+    pub gadget_variant_name: Option<String>,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct GetMyWheelPrizeRedemptionQrCodeRequest {
+    pub wheel_prize_extraction_id: String,
+}
+
+/// The SVG image of the QR code that the winner shows at the booth to redeem the gadget.
+pub type GetMyWheelPrizeRedemptionQrCodeResponse = String;
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct ListUnclaimedWheelPrizeGadgetsRequest {
+    /// If not provided, the unclaimed gadgets of all the events are listed.
+    pub event_id: Option<String>,
+}
+
+/// Oldest first.
+pub type ListUnclaimedWheelPrizeGadgetsResponse = Vec<WheelPrizeExtraction>;
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct RetryWheelPrizeExtractionPayoutsRequest {
+    pub wheel_prize_extraction_id: String,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct GetWheelPrizeExtractionsStatsRequest {
+    /// Defaults to the active event, if any.
+    pub event_id: Option<String>,
+}
+
 #[derive(Debug, Clone, CandidType, Deserialize, PartialEq)]
 pub struct GetWheelPrizeExtractionsStatsResponse {
     pub total_completed_extractions: u32,
@@ -0,0 +1,55 @@
+use candid::{CandidType, Deserialize, Principal};
+
+/// The rules that a principal must satisfy to be extracted.
+///
+/// Wins are the extractions that did not fail, counted in the scope of the active event.
+/// Days are UTC days.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct WheelPrizeExtractionPolicy {
+    /// `None` means no limit.
+    pub max_wins_per_principal: Option<u32>,
+    /// `None` means no limit.
+    pub max_wins_per_principal_per_day: Option<u32>,
+    /// The maximum number of wins in a day, for all the principals.
+    /// `None` means no limit.
+    pub max_wins_per_day: Option<u32>,
+    /// The time to wait after a win before extracting the same principal again.
+    pub spin_cooldown_seconds: u64,
+    /// The time to wait after a failed extraction before extracting the same principal again.
+    pub failed_spin_cooldown_seconds: u64,
+    /// If set, only these principals can be extracted.
+    pub allowed_principals: Option<Vec<Principal>>,
+    pub denied_principals: Vec<Principal>,
+    /// Token prices older than this are refreshed before the draw,
+    /// or their assets are excluded from the draw if the refresh fails.
+    pub max_token_price_age_seconds: u64,
+    /// Whether the players can spin the wheel for themselves with a self-spin code.
+    pub self_spin_enabled: bool,
+    /// The wrong self-spin codes that a principal can submit in an hour.
+    pub self_spin_max_failed_attempts: u32,
+    /// Whether each extraction must consume an invite code, proving that the winner attended.
+    pub invite_code_required: bool,
+    pub updated_at: String,
+}
+
+pub type GetWheelPrizeExtractionPolicyResponse = WheelPrizeExtractionPolicy;
+
+/// Replaces the whole policy.
+#[derive(Debug, Clone, CandidType, Deserialize, PartialEq, Eq)]
+pub struct UpdateWheelPrizeExtractionPolicyRequest {
+    pub max_wins_per_principal: Option<u32>,
+    pub max_wins_per_principal_per_day: Option<u32>,
+    pub max_wins_per_day: Option<u32>,
+    pub spin_cooldown_seconds: u64,
+    pub failed_spin_cooldown_seconds: u64,
+    pub allowed_principals: Option<Vec<Principal>>,
+    pub denied_principals: Vec<Principal>,
+    /// `None` means the default age (2 hours).
+    pub max_token_price_age_seconds: Option<u64>,
+    /// `None` means disabled.
+    pub self_spin_enabled: Option<bool>,
+    /// `None` means the default attempts (5).
+    pub self_spin_max_failed_attempts: Option<u32>,
+    /// `None` means not required.
+    pub invite_code_required: Option<bool>,
+}
@@ -1,8 +1,19 @@
-use candid::Principal;
+use candid::{Nat, Principal};
 use ic_cdk::call::{Call, CallResult};
-use icrc_ledger_types::icrc1::{
-    account::Account,
-    transfer::{BlockIndex, TransferArg, TransferError},
+use icrc_ledger_types::{
+    icrc::generic_metadata_value::MetadataValue,
+    icrc1::{
+        account::Account,
+        transfer::{BlockIndex, TransferArg, TransferError},
+    },
+    icrc2::{
+        allowance::{Allowance, AllowanceArgs},
+        transfer_from::{TransferFromArgs, TransferFromError},
+    },
+    icrc3::{
+        archive::QueryTxArchiveFn,
+        transactions::{GetTransactionsRequest, GetTransactionsResponse, TransactionRange},
+    },
 };
 
 pub struct LedgerCanisterService(pub Principal);
@@ -16,6 +27,41 @@ impl LedgerCanisterService {
         Ok(res)
     }
 
+    pub async fn icrc1_metadata(&self) -> CallResult<Vec<(String, MetadataValue)>> {
+        let (res,) = Call::unbounded_wait(self.0, "icrc1_metadata")
+            .await?
+            .candid_tuple()?;
+        Ok(res)
+    }
+
+    pub async fn icrc1_name(&self) -> CallResult<String> {
+        let (res,) = Call::unbounded_wait(self.0, "icrc1_name")
+            .await?
+            .candid_tuple()?;
+        Ok(res)
+    }
+
+    pub async fn icrc1_symbol(&self) -> CallResult<String> {
+        let (res,) = Call::unbounded_wait(self.0, "icrc1_symbol")
+            .await?
+            .candid_tuple()?;
+        Ok(res)
+    }
+
+    pub async fn icrc1_fee(&self) -> CallResult<Nat> {
+        let (res,) = Call::unbounded_wait(self.0, "icrc1_fee")
+            .await?
+            .candid_tuple()?;
+        Ok(res)
+    }
+
+    pub async fn icrc1_decimals(&self) -> CallResult<u8> {
+        let (res,) = Call::unbounded_wait(self.0, "icrc1_decimals")
+            .await?
+            .candid_tuple()?;
+        Ok(res)
+    }
+
     pub async fn icrc1_transfer(
         &self,
         arg0: TransferArg,
@@ -26,4 +72,47 @@ impl LedgerCanisterService {
             .candid_tuple()?;
         Ok(res)
     }
+
+    pub async fn icrc2_allowance(&self, arg0: AllowanceArgs) -> CallResult<Allowance> {
+        let (res,) = Call::unbounded_wait(self.0, "icrc2_allowance")
+            .with_arg(arg0)
+            .await?
+            .candid_tuple()?;
+        Ok(res)
+    }
+
+    pub async fn icrc2_transfer_from(
+        &self,
+        arg0: TransferFromArgs,
+    ) -> CallResult<Result<Nat, TransferFromError>> {
+        let (res,) = Call::unbounded_wait(self.0, "icrc2_transfer_from")
+            .with_arg(arg0)
+            .await?
+            .candid_tuple()?;
+        Ok(res)
+    }
+
+    pub async fn get_transactions(
+        &self,
+        arg0: GetTransactionsRequest,
+    ) -> CallResult<GetTransactionsResponse> {
+        let (res,) = Call::unbounded_wait(self.0, "get_transactions")
+            .with_arg(arg0)
+            .await?
+            .candid_tuple()?;
+        Ok(res)
+    }
+}
+
+/// Fetches the transactions that the ledger moved to one of its archives,
+/// using the callback returned by [LedgerCanisterService::get_transactions].
+pub async fn get_archived_transactions(
+    callback: &QueryTxArchiveFn,
+    arg0: GetTransactionsRequest,
+) -> CallResult<TransactionRange> {
+    let (res,) = Call::unbounded_wait(callback.canister_id, &callback.method)
+        .with_arg(arg0)
+        .await?
+        .candid_tuple()?;
+    Ok(res)
 }
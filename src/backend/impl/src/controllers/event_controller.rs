@@ -0,0 +1,131 @@
+use backend_api::{
+    ApiError, ApiResult, CreateEventRequest, CreateEventResponse, DeleteEventRequest,
+    GetActiveEventResponse, ListEventsResponse, UpdateEventRequest,
+};
+use backend_macros::log_errors;
+use candid::Principal;
+use ic_cdk::{api::msg_caller, query, update};
+
+use crate::{
+    repositories::{
+        EventRepositoryImpl, UserProfileRepositoryImpl, WheelAssetRepositoryImpl,
+        WheelPrizeExtractionRepositoryImpl,
+    },
+    services::{AccessControlService, AccessControlServiceImpl, EventService, EventServiceImpl},
+};
+
+#[update]
+#[log_errors]
+fn create_event(request: CreateEventRequest) -> ApiResult<CreateEventResponse> {
+    let calling_principal = msg_caller();
+
+    EventController::default()
+        .create_event(calling_principal, request)
+        .into()
+}
+
+#[update]
+#[log_errors]
+fn update_event(request: UpdateEventRequest) -> ApiResult<()> {
+    let calling_principal = msg_caller();
+
+    EventController::default()
+        .update_event(calling_principal, request)
+        .into()
+}
+
+#[update]
+#[log_errors]
+fn delete_event(request: DeleteEventRequest) -> ApiResult<()> {
+    let calling_principal = msg_caller();
+
+    EventController::default()
+        .delete_event(calling_principal, request)
+        .into()
+}
+
+#[query]
+#[log_errors]
+fn list_events() -> ApiResult<ListEventsResponse> {
+    let calling_principal = msg_caller();
+
+    EventController::default()
+        .list_events(calling_principal)
+        .into()
+}
+
+#[query]
+#[log_errors]
+fn get_active_event() -> ApiResult<GetActiveEventResponse> {
+    // anyone can call this endpoint
+    EventController::default().get_active_event().into()
+}
+
+struct EventController<A: AccessControlService, E: EventService> {
+    access_control_service: A,
+    event_service: E,
+}
+
+impl Default
+    for EventController<
+        AccessControlServiceImpl<UserProfileRepositoryImpl>,
+        EventServiceImpl<
+            EventRepositoryImpl,
+            WheelAssetRepositoryImpl,
+            WheelPrizeExtractionRepositoryImpl,
+        >,
+    >
+{
+    fn default() -> Self {
+        Self {
+            access_control_service: AccessControlServiceImpl::default(),
+            event_service: EventServiceImpl::default(),
+        }
+    }
+}
+
+impl<A: AccessControlService, E: EventService> EventController<A, E> {
+    fn create_event(
+        &self,
+        calling_principal: Principal,
+        request: CreateEventRequest,
+    ) -> Result<CreateEventResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(&calling_principal)?;
+
+        self.event_service.create_event(request)
+    }
+
+    fn update_event(
+        &self,
+        calling_principal: Principal,
+        request: UpdateEventRequest,
+    ) -> Result<(), ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(&calling_principal)?;
+
+        self.event_service.update_event(request)
+    }
+
+    fn delete_event(
+        &self,
+        calling_principal: Principal,
+        request: DeleteEventRequest,
+    ) -> Result<(), ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(&calling_principal)?;
+
+        self.event_service.delete_event(request)
+    }
+
+    fn list_events(&self, calling_principal: Principal) -> Result<ListEventsResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin_or_scanner(&calling_principal)?;
+
+        self.event_service.list_events()
+    }
+
+    fn get_active_event(&self) -> Result<GetActiveEventResponse, ApiError> {
+        self.event_service.get_active_event()
+    }
+}
@@ -1,6 +1,17 @@
 use crate::{
-    repositories::{HttpAssetRepositoryImpl, UserProfileRepositoryImpl},
-    services::{HttpAssetService, HttpAssetServiceImpl, InitService, InitServiceImpl},
+    repositories::{
+        EventRepositoryImpl, HttpAssetRepositoryImpl, InviteCodeRepositoryImpl,
+        LedgerMetadataRepositoryImpl, LedgerTransferRepositoryImpl, ManualTransferRepositoryImpl,
+        SelfSpinRepositoryImpl, UserProfileRepositoryImpl, WheelAssetRepositoryImpl,
+        WheelPrizeExtractionPolicyRepositoryImpl, WheelPrizeExtractionRepositoryImpl,
+        WheelPrizeRedemptionRepositoryImpl,
+    },
+    services::{
+        HttpAssetService, HttpAssetServiceImpl, InitService, InitServiceImpl,
+        InviteCodeServiceImpl, SelfSpinServiceImpl, WalletServiceImpl, WheelAssetServiceImpl,
+        WheelPrizeExtractionService, WheelPrizeExtractionServiceImpl, WheelPrizeRedemptionService,
+        WheelPrizeRedemptionServiceImpl,
+    },
 };
 use backend_api::ApiError;
 use candid::Principal;
@@ -25,27 +36,92 @@ fn post_upgrade() {
     InitController::default().post_upgrade(calling_principal);
 }
 
-struct InitController<I: InitService, H: HttpAssetService> {
+struct InitController<
+    I: InitService,
+    H: HttpAssetService,
+    W: WheelPrizeExtractionService,
+    R: WheelPrizeRedemptionService,
+> {
     init_service: I,
     http_asset_service: H,
+    wheel_prize_extraction_service: W,
+    wheel_prize_redemption_service: R,
 }
 
 impl Default
     for InitController<
         InitServiceImpl<UserProfileRepositoryImpl>,
         HttpAssetServiceImpl<HttpAssetRepositoryImpl>,
+        WheelPrizeExtractionServiceImpl<
+            WheelAssetRepositoryImpl,
+            WheelPrizeExtractionRepositoryImpl,
+            UserProfileRepositoryImpl,
+            WalletServiceImpl<
+                UserProfileRepositoryImpl,
+                LedgerTransferRepositoryImpl,
+                ManualTransferRepositoryImpl,
+            >,
+            WheelAssetServiceImpl<
+                WheelAssetRepositoryImpl,
+                HttpAssetRepositoryImpl,
+                EventRepositoryImpl,
+                WheelPrizeExtractionRepositoryImpl,
+                LedgerMetadataRepositoryImpl,
+                LedgerTransferRepositoryImpl,
+            >,
+            EventRepositoryImpl,
+            WheelPrizeExtractionPolicyRepositoryImpl,
+            WheelPrizeRedemptionServiceImpl<WheelPrizeRedemptionRepositoryImpl>,
+            SelfSpinServiceImpl<
+                InviteCodeServiceImpl<
+                    InviteCodeRepositoryImpl,
+                    WheelPrizeExtractionRepositoryImpl,
+                    EventRepositoryImpl,
+                    UserProfileRepositoryImpl,
+                >,
+                SelfSpinRepositoryImpl,
+                WheelPrizeExtractionRepositoryImpl,
+                EventRepositoryImpl,
+                WheelPrizeExtractionPolicyRepositoryImpl,
+            >,
+            InviteCodeServiceImpl<
+                InviteCodeRepositoryImpl,
+                WheelPrizeExtractionRepositoryImpl,
+                EventRepositoryImpl,
+                UserProfileRepositoryImpl,
+            >,
+        >,
+        WheelPrizeRedemptionServiceImpl<WheelPrizeRedemptionRepositoryImpl>,
     >
 {
     fn default() -> Self {
-        Self::new(InitServiceImpl::default(), HttpAssetServiceImpl::default())
+        Self::new(
+            InitServiceImpl::default(),
+            HttpAssetServiceImpl::default(),
+            WheelPrizeExtractionServiceImpl::default(),
+            WheelPrizeRedemptionServiceImpl::default(),
+        )
     }
 }
 
-impl<I: InitService, H: HttpAssetService> InitController<I, H> {
-    fn new(init_service: I, http_asset_service: H) -> Self {
+impl<
+        I: InitService,
+        H: HttpAssetService,
+        W: WheelPrizeExtractionService,
+        R: WheelPrizeRedemptionService,
+    > InitController<I, H, W, R>
+{
+    fn new(
+        init_service: I,
+        http_asset_service: H,
+        wheel_prize_extraction_service: W,
+        wheel_prize_redemption_service: R,
+    ) -> Self {
         Self {
             init_service,
             http_asset_service,
+            wheel_prize_extraction_service,
+            wheel_prize_redemption_service,
         }
     }
 
@@ -82,6 +158,16 @@ impl<I: InitService, H: HttpAssetService> InitController<I, H> {
                 ic_cdk::trap(format!("Failed to initialize http_asset_service: {}", err));
             }
         };
+        match self.wheel_prize_extraction_service.init_indexes() {
+            Ok(_) => println!("init: wheel prize extraction indexes initialized"),
+            Err(err) => {
+                ic_cdk::trap(format!(
+                    "Failed to initialize wheel prize extraction indexes: {}",
+                    err
+                ));
+            }
+        };
+        self.wheel_prize_redemption_service.init_redemption_secret();
 
         jobs::start_jobs();
     }
@@ -95,6 +181,7 @@ mod jobs {
     /// Starts all cron jobs.
     pub fn start_jobs() {
         wheel_assets::start();
+        wheel_prize_extractions::start();
 
         println!("jobs: Jobs started");
     }
@@ -115,4 +202,28 @@ mod jobs {
             println!("jobs:wheel_assets: Job started");
         }
     }
+    mod wheel_prize_extractions {
+        use super::*;
+        use ic_cdk::{futures::spawn, println};
+
+        use crate::controllers::wheel_prize_extraction_controller::WheelPrizeExtractionController;
+
+        pub fn start() {
+            set_timer_interval(Duration::from_secs(300), || {
+                spawn(async {
+                    if let Err(err) = WheelPrizeExtractionController::default()
+                        .reconcile_wheel_prize_extractions_job()
+                        .await
+                    {
+                        println!(
+                            "wheel_prize_extractions: Failed to reconcile extractions: {}",
+                            err
+                        );
+                    }
+                });
+            });
+
+            println!("jobs:wheel_prize_extractions: Job started");
+        }
+    }
 }
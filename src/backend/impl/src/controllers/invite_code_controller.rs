@@ -0,0 +1,134 @@
+use backend_api::{
+    ApiError, ApiResult, ExportInviteCodesResponse, GenerateInviteCodesRequest,
+    GenerateInviteCodesResponse, ListInviteCodesRequest, ListInviteCodesResponse,
+    RevokeInviteCodeRequest,
+};
+use backend_macros::log_errors;
+use candid::Principal;
+use ic_cdk::{api::msg_caller, query, update};
+
+use crate::{
+    repositories::{
+        EventRepositoryImpl, InviteCodeRepositoryImpl, UserProfileRepositoryImpl,
+        WheelPrizeExtractionRepositoryImpl,
+    },
+    services::{
+        AccessControlService, AccessControlServiceImpl, InviteCodeService, InviteCodeServiceImpl,
+    },
+};
+
+#[update]
+#[log_errors]
+async fn generate_invite_codes(
+    request: GenerateInviteCodesRequest,
+) -> ApiResult<GenerateInviteCodesResponse> {
+    let calling_principal = msg_caller();
+
+    InviteCodeController::default()
+        .generate_invite_codes(&calling_principal, request)
+        .await
+        .into()
+}
+
+#[query]
+#[log_errors]
+fn list_invite_codes(request: ListInviteCodesRequest) -> ApiResult<ListInviteCodesResponse> {
+    let calling_principal = msg_caller();
+
+    InviteCodeController::default()
+        .list_invite_codes(&calling_principal, request)
+        .into()
+}
+
+#[update]
+#[log_errors]
+fn revoke_invite_code(request: RevokeInviteCodeRequest) -> ApiResult<()> {
+    let calling_principal = msg_caller();
+
+    InviteCodeController::default()
+        .revoke_invite_code(&calling_principal, request)
+        .into()
+}
+
+#[query]
+#[log_errors]
+fn export_invite_codes(request: ListInviteCodesRequest) -> ApiResult<ExportInviteCodesResponse> {
+    let calling_principal = msg_caller();
+
+    InviteCodeController::default()
+        .export_invite_codes(&calling_principal, request)
+        .into()
+}
+
+struct InviteCodeController<A: AccessControlService, I: InviteCodeService> {
+    access_control_service: A,
+    invite_code_service: I,
+}
+
+impl Default
+    for InviteCodeController<
+        AccessControlServiceImpl<UserProfileRepositoryImpl>,
+        InviteCodeServiceImpl<
+            InviteCodeRepositoryImpl,
+            WheelPrizeExtractionRepositoryImpl,
+            EventRepositoryImpl,
+            UserProfileRepositoryImpl,
+        >,
+    >
+{
+    fn default() -> Self {
+        Self {
+            access_control_service: AccessControlServiceImpl::default(),
+            invite_code_service: InviteCodeServiceImpl::default(),
+        }
+    }
+}
+
+impl<A: AccessControlService, I: InviteCodeService> InviteCodeController<A, I> {
+    async fn generate_invite_codes(
+        &self,
+        calling_principal: &Principal,
+        request: GenerateInviteCodesRequest,
+    ) -> Result<GenerateInviteCodesResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(calling_principal)?;
+
+        self.invite_code_service
+            .generate_invite_codes(calling_principal, request)
+            .await
+    }
+
+    fn list_invite_codes(
+        &self,
+        calling_principal: &Principal,
+        request: ListInviteCodesRequest,
+    ) -> Result<ListInviteCodesResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(calling_principal)?;
+
+        self.invite_code_service.list_invite_codes(request)
+    }
+
+    fn revoke_invite_code(
+        &self,
+        calling_principal: &Principal,
+        request: RevokeInviteCodeRequest,
+    ) -> Result<(), ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(calling_principal)?;
+
+        self.invite_code_service
+            .revoke_invite_code(calling_principal, request)
+    }
+
+    fn export_invite_codes(
+        &self,
+        calling_principal: &Principal,
+        request: ListInviteCodesRequest,
+    ) -> Result<ExportInviteCodesResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(calling_principal)?;
+
+        self.invite_code_service.export_invite_codes(request)
+    }
+}
@@ -1,6 +1,9 @@
 mod custom_domain_record_controller;
+mod event_controller;
 mod http_controller;
 mod init_controller;
+mod invite_code_controller;
+mod self_spin_controller;
 mod user_profile_controller;
 mod wallet_controller;
 mod wheel_asset_controller;
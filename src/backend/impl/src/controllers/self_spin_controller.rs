@@ -0,0 +1,98 @@
+use backend_api::{
+    ApiError, ApiResult, CreateSelfSpinInviteRequest, CreateSelfSpinInviteResponse,
+    SetEventSelfSpinCodeRequest,
+};
+use backend_macros::log_errors;
+use candid::Principal;
+use ic_cdk::{api::msg_caller, update};
+
+use crate::{
+    repositories::{
+        EventRepositoryImpl, InviteCodeRepositoryImpl, SelfSpinRepositoryImpl,
+        UserProfileRepositoryImpl, WheelPrizeExtractionPolicyRepositoryImpl,
+        WheelPrizeExtractionRepositoryImpl,
+    },
+    services::{
+        AccessControlService, AccessControlServiceImpl, InviteCodeServiceImpl, SelfSpinService,
+        SelfSpinServiceImpl,
+    },
+};
+
+#[update]
+#[log_errors]
+async fn create_self_spin_invite(
+    request: CreateSelfSpinInviteRequest,
+) -> ApiResult<CreateSelfSpinInviteResponse> {
+    let calling_principal = msg_caller();
+
+    SelfSpinController::default()
+        .create_self_spin_invite(&calling_principal, request)
+        .await
+        .into()
+}
+
+#[update]
+#[log_errors]
+fn set_event_self_spin_code(request: SetEventSelfSpinCodeRequest) -> ApiResult<()> {
+    let calling_principal = msg_caller();
+
+    SelfSpinController::default()
+        .set_event_self_spin_code(&calling_principal, request)
+        .into()
+}
+
+struct SelfSpinController<A: AccessControlService, S: SelfSpinService> {
+    access_control_service: A,
+    self_spin_service: S,
+}
+
+impl Default
+    for SelfSpinController<
+        AccessControlServiceImpl<UserProfileRepositoryImpl>,
+        SelfSpinServiceImpl<
+            InviteCodeServiceImpl<
+                InviteCodeRepositoryImpl,
+                WheelPrizeExtractionRepositoryImpl,
+                EventRepositoryImpl,
+                UserProfileRepositoryImpl,
+            >,
+            SelfSpinRepositoryImpl,
+            WheelPrizeExtractionRepositoryImpl,
+            EventRepositoryImpl,
+            WheelPrizeExtractionPolicyRepositoryImpl,
+        >,
+    >
+{
+    fn default() -> Self {
+        Self {
+            access_control_service: AccessControlServiceImpl::default(),
+            self_spin_service: SelfSpinServiceImpl::default(),
+        }
+    }
+}
+
+impl<A: AccessControlService, S: SelfSpinService> SelfSpinController<A, S> {
+    async fn create_self_spin_invite(
+        &self,
+        calling_principal: &Principal,
+        request: CreateSelfSpinInviteRequest,
+    ) -> Result<CreateSelfSpinInviteResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(calling_principal)?;
+
+        self.self_spin_service
+            .create_self_spin_invite(calling_principal, request)
+            .await
+    }
+
+    fn set_event_self_spin_code(
+        &self,
+        calling_principal: &Principal,
+        request: SetEventSelfSpinCodeRequest,
+    ) -> Result<(), ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(calling_principal)?;
+
+        self.self_spin_service.set_event_self_spin_code(request)
+    }
+}
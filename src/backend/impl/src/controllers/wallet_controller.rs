@@ -1,10 +1,17 @@
-use backend_api::{ApiError, ApiResult, TransferTokenRequest, TransferTokenResponse};
+use backend_api::{
+    ApiError, ApiResult, ApproveManualTransferRequest, ApproveManualTransferResponse,
+    ListLedgerTransfersRequest, ListLedgerTransfersResponse, ListManualTransferLimitsResponse,
+    ListManualTransfersResponse, RejectManualTransferRequest, SetManualTransferLimitRequest,
+    SetManualTransferLimitResponse, TransferTokenRequest, TransferTokenResponse,
+};
 use backend_macros::log_errors;
 use candid::{Nat, Principal};
-use ic_cdk::{api::msg_caller, update};
+use ic_cdk::{api::msg_caller, query, update};
 
 use crate::{
-    repositories::UserProfileRepositoryImpl,
+    repositories::{
+        LedgerTransferRepositoryImpl, ManualTransferRepositoryImpl, UserProfileRepositoryImpl,
+    },
     services::{AccessControlService, AccessControlServiceImpl, WalletService, WalletServiceImpl},
 };
 
@@ -19,6 +26,73 @@ async fn transfer_token(request: TransferTokenRequest) -> ApiResult<TransferToke
         .into()
 }
 
+#[query]
+#[log_errors]
+fn list_ledger_transfers(
+    request: ListLedgerTransfersRequest,
+) -> ApiResult<ListLedgerTransfersResponse> {
+    let calling_principal = msg_caller();
+
+    WalletController::default()
+        .list_ledger_transfers(calling_principal, request)
+        .into()
+}
+
+#[query]
+#[log_errors]
+fn list_manual_transfers() -> ApiResult<ListManualTransfersResponse> {
+    let calling_principal = msg_caller();
+
+    WalletController::default()
+        .list_manual_transfers(calling_principal)
+        .into()
+}
+
+#[update]
+#[log_errors]
+async fn approve_manual_transfer(
+    request: ApproveManualTransferRequest,
+) -> ApiResult<ApproveManualTransferResponse> {
+    let calling_principal = msg_caller();
+
+    WalletController::default()
+        .approve_manual_transfer(calling_principal, request)
+        .await
+        .into()
+}
+
+#[update]
+#[log_errors]
+fn reject_manual_transfer(request: RejectManualTransferRequest) -> ApiResult<()> {
+    let calling_principal = msg_caller();
+
+    WalletController::default()
+        .reject_manual_transfer(calling_principal, request)
+        .into()
+}
+
+#[update]
+#[log_errors]
+fn set_manual_transfer_limit(
+    request: SetManualTransferLimitRequest,
+) -> ApiResult<SetManualTransferLimitResponse> {
+    let calling_principal = msg_caller();
+
+    WalletController::default()
+        .set_manual_transfer_limit(calling_principal, request)
+        .into()
+}
+
+#[query]
+#[log_errors]
+fn list_manual_transfer_limits() -> ApiResult<ListManualTransferLimitsResponse> {
+    let calling_principal = msg_caller();
+
+    WalletController::default()
+        .list_manual_transfer_limits(calling_principal)
+        .into()
+}
+
 struct WalletController<A: AccessControlService, W: WalletService> {
     access_control_service: A,
     wallet_service: W,
@@ -27,7 +101,11 @@ struct WalletController<A: AccessControlService, W: WalletService> {
 impl Default
     for WalletController<
         AccessControlServiceImpl<UserProfileRepositoryImpl>,
-        WalletServiceImpl<UserProfileRepositoryImpl>,
+        WalletServiceImpl<
+            UserProfileRepositoryImpl,
+            LedgerTransferRepositoryImpl,
+            ManualTransferRepositoryImpl,
+        >,
     >
 {
     fn default() -> Self {
@@ -43,7 +121,7 @@ impl<A: AccessControlService, W: WalletService> WalletController<A, W> {
         &self,
         calling_principal: Principal,
         request: TransferTokenRequest,
-    ) -> Result<Nat, ApiError> {
+    ) -> Result<TransferTokenResponse, ApiError> {
         self.access_control_service
             .assert_principal_is_admin(&calling_principal)?;
 
@@ -51,4 +129,71 @@ impl<A: AccessControlService, W: WalletService> WalletController<A, W> {
             .transfer_token(calling_principal, request)
             .await
     }
+
+    fn list_ledger_transfers(
+        &self,
+        calling_principal: Principal,
+        request: ListLedgerTransfersRequest,
+    ) -> Result<ListLedgerTransfersResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(&calling_principal)?;
+
+        self.wallet_service.list_ledger_transfers(request)
+    }
+
+    fn list_manual_transfers(
+        &self,
+        calling_principal: Principal,
+    ) -> Result<ListManualTransfersResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(&calling_principal)?;
+
+        Ok(self.wallet_service.list_manual_transfers())
+    }
+
+    async fn approve_manual_transfer(
+        &self,
+        calling_principal: Principal,
+        request: ApproveManualTransferRequest,
+    ) -> Result<Nat, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(&calling_principal)?;
+
+        self.wallet_service
+            .approve_manual_transfer(calling_principal, request)
+            .await
+    }
+
+    fn reject_manual_transfer(
+        &self,
+        calling_principal: Principal,
+        request: RejectManualTransferRequest,
+    ) -> Result<(), ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(&calling_principal)?;
+
+        self.wallet_service
+            .reject_manual_transfer(calling_principal, request)
+    }
+
+    fn set_manual_transfer_limit(
+        &self,
+        calling_principal: Principal,
+        request: SetManualTransferLimitRequest,
+    ) -> Result<SetManualTransferLimitResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(&calling_principal)?;
+
+        self.wallet_service.set_manual_transfer_limit(request)
+    }
+
+    fn list_manual_transfer_limits(
+        &self,
+        calling_principal: Principal,
+    ) -> Result<ListManualTransferLimitsResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(&calling_principal)?;
+
+        Ok(self.wallet_service.list_manual_transfer_limits())
+    }
 }
@@ -1,8 +1,8 @@
 use backend_api::{
     ApiError, ApiResult, CreateWheelAssetRequest, CreateWheelAssetResponse,
     DeleteWheelAssetRequest, ListWheelAssetsRequest, ListWheelAssetsResponse,
-    ListWheelPrizesResponse, UpdateWheelAssetImageRequest, UpdateWheelAssetRequest,
-    UpdateWheelPrizesOrderRequest,
+    ListWheelPrizesResponse, RetireWheelAssetRequest, RetireWheelAssetResponse,
+    UpdateWheelAssetImageRequest, UpdateWheelAssetRequest, UpdateWheelPrizesOrderRequest,
 };
 use backend_macros::log_errors;
 use candid::Principal;
@@ -10,8 +10,9 @@ use ic_cdk::{api::msg_caller, query, update};
 
 use crate::{
     repositories::{
-        HttpAssetRepositoryImpl, UserProfileRepositoryImpl, WheelAssetRepositoryImpl,
-        WheelAssetState,
+        EventRepositoryImpl, HttpAssetRepositoryImpl, LedgerMetadataRepositoryImpl,
+        LedgerTransferRepositoryImpl, UserProfileRepositoryImpl, WheelAssetRepositoryImpl,
+        WheelAssetState, WheelPrizeExtractionRepositoryImpl,
     },
     services::{
         AccessControlService, AccessControlServiceImpl, WheelAssetService, WheelAssetServiceImpl,
@@ -50,11 +51,14 @@ fn fetch_tokens_data() -> ApiResult<()> {
 
 #[update]
 #[log_errors]
-fn create_wheel_asset(request: CreateWheelAssetRequest) -> ApiResult<CreateWheelAssetResponse> {
+async fn create_wheel_asset(
+    request: CreateWheelAssetRequest,
+) -> ApiResult<CreateWheelAssetResponse> {
     let calling_principal = msg_caller();
 
     WheelAssetController::default()
         .create_wheel_asset(calling_principal, request)
+        .await
         .into()
 }
 
@@ -78,6 +82,19 @@ fn delete_wheel_asset(request: DeleteWheelAssetRequest) -> ApiResult<()> {
         .into()
 }
 
+#[update]
+#[log_errors]
+async fn retire_wheel_asset(
+    request: RetireWheelAssetRequest,
+) -> ApiResult<RetireWheelAssetResponse> {
+    let calling_principal = msg_caller();
+
+    WheelAssetController::default()
+        .retire_wheel_asset(calling_principal, request)
+        .await
+        .into()
+}
+
 #[update]
 #[log_errors]
 fn update_wheel_asset_image(request: UpdateWheelAssetImageRequest) -> ApiResult<()> {
@@ -113,7 +130,14 @@ pub struct WheelAssetController<A: AccessControlService, W: WheelAssetService> {
 impl Default
     for WheelAssetController<
         AccessControlServiceImpl<UserProfileRepositoryImpl>,
-        WheelAssetServiceImpl<WheelAssetRepositoryImpl, HttpAssetRepositoryImpl>,
+        WheelAssetServiceImpl<
+            WheelAssetRepositoryImpl,
+            HttpAssetRepositoryImpl,
+            EventRepositoryImpl,
+            WheelPrizeExtractionRepositoryImpl,
+            LedgerMetadataRepositoryImpl,
+            LedgerTransferRepositoryImpl,
+        >,
     >
 {
     fn default() -> Self {
@@ -165,7 +189,7 @@ impl<A: AccessControlService, W: WheelAssetService> WheelAssetController<A, W> {
         self.wheel_asset_service.fetch_tokens_data()
     }
 
-    fn create_wheel_asset(
+    async fn create_wheel_asset(
         &self,
         calling_principal: Principal,
         request: CreateWheelAssetRequest,
@@ -173,7 +197,7 @@ impl<A: AccessControlService, W: WheelAssetService> WheelAssetController<A, W> {
         self.access_control_service
             .assert_principal_is_admin(&calling_principal)?;
 
-        self.wheel_asset_service.create_wheel_asset(request)
+        self.wheel_asset_service.create_wheel_asset(request).await
     }
 
     fn update_wheel_asset(
@@ -198,6 +222,17 @@ impl<A: AccessControlService, W: WheelAssetService> WheelAssetController<A, W> {
         self.wheel_asset_service.delete_wheel_asset(request)
     }
 
+    async fn retire_wheel_asset(
+        &self,
+        calling_principal: Principal,
+        request: RetireWheelAssetRequest,
+    ) -> Result<RetireWheelAssetResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(&calling_principal)?;
+
+        self.wheel_asset_service.retire_wheel_asset(request).await
+    }
+
     fn update_wheel_asset_image(
         &self,
         calling_principal: Principal,
@@ -1,7 +1,15 @@
 use backend_api::{
-    ApiError, ApiResult, CreateWheelPrizeExtractionRequest, GetLastWheelPrizeExtractionResponse,
-    GetWheelPrizeExtractionRequest, GetWheelPrizeExtractionResponse,
-    GetWheelPrizeExtractionsStatsResponse, ListWheelPrizeExtractionsResponse,
+    ApiError, ApiResult, CreateMyWheelPrizeExtractionRequest, CreateWheelPrizeExtractionRequest,
+    GetLastWheelPrizeExtractionResponse, GetMyWheelPrizeExtractionsResponse,
+    GetMyWheelPrizeRedemptionQrCodeRequest, GetMyWheelPrizeRedemptionQrCodeResponse,
+    GetWheelPrizeDrawProofRequest, GetWheelPrizeDrawProofResponse,
+    GetWheelPrizeExtractionPolicyResponse, GetWheelPrizeExtractionRequest,
+    GetWheelPrizeExtractionResponse, GetWheelPrizeExtractionsStatsRequest,
+    GetWheelPrizeExtractionsStatsResponse, HandOverWheelPrizeGadgetRequest,
+    ListUnclaimedWheelPrizeGadgetsRequest, ListUnclaimedWheelPrizeGadgetsResponse,
+    ListWheelPrizeExtractionsPageRequest, ListWheelPrizeExtractionsPageResponse,
+    ListWheelPrizeExtractionsResponse, RedeemPrizeRequest, RetryWheelPrizeExtractionPayoutsRequest,
+    UpdateWheelPrizeExtractionPolicyRequest,
 };
 use backend_macros::log_errors;
 use candid::Principal;
@@ -9,12 +17,16 @@ use ic_cdk::{api::msg_caller, query, update};
 
 use crate::{
     repositories::{
-        HttpAssetRepositoryImpl, UserProfileRepositoryImpl, WheelAssetRepositoryImpl,
-        WheelPrizeExtractionRepositoryImpl,
+        EventRepositoryImpl, HttpAssetRepositoryImpl, InviteCodeRepositoryImpl,
+        LedgerMetadataRepositoryImpl, LedgerTransferRepositoryImpl, ManualTransferRepositoryImpl,
+        SelfSpinRepositoryImpl, UserProfileRepositoryImpl, WheelAssetRepositoryImpl,
+        WheelPrizeExtractionPolicyRepositoryImpl, WheelPrizeExtractionRepositoryImpl,
+        WheelPrizeRedemptionRepositoryImpl,
     },
     services::{
-        AccessControlService, AccessControlServiceImpl, WalletServiceImpl, WheelAssetServiceImpl,
-        WheelPrizeExtractionService, WheelPrizeExtractionServiceImpl,
+        AccessControlService, AccessControlServiceImpl, InviteCodeServiceImpl, SelfSpinServiceImpl,
+        WalletServiceImpl, WheelAssetServiceImpl, WheelPrizeExtractionService,
+        WheelPrizeExtractionServiceImpl, WheelPrizeRedemptionServiceImpl,
     },
 };
 
@@ -49,6 +61,28 @@ fn list_wheel_prize_extractions() -> ApiResult<ListWheelPrizeExtractionsResponse
         .into()
 }
 
+#[query]
+#[log_errors]
+fn list_wheel_prize_extractions_page(
+    request: ListWheelPrizeExtractionsPageRequest,
+) -> ApiResult<ListWheelPrizeExtractionsPageResponse> {
+    let calling_principal = msg_caller();
+
+    WheelPrizeExtractionController::default()
+        .list_wheel_prize_extractions_page(&calling_principal, request)
+        .into()
+}
+
+#[query]
+#[log_errors]
+fn get_my_wheel_prize_extractions() -> ApiResult<GetMyWheelPrizeExtractionsResponse> {
+    let calling_principal = msg_caller();
+
+    WheelPrizeExtractionController::default()
+        .get_my_wheel_prize_extractions(&calling_principal)
+        .into()
+}
+
 #[update]
 #[log_errors]
 async fn create_wheel_prize_extraction(
@@ -62,17 +96,122 @@ async fn create_wheel_prize_extraction(
         .into()
 }
 
+#[update]
+#[log_errors]
+async fn create_my_wheel_prize_extraction(
+    request: CreateMyWheelPrizeExtractionRequest,
+) -> ApiResult<()> {
+    let calling_principal = msg_caller();
+
+    WheelPrizeExtractionController::default()
+        .create_my_wheel_prize_extraction(&calling_principal, request)
+        .await
+        .into()
+}
+
+#[update]
+#[log_errors]
+async fn retry_wheel_prize_extraction_payouts(
+    request: RetryWheelPrizeExtractionPayoutsRequest,
+) -> ApiResult<()> {
+    let calling_principal = msg_caller();
+
+    WheelPrizeExtractionController::default()
+        .retry_wheel_prize_extraction_payouts(&calling_principal, request)
+        .await
+        .into()
+}
+
+#[update]
+#[log_errors]
+fn hand_over_wheel_prize_gadget(request: HandOverWheelPrizeGadgetRequest) -> ApiResult<()> {
+    let calling_principal = msg_caller();
+
+    WheelPrizeExtractionController::default()
+        .hand_over_wheel_prize_gadget(&calling_principal, request)
+        .into()
+}
+
+#[update]
+#[log_errors]
+fn redeem_prize(request: RedeemPrizeRequest) -> ApiResult<()> {
+    let calling_principal = msg_caller();
+
+    WheelPrizeExtractionController::default()
+        .redeem_prize(&calling_principal, request)
+        .into()
+}
+
+#[query]
+#[log_errors]
+fn get_my_wheel_prize_redemption_qr_code(
+    request: GetMyWheelPrizeRedemptionQrCodeRequest,
+) -> ApiResult<GetMyWheelPrizeRedemptionQrCodeResponse> {
+    let calling_principal = msg_caller();
+
+    WheelPrizeExtractionController::default()
+        .get_my_wheel_prize_redemption_qr_code(&calling_principal, request)
+        .into()
+}
+
+#[query]
+#[log_errors]
+fn list_unclaimed_wheel_prize_gadgets(
+    request: ListUnclaimedWheelPrizeGadgetsRequest,
+) -> ApiResult<ListUnclaimedWheelPrizeGadgetsResponse> {
+    let calling_principal = msg_caller();
+
+    WheelPrizeExtractionController::default()
+        .list_unclaimed_wheel_prize_gadgets(&calling_principal, request)
+        .into()
+}
+
 #[query]
 #[log_errors]
-fn get_wheel_prize_extractions_stats() -> ApiResult<GetWheelPrizeExtractionsStatsResponse> {
+fn get_wheel_prize_extractions_stats(
+    request: Option<GetWheelPrizeExtractionsStatsRequest>,
+) -> ApiResult<GetWheelPrizeExtractionsStatsResponse> {
+    let calling_principal = msg_caller();
+
+    WheelPrizeExtractionController::default()
+        .get_wheel_prize_extractions_stats(&calling_principal, request)
+        .into()
+}
+
+#[query]
+#[log_errors]
+fn get_wheel_prize_draw_proof(
+    request: GetWheelPrizeDrawProofRequest,
+) -> ApiResult<GetWheelPrizeDrawProofResponse> {
+    // anyone can call this endpoint, to verify the draw of an extraction
+    WheelPrizeExtractionController::default()
+        .get_wheel_prize_draw_proof(request)
+        .into()
+}
+
+#[query]
+#[log_errors]
+fn get_wheel_prize_extraction_policy() -> ApiResult<GetWheelPrizeExtractionPolicyResponse> {
+    let calling_principal = msg_caller();
+
+    WheelPrizeExtractionController::default()
+        .get_wheel_prize_extraction_policy(&calling_principal)
+        .into()
+}
+
+#[update]
+#[log_errors]
+fn update_wheel_prize_extraction_policy(
+    request: UpdateWheelPrizeExtractionPolicyRequest,
+) -> ApiResult<()> {
     let calling_principal = msg_caller();
 
     WheelPrizeExtractionController::default()
-        .get_wheel_prize_extractions_stats(&calling_principal)
+        .update_wheel_prize_extraction_policy(&calling_principal, request)
         .into()
 }
 
-struct WheelPrizeExtractionController<A: AccessControlService, W: WheelPrizeExtractionService> {
+pub struct WheelPrizeExtractionController<A: AccessControlService, W: WheelPrizeExtractionService> {
     access_control_service: A,
     wheel_prize_extraction_service: W,
 }
@@ -84,8 +223,40 @@ impl Default
             WheelAssetRepositoryImpl,
             WheelPrizeExtractionRepositoryImpl,
             UserProfileRepositoryImpl,
-            WalletServiceImpl<UserProfileRepositoryImpl>,
-            WheelAssetServiceImpl<WheelAssetRepositoryImpl, HttpAssetRepositoryImpl>,
+            WalletServiceImpl<
+                UserProfileRepositoryImpl,
+                LedgerTransferRepositoryImpl,
+                ManualTransferRepositoryImpl,
+            >,
+            WheelAssetServiceImpl<
+                WheelAssetRepositoryImpl,
+                HttpAssetRepositoryImpl,
+                EventRepositoryImpl,
+                WheelPrizeExtractionRepositoryImpl,
+                LedgerMetadataRepositoryImpl,
+                LedgerTransferRepositoryImpl,
+            >,
+            EventRepositoryImpl,
+            WheelPrizeExtractionPolicyRepositoryImpl,
+            WheelPrizeRedemptionServiceImpl<WheelPrizeRedemptionRepositoryImpl>,
+            SelfSpinServiceImpl<
+                InviteCodeServiceImpl<
+                    InviteCodeRepositoryImpl,
+                    WheelPrizeExtractionRepositoryImpl,
+                    EventRepositoryImpl,
+                    UserProfileRepositoryImpl,
+                >,
+                SelfSpinRepositoryImpl,
+                WheelPrizeExtractionRepositoryImpl,
+                EventRepositoryImpl,
+                WheelPrizeExtractionPolicyRepositoryImpl,
+            >,
+            InviteCodeServiceImpl<
+                InviteCodeRepositoryImpl,
+                WheelPrizeExtractionRepositoryImpl,
+                EventRepositoryImpl,
+                UserProfileRepositoryImpl,
+            >,
         >,
     >
 {
@@ -128,6 +299,29 @@ impl<A: AccessControlService, W: WheelPrizeExtractionService> WheelPrizeExtracti
             .list_wheel_prize_extractions()
     }
 
+    fn list_wheel_prize_extractions_page(
+        &self,
+        calling_principal: &Principal,
+        request: ListWheelPrizeExtractionsPageRequest,
+    ) -> Result<ListWheelPrizeExtractionsPageResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin_or_scanner(calling_principal)?;
+
+        self.wheel_prize_extraction_service
+            .list_wheel_prize_extractions_page(request)
+    }
+
+    fn get_my_wheel_prize_extractions(
+        &self,
+        calling_principal: &Principal,
+    ) -> Result<GetMyWheelPrizeExtractionsResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_not_anonymous(calling_principal)?;
+
+        self.wheel_prize_extraction_service
+            .get_my_wheel_prize_extractions(calling_principal)
+    }
+
     async fn create_wheel_prize_extraction(
         &self,
         calling_principal: &Principal,
@@ -141,14 +335,126 @@ impl<A: AccessControlService, W: WheelPrizeExtractionService> WheelPrizeExtracti
             .await
     }
 
+    async fn create_my_wheel_prize_extraction(
+        &self,
+        calling_principal: &Principal,
+        request: CreateMyWheelPrizeExtractionRequest,
+    ) -> Result<(), ApiError> {
+        self.access_control_service
+            .assert_principal_not_anonymous(calling_principal)?;
+
+        self.wheel_prize_extraction_service
+            .create_my_wheel_prize_extraction(calling_principal, request)
+            .await
+    }
+
+    async fn retry_wheel_prize_extraction_payouts(
+        &self,
+        calling_principal: &Principal,
+        request: RetryWheelPrizeExtractionPayoutsRequest,
+    ) -> Result<(), ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(calling_principal)?;
+
+        self.wheel_prize_extraction_service
+            .retry_wheel_prize_extraction_payouts(request)
+            .await
+    }
+
+    fn hand_over_wheel_prize_gadget(
+        &self,
+        calling_principal: &Principal,
+        request: HandOverWheelPrizeGadgetRequest,
+    ) -> Result<(), ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin_or_scanner(calling_principal)?;
+
+        self.wheel_prize_extraction_service
+            .hand_over_wheel_prize_gadget(calling_principal, request)
+    }
+
+    fn redeem_prize(
+        &self,
+        calling_principal: &Principal,
+        request: RedeemPrizeRequest,
+    ) -> Result<(), ApiError> {
+        self.access_control_service
+            .assert_principal_is_scanner(calling_principal)?;
+
+        self.wheel_prize_extraction_service
+            .redeem_prize(calling_principal, request)
+    }
+
+    fn get_my_wheel_prize_redemption_qr_code(
+        &self,
+        calling_principal: &Principal,
+        request: GetMyWheelPrizeRedemptionQrCodeRequest,
+    ) -> Result<GetMyWheelPrizeRedemptionQrCodeResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_not_anonymous(calling_principal)?;
+
+        self.wheel_prize_extraction_service
+            .get_my_wheel_prize_redemption_qr_code(calling_principal, request)
+    }
+
+    fn list_unclaimed_wheel_prize_gadgets(
+        &self,
+        calling_principal: &Principal,
+        request: ListUnclaimedWheelPrizeGadgetsRequest,
+    ) -> Result<ListUnclaimedWheelPrizeGadgetsResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin_or_scanner(calling_principal)?;
+
+        self.wheel_prize_extraction_service
+            .list_unclaimed_wheel_prize_gadgets(request)
+    }
+
     fn get_wheel_prize_extractions_stats(
         &self,
         calling_principal: &Principal,
+        request: Option<GetWheelPrizeExtractionsStatsRequest>,
     ) -> Result<GetWheelPrizeExtractionsStatsResponse, ApiError> {
         self.access_control_service
             .assert_principal_is_admin_or_scanner(calling_principal)?;
 
         self.wheel_prize_extraction_service
-            .get_wheel_prize_extractions_stats()
+            .get_wheel_prize_extractions_stats(request)
+    }
+
+    fn get_wheel_prize_draw_proof(
+        &self,
+        request: GetWheelPrizeDrawProofRequest,
+    ) -> Result<GetWheelPrizeDrawProofResponse, ApiError> {
+        self.wheel_prize_extraction_service
+            .get_wheel_prize_draw_proof(request)
+    }
+
+    pub async fn reconcile_wheel_prize_extractions_job(&self) -> Result<(), ApiError> {
+        self.wheel_prize_extraction_service
+            .reconcile_wheel_prize_extractions()
+            .await
+    }
+
+    fn get_wheel_prize_extraction_policy(
+        &self,
+        calling_principal: &Principal,
+    ) -> Result<GetWheelPrizeExtractionPolicyResponse, ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin_or_scanner(calling_principal)?;
+
+        self.wheel_prize_extraction_service
+            .get_wheel_prize_extraction_policy()
+    }
+
+    fn update_wheel_prize_extraction_policy(
+        &self,
+        calling_principal: &Principal,
+        request: UpdateWheelPrizeExtractionPolicyRequest,
+    ) -> Result<(), ApiError> {
+        self.access_control_service
+            .assert_principal_is_admin(calling_principal)?;
+
+        self.wheel_prize_extraction_service
+            .update_wheel_prize_extraction_policy(request)
     }
 }
@@ -0,0 +1,38 @@
+use rstest::*;
+
+use crate::repositories::{hash_code, Event, TimestampFields};
+
+use super::{date_time_a, date_time_future};
+
+#[fixture]
+pub fn event() -> Event {
+    Event {
+        name: "ICP Meetup".to_string(),
+        start_date: date_time_a(),
+        end_date: date_time_future(),
+        active: false,
+        self_spin_code_hash: None,
+        self_spin_code_max_spins: None,
+        self_spin_max_failed_attempts: None,
+        self_spin_failed_attempts: None,
+        timestamps: TimestampFields::new(),
+    }
+}
+
+#[fixture]
+pub fn active_event() -> Event {
+    Event {
+        active: true,
+        ..event()
+    }
+}
+
+#[fixture]
+pub fn active_event_with_self_spin_code() -> Event {
+    Event {
+        self_spin_code_hash: Some(hash_code("MEETUP-2025")),
+        self_spin_code_max_spins: Some(200),
+        self_spin_failed_attempts: Some(1),
+        ..active_event()
+    }
+}
@@ -0,0 +1,39 @@
+use rstest::*;
+
+use crate::repositories::{hash_code, InviteCode, InviteCodePurpose, InviteCodeState};
+
+use super::{date_time_a, principal, uuid_a, uuid_b};
+
+#[fixture]
+pub fn invite_code() -> InviteCode {
+    InviteCode::new_available(
+        hash_code("ABCD-EFGH-JKMN-PQRS"),
+        Some(principal()),
+        Some(uuid_a()),
+        InviteCodePurpose::Attendance,
+        uuid_a(),
+    )
+}
+
+#[fixture]
+pub fn invite_code_used() -> InviteCode {
+    InviteCode {
+        state: InviteCodeState::Used {
+            principal: principal(),
+            wheel_prize_extraction_id: uuid_b(),
+            used_at: date_time_a(),
+        },
+        ..invite_code()
+    }
+}
+
+#[fixture]
+pub fn invite_code_revoked() -> InviteCode {
+    InviteCode {
+        state: InviteCodeState::Revoked {
+            revoked_at: date_time_a(),
+            revoked_by_user_id: uuid_a(),
+        },
+        ..invite_code()
+    }
+}
@@ -0,0 +1,17 @@
+use rstest::*;
+
+use crate::repositories::LedgerMetadata;
+
+use super::date_time_a;
+
+#[fixture]
+pub fn ledger_metadata() -> LedgerMetadata {
+    LedgerMetadata {
+        fee: 10_000,
+        decimals: 8,
+        symbol: Some("ckBTC".to_string()),
+        name: Some("ckBTC".to_string()),
+        logo: Some("data:image/svg+xml;base64,PHN2Zz4=".to_string()),
+        last_fetched_at: date_time_a(),
+    }
+}
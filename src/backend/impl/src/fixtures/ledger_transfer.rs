@@ -0,0 +1,40 @@
+use candid::{Nat, Principal};
+use icrc_ledger_types::icrc1::account::Account;
+use rstest::*;
+
+use crate::repositories::LedgerTransfer;
+
+use super::{date_time_a, principal, uuid};
+
+#[fixture]
+pub fn ledger_transfer_payout() -> LedgerTransfer {
+    LedgerTransfer {
+        ledger_canister_id: Principal::from_text("mxzaz-hqaaa-aaaar-qaada-cai").unwrap(),
+        from: Account {
+            owner: Principal::from_text("rrkah-fqaaa-aaaaa-aaaaq-cai").unwrap(),
+            subaccount: Some([1; 32]),
+        },
+        to: Account {
+            owner: principal(),
+            subaccount: None,
+        },
+        amount: Nat::from(1_000_000u64),
+        fee: Some(Nat::from(10_000u64)),
+        memo: Some(vec![1; 16]),
+        block_index: Nat::from(42u64),
+        initiated_by_user_id: Some(uuid()),
+        wheel_prize_extraction_id: Some(uuid()),
+        wheel_asset_id: Some(uuid()),
+        created_at: date_time_a(),
+    }
+}
+
+#[fixture]
+pub fn ledger_transfer_retirement() -> LedgerTransfer {
+    LedgerTransfer {
+        fee: None,
+        initiated_by_user_id: None,
+        wheel_prize_extraction_id: None,
+        ..ledger_transfer_payout()
+    }
+}
@@ -0,0 +1,48 @@
+use candid::{Nat, Principal};
+use rstest::*;
+
+use crate::repositories::{
+    ManualTransfer, ManualTransferLimit, ManualTransferState, TimestampFields,
+};
+
+use super::{principal, uuid_a, uuid_b};
+
+#[fixture]
+pub fn manual_transfer() -> ManualTransfer {
+    ManualTransfer {
+        ledger_canister_id: Principal::from_text("mxzaz-hqaaa-aaaar-qaada-cai").unwrap(),
+        from_subaccount: Some([2; 32]),
+        to: principal(),
+        to_subaccount: Some([1; 32]),
+        amount: 1_000_000,
+        reason: "Refund of the wrong payout".to_string(),
+        created_at_time: Some(1_706_899_350_000_000_000),
+        requested_by_user_id: uuid_a(),
+        approved_by_user_id: Some(uuid_b()),
+        state: ManualTransferState::Completed {
+            block_index: Nat::from(42u64),
+        },
+        timestamps: TimestampFields::new(),
+    }
+}
+
+#[fixture]
+pub fn manual_transfer_pending_approval() -> ManualTransfer {
+    ManualTransfer {
+        from_subaccount: None,
+        to_subaccount: None,
+        created_at_time: None,
+        approved_by_user_id: None,
+        state: ManualTransferState::PendingApproval,
+        ..manual_transfer()
+    }
+}
+
+#[fixture]
+pub fn manual_transfer_limit() -> ManualTransferLimit {
+    ManualTransferLimit {
+        daily_limit: Some(10_000_000),
+        approval_threshold: Some(1_000_000),
+        timestamps: TimestampFields::new(),
+    }
+}
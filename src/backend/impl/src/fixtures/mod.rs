@@ -1,13 +1,29 @@
 mod custom_domain_record;
 mod date_time;
+mod event;
 mod id;
+mod invite_code;
+mod ledger_metadata;
+mod ledger_transfer;
+mod manual_transfer;
+mod self_spin;
 mod user_profile;
 mod wheel_asset;
 mod wheel_prize_extraction;
+mod wheel_prize_extraction_policy;
+mod wheel_prize_redemption;
 
 pub use custom_domain_record::*;
 pub use date_time::*;
+pub use event::*;
 pub use id::*;
+pub use invite_code::*;
+pub use ledger_metadata::*;
+pub use ledger_transfer::*;
+pub use manual_transfer::*;
+pub use self_spin::*;
 pub use user_profile::*;
 pub use wheel_asset::*;
 pub use wheel_prize_extraction::*;
+pub use wheel_prize_extraction_policy::*;
+pub use wheel_prize_redemption::*;
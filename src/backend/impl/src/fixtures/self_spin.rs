@@ -0,0 +1,13 @@
+use rstest::*;
+
+use crate::repositories::SelfSpinFailedAttempts;
+
+use super::date_time_a;
+
+#[fixture]
+pub fn self_spin_failed_attempts() -> SelfSpinFailedAttempts {
+    SelfSpinFailedAttempts {
+        count: 2,
+        window_started_at: date_time_a(),
+    }
+}
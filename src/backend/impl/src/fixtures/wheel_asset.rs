@@ -3,8 +3,8 @@ use std::path::PathBuf;
 use rstest::*;
 
 use crate::repositories::{
-    HttpAssetPath, TimestampFields, WheelAsset, WheelAssetId, WheelAssetState, WheelAssetType,
-    WheelAssetUiSettings,
+    EventId, HttpAssetPath, TimestampFields, WheelAsset, WheelAssetDrawWeight,
+    WheelAssetGadgetVariant, WheelAssetId, WheelAssetState, WheelAssetType, WheelAssetUiSettings,
 };
 
 #[fixture]
@@ -25,6 +25,9 @@ pub fn wheel_asset_token() -> WheelAsset {
             background_color_hex: "#ffffff".to_string(),
         },
         timestamps: TimestampFields::new(),
+        draw_weight: Some(WheelAssetDrawWeight::default()),
+        event_id: None,
+        retirement: None,
     }
 }
 
@@ -34,6 +37,7 @@ pub fn wheel_asset_gadget() -> WheelAsset {
         name: "Gadget1".to_string(),
         asset_type: WheelAssetType::Gadget {
             article_type: Some("article_type1".to_string()),
+            variants: None,
         },
         total_amount: 100,
         used_amount: 0,
@@ -46,6 +50,9 @@ pub fn wheel_asset_gadget() -> WheelAsset {
             background_color_hex: "#ffffff".to_string(),
         },
         timestamps: TimestampFields::new(),
+        draw_weight: Some(WheelAssetDrawWeight::Fixed { weight: 3 }),
+        event_id: Some(EventId::try_from("0195aacc-f240-7417-8b63-c38f24401a3f").unwrap()),
+        retirement: None,
     }
 }
 
@@ -72,5 +79,35 @@ pub fn wheel_asset_jackpot() -> WheelAsset {
             background_color_hex: "#ffffff".to_string(),
         },
         timestamps: TimestampFields::new(),
+        draw_weight: Some(WheelAssetDrawWeight::AvailableQuantity),
+        event_id: None,
+        retirement: None,
+    }
+}
+
+#[fixture]
+pub fn wheel_asset_gadget_with_variants() -> WheelAsset {
+    WheelAsset {
+        name: "T-shirt".to_string(),
+        asset_type: WheelAssetType::Gadget {
+            article_type: Some("t-shirt".to_string()),
+            variants: Some(vec![
+                WheelAssetGadgetVariant {
+                    name: "M".to_string(),
+                    size: Some("M".to_string()),
+                    color: Some("black".to_string()),
+                    total_amount: 10,
+                    handed_over_amount: 3,
+                },
+                WheelAssetGadgetVariant {
+                    name: "L".to_string(),
+                    size: Some("L".to_string()),
+                    color: Some("black".to_string()),
+                    total_amount: 5,
+                    handed_over_amount: 5,
+                },
+            ]),
+        },
+        ..wheel_asset_gadget()
     }
 }
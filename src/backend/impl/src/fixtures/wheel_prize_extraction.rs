@@ -1,23 +1,217 @@
-use backend_api::ApiError;
+use backend_api::{ApiError, LedgerTransferRejection};
+use candid::{Nat, Principal};
 use rstest::*;
 
 use crate::repositories::{
-    TimestampFields, WheelPrizeExtraction, WheelPrizeExtractionOld, WheelPrizeExtractionState,
-    WheelPrizeExtractionStateOld,
+    DateTime, StaleWheelAssetPrice, StaleWheelAssetPriceResolution, TimestampFields,
+    WheelAssetTokenLedgerConfig, WheelPrizeDrawCandidate, WheelPrizeDrawProof,
+    WheelPrizeExtraction, WheelPrizeExtractionOld, WheelPrizeExtractionState,
+    WheelPrizeExtractionStateOld, WheelPrizeGadgetClaim, WheelPrizePayout, WheelPrizePayoutState,
+    WheelPrizeSelfSpin,
 };
 
-use super::{principal, uuid};
+use super::{date_time_a, principal, uuid, uuid_a, uuid_b};
+
+#[fixture]
+pub fn wheel_prize_draw_proof() -> WheelPrizeDrawProof {
+    WheelPrizeDrawProof {
+        seed: [7; 32],
+        candidates: vec![
+            WheelPrizeDrawCandidate {
+                wheel_asset_id: uuid(),
+                weight: 1,
+            },
+            WheelPrizeDrawCandidate {
+                wheel_asset_id: uuid(),
+                weight: 3,
+            },
+        ],
+        chosen_index: 1,
+        excluded_draws: Some(vec![]),
+    }
+}
+
+#[fixture]
+pub fn wheel_prize_payout() -> WheelPrizePayout {
+    WheelPrizePayout::new_pending(
+        uuid(),
+        uuid(),
+        &WheelAssetTokenLedgerConfig {
+            ledger_canister_id: Principal::from_text("mxzaz-hqaaa-aaaar-qaada-cai").unwrap(),
+            decimals: 8,
+            fee: Some(10_000),
+            symbol: None,
+            name: None,
+        },
+        None,
+        Some([1; 32]),
+        1_000_000,
+        1_706_899_350_000_000_000,
+    )
+}
+
+#[fixture]
+pub fn wheel_prize_payout_completed() -> WheelPrizePayout {
+    WheelPrizePayout {
+        state: WheelPrizePayoutState::Completed {
+            block_index: Nat::from(42u64),
+        },
+        ..wheel_prize_payout()
+    }
+}
+
+#[fixture]
+pub fn stale_wheel_asset_price_refreshed() -> StaleWheelAssetPrice {
+    StaleWheelAssetPrice {
+        wheel_asset_id: uuid(),
+        last_fetched_at: DateTime::from_timestamp_micros(1_706_889_350_000_000).unwrap(),
+        resolution: StaleWheelAssetPriceResolution::Refreshed { usd_price: 42.42 },
+    }
+}
+
+#[fixture]
+pub fn stale_wheel_asset_price_excluded() -> StaleWheelAssetPrice {
+    StaleWheelAssetPrice {
+        resolution: StaleWheelAssetPriceResolution::Excluded {
+            error: ApiError::internal("Failed to call the exchange rate canister"),
+        },
+        ..stale_wheel_asset_price_refreshed()
+    }
+}
 
 #[fixture]
 pub fn wheel_prize_extraction() -> WheelPrizeExtraction {
+    let draw_proof = wheel_prize_draw_proof();
+
     WheelPrizeExtraction {
         extracted_for_principal: principal(),
+        extracted_for_subaccount: None,
         state: WheelPrizeExtractionState::Completed {
             prize_usd_amount: Some(2.3),
         },
         extracted_by_user_id: uuid(),
         timestamps: TimestampFields::new(),
+        wheel_asset_id: draw_proof.chosen_wheel_asset_id(),
+        draw_proof: Some(draw_proof),
+        event_id: None,
+        payouts: Some(vec![wheel_prize_payout_completed()]),
+        stale_prices: Some(vec![stale_wheel_asset_price_refreshed()]),
+        gadget_claim: None,
+        self_spin: None,
+        invite_code_id: Some(uuid_b()),
+    }
+}
+
+#[fixture]
+pub fn wheel_prize_extraction_processing() -> WheelPrizeExtraction {
+    let draw_proof = wheel_prize_draw_proof();
+
+    WheelPrizeExtraction {
+        extracted_for_principal: principal(),
+        extracted_for_subaccount: Some([1; 32]),
+        state: WheelPrizeExtractionState::Processing,
+        extracted_by_user_id: uuid(),
+        timestamps: TimestampFields::new(),
+        wheel_asset_id: draw_proof.chosen_wheel_asset_id(),
+        draw_proof: Some(draw_proof),
+        event_id: Some(uuid()),
+        payouts: Some(vec![wheel_prize_payout()]),
+        stale_prices: Some(vec![]),
+        gadget_claim: None,
+        self_spin: Some(WheelPrizeSelfSpin::EventCode),
+        invite_code_id: None,
+    }
+}
+
+#[fixture]
+pub fn wheel_prize_extraction_partially_completed() -> WheelPrizeExtraction {
+    let draw_proof = wheel_prize_draw_proof();
+    let error = ApiError::ledger_transfer_rejected(
+        Principal::from_text("mxzaz-hqaaa-aaaar-qaada-cai").unwrap(),
+        LedgerTransferRejection::InsufficientFunds {
+            balance: Nat::from(0u32),
+        },
+    );
+
+    WheelPrizeExtraction {
+        extracted_for_principal: principal(),
+        extracted_for_subaccount: None,
+        state: WheelPrizeExtractionState::PartiallyCompleted {
+            prize_usd_amount: Some(1.0),
+            error: error.clone(),
+        },
+        extracted_by_user_id: uuid(),
+        timestamps: TimestampFields::new(),
+        wheel_asset_id: draw_proof.chosen_wheel_asset_id(),
+        draw_proof: Some(draw_proof),
+        event_id: None,
+        payouts: Some(vec![
+            wheel_prize_payout_completed(),
+            WheelPrizePayout {
+                state: WheelPrizePayoutState::Failed { error },
+                ..wheel_prize_payout()
+            },
+        ]),
+        stale_prices: Some(vec![stale_wheel_asset_price_excluded()]),
+        gadget_claim: None,
+        self_spin: None,
+        invite_code_id: None,
+    }
+}
+
+#[fixture]
+pub fn wheel_prize_extraction_failed() -> WheelPrizeExtraction {
+    WheelPrizeExtraction {
+        extracted_for_principal: principal(),
+        extracted_for_subaccount: None,
+        state: WheelPrizeExtractionState::Failed {
+            error: ApiError::internal("error"),
+        },
+        extracted_by_user_id: uuid(),
+        timestamps: TimestampFields::new(),
         wheel_asset_id: Some(uuid()),
+        draw_proof: Some(wheel_prize_draw_proof()),
+        event_id: Some(uuid()),
+        payouts: None,
+        stale_prices: None,
+        gadget_claim: None,
+        self_spin: None,
+        invite_code_id: None,
+    }
+}
+
+#[fixture]
+pub fn wheel_prize_extraction_gadget() -> WheelPrizeExtraction {
+    WheelPrizeExtraction {
+        extracted_for_principal: principal(),
+        extracted_for_subaccount: None,
+        state: WheelPrizeExtractionState::Completed {
+            prize_usd_amount: None,
+        },
+        extracted_by_user_id: uuid(),
+        timestamps: TimestampFields::new(),
+        wheel_asset_id: Some(uuid()),
+        draw_proof: Some(wheel_prize_draw_proof()),
+        event_id: Some(uuid()),
+        payouts: Some(vec![]),
+        stale_prices: Some(vec![]),
+        gadget_claim: Some(WheelPrizeGadgetClaim::Won),
+        self_spin: Some(WheelPrizeSelfSpin::InviteCode {
+            invite_code_id: uuid_a(),
+        }),
+        invite_code_id: None,
+    }
+}
+
+#[fixture]
+pub fn wheel_prize_extraction_gadget_handed_over() -> WheelPrizeExtraction {
+    WheelPrizeExtraction {
+        gadget_claim: Some(WheelPrizeGadgetClaim::HandedOver {
+            handed_over_at: date_time_a(),
+            handed_over_by_user_id: uuid_a(),
+            gadget_variant_name: Some("M".to_string()),
+        }),
+        ..wheel_prize_extraction_gadget()
     }
 }
 
@@ -0,0 +1,24 @@
+use candid::Principal;
+use rstest::*;
+
+use crate::repositories::{TimestampFields, WheelPrizeExtractionPolicy};
+
+use super::principal;
+
+#[fixture]
+pub fn wheel_prize_extraction_policy() -> WheelPrizeExtractionPolicy {
+    WheelPrizeExtractionPolicy {
+        max_wins_per_principal: Some(3),
+        max_wins_per_principal_per_day: Some(2),
+        max_wins_per_day: Some(100),
+        spin_cooldown_seconds: 120,
+        failed_spin_cooldown_seconds: 10,
+        allowed_principals: Some(vec![Principal::from_slice(&[1])]),
+        denied_principals: vec![principal()],
+        max_token_price_age_seconds: Some(600),
+        self_spin_enabled: Some(true),
+        self_spin_max_failed_attempts: Some(3),
+        invite_code_required: Some(true),
+        timestamps: TimestampFields::new(),
+    }
+}
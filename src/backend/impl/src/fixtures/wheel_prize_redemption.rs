@@ -0,0 +1,8 @@
+use rstest::*;
+
+use crate::repositories::WheelPrizeRedemptionSecret;
+
+#[fixture]
+pub fn wheel_prize_redemption_secret() -> WheelPrizeRedemptionSecret {
+    WheelPrizeRedemptionSecret::new([1; 32])
+}
@@ -0,0 +1,20 @@
+use crate::repositories::{Event, EventId};
+
+pub fn map_event(event_id: EventId, event: Event) -> backend_api::Event {
+    let self_spin_max_failed_attempts = event.self_spin_max_failed_attempts();
+    let self_spin_failed_attempts = event.self_spin_failed_attempts();
+
+    backend_api::Event {
+        id: event_id.to_string(),
+        name: event.name,
+        start_date: event.start_date.to_string(),
+        end_date: event.end_date.to_string(),
+        active: event.active,
+        has_self_spin_code: event.self_spin_code_hash.is_some(),
+        self_spin_code_max_spins: event.self_spin_code_max_spins,
+        self_spin_max_failed_attempts,
+        self_spin_failed_attempts,
+        created_at: event.timestamps.created_at.to_string(),
+        updated_at: event.timestamps.updated_at.to_string(),
+    }
+}
@@ -0,0 +1,109 @@
+use crate::repositories::{InviteCode, InviteCodeId, InviteCodePurpose, InviteCodeState};
+
+const INVITE_CODES_CSV_HEADER: &str =
+    "id,event_id,principal,purpose,state,used_by_principal,wheel_prize_extraction_id,used_at,revoked_at,created_at";
+
+impl From<backend_api::InviteCodePurpose> for InviteCodePurpose {
+    fn from(purpose: backend_api::InviteCodePurpose) -> Self {
+        match purpose {
+            backend_api::InviteCodePurpose::Attendance => InviteCodePurpose::Attendance,
+            backend_api::InviteCodePurpose::SelfSpin => InviteCodePurpose::SelfSpin,
+        }
+    }
+}
+
+impl From<InviteCodePurpose> for backend_api::InviteCodePurpose {
+    fn from(purpose: InviteCodePurpose) -> Self {
+        match purpose {
+            InviteCodePurpose::Attendance => backend_api::InviteCodePurpose::Attendance,
+            InviteCodePurpose::SelfSpin => backend_api::InviteCodePurpose::SelfSpin,
+        }
+    }
+}
+
+impl From<InviteCodeState> for backend_api::InviteCodeState {
+    fn from(state: InviteCodeState) -> Self {
+        match state {
+            InviteCodeState::Available => backend_api::InviteCodeState::Available,
+            InviteCodeState::Used {
+                principal,
+                wheel_prize_extraction_id,
+                used_at,
+            } => backend_api::InviteCodeState::Used {
+                principal,
+                wheel_prize_extraction_id: wheel_prize_extraction_id.to_string(),
+                used_at: used_at.to_string(),
+            },
+            InviteCodeState::Revoked {
+                revoked_at,
+                revoked_by_user_id,
+            } => backend_api::InviteCodeState::Revoked {
+                revoked_at: revoked_at.to_string(),
+                revoked_by_user_id: revoked_by_user_id.to_string(),
+            },
+        }
+    }
+}
+
+pub fn map_invite_code(
+    invite_code_id: InviteCodeId,
+    invite_code: InviteCode,
+) -> backend_api::InviteCode {
+    backend_api::InviteCode {
+        id: invite_code_id.to_string(),
+        principal: invite_code.principal,
+        event_id: invite_code.event_id.map(|id| id.to_string()),
+        purpose: invite_code.purpose.map(Into::into),
+        state: invite_code.state.into(),
+        created_by_user_id: invite_code.created_by_user_id.to_string(),
+        created_at: invite_code.timestamps.created_at.to_string(),
+        updated_at: invite_code.timestamps.updated_at.to_string(),
+    }
+}
+
+/// None of the values can contain commas or quotes, so they don't need to be escaped.
+pub fn map_invite_codes_csv(invite_codes: Vec<(InviteCodeId, InviteCode)>) -> String {
+    let mut csv = String::from(INVITE_CODES_CSV_HEADER);
+    for (invite_code_id, invite_code) in invite_codes {
+        let (state, used_by_principal, wheel_prize_extraction_id, used_at, revoked_at) =
+            match invite_code.state {
+                InviteCodeState::Available => ("available", None, None, None, None),
+                InviteCodeState::Used {
+                    principal,
+                    wheel_prize_extraction_id,
+                    used_at,
+                } => (
+                    "used",
+                    Some(principal.to_text()),
+                    Some(wheel_prize_extraction_id.to_string()),
+                    Some(used_at.to_string()),
+                    None,
+                ),
+                InviteCodeState::Revoked { revoked_at, .. } => {
+                    ("revoked", None, None, None, Some(revoked_at.to_string()))
+                }
+            };
+
+        let row = [
+            Some(invite_code_id.to_string()),
+            invite_code.event_id.map(|id| id.to_string()),
+            invite_code.principal.map(|principal| principal.to_text()),
+            invite_code.purpose.map(|purpose| {
+                match purpose {
+                    InviteCodePurpose::Attendance => "attendance",
+                    InviteCodePurpose::SelfSpin => "self_spin",
+                }
+                .to_string()
+            }),
+            Some(state.to_string()),
+            used_by_principal,
+            wheel_prize_extraction_id,
+            used_at,
+            revoked_at,
+            Some(invite_code.timestamps.created_at.to_string()),
+        ];
+        csv.push('\n');
+        csv.push_str(&row.map(Option::unwrap_or_default).join(","));
+    }
+    csv
+}
@@ -1,9 +1,17 @@
 mod custom_domain_record;
+mod event;
+mod invite_code;
 mod user_profile;
+mod wallet;
 mod wheel_asset;
 mod wheel_prize_extraction;
+mod wheel_prize_extraction_policy;
 
 pub use custom_domain_record::*;
+pub use event::*;
+pub use invite_code::*;
 pub use user_profile::*;
+pub use wallet::*;
 pub use wheel_asset::*;
 pub use wheel_prize_extraction::*;
+pub use wheel_prize_extraction_policy::*;
@@ -0,0 +1,155 @@
+use std::str::FromStr;
+
+use backend_api::ApiError;
+use candid::{Nat, Principal};
+use icrc_ledger_types::icrc1::account::{Account, Subaccount, DEFAULT_SUBACCOUNT};
+
+use crate::repositories::{
+    LedgerTransfer, LedgerTransferId, ManualTransfer, ManualTransferId, ManualTransferLimit,
+    ManualTransferState,
+};
+
+pub fn map_account(account: Account) -> backend_api::Account {
+    backend_api::Account {
+        owner: account.owner,
+        subaccount: account.subaccount.map(|el| el.to_vec()),
+    }
+}
+
+pub fn into_subaccount(subaccount: &[u8]) -> Result<Subaccount, ApiError> {
+    Subaccount::try_from(subaccount)
+        .map_err(|_| ApiError::invalid_argument("Subaccount must be 32 bytes long"))
+}
+
+pub fn into_account(account: backend_api::Account) -> Result<Account, ApiError> {
+    let subaccount = account
+        .subaccount
+        .map(|el| into_subaccount(&el))
+        .transpose()?;
+
+    Ok(Account {
+        owner: account.owner,
+        subaccount,
+    })
+}
+
+/// Resolves the recipient of a transfer, given either as the owner of the default account
+/// or as an account, in which case a default subaccount is the same as no subaccount.
+pub fn into_recipient_account(
+    principal: Option<Principal>,
+    account: Option<backend_api::RecipientAccount>,
+) -> Result<Account, ApiError> {
+    let account = match (principal, account) {
+        (Some(owner), None) => Account {
+            owner,
+            subaccount: None,
+        },
+        (None, Some(backend_api::RecipientAccount::Account(account))) => into_account(account)?,
+        (None, Some(backend_api::RecipientAccount::Text(text))) => Account::from_str(text.trim())
+            .map_err(|err| {
+            ApiError::invalid_argument(&format!("Invalid account {}: {}", text, err))
+        })?,
+        _ => {
+            return Err(ApiError::invalid_argument(
+                "Exactly one of principal and account must be provided",
+            ))
+        }
+    };
+
+    Ok(Account {
+        owner: account.owner,
+        subaccount: account
+            .subaccount
+            .filter(|subaccount| subaccount != DEFAULT_SUBACCOUNT),
+    })
+}
+
+pub fn map_ledger_transfer(
+    id: LedgerTransferId,
+    ledger_transfer: LedgerTransfer,
+) -> backend_api::LedgerTransfer {
+    backend_api::LedgerTransfer {
+        id: id.to_string(),
+        ledger_canister_id: ledger_transfer.ledger_canister_id,
+        from: map_account(ledger_transfer.from),
+        to: map_account(ledger_transfer.to),
+        amount: ledger_transfer.amount,
+        fee: ledger_transfer.fee,
+        memo: ledger_transfer.memo,
+        block_index: ledger_transfer.block_index,
+        initiated_by_user_id: ledger_transfer
+            .initiated_by_user_id
+            .map(|user_id| user_id.to_string()),
+        wheel_prize_extraction_id: ledger_transfer
+            .wheel_prize_extraction_id
+            .map(|wheel_prize_extraction_id| wheel_prize_extraction_id.to_string()),
+        wheel_asset_id: ledger_transfer
+            .wheel_asset_id
+            .map(|wheel_asset_id| wheel_asset_id.to_string()),
+        created_at: ledger_transfer.created_at.to_string(),
+    }
+}
+
+impl From<ManualTransferState> for backend_api::ManualTransferState {
+    fn from(state: ManualTransferState) -> Self {
+        match state {
+            ManualTransferState::PendingApproval => {
+                backend_api::ManualTransferState::PendingApproval
+            }
+            ManualTransferState::Processing => backend_api::ManualTransferState::Processing,
+            ManualTransferState::Completed { block_index } => {
+                backend_api::ManualTransferState::Completed { block_index }
+            }
+            ManualTransferState::Failed { error } => {
+                backend_api::ManualTransferState::Failed { error }
+            }
+            ManualTransferState::Denied { error } => {
+                backend_api::ManualTransferState::Denied { error }
+            }
+            ManualTransferState::Rejected {
+                rejected_by_user_id,
+            } => backend_api::ManualTransferState::Rejected {
+                rejected_by_user_id: rejected_by_user_id.to_string(),
+            },
+        }
+    }
+}
+
+pub fn map_manual_transfer(
+    id: ManualTransferId,
+    manual_transfer: ManualTransfer,
+) -> backend_api::ManualTransfer {
+    backend_api::ManualTransfer {
+        id: id.to_string(),
+        ledger_canister_id: manual_transfer.ledger_canister_id,
+        from_subaccount: manual_transfer
+            .from_subaccount
+            .map(|subaccount| subaccount.to_vec()),
+        to: manual_transfer.to,
+        to_subaccount: manual_transfer
+            .to_subaccount
+            .map(|subaccount| subaccount.to_vec()),
+        amount: Nat::from(manual_transfer.amount),
+        reason: manual_transfer.reason,
+        created_at_time: manual_transfer.created_at_time,
+        requested_by_user_id: manual_transfer.requested_by_user_id.to_string(),
+        approved_by_user_id: manual_transfer
+            .approved_by_user_id
+            .map(|user_id| user_id.to_string()),
+        state: manual_transfer.state.into(),
+        created_at: manual_transfer.timestamps.created_at.to_string(),
+        updated_at: manual_transfer.timestamps.updated_at.to_string(),
+    }
+}
+
+pub fn map_manual_transfer_limit(
+    ledger_canister_id: Principal,
+    manual_transfer_limit: ManualTransferLimit,
+) -> backend_api::ManualTransferLimit {
+    backend_api::ManualTransferLimit {
+        ledger_canister_id,
+        daily_limit: manual_transfer_limit.daily_limit.map(Nat::from),
+        approval_threshold: manual_transfer_limit.approval_threshold.map(Nat::from),
+        updated_at: manual_transfer_limit.timestamps.updated_at.to_string(),
+    }
+}
@@ -1,10 +1,15 @@
 use backend_api::ApiError;
+use icrc_ledger_types::icrc1::account::Account;
 
 use crate::repositories::{
-    WheelAsset, WheelAssetId, WheelAssetState, WheelAssetTokenBalance, WheelAssetTokenLedgerConfig,
-    WheelAssetTokenPrice, WheelAssetType, WheelAssetUiSettings,
+    WheelAsset, WheelAssetDrawWeight, WheelAssetGadgetVariant, WheelAssetId, WheelAssetState,
+    WheelAssetTokenBalance, WheelAssetTokenFetchStatus, WheelAssetTokenFundingSource,
+    WheelAssetTokenLedgerConfig, WheelAssetTokenPrice, WheelAssetTokenSubaccount, WheelAssetType,
+    WheelAssetUiSettings,
 };
 
+use super::{into_account, map_account};
+
 impl From<WheelAssetState> for backend_api::WheelAssetState {
     fn from(state: WheelAssetState) -> Self {
         match state {
@@ -41,51 +46,164 @@ impl From<WheelAssetTokenBalance> for backend_api::WheelAssetTokenBalance {
     }
 }
 
+impl From<WheelAssetTokenFetchStatus> for backend_api::WheelAssetTokenFetchStatus {
+    fn from(value: WheelAssetTokenFetchStatus) -> Self {
+        backend_api::WheelAssetTokenFetchStatus {
+            failed_attempts: value.failed_attempts,
+            last_error: value.last_error,
+            last_failed_at: value.last_failed_at.to_string(),
+        }
+    }
+}
+
 impl From<WheelAssetTokenLedgerConfig> for backend_api::WheelAssetTokenLedgerConfig {
     fn from(value: WheelAssetTokenLedgerConfig) -> Self {
         backend_api::WheelAssetTokenLedgerConfig {
             ledger_canister_id: value.ledger_canister_id,
             decimals: value.decimals,
+            symbol: value.symbol,
+            name: value.name,
         }
     }
 }
 
-impl From<backend_api::WheelAssetTokenLedgerConfig> for WheelAssetTokenLedgerConfig {
-    fn from(value: backend_api::WheelAssetTokenLedgerConfig) -> Self {
+impl From<backend_api::CreateWheelAssetTokenLedgerConfig> for WheelAssetTokenLedgerConfig {
+    /// The decimals and the other metadata are set once fetched from the ledger.
+    fn from(value: backend_api::CreateWheelAssetTokenLedgerConfig) -> Self {
         WheelAssetTokenLedgerConfig {
             ledger_canister_id: value.ledger_canister_id,
-            decimals: value.decimals,
+            decimals: value.decimals.unwrap_or_default(),
+            fee: None,
+            symbol: None,
+            name: None,
         }
     }
 }
 
-impl From<WheelAssetType> for backend_api::WheelAssetType {
-    fn from(asset_type: WheelAssetType) -> Self {
-        match asset_type.clone() {
-            WheelAssetType::Token {
-                ledger_config,
-                exchange_rate_symbol,
-                usd_price,
-                balance,
-                prize_usd_amount,
-            } => backend_api::WheelAssetType::Token {
+impl From<WheelAssetTokenFundingSource> for backend_api::WheelAssetTokenFundingSource {
+    fn from(value: WheelAssetTokenFundingSource) -> Self {
+        match value {
+            WheelAssetTokenFundingSource::CanisterAccount => {
+                backend_api::WheelAssetTokenFundingSource::CanisterAccount
+            }
+            WheelAssetTokenFundingSource::SponsorAllowance { sponsor_account } => {
+                backend_api::WheelAssetTokenFundingSource::SponsorAllowance {
+                    sponsor_account: map_account(sponsor_account),
+                }
+            }
+        }
+    }
+}
+
+impl TryFrom<backend_api::WheelAssetTokenFundingSource> for WheelAssetTokenFundingSource {
+    type Error = ApiError;
+
+    fn try_from(value: backend_api::WheelAssetTokenFundingSource) -> Result<Self, Self::Error> {
+        Ok(match value {
+            backend_api::WheelAssetTokenFundingSource::CanisterAccount => {
+                WheelAssetTokenFundingSource::CanisterAccount
+            }
+            backend_api::WheelAssetTokenFundingSource::SponsorAllowance { sponsor_account } => {
+                WheelAssetTokenFundingSource::SponsorAllowance {
+                    sponsor_account: into_account(sponsor_account)?,
+                }
+            }
+        })
+    }
+}
+
+pub fn map_wheel_asset_type(
+    wheel_asset_id: &WheelAssetId,
+    asset_type: WheelAssetType,
+) -> backend_api::WheelAssetType {
+    match asset_type.clone() {
+        WheelAssetType::Token {
+            ledger_config,
+            exchange_rate_symbol,
+            usd_price,
+            usd_price_fetch_status,
+            funding_source,
+            subaccount: _,
+            balance,
+            balance_fetch_status,
+            prize_usd_amount,
+            prize_token_amount,
+        } => {
+            let deposit_account = Account {
+                owner: ic_cdk::api::canister_self(),
+                subaccount: asset_type.token_subaccount(wheel_asset_id),
+            };
+            let transfer_fee = ledger_config.fee;
+            backend_api::WheelAssetType::Token {
                 ledger_config: ledger_config.into(),
                 exchange_rate_symbol,
                 usd_price: usd_price.map(|el| el.into()),
+                usd_price_fetch_status: usd_price_fetch_status.map(|el| el.into()),
+                funding_source: funding_source
+                    .unwrap_or(WheelAssetTokenFundingSource::CanisterAccount)
+                    .into(),
+                deposit_account: map_account(deposit_account),
+                deposit_account_text: deposit_account.to_string(),
                 balance: balance.map(|el| el.into()),
+                balance_fetch_status: balance_fetch_status.map(|el| el.into()),
                 prize_usd_amount,
+                prize_token_amount,
+                transfer_fee,
                 available_draws_count: asset_type.available_token_draws_count().unwrap_or(0),
-            },
-            WheelAssetType::Gadget { article_type } => {
-                backend_api::WheelAssetType::Gadget { article_type }
             }
-            WheelAssetType::Jackpot { wheel_asset_ids } => backend_api::WheelAssetType::Jackpot {
-                wheel_asset_ids: wheel_asset_ids.iter().map(|el| el.to_string()).collect(),
-            },
+        }
+        WheelAssetType::Gadget {
+            article_type,
+            variants,
+        } => backend_api::WheelAssetType::Gadget {
+            article_type,
+            variants: variants
+                .unwrap_or_default()
+                .into_iter()
+                .map(|variant| variant.into())
+                .collect(),
+        },
+        WheelAssetType::Jackpot { wheel_asset_ids } => backend_api::WheelAssetType::Jackpot {
+            wheel_asset_ids: wheel_asset_ids.iter().map(|el| el.to_string()).collect(),
+        },
+    }
+}
+
+impl From<WheelAssetGadgetVariant> for backend_api::WheelAssetGadgetVariant {
+    fn from(variant: WheelAssetGadgetVariant) -> Self {
+        Self {
+            available_amount: variant.available_amount(),
+            name: variant.name,
+            size: variant.size,
+            color: variant.color,
+            total_amount: variant.total_amount,
+            handed_over_amount: variant.handed_over_amount,
         }
     }
 }
 
+/// The variants that keep the name of one of the `existing_variants`
+/// keep its handed over amount.
+pub fn into_wheel_asset_gadget_variants(
+    variants: Vec<backend_api::WheelAssetGadgetVariantConfig>,
+    existing_variants: &[WheelAssetGadgetVariant],
+) -> Vec<WheelAssetGadgetVariant> {
+    variants
+        .into_iter()
+        .map(|variant| WheelAssetGadgetVariant {
+            handed_over_amount: existing_variants
+                .iter()
+                .find(|existing_variant| existing_variant.name == variant.name)
+                .map(|existing_variant| existing_variant.handed_over_amount)
+                .unwrap_or(0),
+            name: variant.name,
+            size: variant.size,
+            color: variant.color,
+            total_amount: variant.total_amount,
+        })
+        .collect()
+}
+
 pub fn into_wheel_asset_ids(value: Vec<String>) -> Result<Vec<WheelAssetId>, ApiError> {
     value
         .iter()
@@ -102,19 +220,47 @@ impl TryFrom<backend_api::CreateWheelAssetTypeConfig> for WheelAssetType {
                 ledger_config,
                 exchange_rate_symbol,
                 prize_usd_amount,
+                funding_source,
             } => WheelAssetType::Token {
                 ledger_config: ledger_config.into(),
                 usd_price: match &exchange_rate_symbol {
                     Some(_) => None,
                     None => Some(WheelAssetTokenPrice::default_price()),
                 },
+                usd_price_fetch_status: None,
                 exchange_rate_symbol,
+                funding_source: funding_source.map(TryInto::try_into).transpose()?,
+                subaccount: Some(WheelAssetTokenSubaccount::Derived),
                 balance: None,
+                balance_fetch_status: None,
                 prize_usd_amount,
+                prize_token_amount: None,
+            },
+            backend_api::CreateWheelAssetTypeConfig::TokenFixedAmount {
+                ledger_config,
+                exchange_rate_symbol,
+                prize_token_amount,
+                funding_source,
+            } => WheelAssetType::Token {
+                ledger_config: ledger_config.into(),
+                // without an exchange rate symbol, the USD value of the prizes is unknown
+                usd_price: None,
+                usd_price_fetch_status: None,
+                exchange_rate_symbol,
+                funding_source: funding_source.map(TryInto::try_into).transpose()?,
+                subaccount: Some(WheelAssetTokenSubaccount::Derived),
+                balance: None,
+                balance_fetch_status: None,
+                prize_usd_amount: 0.0,
+                prize_token_amount: Some(prize_token_amount),
+            },
+            backend_api::CreateWheelAssetTypeConfig::Gadget {
+                article_type,
+                variants,
+            } => WheelAssetType::Gadget {
+                article_type,
+                variants: variants.map(|variants| into_wheel_asset_gadget_variants(variants, &[])),
             },
-            backend_api::CreateWheelAssetTypeConfig::Gadget { article_type } => {
-                WheelAssetType::Gadget { article_type }
-            }
             backend_api::CreateWheelAssetTypeConfig::Jackpot { wheel_asset_ids } => {
                 WheelAssetType::Jackpot {
                     wheel_asset_ids: into_wheel_asset_ids(wheel_asset_ids)?,
@@ -141,15 +287,43 @@ impl From<backend_api::WheelAssetUiSettings> for WheelAssetUiSettings {
     }
 }
 
+impl From<WheelAssetDrawWeight> for backend_api::WheelAssetDrawWeight {
+    fn from(value: WheelAssetDrawWeight) -> Self {
+        match value {
+            WheelAssetDrawWeight::Fixed { weight } => {
+                backend_api::WheelAssetDrawWeight::Fixed { weight }
+            }
+            WheelAssetDrawWeight::AvailableQuantity => {
+                backend_api::WheelAssetDrawWeight::AvailableQuantity
+            }
+        }
+    }
+}
+
+impl From<backend_api::WheelAssetDrawWeight> for WheelAssetDrawWeight {
+    fn from(value: backend_api::WheelAssetDrawWeight) -> Self {
+        match value {
+            backend_api::WheelAssetDrawWeight::Fixed { weight } => {
+                WheelAssetDrawWeight::Fixed { weight }
+            }
+            backend_api::WheelAssetDrawWeight::AvailableQuantity => {
+                WheelAssetDrawWeight::AvailableQuantity
+            }
+        }
+    }
+}
+
 pub fn map_wheel_asset(
     wheel_asset_id: WheelAssetId,
     wheel_asset: WheelAsset,
+    jackpot_components: &[WheelAsset],
 ) -> backend_api::WheelAsset {
-    let available_amount = wheel_asset.available_quantity();
+    let available_amount = wheel_asset.available_quantity(jackpot_components);
+    let draw_weight = wheel_asset.draw_weight().into();
     backend_api::WheelAsset {
         id: wheel_asset_id.to_string(),
         name: wheel_asset.name,
-        asset_type: wheel_asset.asset_type.into(),
+        asset_type: map_wheel_asset_type(&wheel_asset_id, wheel_asset.asset_type),
         total_amount: wheel_asset.total_amount,
         used_amount: wheel_asset.used_amount,
         available_amount,
@@ -157,12 +331,15 @@ pub fn map_wheel_asset(
         wheel_image_path: wheel_asset.wheel_image_path.map(|el| el.to_string()),
         modal_image_path: wheel_asset.modal_image_path.map(|el| el.to_string()),
         wheel_ui_settings: wheel_asset.wheel_ui_settings.into(),
+        draw_weight,
+        event_id: wheel_asset.event_id.map(|id| id.to_string()),
     }
 }
 
 pub fn map_wheel_prize(
     wheel_asset_id: WheelAssetId,
     wheel_asset: WheelAsset,
+    draw_probability: f64,
 ) -> backend_api::WheelPrize {
     let is_token = wheel_asset.is_token();
     let wheel_image_path = wheel_asset
@@ -188,5 +365,6 @@ pub fn map_wheel_prize(
         modal_image_path,
         wheel_ui_settings: wheel_asset.wheel_ui_settings.clone().into(),
         prize_usd_amount: wheel_asset.prize_usd_amount(),
+        draw_probability,
     }
 }
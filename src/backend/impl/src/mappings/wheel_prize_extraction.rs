@@ -1,7 +1,12 @@
 use crate::repositories::{
-    WheelPrizeExtraction, WheelPrizeExtractionId, WheelPrizeExtractionState,
+    StaleWheelAssetPrice, StaleWheelAssetPriceResolution, WheelPrizeDrawCandidate,
+    WheelPrizeDrawProof, WheelPrizeExcludedDraw, WheelPrizeExtraction, WheelPrizeExtractionId,
+    WheelPrizeExtractionState, WheelPrizeGadgetClaim, WheelPrizePayout, WheelPrizePayoutState,
+    WheelPrizeSelfSpin,
 };
 
+use super::map_account;
+
 impl From<&WheelPrizeExtractionState> for backend_api::WheelPrizeExtractionState {
     fn from(state: &WheelPrizeExtractionState) -> Self {
         match state {
@@ -18,6 +23,13 @@ impl From<&WheelPrizeExtractionState> for backend_api::WheelPrizeExtractionState
                     error: error.clone(),
                 }
             }
+            WheelPrizeExtractionState::PartiallyCompleted {
+                prize_usd_amount,
+                error,
+            } => backend_api::WheelPrizeExtractionState::PartiallyCompleted {
+                prize_usd_amount: *prize_usd_amount,
+                error: error.clone(),
+            },
         }
     }
 }
@@ -28,6 +40,101 @@ impl From<WheelPrizeExtractionState> for backend_api::WheelPrizeExtractionState
     }
 }
 
+/// Only the variant of the mapped state is meaningful, to look up the state index.
+impl From<backend_api::WheelPrizeExtractionStateFilter> for WheelPrizeExtractionState {
+    fn from(state: backend_api::WheelPrizeExtractionStateFilter) -> Self {
+        match state {
+            backend_api::WheelPrizeExtractionStateFilter::Processing => {
+                WheelPrizeExtractionState::Processing
+            }
+            backend_api::WheelPrizeExtractionStateFilter::Completed => {
+                WheelPrizeExtractionState::default_completed()
+            }
+            backend_api::WheelPrizeExtractionStateFilter::Failed => {
+                WheelPrizeExtractionState::default_failed()
+            }
+            backend_api::WheelPrizeExtractionStateFilter::PartiallyCompleted => {
+                WheelPrizeExtractionState::default_partially_completed()
+            }
+        }
+    }
+}
+
+impl From<WheelPrizePayoutState> for backend_api::WheelPrizePayoutState {
+    fn from(state: WheelPrizePayoutState) -> Self {
+        match state {
+            WheelPrizePayoutState::Pending => backend_api::WheelPrizePayoutState::Pending,
+            WheelPrizePayoutState::Completed { block_index } => {
+                backend_api::WheelPrizePayoutState::Completed { block_index }
+            }
+            WheelPrizePayoutState::Failed { error } => {
+                backend_api::WheelPrizePayoutState::Failed { error }
+            }
+        }
+    }
+}
+
+impl From<WheelPrizePayout> for backend_api::WheelPrizePayout {
+    fn from(payout: WheelPrizePayout) -> Self {
+        backend_api::WheelPrizePayout {
+            wheel_asset_id: payout.wheel_asset_id.to_string(),
+            ledger_canister_id: payout.ledger_canister_id,
+            from_account: payout.from_account.map(map_account),
+            amount: payout.amount.into(),
+            memo: payout.memo,
+            created_at_time: payout.created_at_time,
+            state: payout.state.into(),
+        }
+    }
+}
+
+impl From<StaleWheelAssetPrice> for backend_api::StaleWheelAssetPrice {
+    fn from(stale_price: StaleWheelAssetPrice) -> Self {
+        backend_api::StaleWheelAssetPrice {
+            wheel_asset_id: stale_price.wheel_asset_id.to_string(),
+            last_fetched_at: stale_price.last_fetched_at.to_string(),
+            resolution: match stale_price.resolution {
+                StaleWheelAssetPriceResolution::Refreshed { usd_price } => {
+                    backend_api::StaleWheelAssetPriceResolution::Refreshed { usd_price }
+                }
+                StaleWheelAssetPriceResolution::Excluded { error } => {
+                    backend_api::StaleWheelAssetPriceResolution::Excluded { error }
+                }
+            },
+        }
+    }
+}
+
+impl From<WheelPrizeGadgetClaim> for backend_api::WheelPrizeGadgetClaim {
+    fn from(claim: WheelPrizeGadgetClaim) -> Self {
+        match claim {
+            WheelPrizeGadgetClaim::Won => backend_api::WheelPrizeGadgetClaim::Won,
+            WheelPrizeGadgetClaim::HandedOver {
+                handed_over_at,
+                handed_over_by_user_id,
+                gadget_variant_name,
+            } => backend_api::WheelPrizeGadgetClaim::HandedOver {
+                handed_over_at: handed_over_at.to_string(),
+                handed_over_by_user_id: handed_over_by_user_id.to_string(),
+                gadget_variant_name,
+            },
+        }
+    }
+}
+
+impl From<WheelPrizeSelfSpin> for backend_api::WheelPrizeSelfSpin {
+    fn from(self_spin: WheelPrizeSelfSpin) -> Self {
+        match self_spin {
+            WheelPrizeSelfSpin::EventCode => backend_api::WheelPrizeSelfSpin::EventCode,
+            WheelPrizeSelfSpin::InviteCode { invite_code_id } => {
+                backend_api::WheelPrizeSelfSpin::InviteCode {
+                    invite_code_id: invite_code_id.to_string(),
+                }
+            }
+        }
+    }
+}
+
 pub fn map_wheel_prize_extraction(
     wheel_prize_extraction_id: WheelPrizeExtractionId,
     wheel_prize_extraction: WheelPrizeExtraction,
@@ -35,12 +142,120 @@ pub fn map_wheel_prize_extraction(
     backend_api::WheelPrizeExtraction {
         id: wheel_prize_extraction_id.to_string(),
         extracted_for_principal: wheel_prize_extraction.extracted_for_principal,
+        extracted_for_subaccount: wheel_prize_extraction
+            .extracted_for_subaccount
+            .map(|subaccount| subaccount.to_vec()),
         extracted_by_user_id: wheel_prize_extraction.extracted_by_user_id.to_string(),
         state: wheel_prize_extraction.state.into(),
         wheel_asset_id: wheel_prize_extraction
             .wheel_asset_id
             .map(|id| id.to_string()),
+        event_id: wheel_prize_extraction.event_id.map(|id| id.to_string()),
+        payouts: wheel_prize_extraction
+            .payouts
+            .unwrap_or_default()
+            .into_iter()
+            .map(|payout| payout.into())
+            .collect(),
+        stale_prices: wheel_prize_extraction
+            .stale_prices
+            .unwrap_or_default()
+            .into_iter()
+            .map(|stale_price| stale_price.into())
+            .collect(),
+        gadget_claim: wheel_prize_extraction
+            .gadget_claim
+            .map(|claim| claim.into()),
+        self_spin: wheel_prize_extraction
+            .self_spin
+            .map(|self_spin| self_spin.into()),
+        invite_code_id: wheel_prize_extraction
+            .invite_code_id
+            .map(|id| id.to_string()),
         created_at: wheel_prize_extraction.timestamps.created_at.to_string(),
         updated_at: wheel_prize_extraction.timestamps.updated_at.to_string(),
     }
 }
+
+impl From<WheelPrizePayout> for backend_api::MyWheelPrizePayout {
+    fn from(payout: WheelPrizePayout) -> Self {
+        backend_api::MyWheelPrizePayout {
+            ledger_canister_id: payout.ledger_canister_id,
+            amount: payout.amount.into(),
+            block_index: match payout.state {
+                WheelPrizePayoutState::Completed { block_index } => Some(block_index),
+                WheelPrizePayoutState::Pending | WheelPrizePayoutState::Failed { .. } => None,
+            },
+        }
+    }
+}
+
+pub fn map_my_wheel_prize_extraction(
+    wheel_prize_extraction_id: WheelPrizeExtractionId,
+    wheel_prize_extraction: WheelPrizeExtraction,
+    prize_name: Option<String>,
+) -> backend_api::MyWheelPrizeExtraction {
+    backend_api::MyWheelPrizeExtraction {
+        id: wheel_prize_extraction_id.to_string(),
+        state: wheel_prize_extraction.state.into(),
+        wheel_asset_id: wheel_prize_extraction
+            .wheel_asset_id
+            .map(|id| id.to_string()),
+        prize_name,
+        extracted_for_subaccount: wheel_prize_extraction
+            .extracted_for_subaccount
+            .map(|subaccount| subaccount.to_vec()),
+        payouts: wheel_prize_extraction
+            .payouts
+            .unwrap_or_default()
+            .into_iter()
+            .map(|payout| payout.into())
+            .collect(),
+        gadget_claim: wheel_prize_extraction
+            .gadget_claim
+            .map(|claim| claim.into()),
+        created_at: wheel_prize_extraction.timestamps.created_at.to_string(),
+    }
+}
+
+impl From<WheelPrizeDrawCandidate> for backend_api::WheelPrizeDrawCandidate {
+    fn from(candidate: WheelPrizeDrawCandidate) -> Self {
+        backend_api::WheelPrizeDrawCandidate {
+            wheel_asset_id: candidate.wheel_asset_id.to_string(),
+            weight: candidate.weight,
+        }
+    }
+}
+
+impl From<WheelPrizeDrawProof> for backend_api::WheelPrizeDrawProof {
+    fn from(draw_proof: WheelPrizeDrawProof) -> Self {
+        backend_api::WheelPrizeDrawProof {
+            seed: draw_proof.seed.to_vec(),
+            candidates: draw_proof
+                .candidates
+                .into_iter()
+                .map(|candidate| candidate.into())
+                .collect(),
+            chosen_index: draw_proof.chosen_index,
+            excluded_draws: draw_proof
+                .excluded_draws
+                .unwrap_or_default()
+                .into_iter()
+                .map(|excluded_draw| excluded_draw.into())
+                .collect(),
+        }
+    }
+}
+
+impl From<WheelPrizeExcludedDraw> for backend_api::WheelPrizeExcludedDraw {
+    fn from(excluded_draw: WheelPrizeExcludedDraw) -> Self {
+        backend_api::WheelPrizeExcludedDraw {
+            candidates: excluded_draw
+                .candidates
+                .into_iter()
+                .map(|candidate| candidate.into())
+                .collect(),
+            chosen_index: excluded_draw.chosen_index,
+        }
+    }
+}
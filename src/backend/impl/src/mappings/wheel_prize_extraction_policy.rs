@@ -0,0 +1,20 @@
+use crate::repositories::WheelPrizeExtractionPolicy;
+
+pub fn map_wheel_prize_extraction_policy(
+    policy: WheelPrizeExtractionPolicy,
+) -> backend_api::WheelPrizeExtractionPolicy {
+    backend_api::WheelPrizeExtractionPolicy {
+        max_token_price_age_seconds: policy.max_token_price_age_seconds(),
+        self_spin_enabled: policy.is_self_spin_enabled(),
+        self_spin_max_failed_attempts: policy.self_spin_max_failed_attempts(),
+        invite_code_required: policy.is_invite_code_required(),
+        max_wins_per_principal: policy.max_wins_per_principal,
+        max_wins_per_principal_per_day: policy.max_wins_per_principal_per_day,
+        max_wins_per_day: policy.max_wins_per_day,
+        spin_cooldown_seconds: policy.spin_cooldown_seconds,
+        failed_spin_cooldown_seconds: policy.failed_spin_cooldown_seconds,
+        allowed_principals: policy.allowed_principals,
+        denied_principals: policy.denied_principals,
+        updated_at: policy.timestamps.updated_at.to_string(),
+    }
+}
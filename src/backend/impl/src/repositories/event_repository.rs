@@ -0,0 +1,101 @@
+use std::cell::RefCell;
+
+use backend_api::ApiError;
+
+use super::{init_events, Event, EventId, EventMemory, Timestamped};
+
+#[cfg_attr(test, mockall::automock)]
+pub trait EventRepository {
+    fn get_event(&self, id: &EventId) -> Option<Event>;
+
+    /// Returns the event the wheel is currently set up for, if any.
+    fn get_active_event(&self) -> Option<(EventId, Event)>;
+
+    fn list_events(&self) -> Vec<(EventId, Event)>;
+
+    fn create_event(&self, event: Event) -> Result<EventId, ApiError>;
+
+    fn update_event(&self, id: EventId, event: Event) -> Result<(), ApiError>;
+
+    fn delete_event(&self, id: &EventId) -> Result<(), ApiError>;
+}
+
+pub struct EventRepositoryImpl {}
+
+impl Default for EventRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventRepository for EventRepositoryImpl {
+    fn get_event(&self, id: &EventId) -> Option<Event> {
+        STATE.with_borrow(|s| s.events.get(id))
+    }
+
+    fn get_active_event(&self) -> Option<(EventId, Event)> {
+        // there are only a few events, no need for an index
+        STATE.with_borrow(|s| s.events.iter().find(|(_, event)| event.active))
+    }
+
+    fn list_events(&self) -> Vec<(EventId, Event)> {
+        STATE.with_borrow(|s| s.events.iter().rev().collect())
+    }
+
+    fn create_event(&self, event: Event) -> Result<EventId, ApiError> {
+        let id = EventId::new();
+
+        STATE.with_borrow_mut(|s| {
+            s.events.insert(id, event);
+
+            Ok(id)
+        })
+    }
+
+    fn update_event(&self, id: EventId, mut event: Event) -> Result<(), ApiError> {
+        STATE.with_borrow_mut(|s| {
+            if !s.events.contains_key(&id) {
+                return Err(ApiError::not_found(&format!(
+                    "Event with id {} not found",
+                    id
+                )));
+            }
+
+            event.update_timestamp();
+            s.events.insert(id, event);
+
+            Ok(())
+        })
+    }
+
+    fn delete_event(&self, id: &EventId) -> Result<(), ApiError> {
+        STATE.with_borrow_mut(|s| {
+            s.events
+                .remove(id)
+                .map(|_| ())
+                .ok_or_else(|| ApiError::not_found(&format!("Event with id {} not found", id)))
+        })
+    }
+}
+
+impl EventRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+struct EventState {
+    events: EventMemory,
+}
+
+impl Default for EventState {
+    fn default() -> Self {
+        Self {
+            events: init_events(),
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<EventState> = RefCell::new(EventState::default());
+}
@@ -0,0 +1,117 @@
+use std::cell::RefCell;
+
+use backend_api::ApiError;
+
+use super::{
+    init_invite_code_hash_index, init_invite_codes, InviteCode, InviteCodeHashIndexMemory,
+    InviteCodeId, InviteCodeMemory, SelfSpinCodeHash, Timestamped,
+};
+
+#[cfg_attr(test, mockall::automock)]
+pub trait InviteCodeRepository {
+    fn get_invite_code(&self, id: &InviteCodeId) -> Option<InviteCode>;
+
+    fn get_invite_code_by_hash(
+        &self,
+        code_hash: &SelfSpinCodeHash,
+    ) -> Option<(InviteCodeId, InviteCode)>;
+
+    /// Lists all the invite codes, newest first.
+    fn list_invite_codes(&self) -> Vec<(InviteCodeId, InviteCode)>;
+
+    fn create_invite_code(&self, invite_code: InviteCode) -> Result<InviteCodeId, ApiError>;
+
+    fn update_invite_code(&self, id: InviteCodeId, invite_code: InviteCode)
+        -> Result<(), ApiError>;
+}
+
+pub struct InviteCodeRepositoryImpl {}
+
+impl Default for InviteCodeRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InviteCodeRepository for InviteCodeRepositoryImpl {
+    fn get_invite_code(&self, id: &InviteCodeId) -> Option<InviteCode> {
+        STATE.with_borrow(|s| s.invite_codes.get(id))
+    }
+
+    fn get_invite_code_by_hash(
+        &self,
+        code_hash: &SelfSpinCodeHash,
+    ) -> Option<(InviteCodeId, InviteCode)> {
+        STATE.with_borrow(|s| {
+            let id = s.invite_code_hash_index.get(code_hash)?;
+            s.invite_codes.get(&id).map(|invite_code| (id, invite_code))
+        })
+    }
+
+    fn list_invite_codes(&self) -> Vec<(InviteCodeId, InviteCode)> {
+        STATE.with_borrow(|s| s.invite_codes.iter().rev().collect())
+    }
+
+    fn create_invite_code(&self, invite_code: InviteCode) -> Result<InviteCodeId, ApiError> {
+        let id = InviteCodeId::new();
+
+        STATE.with_borrow_mut(|s| {
+            if s.invite_code_hash_index
+                .contains_key(&invite_code.code_hash)
+            {
+                return Err(ApiError::conflict("Invite code already exists"));
+            }
+
+            s.invite_code_hash_index.insert(invite_code.code_hash, id);
+            s.invite_codes.insert(id, invite_code);
+
+            Ok(id)
+        })
+    }
+
+    fn update_invite_code(
+        &self,
+        id: InviteCodeId,
+        mut invite_code: InviteCode,
+    ) -> Result<(), ApiError> {
+        STATE.with_borrow_mut(|s| {
+            let existing_invite_code = s.invite_codes.get(&id).ok_or_else(|| {
+                ApiError::not_found(&format!("Invite code with id {} not found", id))
+            })?;
+            if existing_invite_code.code_hash != invite_code.code_hash {
+                return Err(ApiError::invalid_argument(
+                    "The hash of an invite code can't be changed",
+                ));
+            }
+
+            invite_code.update_timestamp();
+            s.invite_codes.insert(id, invite_code);
+
+            Ok(())
+        })
+    }
+}
+
+impl InviteCodeRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+struct InviteCodeState {
+    invite_codes: InviteCodeMemory,
+    invite_code_hash_index: InviteCodeHashIndexMemory,
+}
+
+impl Default for InviteCodeState {
+    fn default() -> Self {
+        Self {
+            invite_codes: init_invite_codes(),
+            invite_code_hash_index: init_invite_code_hash_index(),
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<InviteCodeState> = RefCell::new(InviteCodeState::default());
+}
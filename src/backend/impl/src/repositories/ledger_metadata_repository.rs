@@ -0,0 +1,55 @@
+use std::cell::RefCell;
+
+use candid::Principal;
+
+use super::{init_ledger_metadata, LedgerMetadata, LedgerMetadataMemory};
+
+#[cfg_attr(test, mockall::automock)]
+pub trait LedgerMetadataRepository {
+    fn get_ledger_metadata(&self, ledger_canister_id: &Principal) -> Option<LedgerMetadata>;
+
+    fn set_ledger_metadata(&self, ledger_canister_id: Principal, ledger_metadata: LedgerMetadata);
+}
+
+pub struct LedgerMetadataRepositoryImpl {}
+
+impl Default for LedgerMetadataRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LedgerMetadataRepository for LedgerMetadataRepositoryImpl {
+    fn get_ledger_metadata(&self, ledger_canister_id: &Principal) -> Option<LedgerMetadata> {
+        STATE.with_borrow(|s| s.ledger_metadata.get(ledger_canister_id))
+    }
+
+    fn set_ledger_metadata(&self, ledger_canister_id: Principal, ledger_metadata: LedgerMetadata) {
+        STATE.with_borrow_mut(|s| {
+            s.ledger_metadata
+                .insert(ledger_canister_id, ledger_metadata);
+        });
+    }
+}
+
+impl LedgerMetadataRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+struct LedgerMetadataState {
+    ledger_metadata: LedgerMetadataMemory,
+}
+
+impl Default for LedgerMetadataState {
+    fn default() -> Self {
+        Self {
+            ledger_metadata: init_ledger_metadata(),
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<LedgerMetadataState> = RefCell::new(LedgerMetadataState::default());
+}
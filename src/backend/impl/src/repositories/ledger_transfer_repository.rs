@@ -0,0 +1,216 @@
+use std::cell::RefCell;
+
+use backend_api::ApiError;
+
+use super::{
+    init_ledger_transfer_block_index, init_ledger_transfer_ledger_index,
+    init_ledger_transfer_ledger_principal_index, init_ledger_transfer_principal_index,
+    init_ledger_transfer_wheel_prize_extraction_id_index, init_ledger_transfers, LedgerTransfer,
+    LedgerTransferBlockIndexMemory, LedgerTransferBlockKey, LedgerTransferFilter, LedgerTransferId,
+    LedgerTransferLedgerIndexMemory, LedgerTransferLedgerPrincipalIndexMemory,
+    LedgerTransferLedgerPrincipalKey, LedgerTransferLedgerPrincipalRange, LedgerTransferMemory,
+    LedgerTransferPrincipalIndexMemory, LedgerTransferPrincipalKey, LedgerTransferPrincipalRange,
+    LedgerTransferWheelPrizeExtractionIdIndexMemory, LedgerTransferWheelPrizeExtractionIdKey,
+    LedgerTransferWheelPrizeExtractionIdRange,
+};
+
+#[cfg_attr(test, mockall::automock)]
+pub trait LedgerTransferRepository {
+    /// Lists at most `limit` transfers matching the filter, oldest first,
+    /// starting after the given transfer if provided.
+    fn list_ledger_transfers(
+        &self,
+        filter: &LedgerTransferFilter,
+        start_after: Option<LedgerTransferId>,
+        limit: usize,
+    ) -> Result<Vec<(LedgerTransferId, LedgerTransfer)>, ApiError>;
+
+    /// Logs the transfer, unless the same ledger block has already been logged
+    /// (e.g. when the ledger reports a retried transfer as a duplicate),
+    /// in which case the id of the logged transfer is returned.
+    fn create_ledger_transfer(
+        &self,
+        ledger_transfer: LedgerTransfer,
+    ) -> Result<LedgerTransferId, ApiError>;
+}
+
+pub struct LedgerTransferRepositoryImpl {}
+
+impl Default for LedgerTransferRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LedgerTransferRepository for LedgerTransferRepositoryImpl {
+    fn list_ledger_transfers(
+        &self,
+        filter: &LedgerTransferFilter,
+        start_after: Option<LedgerTransferId>,
+        limit: usize,
+    ) -> Result<Vec<(LedgerTransferId, LedgerTransfer)>, ApiError> {
+        // items are indexed by uuid v7, which already has the timestamp included,
+        // and each combination of filters is range scanned on the index keyed by it,
+        // except for the extraction id, whose few transfers are filtered by the other criteria
+        STATE.with_borrow(|s| {
+            let ids: Box<dyn Iterator<Item = LedgerTransferId>> = match (
+                filter.wheel_prize_extraction_id,
+                filter.principal,
+                filter.ledger_canister_id,
+            ) {
+                (Some(wheel_prize_extraction_id), _, _) => {
+                    let range = LedgerTransferWheelPrizeExtractionIdRange::new(
+                        wheel_prize_extraction_id,
+                        start_after,
+                    )?;
+                    Box::new(
+                        s.ledger_transfer_wheel_prize_extraction_id_index
+                            .range(range)
+                            .map(|(_, id)| id),
+                    )
+                }
+                (None, Some(principal), Some(ledger_canister_id)) => {
+                    let range = LedgerTransferLedgerPrincipalRange::new(
+                        &ledger_canister_id,
+                        &principal,
+                        start_after,
+                    )?;
+                    Box::new(
+                        s.ledger_transfer_ledger_principal_index
+                            .range(range)
+                            .map(|(_, id)| id),
+                    )
+                }
+                (None, Some(principal), None) => {
+                    let range = LedgerTransferPrincipalRange::new(&principal, start_after)?;
+                    Box::new(
+                        s.ledger_transfer_principal_index
+                            .range(range)
+                            .map(|(_, id)| id),
+                    )
+                }
+                (None, None, Some(ledger_canister_id)) => {
+                    let range =
+                        LedgerTransferPrincipalRange::new(&ledger_canister_id, start_after)?;
+                    Box::new(
+                        s.ledger_transfer_ledger_index
+                            .range(range)
+                            .map(|(_, id)| id),
+                    )
+                }
+                (None, None, None) => match start_after {
+                    Some(start_after) => Box::new(
+                        s.ledger_transfers
+                            .range((
+                                std::ops::Bound::Excluded(start_after),
+                                std::ops::Bound::Unbounded,
+                            ))
+                            .map(|(id, _)| id),
+                    ),
+                    None => Box::new(s.ledger_transfers.iter().map(|(id, _)| id)),
+                },
+            };
+
+            Ok(ids
+                .map(|id| {
+                    // SAFETY: ledger transfer with this id should always exist
+                    (id, s.ledger_transfers.get(&id).unwrap())
+                })
+                .filter(|(_, ledger_transfer)| filter.matches(ledger_transfer))
+                .take(limit)
+                .collect())
+        })
+    }
+
+    fn create_ledger_transfer(
+        &self,
+        ledger_transfer: LedgerTransfer,
+    ) -> Result<LedgerTransferId, ApiError> {
+        let block_key = LedgerTransferBlockKey::new(
+            &ledger_transfer.ledger_canister_id,
+            &ledger_transfer.block_index,
+        )?;
+        if let Some(logged_id) =
+            STATE.with_borrow(|s| s.ledger_transfer_block_index.get(&block_key))
+        {
+            return Ok(logged_id);
+        }
+
+        let id = LedgerTransferId::new();
+        let ledger_key = LedgerTransferPrincipalKey::new(&ledger_transfer.ledger_canister_id, id)?;
+        let principals = ledger_transfer.principals();
+        let principal_keys = principals
+            .iter()
+            .map(|principal| LedgerTransferPrincipalKey::new(principal, id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let ledger_principal_keys = principals
+            .iter()
+            .map(|principal| {
+                LedgerTransferLedgerPrincipalKey::new(
+                    &ledger_transfer.ledger_canister_id,
+                    principal,
+                    id,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let wheel_prize_extraction_id_key = ledger_transfer
+            .wheel_prize_extraction_id
+            .map(|wheel_prize_extraction_id| {
+                LedgerTransferWheelPrizeExtractionIdKey::new(wheel_prize_extraction_id, id)
+            })
+            .transpose()?;
+
+        STATE.with_borrow_mut(|s| {
+            s.ledger_transfers.insert(id, ledger_transfer);
+            s.ledger_transfer_block_index.insert(block_key, id);
+            s.ledger_transfer_ledger_index.insert(ledger_key, id);
+            for principal_key in principal_keys {
+                s.ledger_transfer_principal_index.insert(principal_key, id);
+            }
+            for ledger_principal_key in ledger_principal_keys {
+                s.ledger_transfer_ledger_principal_index
+                    .insert(ledger_principal_key, id);
+            }
+            if let Some(wheel_prize_extraction_id_key) = wheel_prize_extraction_id_key {
+                s.ledger_transfer_wheel_prize_extraction_id_index
+                    .insert(wheel_prize_extraction_id_key, id);
+            }
+        });
+
+        Ok(id)
+    }
+}
+
+impl LedgerTransferRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+struct LedgerTransferState {
+    ledger_transfers: LedgerTransferMemory,
+    ledger_transfer_ledger_index: LedgerTransferLedgerIndexMemory,
+    ledger_transfer_principal_index: LedgerTransferPrincipalIndexMemory,
+    ledger_transfer_wheel_prize_extraction_id_index:
+        LedgerTransferWheelPrizeExtractionIdIndexMemory,
+    ledger_transfer_block_index: LedgerTransferBlockIndexMemory,
+    ledger_transfer_ledger_principal_index: LedgerTransferLedgerPrincipalIndexMemory,
+}
+
+impl Default for LedgerTransferState {
+    fn default() -> Self {
+        Self {
+            ledger_transfers: init_ledger_transfers(),
+            ledger_transfer_ledger_index: init_ledger_transfer_ledger_index(),
+            ledger_transfer_principal_index: init_ledger_transfer_principal_index(),
+            ledger_transfer_wheel_prize_extraction_id_index:
+                init_ledger_transfer_wheel_prize_extraction_id_index(),
+            ledger_transfer_block_index: init_ledger_transfer_block_index(),
+            ledger_transfer_ledger_principal_index: init_ledger_transfer_ledger_principal_index(),
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<LedgerTransferState> = RefCell::new(LedgerTransferState::default());
+}
@@ -0,0 +1,161 @@
+use std::cell::RefCell;
+
+use backend_api::ApiError;
+use candid::Principal;
+
+use super::{
+    init_manual_transfer_limits, init_manual_transfers, DateTime, ManualTransfer, ManualTransferId,
+    ManualTransferLimit, ManualTransferLimitMemory, ManualTransferMemory, Timestamped,
+};
+
+#[cfg_attr(test, mockall::automock)]
+pub trait ManualTransferRepository {
+    fn get_manual_transfer(&self, id: &ManualTransferId) -> Option<ManualTransfer>;
+
+    /// Lists all the manual transfers, newest first.
+    fn list_manual_transfers(&self) -> Vec<(ManualTransferId, ManualTransfer)>;
+
+    /// Lists the manual transfers created at or after the given date time, newest first.
+    fn list_manual_transfers_created_after(
+        &self,
+        created_after: &DateTime,
+    ) -> Vec<(ManualTransferId, ManualTransfer)>;
+
+    fn create_manual_transfer(
+        &self,
+        manual_transfer: ManualTransfer,
+    ) -> Result<ManualTransferId, ApiError>;
+
+    fn update_manual_transfer(
+        &self,
+        id: ManualTransferId,
+        manual_transfer: ManualTransfer,
+    ) -> Result<(), ApiError>;
+
+    fn get_manual_transfer_limit(
+        &self,
+        ledger_canister_id: &Principal,
+    ) -> Option<ManualTransferLimit>;
+
+    fn list_manual_transfer_limits(&self) -> Vec<(Principal, ManualTransferLimit)>;
+
+    fn set_manual_transfer_limit(
+        &self,
+        ledger_canister_id: Principal,
+        manual_transfer_limit: ManualTransferLimit,
+    );
+}
+
+pub struct ManualTransferRepositoryImpl {}
+
+impl Default for ManualTransferRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ManualTransferRepository for ManualTransferRepositoryImpl {
+    fn get_manual_transfer(&self, id: &ManualTransferId) -> Option<ManualTransfer> {
+        STATE.with_borrow(|s| s.manual_transfers.get(id))
+    }
+
+    fn list_manual_transfers(&self) -> Vec<(ManualTransferId, ManualTransfer)> {
+        STATE.with_borrow(|s| s.manual_transfers.iter().rev().collect())
+    }
+
+    fn list_manual_transfers_created_after(
+        &self,
+        created_after: &DateTime,
+    ) -> Vec<(ManualTransferId, ManualTransfer)> {
+        // items are indexed by uuid v7, which already has the timestamp included
+        STATE.with_borrow(|s| {
+            s.manual_transfers
+                .iter()
+                .rev()
+                .take_while(|(_, manual_transfer)| {
+                    manual_transfer.timestamps.created_at >= *created_after
+                })
+                .collect()
+        })
+    }
+
+    fn create_manual_transfer(
+        &self,
+        manual_transfer: ManualTransfer,
+    ) -> Result<ManualTransferId, ApiError> {
+        let id = ManualTransferId::new();
+
+        STATE.with_borrow_mut(|s| {
+            s.manual_transfers.insert(id, manual_transfer);
+
+            Ok(id)
+        })
+    }
+
+    fn update_manual_transfer(
+        &self,
+        id: ManualTransferId,
+        mut manual_transfer: ManualTransfer,
+    ) -> Result<(), ApiError> {
+        STATE.with_borrow_mut(|s| {
+            if !s.manual_transfers.contains_key(&id) {
+                return Err(ApiError::not_found(&format!(
+                    "Manual transfer with id {} not found",
+                    id
+                )));
+            }
+
+            manual_transfer.update_timestamp();
+            s.manual_transfers.insert(id, manual_transfer);
+
+            Ok(())
+        })
+    }
+
+    fn get_manual_transfer_limit(
+        &self,
+        ledger_canister_id: &Principal,
+    ) -> Option<ManualTransferLimit> {
+        STATE.with_borrow(|s| s.manual_transfer_limits.get(ledger_canister_id))
+    }
+
+    fn list_manual_transfer_limits(&self) -> Vec<(Principal, ManualTransferLimit)> {
+        STATE.with_borrow(|s| s.manual_transfer_limits.iter().collect())
+    }
+
+    fn set_manual_transfer_limit(
+        &self,
+        ledger_canister_id: Principal,
+        mut manual_transfer_limit: ManualTransferLimit,
+    ) {
+        manual_transfer_limit.update_timestamp();
+        STATE.with_borrow_mut(|s| {
+            s.manual_transfer_limits
+                .insert(ledger_canister_id, manual_transfer_limit);
+        });
+    }
+}
+
+impl ManualTransferRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+struct ManualTransferState {
+    manual_transfers: ManualTransferMemory,
+    manual_transfer_limits: ManualTransferLimitMemory,
+}
+
+impl Default for ManualTransferState {
+    fn default() -> Self {
+        Self {
+            manual_transfers: init_manual_transfers(),
+            manual_transfer_limits: init_manual_transfer_limits(),
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<ManualTransferState> = RefCell::new(ManualTransferState::default());
+}
@@ -0,0 +1,15 @@
+use ic_stable_structures::BTreeMap;
+
+use crate::repositories::{Event, EventId};
+
+use super::{memory_manager::MEMORY_MANAGER, Memory, EVENTS_MEMORY_ID};
+
+pub type EventMemory = BTreeMap<EventId, Event, Memory>;
+
+pub fn init_events() -> EventMemory {
+    EventMemory::init(get_events_memory())
+}
+
+fn get_events_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(EVENTS_MEMORY_ID))
+}
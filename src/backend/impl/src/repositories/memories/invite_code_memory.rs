@@ -0,0 +1,27 @@
+use ic_stable_structures::BTreeMap;
+
+use crate::repositories::{InviteCode, InviteCodeId, SelfSpinCodeHash};
+
+use super::{
+    memory_manager::MEMORY_MANAGER, Memory, INVITE_CODES_MEMORY_ID,
+    INVITE_CODE_HASH_INDEX_MEMORY_ID,
+};
+
+pub type InviteCodeMemory = BTreeMap<InviteCodeId, InviteCode, Memory>;
+pub type InviteCodeHashIndexMemory = BTreeMap<SelfSpinCodeHash, InviteCodeId, Memory>;
+
+pub fn init_invite_codes() -> InviteCodeMemory {
+    InviteCodeMemory::init(get_invite_codes_memory())
+}
+
+pub fn init_invite_code_hash_index() -> InviteCodeHashIndexMemory {
+    InviteCodeHashIndexMemory::init(get_invite_code_hash_index_memory())
+}
+
+fn get_invite_codes_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(INVITE_CODES_MEMORY_ID))
+}
+
+fn get_invite_code_hash_index_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(INVITE_CODE_HASH_INDEX_MEMORY_ID))
+}
@@ -0,0 +1,16 @@
+use candid::Principal;
+use ic_stable_structures::BTreeMap;
+
+use crate::repositories::LedgerMetadata;
+
+use super::{memory_manager::MEMORY_MANAGER, Memory, LEDGER_METADATA_MEMORY_ID};
+
+pub type LedgerMetadataMemory = BTreeMap<Principal, LedgerMetadata, Memory>;
+
+pub fn init_ledger_metadata() -> LedgerMetadataMemory {
+    LedgerMetadataMemory::init(get_ledger_metadata_memory())
+}
+
+fn get_ledger_metadata_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(LEDGER_METADATA_MEMORY_ID))
+}
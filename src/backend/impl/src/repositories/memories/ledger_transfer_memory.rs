@@ -0,0 +1,84 @@
+use ic_stable_structures::BTreeMap;
+
+use crate::repositories::{
+    LedgerTransfer, LedgerTransferBlockKey, LedgerTransferId, LedgerTransferLedgerPrincipalKey,
+    LedgerTransferPrincipalKey, LedgerTransferWheelPrizeExtractionIdKey,
+};
+
+use super::{
+    memory_manager::MEMORY_MANAGER, Memory, LEDGER_TRANSFERS_MEMORY_ID,
+    LEDGER_TRANSFER_BLOCK_INDEX_MEMORY_ID, LEDGER_TRANSFER_LEDGER_INDEX_MEMORY_ID,
+    LEDGER_TRANSFER_LEDGER_PRINCIPAL_INDEX_MEMORY_ID, LEDGER_TRANSFER_PRINCIPAL_INDEX_MEMORY_ID,
+    LEDGER_TRANSFER_WHEEL_PRIZE_EXTRACTION_ID_INDEX_MEMORY_ID,
+};
+
+pub type LedgerTransferMemory = BTreeMap<LedgerTransferId, LedgerTransfer, Memory>;
+pub type LedgerTransferLedgerIndexMemory =
+    BTreeMap<LedgerTransferPrincipalKey, LedgerTransferId, Memory>;
+pub type LedgerTransferPrincipalIndexMemory =
+    BTreeMap<LedgerTransferPrincipalKey, LedgerTransferId, Memory>;
+pub type LedgerTransferWheelPrizeExtractionIdIndexMemory =
+    BTreeMap<LedgerTransferWheelPrizeExtractionIdKey, LedgerTransferId, Memory>;
+pub type LedgerTransferBlockIndexMemory =
+    BTreeMap<LedgerTransferBlockKey, LedgerTransferId, Memory>;
+pub type LedgerTransferLedgerPrincipalIndexMemory =
+    BTreeMap<LedgerTransferLedgerPrincipalKey, LedgerTransferId, Memory>;
+
+pub fn init_ledger_transfers() -> LedgerTransferMemory {
+    LedgerTransferMemory::init(get_ledger_transfers_memory())
+}
+
+pub fn init_ledger_transfer_ledger_index() -> LedgerTransferLedgerIndexMemory {
+    LedgerTransferLedgerIndexMemory::init(get_ledger_transfer_ledger_index_memory())
+}
+
+pub fn init_ledger_transfer_principal_index() -> LedgerTransferPrincipalIndexMemory {
+    LedgerTransferPrincipalIndexMemory::init(get_ledger_transfer_principal_index_memory())
+}
+
+pub fn init_ledger_transfer_wheel_prize_extraction_id_index(
+) -> LedgerTransferWheelPrizeExtractionIdIndexMemory {
+    LedgerTransferWheelPrizeExtractionIdIndexMemory::init(
+        get_ledger_transfer_wheel_prize_extraction_id_index_memory(),
+    )
+}
+
+pub fn init_ledger_transfer_block_index() -> LedgerTransferBlockIndexMemory {
+    LedgerTransferBlockIndexMemory::init(get_ledger_transfer_block_index_memory())
+}
+
+pub fn init_ledger_transfer_ledger_principal_index() -> LedgerTransferLedgerPrincipalIndexMemory {
+    LedgerTransferLedgerPrincipalIndexMemory::init(
+        get_ledger_transfer_ledger_principal_index_memory(),
+    )
+}
+
+fn get_ledger_transfers_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(LEDGER_TRANSFERS_MEMORY_ID))
+}
+
+fn get_ledger_transfer_ledger_index_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(LEDGER_TRANSFER_LEDGER_INDEX_MEMORY_ID))
+}
+
+fn get_ledger_transfer_principal_index_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(LEDGER_TRANSFER_PRINCIPAL_INDEX_MEMORY_ID))
+}
+
+fn get_ledger_transfer_wheel_prize_extraction_id_index_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| {
+        m.borrow()
+            .get(LEDGER_TRANSFER_WHEEL_PRIZE_EXTRACTION_ID_INDEX_MEMORY_ID)
+    })
+}
+
+fn get_ledger_transfer_block_index_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(LEDGER_TRANSFER_BLOCK_INDEX_MEMORY_ID))
+}
+
+fn get_ledger_transfer_ledger_principal_index_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| {
+        m.borrow()
+            .get(LEDGER_TRANSFER_LEDGER_PRINCIPAL_INDEX_MEMORY_ID)
+    })
+}
@@ -0,0 +1,28 @@
+use candid::Principal;
+use ic_stable_structures::BTreeMap;
+
+use crate::repositories::{ManualTransfer, ManualTransferId, ManualTransferLimit};
+
+use super::{
+    memory_manager::MEMORY_MANAGER, Memory, MANUAL_TRANSFERS_MEMORY_ID,
+    MANUAL_TRANSFER_LIMITS_MEMORY_ID,
+};
+
+pub type ManualTransferMemory = BTreeMap<ManualTransferId, ManualTransfer, Memory>;
+pub type ManualTransferLimitMemory = BTreeMap<Principal, ManualTransferLimit, Memory>;
+
+pub fn init_manual_transfers() -> ManualTransferMemory {
+    ManualTransferMemory::init(get_manual_transfers_memory())
+}
+
+pub fn init_manual_transfer_limits() -> ManualTransferLimitMemory {
+    ManualTransferLimitMemory::init(get_manual_transfer_limits_memory())
+}
+
+fn get_manual_transfers_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(MANUAL_TRANSFERS_MEMORY_ID))
+}
+
+fn get_manual_transfer_limits_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(MANUAL_TRANSFER_LIMITS_MEMORY_ID))
+}
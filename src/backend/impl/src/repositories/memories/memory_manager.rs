@@ -22,5 +22,23 @@ pub(super) const WHEEL_PRIZE_EXTRACTIONS_MEMORY_ID: MemoryId = MemoryId::new(7);
 pub(super) const WHEEL_PRIZE_EXTRACTION_STATE_INDEX_MEMORY_ID: MemoryId = MemoryId::new(8);
 pub(super) const WHEEL_PRIZE_EXTRACTION_ASSET_ID_INDEX_MEMORY_ID: MemoryId = MemoryId::new(9);
 pub(super) const WHEEL_PRIZE_EXTRACTION_USER_ID_INDEX_MEMORY_ID: MemoryId = MemoryId::new(10);
-pub(super) const WHEEL_PRIZE_EXTRACTION_PRINCIPAL_INDEX_MEMORY_ID: MemoryId = MemoryId::new(11);
+// memory ID 11 was used by the old principal index (one extraction per principal), do not reuse it
 pub(super) const CUSTOM_DOMAIN_RECORDS_MEMORY_ID: MemoryId = MemoryId::new(12);
+pub(super) const EVENTS_MEMORY_ID: MemoryId = MemoryId::new(13);
+pub(super) const WHEEL_PRIZE_EXTRACTION_EVENT_ID_INDEX_MEMORY_ID: MemoryId = MemoryId::new(14);
+pub(super) const WHEEL_PRIZE_EXTRACTION_PRINCIPAL_INDEX_MEMORY_ID: MemoryId = MemoryId::new(15);
+pub(super) const WHEEL_PRIZE_EXTRACTION_POLICY_MEMORY_ID: MemoryId = MemoryId::new(16);
+pub(super) const LEDGER_METADATA_MEMORY_ID: MemoryId = MemoryId::new(17);
+pub(super) const LEDGER_TRANSFERS_MEMORY_ID: MemoryId = MemoryId::new(18);
+pub(super) const LEDGER_TRANSFER_LEDGER_INDEX_MEMORY_ID: MemoryId = MemoryId::new(19);
+pub(super) const LEDGER_TRANSFER_PRINCIPAL_INDEX_MEMORY_ID: MemoryId = MemoryId::new(20);
+pub(super) const LEDGER_TRANSFER_WHEEL_PRIZE_EXTRACTION_ID_INDEX_MEMORY_ID: MemoryId =
+    MemoryId::new(21);
+pub(super) const MANUAL_TRANSFERS_MEMORY_ID: MemoryId = MemoryId::new(22);
+pub(super) const MANUAL_TRANSFER_LIMITS_MEMORY_ID: MemoryId = MemoryId::new(23);
+pub(super) const WHEEL_PRIZE_REDEMPTION_SECRET_MEMORY_ID: MemoryId = MemoryId::new(24);
+pub(super) const INVITE_CODES_MEMORY_ID: MemoryId = MemoryId::new(25);
+pub(super) const INVITE_CODE_HASH_INDEX_MEMORY_ID: MemoryId = MemoryId::new(26);
+pub(super) const SELF_SPIN_FAILED_ATTEMPTS_MEMORY_ID: MemoryId = MemoryId::new(27);
+pub(super) const LEDGER_TRANSFER_BLOCK_INDEX_MEMORY_ID: MemoryId = MemoryId::new(28);
+pub(super) const LEDGER_TRANSFER_LEDGER_PRINCIPAL_INDEX_MEMORY_ID: MemoryId = MemoryId::new(29);
@@ -1,13 +1,29 @@
 mod custom_domain_record_memory;
+mod event_memory;
 mod http_asset_memory;
+mod invite_code_memory;
+mod ledger_metadata_memory;
+mod ledger_transfer_memory;
+mod manual_transfer_memory;
 mod memory_manager;
+mod self_spin_memory;
 mod user_profile_memory;
 mod wheel_asset_memory;
 mod wheel_prize_extraction_memory;
+mod wheel_prize_extraction_policy_memory;
+mod wheel_prize_redemption_memory;
 
 pub(super) use custom_domain_record_memory::*;
+pub(super) use event_memory::*;
 pub(super) use http_asset_memory::*;
+pub(super) use invite_code_memory::*;
+pub(super) use ledger_metadata_memory::*;
+pub(super) use ledger_transfer_memory::*;
+pub(super) use manual_transfer_memory::*;
 use memory_manager::*;
+pub(super) use self_spin_memory::*;
 pub(super) use user_profile_memory::*;
 pub(super) use wheel_asset_memory::*;
 pub(super) use wheel_prize_extraction_memory::*;
+pub(super) use wheel_prize_extraction_policy_memory::*;
+pub(super) use wheel_prize_redemption_memory::*;
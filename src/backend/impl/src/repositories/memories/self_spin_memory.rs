@@ -0,0 +1,16 @@
+use candid::Principal;
+use ic_stable_structures::BTreeMap;
+
+use crate::repositories::SelfSpinFailedAttempts;
+
+use super::{memory_manager::MEMORY_MANAGER, Memory, SELF_SPIN_FAILED_ATTEMPTS_MEMORY_ID};
+
+pub type SelfSpinFailedAttemptsMemory = BTreeMap<Principal, SelfSpinFailedAttempts, Memory>;
+
+pub fn init_self_spin_failed_attempts() -> SelfSpinFailedAttemptsMemory {
+    SelfSpinFailedAttemptsMemory::init(get_self_spin_failed_attempts_memory())
+}
+
+fn get_self_spin_failed_attempts_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(SELF_SPIN_FAILED_ATTEMPTS_MEMORY_ID))
+}
@@ -1,14 +1,15 @@
-use candid::Principal;
 use ic_stable_structures::BTreeMap;
 
 use crate::repositories::{
-    WheelPrizeExtraction, WheelPrizeExtractionAssetIdKey, WheelPrizeExtractionId,
-    WheelPrizeExtractionStateKey, WheelPrizeExtractionUserIdKey,
+    WheelPrizeExtraction, WheelPrizeExtractionAssetIdKey, WheelPrizeExtractionEventIdKey,
+    WheelPrizeExtractionId, WheelPrizeExtractionPrincipalKey, WheelPrizeExtractionStateKey,
+    WheelPrizeExtractionUserIdKey,
 };
 
 use super::{
     memory_manager::MEMORY_MANAGER, Memory, WHEEL_PRIZE_EXTRACTIONS_MEMORY_ID,
     WHEEL_PRIZE_EXTRACTION_ASSET_ID_INDEX_MEMORY_ID,
+    WHEEL_PRIZE_EXTRACTION_EVENT_ID_INDEX_MEMORY_ID,
     WHEEL_PRIZE_EXTRACTION_PRINCIPAL_INDEX_MEMORY_ID, WHEEL_PRIZE_EXTRACTION_STATE_INDEX_MEMORY_ID,
     WHEEL_PRIZE_EXTRACTION_USER_ID_INDEX_MEMORY_ID,
 };
@@ -22,7 +23,9 @@ pub type WheelPrizeExtractionAssetIdIndexMemory =
 pub type WheelPrizeExtractionUserIdIndexMemory =
     BTreeMap<WheelPrizeExtractionUserIdKey, WheelPrizeExtractionId, Memory>;
 pub type WheelPrizeExtractionPrincipalIndexMemory =
-    BTreeMap<Principal, WheelPrizeExtractionId, Memory>;
+    BTreeMap<WheelPrizeExtractionPrincipalKey, WheelPrizeExtractionId, Memory>;
+pub type WheelPrizeExtractionEventIdIndexMemory =
+    BTreeMap<WheelPrizeExtractionEventIdKey, WheelPrizeExtractionId, Memory>;
 
 pub fn init_wheel_prize_extractions() -> WheelPrizeExtractionMemory {
     WheelPrizeExtractionMemory::init(get_wheel_prize_extractions_memory())
@@ -46,6 +49,10 @@ pub fn init_wheel_prize_extraction_principal_index() -> WheelPrizeExtractionPrin
     )
 }
 
+pub fn init_wheel_prize_extraction_event_id_index() -> WheelPrizeExtractionEventIdIndexMemory {
+    WheelPrizeExtractionEventIdIndexMemory::init(get_wheel_prize_extraction_event_id_index_memory())
+}
+
 fn get_wheel_prize_extractions_memory() -> Memory {
     MEMORY_MANAGER.with(|m| m.borrow().get(WHEEL_PRIZE_EXTRACTIONS_MEMORY_ID))
 }
@@ -74,3 +81,10 @@ fn get_wheel_prize_extraction_principal_index_memory() -> Memory {
             .get(WHEEL_PRIZE_EXTRACTION_PRINCIPAL_INDEX_MEMORY_ID)
     })
 }
+
+fn get_wheel_prize_extraction_event_id_index_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| {
+        m.borrow()
+            .get(WHEEL_PRIZE_EXTRACTION_EVENT_ID_INDEX_MEMORY_ID)
+    })
+}
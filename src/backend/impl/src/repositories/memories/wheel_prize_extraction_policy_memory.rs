@@ -0,0 +1,19 @@
+use ic_stable_structures::Cell;
+
+use crate::repositories::WheelPrizeExtractionPolicy;
+
+use super::{memory_manager::MEMORY_MANAGER, Memory, WHEEL_PRIZE_EXTRACTION_POLICY_MEMORY_ID};
+
+pub type WheelPrizeExtractionPolicyMemory = Cell<WheelPrizeExtractionPolicy, Memory>;
+
+pub fn init_wheel_prize_extraction_policy() -> WheelPrizeExtractionPolicyMemory {
+    WheelPrizeExtractionPolicyMemory::init(
+        get_wheel_prize_extraction_policy_memory(),
+        WheelPrizeExtractionPolicy::default(),
+    )
+    .expect("Failed to init wheel prize extraction policy memory")
+}
+
+fn get_wheel_prize_extraction_policy_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(WHEEL_PRIZE_EXTRACTION_POLICY_MEMORY_ID))
+}
@@ -0,0 +1,16 @@
+use ic_stable_structures::Cell;
+
+use crate::repositories::WheelPrizeRedemptionSecret;
+
+use super::{memory_manager::MEMORY_MANAGER, Memory, WHEEL_PRIZE_REDEMPTION_SECRET_MEMORY_ID};
+
+pub type WheelPrizeRedemptionSecretMemory = Cell<Option<WheelPrizeRedemptionSecret>, Memory>;
+
+pub fn init_wheel_prize_redemption_secret() -> WheelPrizeRedemptionSecretMemory {
+    WheelPrizeRedemptionSecretMemory::init(get_wheel_prize_redemption_secret_memory(), None)
+        .expect("Failed to init wheel prize redemption secret memory")
+}
+
+fn get_wheel_prize_redemption_secret_memory() -> Memory {
+    MEMORY_MANAGER.with(|m| m.borrow().get(WHEEL_PRIZE_REDEMPTION_SECRET_MEMORY_ID))
+}
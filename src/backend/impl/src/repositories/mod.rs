@@ -1,15 +1,31 @@
 mod custom_domain_record_repository;
+mod event_repository;
 mod http_asset_repository;
+mod invite_code_repository;
+mod ledger_metadata_repository;
+mod ledger_transfer_repository;
+mod manual_transfer_repository;
 mod memories;
+mod self_spin_repository;
 mod types;
 mod user_profile_repository;
 mod wheel_asset_repository;
+mod wheel_prize_extraction_policy_repository;
 mod wheel_prize_extraction_repository;
+mod wheel_prize_redemption_repository;
 
 pub use custom_domain_record_repository::*;
+pub use event_repository::*;
 pub use http_asset_repository::*;
+pub use invite_code_repository::*;
+pub use ledger_metadata_repository::*;
+pub use ledger_transfer_repository::*;
+pub use manual_transfer_repository::*;
 use memories::*;
+pub use self_spin_repository::*;
 pub use types::*;
 pub use user_profile_repository::*;
 pub use wheel_asset_repository::*;
+pub use wheel_prize_extraction_policy_repository::*;
 pub use wheel_prize_extraction_repository::*;
+pub use wheel_prize_redemption_repository::*;
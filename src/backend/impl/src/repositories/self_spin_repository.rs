@@ -0,0 +1,77 @@
+use std::cell::RefCell;
+
+use candid::Principal;
+
+use super::{init_self_spin_failed_attempts, SelfSpinFailedAttempts, SelfSpinFailedAttemptsMemory};
+
+#[cfg_attr(test, mockall::automock)]
+pub trait SelfSpinRepository {
+    fn get_self_spin_failed_attempts(
+        &self,
+        principal: &Principal,
+    ) -> Option<SelfSpinFailedAttempts>;
+
+    fn set_self_spin_failed_attempts(
+        &self,
+        principal: Principal,
+        failed_attempts: SelfSpinFailedAttempts,
+    );
+
+    fn clear_self_spin_failed_attempts(&self, principal: &Principal);
+}
+
+pub struct SelfSpinRepositoryImpl {}
+
+impl Default for SelfSpinRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SelfSpinRepository for SelfSpinRepositoryImpl {
+    fn get_self_spin_failed_attempts(
+        &self,
+        principal: &Principal,
+    ) -> Option<SelfSpinFailedAttempts> {
+        STATE.with_borrow(|s| s.self_spin_failed_attempts.get(principal))
+    }
+
+    fn set_self_spin_failed_attempts(
+        &self,
+        principal: Principal,
+        failed_attempts: SelfSpinFailedAttempts,
+    ) {
+        STATE.with_borrow_mut(|s| {
+            s.self_spin_failed_attempts
+                .insert(principal, failed_attempts);
+        });
+    }
+
+    fn clear_self_spin_failed_attempts(&self, principal: &Principal) {
+        STATE.with_borrow_mut(|s| {
+            s.self_spin_failed_attempts.remove(principal);
+        });
+    }
+}
+
+impl SelfSpinRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+struct SelfSpinState {
+    self_spin_failed_attempts: SelfSpinFailedAttemptsMemory,
+}
+
+impl Default for SelfSpinState {
+    fn default() -> Self {
+        Self {
+            self_spin_failed_attempts: init_self_spin_failed_attempts(),
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<SelfSpinState> = RefCell::new(SelfSpinState::default());
+}
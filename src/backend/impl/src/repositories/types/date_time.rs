@@ -34,10 +34,24 @@ impl DateTime {
         Self::new(dt)
     }
 
+    pub fn add(&self, duration: chrono::Duration) -> Self {
+        Self(self.0 + duration)
+    }
+
     pub fn sub(&self, duration: chrono::Duration) -> Self {
         Self(self.0 - duration)
     }
 
+    /// Returns the midnight (UTC) of the same day.
+    pub fn start_of_day(&self) -> Self {
+        Self(
+            self.0
+                .date_naive()
+                .and_time(chrono::NaiveTime::MIN)
+                .and_utc(),
+        )
+    }
+
     pub fn min() -> Self {
         Self(chrono::DateTime::<chrono::Utc>::UNIX_EPOCH)
     }
@@ -62,11 +76,28 @@ impl DateTime {
         self.0.timestamp_micros().try_into().unwrap()
     }
 
+    pub fn timestamp_millis(&self) -> u64 {
+        self.0.timestamp_millis().try_into().unwrap()
+    }
+
     pub fn timestamp_seconds(&self) -> u64 {
         self.0.timestamp().try_into().unwrap()
     }
 }
 
+impl TryFrom<&str> for DateTime {
+    type Error = ApiError;
+
+    /// Parses an RFC 3339 date time, e.g. `2024-02-02T18:42:30+00:00`.
+    fn try_from(date_time: &str) -> Result<Self, Self::Error> {
+        let date_time = chrono::DateTime::parse_from_rfc3339(date_time).map_err(|_| {
+            ApiError::invalid_argument(&format!("Invalid RFC 3339 date time: {}", date_time))
+        })?;
+
+        Self::new(date_time.into())
+    }
+}
+
 impl Display for DateTime {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&self.0.to_rfc3339_opts(chrono::SecondsFormat::Secs, false))
@@ -120,10 +151,6 @@ pub fn get_current_date_time() -> DateTime {
     get_date_time().and_then(DateTime::new).unwrap()
 }
 
-pub fn elapsed_since(timestamp_ns: &DateTime) -> std::time::Duration {
-    std::time::Duration::from_nanos(ic_cdk::api::time() - timestamp_ns.timestamp_nanos())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +177,26 @@ mod tests {
         assert_eq!(date_time.timestamp_micros(), timestamp);
     }
 
+    #[rstest]
+    fn try_from() {
+        let date_time = fixtures::date_time_a();
+
+        let result = DateTime::try_from(date_time.to_string().as_str()).unwrap();
+
+        assert_eq!(result, date_time);
+        assert!(DateTime::try_from("not a date time").is_err());
+    }
+
+    #[rstest]
+    fn start_of_day() {
+        let date_time = fixtures::date_time_a();
+
+        let result = date_time.start_of_day();
+
+        assert_eq!(result.to_string(), "2021-12-04T00:00:00+00:00");
+        assert_eq!(result.start_of_day(), result);
+    }
+
     #[fixture]
     fn timestamp_micros() -> (u64, String) {
         (1706899350000000, "2024-02-02T18:42:30+00:00".to_string())
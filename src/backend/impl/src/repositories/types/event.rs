@@ -0,0 +1,214 @@
+use std::borrow::Cow;
+
+use backend_api::ApiError;
+use candid::{CandidType, Decode, Deserialize, Encode};
+use ic_stable_structures::{storable::Bound, Storable};
+
+use super::{DateTime, SelfSpinCodeHash, TimestampFields, Timestamped, Uuid};
+
+pub type EventId = Uuid;
+
+/// How many wrong self-spin codes can be submitted during an event, by all the principals,
+/// before its self-spin code is disabled, if the event doesn't set its own limit.
+pub const DEFAULT_EVENT_SELF_SPIN_MAX_FAILED_ATTEMPTS: u32 = 100;
+
+/// An event (e.g. a meetup) that has its own wheel assets, prizes order and extractions.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct Event {
+    pub name: String,
+    pub start_date: DateTime,
+    pub end_date: DateTime,
+    /// Whether the wheel is currently set up for this event.
+    /// At most one event can be active at a time.
+    pub active: bool,
+    /// The hash of the code with which each player can spin the wheel for themselves once
+    /// during this event, if set by an admin.
+    pub self_spin_code_hash: Option<SelfSpinCodeHash>,
+    /// How many self-spins the event code allows in total, not counting the failed extractions.
+    /// A code without it can't be used.
+    pub self_spin_code_max_spins: Option<u32>,
+    /// How many wrong self-spin codes can be submitted during the event before
+    /// the event code is disabled. `None` means [DEFAULT_EVENT_SELF_SPIN_MAX_FAILED_ATTEMPTS].
+    pub self_spin_max_failed_attempts: Option<u32>,
+    /// The wrong self-spin codes submitted during the event since its code was set.
+    pub self_spin_failed_attempts: Option<u32>,
+    pub timestamps: TimestampFields,
+}
+
+impl Event {
+    pub fn new_inactive(name: String, start_date: DateTime, end_date: DateTime) -> Self {
+        Self {
+            name,
+            start_date,
+            end_date,
+            active: false,
+            self_spin_code_hash: None,
+            self_spin_code_max_spins: None,
+            self_spin_max_failed_attempts: None,
+            self_spin_failed_attempts: None,
+            timestamps: TimestampFields::new(),
+        }
+    }
+
+    /// Replaces the self-spin code and its limits, resetting the count of the wrong codes.
+    pub fn set_self_spin_code(
+        &mut self,
+        self_spin_code_hash: Option<SelfSpinCodeHash>,
+        max_spins: Option<u32>,
+        max_failed_attempts: Option<u32>,
+    ) {
+        self.self_spin_code_hash = self_spin_code_hash;
+        self.self_spin_code_max_spins = max_spins;
+        self.self_spin_max_failed_attempts = max_failed_attempts;
+        self.self_spin_failed_attempts = None;
+    }
+
+    pub fn self_spin_max_failed_attempts(&self) -> u32 {
+        self.self_spin_max_failed_attempts
+            .unwrap_or(DEFAULT_EVENT_SELF_SPIN_MAX_FAILED_ATTEMPTS)
+    }
+
+    pub fn self_spin_failed_attempts(&self) -> u32 {
+        self.self_spin_failed_attempts.unwrap_or(0)
+    }
+
+    /// Whether too many wrong codes have been submitted during the event
+    /// for its code to be guessed, so that it can't be used anymore.
+    pub fn is_self_spin_code_disabled(&self) -> bool {
+        self.self_spin_failed_attempts() >= self.self_spin_max_failed_attempts()
+    }
+
+    pub fn record_self_spin_failed_attempt(&mut self) {
+        self.self_spin_failed_attempts = Some(self.self_spin_failed_attempts().saturating_add(1));
+    }
+
+    /// Checks that the event code allows another self-spin,
+    /// given the self-spins already made with it.
+    pub fn assert_self_spin_code_spins_available(&self, spins: usize) -> Result<(), ApiError> {
+        let max_spins = self.self_spin_code_max_spins.unwrap_or(0);
+        if spins >= max_spins as usize {
+            return Err(ApiError::conflict(&format!(
+                "The self-spin code of this event has been used for all its {} spins",
+                max_spins
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the given date time is between the start and end dates of the event.
+    pub fn is_running_at(&self, date_time: &DateTime) -> bool {
+        self.start_date <= *date_time && *date_time <= self.end_date
+    }
+}
+
+impl Timestamped for Event {
+    fn update_timestamp(&mut self) {
+        self.timestamps.update();
+    }
+}
+
+impl Storable for Event {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use rstest::*;
+
+    #[rstest]
+    #[case::inactive(fixtures::event())]
+    #[case::active(fixtures::active_event())]
+    #[case::self_spin_code(fixtures::active_event_with_self_spin_code())]
+    fn storable_impl(#[case] event: Event) {
+        let serialized_event = event.to_bytes();
+        let deserialized_event = Event::from_bytes(serialized_event);
+
+        assert_eq!(event, deserialized_event);
+    }
+
+    #[rstest]
+    fn set_self_spin_code() {
+        let mut event = fixtures::active_event_with_self_spin_code();
+        event.record_self_spin_failed_attempt();
+
+        event.set_self_spin_code(None, Some(5), Some(10));
+
+        assert_eq!(event.self_spin_code_hash, None);
+        assert_eq!(event.self_spin_code_max_spins, Some(5));
+        assert_eq!(event.self_spin_max_failed_attempts(), 10);
+        assert_eq!(event.self_spin_failed_attempts(), 0);
+    }
+
+    #[rstest]
+    #[case::default_not_reached(None, DEFAULT_EVENT_SELF_SPIN_MAX_FAILED_ATTEMPTS - 1, false)]
+    #[case::default_reached(None, DEFAULT_EVENT_SELF_SPIN_MAX_FAILED_ATTEMPTS, true)]
+    #[case::not_reached(Some(3), 2, false)]
+    #[case::reached(Some(3), 3, true)]
+    fn is_self_spin_code_disabled(
+        #[case] max_failed_attempts: Option<u32>,
+        #[case] failed_attempts: u32,
+        #[case] expected: bool,
+    ) {
+        let mut event = Event {
+            self_spin_max_failed_attempts: max_failed_attempts,
+            self_spin_failed_attempts: None,
+            ..fixtures::active_event_with_self_spin_code()
+        };
+        for _ in 0..failed_attempts {
+            event.record_self_spin_failed_attempt();
+        }
+
+        assert_eq!(event.self_spin_failed_attempts(), failed_attempts);
+        assert_eq!(event.is_self_spin_code_disabled(), expected);
+    }
+
+    #[rstest]
+    #[case::available(Some(3), 2, true)]
+    #[case::all_used(Some(3), 3, false)]
+    #[case::no_max_spins(None, 0, false)]
+    fn assert_self_spin_code_spins_available(
+        #[case] max_spins: Option<u32>,
+        #[case] spins: usize,
+        #[case] expected: bool,
+    ) {
+        let event = Event {
+            self_spin_code_max_spins: max_spins,
+            ..fixtures::active_event_with_self_spin_code()
+        };
+
+        let result = event.assert_self_spin_code_spins_available(spins);
+
+        if expected {
+            assert_eq!(result, Ok(()));
+        } else {
+            assert_eq!(
+                result,
+                Err(ApiError::conflict(&format!(
+                    "The self-spin code of this event has been used for all its {} spins",
+                    max_spins.unwrap_or(0)
+                )))
+            );
+        }
+    }
+
+    #[rstest]
+    #[case::before_start(fixtures::date_time_b(), false)]
+    #[case::start(fixtures::date_time_a(), true)]
+    #[case::end(fixtures::date_time_future(), true)]
+    fn is_running_at(#[case] date_time: DateTime, #[case] expected: bool) {
+        let event = fixtures::event();
+
+        assert_eq!(event.is_running_at(&date_time), expected);
+    }
+}
@@ -0,0 +1,390 @@
+use std::borrow::Cow;
+
+use backend_api::ApiError;
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use hmac::{Hmac, Mac};
+use ic_stable_structures::{storable::Bound, Storable};
+use sha2::Sha256;
+
+use super::{
+    DateTime, EventId, SelfSpinCodeHash, TimestampFields, Timestamped, UserId, Uuid,
+    WheelPrizeExtractionId,
+};
+
+pub type InviteCodeId = Uuid;
+
+/// The number of random bytes of an invite code, 80 bits.
+pub const INVITE_CODE_BYTES_SIZE: usize = 10;
+
+/// Crockford's base32 alphabet, which doesn't contain ambiguous letters (I, L, O and U).
+const INVITE_CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const INVITE_CODE_GROUP_SIZE: usize = 4;
+
+/// Formats the random bytes as groups of 4 base32 characters, e.g. `ABCD-EFGH-JKMN-PQRS`.
+pub fn format_invite_code(bytes: [u8; INVITE_CODE_BYTES_SIZE]) -> String {
+    let mut chars = Vec::with_capacity(INVITE_CODE_BYTES_SIZE * 8 / 5);
+    let mut buffer = 0u16;
+    let mut buffer_bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | byte as u16;
+        buffer_bits += 8;
+        while buffer_bits >= 5 {
+            buffer_bits -= 5;
+            chars.push(INVITE_CODE_ALPHABET[((buffer >> buffer_bits) & 0x1f) as usize] as char);
+        }
+    }
+
+    chars
+        .chunks(INVITE_CODE_GROUP_SIZE)
+        .map(|group| group.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Derives the invite code at the given index of a batch from the random seed of the batch,
+/// so that a whole batch can be generated with a single call to the management canister.
+pub fn derive_invite_code(seed: &[u8; 32], index: u32) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(seed).expect("HMAC accepts keys of any size");
+    mac.update(&index.to_be_bytes());
+    let tag = mac.finalize().into_bytes();
+
+    let mut code_bytes = [0; INVITE_CODE_BYTES_SIZE];
+    code_bytes.copy_from_slice(&tag[..INVITE_CODE_BYTES_SIZE]);
+    format_invite_code(code_bytes)
+}
+
+/// What a batch of invite codes is issued for.
+#[derive(Debug, CandidType, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum InviteCodePurpose {
+    /// Proves that the winner attended, when an admin or a scanner extracts them.
+    Attendance,
+    /// Lets a player spin the wheel for themselves.
+    SelfSpin,
+}
+
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub enum InviteCodeState {
+    Available,
+    Used {
+        principal: Principal,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+        used_at: DateTime,
+    },
+    Revoked {
+        revoked_at: DateTime,
+        revoked_by_user_id: UserId,
+    },
+}
+
+/// A single-use code issued by an admin, either to prove the attendance of a winner
+/// or to let a player spin the wheel for themselves. Only the hash of the code is stored.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct InviteCode {
+    pub code_hash: SelfSpinCodeHash,
+    /// If set, only this principal can use the code.
+    pub principal: Option<Principal>,
+    /// If set, the code can be used only during this event.
+    pub event_id: Option<EventId>,
+    /// The codes issued before the purposes were introduced don't have it,
+    /// and can be used for any purpose.
+    pub purpose: Option<InviteCodePurpose>,
+    pub state: InviteCodeState,
+    pub created_by_user_id: UserId,
+    pub timestamps: TimestampFields,
+}
+
+impl InviteCode {
+    pub fn new_available(
+        code_hash: SelfSpinCodeHash,
+        principal: Option<Principal>,
+        event_id: Option<EventId>,
+        purpose: InviteCodePurpose,
+        created_by_user_id: UserId,
+    ) -> Self {
+        Self {
+            code_hash,
+            principal,
+            event_id,
+            purpose: Some(purpose),
+            state: InviteCodeState::Available,
+            created_by_user_id,
+            timestamps: TimestampFields::new(),
+        }
+    }
+
+    pub fn has_purpose(&self, purpose: InviteCodePurpose) -> bool {
+        self.purpose
+            .is_none_or(|code_purpose| code_purpose == purpose)
+    }
+
+    /// A code can be used again by the same principal if the extraction that used it failed,
+    /// as checked by `is_extraction_failed`.
+    pub fn assert_can_be_used(
+        &self,
+        principal: &Principal,
+        event_id: Option<EventId>,
+        is_extraction_failed: impl FnOnce(&WheelPrizeExtractionId) -> bool,
+    ) -> Result<(), ApiError> {
+        if self
+            .principal
+            .is_some_and(|code_principal| code_principal != *principal)
+        {
+            return Err(ApiError::permission_denied(
+                "Invite code was issued for another principal",
+            ));
+        }
+
+        if self.event_id.is_some() && self.event_id != event_id {
+            return Err(ApiError::permission_denied(
+                "Invite code is not valid for the current event",
+            ));
+        }
+
+        match &self.state {
+            InviteCodeState::Available => Ok(()),
+            InviteCodeState::Used {
+                principal: used_by_principal,
+                wheel_prize_extraction_id,
+                ..
+            } if used_by_principal == principal
+                && is_extraction_failed(wheel_prize_extraction_id) =>
+            {
+                Ok(())
+            }
+            InviteCodeState::Used { .. } => {
+                Err(ApiError::conflict("Invite code has already been used"))
+            }
+            InviteCodeState::Revoked { .. } => {
+                Err(ApiError::permission_denied("Invite code has been revoked"))
+            }
+        }
+    }
+
+    /// Only the codes that haven't been used yet can be revoked.
+    pub fn revoke(
+        &mut self,
+        revoked_by_user_id: UserId,
+        revoked_at: DateTime,
+    ) -> Result<(), ApiError> {
+        match self.state {
+            InviteCodeState::Available => {
+                self.state = InviteCodeState::Revoked {
+                    revoked_at,
+                    revoked_by_user_id,
+                };
+                Ok(())
+            }
+            InviteCodeState::Used { .. } => {
+                Err(ApiError::conflict("Invite code has already been used"))
+            }
+            InviteCodeState::Revoked { .. } => {
+                Err(ApiError::conflict("Invite code has already been revoked"))
+            }
+        }
+    }
+
+    pub fn set_used(
+        &mut self,
+        principal: Principal,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+        used_at: DateTime,
+    ) {
+        self.state = InviteCodeState::Used {
+            principal,
+            wheel_prize_extraction_id,
+            used_at,
+        };
+    }
+}
+
+impl Timestamped for InviteCode {
+    fn update_timestamp(&mut self) {
+        self.timestamps.update();
+    }
+}
+
+impl Storable for InviteCode {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures, repositories::hash_code};
+    use rstest::*;
+
+    #[rstest]
+    #[case::available(fixtures::invite_code())]
+    #[case::used(fixtures::invite_code_used())]
+    #[case::revoked(fixtures::invite_code_revoked())]
+    fn storable_impl(#[case] invite_code: InviteCode) {
+        let serialized_invite_code = invite_code.to_bytes();
+        let deserialized_invite_code = InviteCode::from_bytes(serialized_invite_code);
+
+        assert_eq!(invite_code, deserialized_invite_code);
+    }
+
+    #[rstest]
+    #[case::zeros([0; 10], "0000-0000-0000-0000")]
+    #[case::ones([0xff; 10], "ZZZZ-ZZZZ-ZZZZ-ZZZZ")]
+    #[case::mixed(
+        [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01, 0x23],
+        "04HM-ASW9-NF6Y-Y093"
+    )]
+    fn format_invite_code_base32(#[case] bytes: [u8; 10], #[case] expected: &str) {
+        let invite_code = format_invite_code(bytes);
+
+        assert_eq!(invite_code, expected);
+        assert_eq!(
+            hash_code(&invite_code),
+            hash_code(&invite_code.to_lowercase().replace('-', ""))
+        );
+    }
+
+    #[rstest]
+    fn assert_can_be_used() {
+        let invite_code = fixtures::invite_code();
+
+        assert_eq!(
+            invite_code.assert_can_be_used(
+                &fixtures::principal(),
+                Some(fixtures::uuid_a()),
+                |_| false
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            invite_code.assert_can_be_used(
+                &Principal::from_slice(&[1]),
+                Some(fixtures::uuid_a()),
+                |_| false
+            ),
+            Err(ApiError::permission_denied(
+                "Invite code was issued for another principal"
+            ))
+        );
+        assert_eq!(
+            invite_code.assert_can_be_used(&fixtures::principal(), None, |_| false),
+            Err(ApiError::permission_denied(
+                "Invite code is not valid for the current event"
+            ))
+        );
+    }
+
+    #[rstest]
+    #[case::extraction_succeeded(
+        false,
+        Err(ApiError::conflict("Invite code has already been used"))
+    )]
+    #[case::extraction_failed(true, Ok(()))]
+    fn assert_can_be_used_used(
+        #[case] is_extraction_failed: bool,
+        #[case] expected: Result<(), ApiError>,
+    ) {
+        let invite_code = fixtures::invite_code_used();
+
+        assert_eq!(
+            invite_code.assert_can_be_used(
+                &fixtures::principal(),
+                Some(fixtures::uuid_a()),
+                |id| {
+                    assert_eq!(*id, fixtures::uuid_b());
+                    is_extraction_failed
+                }
+            ),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case::attendance(
+        Some(InviteCodePurpose::Attendance),
+        InviteCodePurpose::Attendance,
+        true
+    )]
+    #[case::other_purpose(
+        Some(InviteCodePurpose::Attendance),
+        InviteCodePurpose::SelfSpin,
+        false
+    )]
+    #[case::no_purpose(None, InviteCodePurpose::SelfSpin, true)]
+    fn has_purpose(
+        #[case] code_purpose: Option<InviteCodePurpose>,
+        #[case] purpose: InviteCodePurpose,
+        #[case] expected: bool,
+    ) {
+        let invite_code = InviteCode {
+            purpose: code_purpose,
+            ..fixtures::invite_code()
+        };
+
+        assert_eq!(invite_code.has_purpose(purpose), expected);
+    }
+
+    #[rstest]
+    fn derive_invite_code_batch() {
+        let seed = [3; 32];
+
+        let first_code = derive_invite_code(&seed, 0);
+
+        assert_eq!(first_code.len(), 19);
+        assert_eq!(first_code, derive_invite_code(&seed, 0));
+        assert_ne!(first_code, derive_invite_code(&seed, 1));
+        assert_ne!(first_code, derive_invite_code(&[4; 32], 0));
+    }
+
+    #[rstest]
+    fn assert_can_be_used_revoked() {
+        let invite_code = fixtures::invite_code_revoked();
+
+        assert_eq!(
+            invite_code.assert_can_be_used(
+                &fixtures::principal(),
+                Some(fixtures::uuid_a()),
+                |_| true
+            ),
+            Err(ApiError::permission_denied("Invite code has been revoked"))
+        );
+    }
+
+    #[rstest]
+    #[case::available(fixtures::invite_code(), None)]
+    #[case::used(
+        fixtures::invite_code_used(),
+        Some(ApiError::conflict("Invite code has already been used"))
+    )]
+    #[case::revoked(
+        fixtures::invite_code_revoked(),
+        Some(ApiError::conflict("Invite code has already been revoked"))
+    )]
+    fn revoke(#[case] mut invite_code: InviteCode, #[case] expected_error: Option<ApiError>) {
+        let previous_state = invite_code.state.clone();
+
+        let result = invite_code.revoke(fixtures::uuid_b(), fixtures::date_time_a());
+
+        match expected_error {
+            None => {
+                assert_eq!(result, Ok(()));
+                assert_eq!(
+                    invite_code.state,
+                    InviteCodeState::Revoked {
+                        revoked_at: fixtures::date_time_a(),
+                        revoked_by_user_id: fixtures::uuid_b(),
+                    }
+                );
+            }
+            Some(error) => {
+                assert_eq!(result, Err(error));
+                assert_eq!(invite_code.state, previous_state);
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::borrow::Cow;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use candid::{CandidType, Decode, Deserialize, Encode};
+use ic_stable_structures::{storable::Bound, Storable};
+use icrc_ledger_types::icrc::generic_metadata_value::MetadataValue;
+
+use super::{get_current_date_time, DateTime};
+
+/// The metadata of an ICRC-1 ledger, fetched from the ledger itself
+/// and cached to avoid calling the ledger for each token asset.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct LedgerMetadata {
+    /// The fee of a transfer, in token base units.
+    pub fee: u128,
+    pub decimals: u8,
+    /// `None` for the metadata fetched before the symbol was stored.
+    pub symbol: Option<String>,
+    /// `None` for the metadata fetched before the name was stored.
+    pub name: Option<String>,
+    /// The `icrc1:logo` data URL, if the ledger provides one.
+    pub logo: Option<String>,
+    pub last_fetched_at: DateTime,
+}
+
+impl LedgerMetadata {
+    pub fn new(
+        fee: u128,
+        decimals: u8,
+        symbol: String,
+        name: String,
+        logo: Option<String>,
+    ) -> Self {
+        Self {
+            fee,
+            decimals,
+            symbol: Some(symbol),
+            name: Some(name),
+            logo,
+            last_fetched_at: get_current_date_time(),
+        }
+    }
+
+    /// Whether the metadata has been fetched more than the given age ago.
+    pub fn is_stale(&self, max_age_seconds: u64, now: &DateTime) -> bool {
+        now.timestamp_seconds()
+            .saturating_sub(self.last_fetched_at.timestamp_seconds())
+            > max_age_seconds
+    }
+
+    /// The content type and the bytes of the logo,
+    /// if it's a base64 encoded data URL (e.g. `data:image/png;base64,...`).
+    pub fn logo_image(&self) -> Option<(String, Vec<u8>)> {
+        let (media_type, data) = self.logo.as_ref()?.strip_prefix("data:")?.split_once(',')?;
+        let content_type = media_type.strip_suffix(";base64")?;
+        let content_bytes = BASE64.decode(data).ok()?;
+
+        Some((content_type.to_string(), content_bytes))
+    }
+}
+
+impl Storable for LedgerMetadata {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// The standard entries of the metadata returned by `icrc1_metadata`.
+/// Missing or invalid entries are `None`, and must be fetched with their own endpoints.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Icrc1MetadataEntries {
+    pub fee: Option<u128>,
+    pub decimals: Option<u8>,
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub logo: Option<String>,
+}
+
+impl Icrc1MetadataEntries {
+    pub fn new(metadata: &[(String, MetadataValue)]) -> Self {
+        let mut entries = Self::default();
+
+        for (key, value) in metadata {
+            match (key.as_str(), value) {
+                ("icrc1:fee", MetadataValue::Nat(fee)) => {
+                    entries.fee = u128::try_from(fee.0.clone()).ok();
+                }
+                ("icrc1:decimals", MetadataValue::Nat(decimals)) => {
+                    entries.decimals = u8::try_from(decimals.0.clone()).ok();
+                }
+                ("icrc1:symbol", MetadataValue::Text(symbol)) => {
+                    entries.symbol = Some(symbol.clone());
+                }
+                ("icrc1:name", MetadataValue::Text(name)) => {
+                    entries.name = Some(name.clone());
+                }
+                ("icrc1:logo", MetadataValue::Text(logo)) => {
+                    entries.logo = Some(logo.clone());
+                }
+                _ => {}
+            }
+        }
+
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use candid::Nat;
+    use rstest::*;
+
+    #[rstest]
+    fn storable_impl() {
+        let ledger_metadata = fixtures::ledger_metadata();
+
+        let serialized_ledger_metadata = ledger_metadata.to_bytes();
+        let deserialized_ledger_metadata = LedgerMetadata::from_bytes(serialized_ledger_metadata);
+
+        assert_eq!(ledger_metadata, deserialized_ledger_metadata);
+    }
+
+    #[rstest]
+    #[case::fresh(60, false)]
+    #[case::max_age(3_600, false)]
+    #[case::stale(3_601, true)]
+    fn is_stale(#[case] seconds_after_fetch: u64, #[case] expected_stale: bool) {
+        let ledger_metadata = fixtures::ledger_metadata();
+        let now = DateTime::from_timestamp_micros(
+            ledger_metadata.last_fetched_at.timestamp_micros() + seconds_after_fetch * 1_000_000,
+        )
+        .unwrap();
+
+        assert_eq!(ledger_metadata.is_stale(3_600, &now), expected_stale);
+    }
+
+    #[rstest]
+    #[case::png(
+        Some("data:image/png;base64,iVBORw0K"),
+        Some(("image/png", vec![0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a]))
+    )]
+    #[case::svg(
+        Some("data:image/svg+xml;base64,PHN2Zz4="),
+        Some(("image/svg+xml", b"<svg>".to_vec()))
+    )]
+    #[case::not_base64(Some("data:image/svg+xml,<svg></svg>"), None)]
+    #[case::invalid_base64(Some("data:image/png;base64,!!!"), None)]
+    #[case::not_data_url(Some("https://example.com/logo.png"), None)]
+    #[case::no_logo(None, None)]
+    fn logo_image(#[case] logo: Option<&str>, #[case] expected: Option<(&str, Vec<u8>)>) {
+        let ledger_metadata = LedgerMetadata {
+            logo: logo.map(ToString::to_string),
+            ..fixtures::ledger_metadata()
+        };
+
+        assert_eq!(
+            ledger_metadata.logo_image(),
+            expected.map(|(content_type, bytes)| (content_type.to_string(), bytes))
+        );
+    }
+
+    #[rstest]
+    fn icrc1_metadata_entries() {
+        let metadata = vec![
+            MetadataValue::entry("icrc1:decimals", Nat::from(8u8)),
+            MetadataValue::entry("icrc1:fee", Nat::from(10_000u64)),
+            MetadataValue::entry("icrc1:symbol", "ckBTC"),
+            MetadataValue::entry("icrc1:name", "ckBTC"),
+            MetadataValue::entry("icrc1:logo", "data:image/png;base64,iVBORw0K"),
+            MetadataValue::entry("icrc1:max_memo_length", Nat::from(80u8)),
+        ];
+
+        assert_eq!(
+            Icrc1MetadataEntries::new(&metadata),
+            Icrc1MetadataEntries {
+                fee: Some(10_000),
+                decimals: Some(8),
+                symbol: Some("ckBTC".to_string()),
+                name: Some("ckBTC".to_string()),
+                logo: Some("data:image/png;base64,iVBORw0K".to_string()),
+            }
+        );
+    }
+
+    #[rstest]
+    fn icrc1_metadata_entries_missing_or_invalid() {
+        let metadata = vec![
+            MetadataValue::entry("icrc1:decimals", Nat::from(256u16)),
+            MetadataValue::entry("icrc1:fee", "10000"),
+        ];
+
+        assert_eq!(
+            Icrc1MetadataEntries::new(&metadata),
+            Icrc1MetadataEntries::default()
+        );
+    }
+}
@@ -0,0 +1,660 @@
+use std::{borrow::Cow, ops::RangeBounds};
+
+use backend_api::ApiError;
+use candid::{CandidType, Decode, Deserialize, Encode, Nat, Principal};
+use ic_stable_structures::{
+    storable::{Blob, Bound},
+    Storable,
+};
+use icrc_ledger_types::{icrc1::account::Account, icrc3::transactions::Transaction};
+
+use super::{
+    get_current_date_time, wheel_prize_extraction::PRINCIPAL_MAX_SIZE, DateTime, UserId, Uuid,
+    WheelAssetId, WheelPrizeExtractionId,
+};
+
+pub type LedgerTransferId = Uuid;
+
+/// An outgoing transfer executed by a ledger,
+/// logged so that it can be reconciled against the ledger blocks.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct LedgerTransfer {
+    pub ledger_canister_id: Principal,
+    /// The account the tokens are transferred from: a canister account,
+    /// or the sponsor's account for the transfers made with `icrc2_transfer_from`.
+    pub from: Account,
+    pub to: Account,
+    pub amount: Nat,
+    /// The fee set in the transfer, `None` if the ledger's fee was used.
+    pub fee: Option<Nat>,
+    pub memo: Option<Vec<u8>>,
+    pub block_index: Nat,
+    /// The user who initiated the transfer, if any.
+    pub initiated_by_user_id: Option<UserId>,
+    /// The extraction the transfer pays out, if any.
+    pub wheel_prize_extraction_id: Option<WheelPrizeExtractionId>,
+    /// The token asset whose funds are transferred, if any.
+    pub wheel_asset_id: Option<WheelAssetId>,
+    pub created_at: DateTime,
+}
+
+impl LedgerTransfer {
+    pub fn new(
+        ledger_canister_id: Principal,
+        from: Account,
+        to: Account,
+        amount: Nat,
+        fee: Option<Nat>,
+        memo: Option<Vec<u8>>,
+        block_index: Nat,
+    ) -> Self {
+        Self {
+            ledger_canister_id,
+            from,
+            to,
+            amount,
+            fee,
+            memo,
+            block_index,
+            initiated_by_user_id: None,
+            wheel_prize_extraction_id: None,
+            wheel_asset_id: None,
+            created_at: get_current_date_time(),
+        }
+    }
+
+    /// The owners of the accounts involved in the transfer, used to index it by principal.
+    pub fn principals(&self) -> Vec<Principal> {
+        if self.from.owner == self.to.owner {
+            vec![self.from.owner]
+        } else {
+            vec![self.from.owner, self.to.owner]
+        }
+    }
+}
+
+/// The deduplication parameters of a transfer sent to a ledger, used to look it up
+/// in the ledger's transactions when the ledger can't deduplicate it anymore.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentLedgerTransfer {
+    pub ledger_canister_id: Principal,
+    pub to: Account,
+    pub amount: u128,
+    pub memo: Vec<u8>,
+    pub created_at_time: u64,
+}
+
+impl SentLedgerTransfer {
+    /// Whether the ledger transaction is this transfer.
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        transaction.transfer.as_ref().is_some_and(|transfer| {
+            transfer.to == self.to
+                && transfer.amount == self.amount
+                && transfer.created_at_time == Some(self.created_at_time)
+                && transfer
+                    .memo
+                    .as_ref()
+                    .is_some_and(|memo| memo.0.as_slice() == self.memo.as_slice())
+        })
+    }
+}
+
+/// The criteria to list the transfers, all of which must match.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LedgerTransferFilter {
+    pub ledger_canister_id: Option<Principal>,
+    /// The owner of either the source or the destination account.
+    pub principal: Option<Principal>,
+    pub wheel_prize_extraction_id: Option<WheelPrizeExtractionId>,
+}
+
+impl LedgerTransferFilter {
+    pub fn matches(&self, ledger_transfer: &LedgerTransfer) -> bool {
+        self.ledger_canister_id.is_none_or(|ledger_canister_id| {
+            ledger_transfer.ledger_canister_id == ledger_canister_id
+        }) && self
+            .principal
+            .is_none_or(|principal| ledger_transfer.principals().contains(&principal))
+            && self
+                .wheel_prize_extraction_id
+                .is_none_or(|wheel_prize_extraction_id| {
+                    ledger_transfer.wheel_prize_extraction_id == Some(wheel_prize_extraction_id)
+                })
+    }
+}
+
+impl Storable for LedgerTransfer {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// Indexes the transfers by principal and id,
+/// used both for the ledgers and for the owners of the accounts.
+///
+/// The principal is stored as its length followed by its bytes padded to the maximum principal size,
+/// so that all the keys have the same size and the transfers of a principal are sorted by id.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LedgerTransferPrincipalKey(Blob<{ Self::MAX_SIZE as usize }>);
+
+impl LedgerTransferPrincipalKey {
+    const MAX_SIZE: u32 = 1 + PRINCIPAL_MAX_SIZE as u32 + LedgerTransferId::BOUND.max_size();
+
+    pub fn new(
+        principal: &Principal,
+        ledger_transfer_id: LedgerTransferId,
+    ) -> Result<Self, ApiError> {
+        let mut bytes = Vec::with_capacity(Self::MAX_SIZE as usize);
+        extend_with_padded_principal(&mut bytes, principal);
+        bytes.extend_from_slice(&ledger_transfer_id.to_bytes());
+
+        Ok(Self(Blob::try_from(bytes.as_slice()).map_err(|_| {
+            ApiError::internal(&format!(
+                "Failed to convert principal {}, ledger transfer id {:?} to bytes.",
+                principal, ledger_transfer_id
+            ))
+        })?))
+    }
+}
+
+impl Storable for LedgerTransferPrincipalKey {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        self.0.to_bytes()
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(Blob::from_bytes(bytes))
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: Self::MAX_SIZE,
+        is_fixed_size: true,
+    };
+}
+
+pub struct LedgerTransferPrincipalRange {
+    start_bound: LedgerTransferPrincipalKey,
+    start_after: bool,
+    end_bound: LedgerTransferPrincipalKey,
+}
+
+impl LedgerTransferPrincipalRange {
+    /// The range of the transfers of the principal,
+    /// starting after the given transfer if provided.
+    pub fn new(
+        principal: &Principal,
+        start_after: Option<LedgerTransferId>,
+    ) -> Result<Self, ApiError> {
+        Ok(Self {
+            start_bound: LedgerTransferPrincipalKey::new(
+                principal,
+                start_after.unwrap_or_else(Uuid::min),
+            )?,
+            start_after: start_after.is_some(),
+            end_bound: LedgerTransferPrincipalKey::new(principal, Uuid::max())?,
+        })
+    }
+}
+
+impl RangeBounds<LedgerTransferPrincipalKey> for LedgerTransferPrincipalRange {
+    fn start_bound(&self) -> std::ops::Bound<&LedgerTransferPrincipalKey> {
+        if self.start_after {
+            std::ops::Bound::Excluded(&self.start_bound)
+        } else {
+            std::ops::Bound::Included(&self.start_bound)
+        }
+    }
+
+    fn end_bound(&self) -> std::ops::Bound<&LedgerTransferPrincipalKey> {
+        std::ops::Bound::Included(&self.end_bound)
+    }
+}
+
+/// Indexes the transfers by ledger, principal and id,
+/// so that the transfers of a principal on a ledger can be listed without scanning the others.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LedgerTransferLedgerPrincipalKey(Blob<{ Self::MAX_SIZE as usize }>);
+
+impl LedgerTransferLedgerPrincipalKey {
+    const MAX_SIZE: u32 = 2 * (1 + PRINCIPAL_MAX_SIZE as u32) + LedgerTransferId::BOUND.max_size();
+
+    pub fn new(
+        ledger_canister_id: &Principal,
+        principal: &Principal,
+        ledger_transfer_id: LedgerTransferId,
+    ) -> Result<Self, ApiError> {
+        let mut bytes = Vec::with_capacity(Self::MAX_SIZE as usize);
+        extend_with_padded_principal(&mut bytes, ledger_canister_id);
+        extend_with_padded_principal(&mut bytes, principal);
+        bytes.extend_from_slice(&ledger_transfer_id.to_bytes());
+
+        Ok(Self(Blob::try_from(bytes.as_slice()).map_err(|_| {
+            ApiError::internal(&format!(
+                "Failed to convert ledger canister id {}, principal {}, ledger transfer id {:?} to bytes.",
+                ledger_canister_id, principal, ledger_transfer_id
+            ))
+        })?))
+    }
+}
+
+impl Storable for LedgerTransferLedgerPrincipalKey {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        self.0.to_bytes()
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(Blob::from_bytes(bytes))
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: Self::MAX_SIZE,
+        is_fixed_size: true,
+    };
+}
+
+pub struct LedgerTransferLedgerPrincipalRange {
+    start_bound: LedgerTransferLedgerPrincipalKey,
+    start_after: bool,
+    end_bound: LedgerTransferLedgerPrincipalKey,
+}
+
+impl LedgerTransferLedgerPrincipalRange {
+    /// The range of the transfers of the principal on the ledger,
+    /// starting after the given transfer if provided.
+    pub fn new(
+        ledger_canister_id: &Principal,
+        principal: &Principal,
+        start_after: Option<LedgerTransferId>,
+    ) -> Result<Self, ApiError> {
+        Ok(Self {
+            start_bound: LedgerTransferLedgerPrincipalKey::new(
+                ledger_canister_id,
+                principal,
+                start_after.unwrap_or_else(Uuid::min),
+            )?,
+            start_after: start_after.is_some(),
+            end_bound: LedgerTransferLedgerPrincipalKey::new(
+                ledger_canister_id,
+                principal,
+                Uuid::max(),
+            )?,
+        })
+    }
+}
+
+impl RangeBounds<LedgerTransferLedgerPrincipalKey> for LedgerTransferLedgerPrincipalRange {
+    fn start_bound(&self) -> std::ops::Bound<&LedgerTransferLedgerPrincipalKey> {
+        if self.start_after {
+            std::ops::Bound::Excluded(&self.start_bound)
+        } else {
+            std::ops::Bound::Included(&self.start_bound)
+        }
+    }
+
+    fn end_bound(&self) -> std::ops::Bound<&LedgerTransferLedgerPrincipalKey> {
+        std::ops::Bound::Included(&self.end_bound)
+    }
+}
+
+/// Indexes the transfers by ledger and block index, used to find out if a block was already logged.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LedgerTransferBlockKey(Blob<{ Self::MAX_SIZE as usize }>);
+
+impl LedgerTransferBlockKey {
+    const MAX_SIZE: u32 = 1 + PRINCIPAL_MAX_SIZE as u32 + u64::BOUND.max_size();
+
+    pub fn new(ledger_canister_id: &Principal, block_index: &Nat) -> Result<Self, ApiError> {
+        let block_index = u64::try_from(block_index.0.clone()).map_err(|_| {
+            ApiError::internal(&format!(
+                "Block index {} of ledger {} does not fit in 64 bits.",
+                block_index, ledger_canister_id
+            ))
+        })?;
+        let mut bytes = Vec::with_capacity(Self::MAX_SIZE as usize);
+        extend_with_padded_principal(&mut bytes, ledger_canister_id);
+        // big endian, so that the keys are sorted by block index
+        bytes.extend_from_slice(&block_index.to_be_bytes());
+
+        Ok(Self(Blob::try_from(bytes.as_slice()).map_err(|_| {
+            ApiError::internal(&format!(
+                "Failed to convert ledger canister id {}, block index {} to bytes.",
+                ledger_canister_id, block_index
+            ))
+        })?))
+    }
+}
+
+impl Storable for LedgerTransferBlockKey {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        self.0.to_bytes()
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(Blob::from_bytes(bytes))
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: Self::MAX_SIZE,
+        is_fixed_size: true,
+    };
+}
+
+/// Appends the length of the principal followed by its bytes padded to the maximum principal size.
+fn extend_with_padded_principal(bytes: &mut Vec<u8>, principal: &Principal) {
+    let principal_bytes = principal.as_slice();
+    let padded_len = bytes.len() + 1 + PRINCIPAL_MAX_SIZE;
+    bytes.push(principal_bytes.len() as u8);
+    bytes.extend_from_slice(principal_bytes);
+    bytes.resize(padded_len, 0);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LedgerTransferWheelPrizeExtractionIdKey(Blob<{ Self::MAX_SIZE as usize }>);
+
+impl LedgerTransferWheelPrizeExtractionIdKey {
+    const MAX_SIZE: u32 = <(WheelPrizeExtractionId, LedgerTransferId)>::BOUND.max_size();
+
+    pub fn new(
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+        ledger_transfer_id: LedgerTransferId,
+    ) -> Result<Self, ApiError> {
+        Ok(Self(
+            Blob::try_from(
+                (wheel_prize_extraction_id, ledger_transfer_id)
+                    .to_bytes()
+                    .as_ref(),
+            )
+            .map_err(|_| {
+                ApiError::internal(&format!(
+                    "Failed to convert wheel prize extraction id {:?}, ledger transfer id {:?} to bytes.",
+                    wheel_prize_extraction_id, ledger_transfer_id
+                ))
+            })?,
+        ))
+    }
+}
+
+impl Storable for LedgerTransferWheelPrizeExtractionIdKey {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        self.0.to_bytes()
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(Blob::from_bytes(bytes))
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: Self::MAX_SIZE,
+        is_fixed_size: true,
+    };
+}
+
+pub struct LedgerTransferWheelPrizeExtractionIdRange {
+    start_bound: LedgerTransferWheelPrizeExtractionIdKey,
+    start_after: bool,
+    end_bound: LedgerTransferWheelPrizeExtractionIdKey,
+}
+
+impl LedgerTransferWheelPrizeExtractionIdRange {
+    /// The range of the transfers of the extraction,
+    /// starting after the given transfer if provided.
+    pub fn new(
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+        start_after: Option<LedgerTransferId>,
+    ) -> Result<Self, ApiError> {
+        Ok(Self {
+            start_bound: LedgerTransferWheelPrizeExtractionIdKey::new(
+                wheel_prize_extraction_id,
+                start_after.unwrap_or_else(Uuid::min),
+            )?,
+            start_after: start_after.is_some(),
+            end_bound: LedgerTransferWheelPrizeExtractionIdKey::new(
+                wheel_prize_extraction_id,
+                Uuid::max(),
+            )?,
+        })
+    }
+}
+
+impl RangeBounds<LedgerTransferWheelPrizeExtractionIdKey>
+    for LedgerTransferWheelPrizeExtractionIdRange
+{
+    fn start_bound(&self) -> std::ops::Bound<&LedgerTransferWheelPrizeExtractionIdKey> {
+        if self.start_after {
+            std::ops::Bound::Excluded(&self.start_bound)
+        } else {
+            std::ops::Bound::Included(&self.start_bound)
+        }
+    }
+
+    fn end_bound(&self) -> std::ops::Bound<&LedgerTransferWheelPrizeExtractionIdKey> {
+        std::ops::Bound::Included(&self.end_bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use icrc_ledger_types::icrc3::transactions::Transfer;
+    use rstest::*;
+
+    #[rstest]
+    #[case::same_transfer(|_: &mut Transfer| {}, true)]
+    #[case::other_recipient(|transfer: &mut Transfer| transfer.to.subaccount = Some([1; 32]), false)]
+    #[case::other_amount(|transfer: &mut Transfer| transfer.amount += 1u64, false)]
+    #[case::other_created_at_time(|transfer: &mut Transfer| transfer.created_at_time = None, false)]
+    #[case::other_memo(|transfer: &mut Transfer| transfer.memo = Some(vec![1].into()), false)]
+    fn sent_ledger_transfer_matches(
+        #[case] change_transfer: fn(&mut Transfer),
+        #[case] expected_matches: bool,
+    ) {
+        let sent_transfer = SentLedgerTransfer {
+            ledger_canister_id: fixtures::principal(),
+            to: Account {
+                owner: fixtures::principal(),
+                subaccount: None,
+            },
+            amount: 1_000_000,
+            memo: vec![0; 16],
+            created_at_time: 1_706_899_350_000_000_000,
+        };
+        let mut transfer = Transfer {
+            amount: sent_transfer.amount.into(),
+            from: Account {
+                owner: Principal::from_slice(&[1]),
+                subaccount: None,
+            },
+            to: sent_transfer.to,
+            spender: None,
+            memo: Some(sent_transfer.memo.clone().into()),
+            fee: None,
+            created_at_time: Some(sent_transfer.created_at_time),
+        };
+        change_transfer(&mut transfer);
+        let transaction = Transaction::transfer(transfer, sent_transfer.created_at_time);
+
+        assert_eq!(sent_transfer.matches(&transaction), expected_matches);
+    }
+
+    #[rstest]
+    #[case::payout(fixtures::ledger_transfer_payout())]
+    #[case::retirement(fixtures::ledger_transfer_retirement())]
+    fn storable_impl(#[case] ledger_transfer: LedgerTransfer) {
+        let serialized_ledger_transfer = ledger_transfer.to_bytes();
+        let deserialized_ledger_transfer = LedgerTransfer::from_bytes(serialized_ledger_transfer);
+
+        assert_eq!(ledger_transfer, deserialized_ledger_transfer);
+    }
+
+    #[rstest]
+    fn ledger_transfer_principals() {
+        let ledger_transfer = fixtures::ledger_transfer_payout();
+        assert_eq!(
+            ledger_transfer.principals(),
+            vec![ledger_transfer.from.owner, ledger_transfer.to.owner]
+        );
+
+        let self_transfer = LedgerTransfer {
+            to: ledger_transfer.from,
+            ..ledger_transfer.clone()
+        };
+        assert_eq!(self_transfer.principals(), vec![ledger_transfer.from.owner]);
+    }
+
+    #[rstest]
+    fn ledger_transfer_filter_matches() {
+        let ledger_transfer = fixtures::ledger_transfer_payout();
+
+        assert!(LedgerTransferFilter::default().matches(&ledger_transfer));
+        assert!(LedgerTransferFilter {
+            ledger_canister_id: Some(ledger_transfer.ledger_canister_id),
+            principal: Some(ledger_transfer.from.owner),
+            wheel_prize_extraction_id: ledger_transfer.wheel_prize_extraction_id,
+        }
+        .matches(&ledger_transfer));
+        assert!(LedgerTransferFilter {
+            principal: Some(ledger_transfer.to.owner),
+            ..Default::default()
+        }
+        .matches(&ledger_transfer));
+        assert!(!LedgerTransferFilter {
+            ledger_canister_id: Some(ledger_transfer.to.owner),
+            ..Default::default()
+        }
+        .matches(&ledger_transfer));
+        assert!(!LedgerTransferFilter {
+            principal: Some(Principal::management_canister()),
+            ..Default::default()
+        }
+        .matches(&ledger_transfer));
+        assert!(!LedgerTransferFilter {
+            wheel_prize_extraction_id: Some(Uuid::max()),
+            ..Default::default()
+        }
+        .matches(&ledger_transfer));
+    }
+
+    #[rstest]
+    fn ledger_transfer_principal_key_storable_impl() {
+        let key =
+            LedgerTransferPrincipalKey::new(&fixtures::principal(), fixtures::uuid()).unwrap();
+        let serialized_key = key.to_bytes();
+        let deserialized_key = LedgerTransferPrincipalKey::from_bytes(serialized_key);
+
+        assert_eq!(key, deserialized_key);
+    }
+
+    #[rstest]
+    fn ledger_transfer_principal_range() {
+        let principal = fixtures::principal();
+        let other_principal = Principal::management_canister();
+        let ledger_transfer_id = fixtures::uuid_a();
+        let key = LedgerTransferPrincipalKey::new(&principal, ledger_transfer_id).unwrap();
+
+        let range = LedgerTransferPrincipalRange::new(&principal, None).unwrap();
+        assert!(range.contains(&key));
+        assert!(!range.contains(
+            &LedgerTransferPrincipalKey::new(&other_principal, ledger_transfer_id).unwrap()
+        ));
+
+        let range =
+            LedgerTransferPrincipalRange::new(&principal, Some(ledger_transfer_id)).unwrap();
+        assert!(!range.contains(&key));
+        assert!(range.contains(&LedgerTransferPrincipalKey::new(&principal, Uuid::max()).unwrap()));
+    }
+
+    #[rstest]
+    fn ledger_transfer_ledger_principal_range() {
+        let ledger_canister_id = fixtures::principal();
+        let principal = Principal::from_slice(&[1]);
+        let ledger_transfer_id = fixtures::uuid_a();
+        let key = LedgerTransferLedgerPrincipalKey::new(
+            &ledger_canister_id,
+            &principal,
+            ledger_transfer_id,
+        )
+        .unwrap();
+        assert_eq!(
+            LedgerTransferLedgerPrincipalKey::from_bytes(key.to_bytes()),
+            key
+        );
+
+        let range =
+            LedgerTransferLedgerPrincipalRange::new(&ledger_canister_id, &principal, None).unwrap();
+        assert!(range.contains(&key));
+        assert!(!range.contains(
+            &LedgerTransferLedgerPrincipalKey::new(
+                &ledger_canister_id,
+                &Principal::management_canister(),
+                ledger_transfer_id
+            )
+            .unwrap()
+        ));
+        assert!(!range.contains(
+            &LedgerTransferLedgerPrincipalKey::new(&principal, &principal, ledger_transfer_id)
+                .unwrap()
+        ));
+
+        let range = LedgerTransferLedgerPrincipalRange::new(
+            &ledger_canister_id,
+            &principal,
+            Some(ledger_transfer_id),
+        )
+        .unwrap();
+        assert!(!range.contains(&key));
+        assert!(range.contains(
+            &LedgerTransferLedgerPrincipalKey::new(&ledger_canister_id, &principal, Uuid::max())
+                .unwrap()
+        ));
+    }
+
+    #[rstest]
+    fn ledger_transfer_block_key() {
+        let ledger_canister_id = fixtures::principal();
+        let key = LedgerTransferBlockKey::new(&ledger_canister_id, &Nat::from(256u64)).unwrap();
+        assert_eq!(LedgerTransferBlockKey::from_bytes(key.to_bytes()), key);
+
+        assert!(
+            LedgerTransferBlockKey::new(&ledger_canister_id, &Nat::from(255u64)).unwrap() < key
+        );
+        assert_ne!(
+            LedgerTransferBlockKey::new(&Principal::management_canister(), &Nat::from(256u64))
+                .unwrap(),
+            key
+        );
+        assert!(LedgerTransferBlockKey::new(&ledger_canister_id, &Nat::from(u128::MAX)).is_err());
+    }
+
+    #[rstest]
+    fn ledger_transfer_wheel_prize_extraction_id_range() {
+        let wheel_prize_extraction_id = fixtures::uuid_a();
+        let ledger_transfer_id = fixtures::uuid_b();
+        let key = LedgerTransferWheelPrizeExtractionIdKey::new(
+            wheel_prize_extraction_id,
+            ledger_transfer_id,
+        )
+        .unwrap();
+        assert_eq!(
+            LedgerTransferWheelPrizeExtractionIdKey::from_bytes(key.to_bytes()),
+            key
+        );
+
+        let range = LedgerTransferWheelPrizeExtractionIdRange::new(wheel_prize_extraction_id, None)
+            .unwrap();
+        assert!(range.contains(&key));
+
+        let range = LedgerTransferWheelPrizeExtractionIdRange::new(
+            wheel_prize_extraction_id,
+            Some(ledger_transfer_id),
+        )
+        .unwrap();
+        assert!(!range.contains(&key));
+    }
+}
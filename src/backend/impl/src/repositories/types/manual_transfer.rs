@@ -0,0 +1,366 @@
+use std::{borrow::Cow, fmt::Display};
+
+use backend_api::ApiError;
+use candid::{CandidType, Decode, Deserialize, Encode, Nat, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use icrc_ledger_types::icrc1::account::{Account, Subaccount};
+
+use super::{DateTime, TimestampFields, Timestamped, UserId, Uuid};
+
+/// How long a transfer waits for the approval of a second admin before expiring.
+pub const MANUAL_TRANSFER_APPROVAL_EXPIRY_SECONDS: u64 = 24 * 60 * 60;
+
+pub type ManualTransferId = Uuid;
+
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub enum ManualTransferState {
+    /// The amount is above the approval threshold of the ledger,
+    /// so the transfer waits for the approval of a second admin.
+    PendingApproval,
+    Processing,
+    Completed {
+        block_index: Nat,
+    },
+    Failed {
+        error: ApiError,
+    },
+    /// The transfer has not been sent to the ledger, e.g. because of the daily limit.
+    Denied {
+        error: ApiError,
+    },
+    Rejected {
+        rejected_by_user_id: UserId,
+    },
+}
+
+impl Display for ManualTransferState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManualTransferState::PendingApproval => write!(f, "PendingApproval"),
+            ManualTransferState::Processing => write!(f, "Processing"),
+            ManualTransferState::Completed { block_index } => {
+                write!(f, "Completed (block_index:{block_index})")
+            }
+            ManualTransferState::Failed { error } => write!(f, "Failed (error:{error})"),
+            ManualTransferState::Denied { error } => write!(f, "Denied (error:{error})"),
+            ManualTransferState::Rejected {
+                rejected_by_user_id,
+            } => write!(f, "Rejected (rejected_by_user_id:{rejected_by_user_id})"),
+        }
+    }
+}
+
+/// A transfer requested by an admin, kept as the audit trail of the manual transfers,
+/// including the ones that have been denied or rejected.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct ManualTransfer {
+    pub ledger_canister_id: Principal,
+    /// The subaccount of the canister the tokens are sent from,
+    /// the transfers from the default account don't have it.
+    pub from_subaccount: Option<Subaccount>,
+    pub to: Principal,
+    /// Transfers to the default account don't have it.
+    pub to_subaccount: Option<Subaccount>,
+    pub amount: u128,
+    pub reason: String,
+    /// The `created_at_time` sent to the ledger, stored before the transfer is executed
+    /// so that the transfer can be looked up in the ledger if its outcome is unknown.
+    pub created_at_time: Option<u64>,
+    pub requested_by_user_id: UserId,
+    pub approved_by_user_id: Option<UserId>,
+    pub state: ManualTransferState,
+    pub timestamps: TimestampFields,
+}
+
+impl ManualTransfer {
+    pub fn new_processing(
+        ledger_canister_id: Principal,
+        from_subaccount: Option<Subaccount>,
+        to: Account,
+        amount: u128,
+        reason: String,
+        requested_by_user_id: UserId,
+    ) -> Self {
+        Self {
+            ledger_canister_id,
+            from_subaccount,
+            to: to.owner,
+            to_subaccount: to.subaccount,
+            amount,
+            reason,
+            created_at_time: None,
+            requested_by_user_id,
+            approved_by_user_id: None,
+            state: ManualTransferState::Processing,
+            timestamps: TimestampFields::new(),
+        }
+    }
+
+    pub fn to_account(&self) -> Account {
+        Account {
+            owner: self.to,
+            subaccount: self.to_subaccount,
+        }
+    }
+
+    pub fn is_pending_approval(&self) -> bool {
+        matches!(self.state, ManualTransferState::PendingApproval)
+    }
+
+    pub fn is_approval_expired(&self, now: &DateTime) -> bool {
+        self.is_pending_approval()
+            && now
+                .timestamp_seconds()
+                .saturating_sub(self.timestamps.created_at.timestamp_seconds())
+                > MANUAL_TRANSFER_APPROVAL_EXPIRY_SECONDS
+    }
+
+    /// Whether the amount may have left the canister: the transfers being executed,
+    /// the executed ones and the ones that failed without a rejection from the ledger.
+    pub fn is_possibly_transferred(&self) -> bool {
+        match &self.state {
+            ManualTransferState::Processing | ManualTransferState::Completed { .. } => true,
+            ManualTransferState::Failed { error } => !error.is_ledger_transfer_rejected(),
+            ManualTransferState::PendingApproval
+            | ManualTransferState::Denied { .. }
+            | ManualTransferState::Rejected { .. } => false,
+        }
+    }
+}
+
+impl Timestamped for ManualTransfer {
+    fn update_timestamp(&mut self) {
+        self.timestamps.update();
+    }
+}
+
+impl Storable for ManualTransfer {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// The limits of the manual transfers of a ledger, configured by the admins.
+/// The ledgers without limits can be transferred without restrictions.
+/// Amounts are in token base units and days are UTC days.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct ManualTransferLimit {
+    pub daily_limit: Option<u128>,
+    /// The transfers above this amount must be approved by a second admin.
+    pub approval_threshold: Option<u128>,
+    pub timestamps: TimestampFields,
+}
+
+impl Default for ManualTransferLimit {
+    fn default() -> Self {
+        Self {
+            daily_limit: None,
+            approval_threshold: None,
+            timestamps: TimestampFields::new(),
+        }
+    }
+}
+
+impl ManualTransferLimit {
+    pub fn requires_approval(&self, amount: u128) -> bool {
+        self.approval_threshold
+            .is_some_and(|approval_threshold| amount > approval_threshold)
+    }
+
+    /// Checks that the amount can be transferred, given the transfers
+    /// of the ledger that may have been executed since the start of the day.
+    pub fn assert_within_daily_limit(
+        &self,
+        amount: u128,
+        ledger_transfers: &[ManualTransfer],
+        now: &DateTime,
+    ) -> Result<(), ApiError> {
+        let Some(daily_limit) = self.daily_limit else {
+            return Ok(());
+        };
+
+        let start_of_day = now.start_of_day();
+        let transferred_today = ledger_transfers
+            .iter()
+            .filter(|transfer| {
+                transfer.is_possibly_transferred() && transfer.timestamps.updated_at >= start_of_day
+            })
+            .fold(0u128, |total, transfer| {
+                total.saturating_add(transfer.amount)
+            });
+
+        if transferred_today.saturating_add(amount) > daily_limit {
+            return Err(ApiError::too_many_requests(&format!(
+                "The amount exceeds the daily limit of the ledger ({}), {} already transferred today",
+                daily_limit, transferred_today
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Timestamped for ManualTransferLimit {
+    fn update_timestamp(&mut self) {
+        self.timestamps.update();
+    }
+}
+
+impl Storable for ManualTransferLimit {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use rstest::*;
+
+    #[rstest]
+    #[case::completed(fixtures::manual_transfer())]
+    #[case::pending_approval(fixtures::manual_transfer_pending_approval())]
+    fn storable_impl(#[case] manual_transfer: ManualTransfer) {
+        let serialized_manual_transfer = manual_transfer.to_bytes();
+        let deserialized_manual_transfer = ManualTransfer::from_bytes(serialized_manual_transfer);
+
+        assert_eq!(manual_transfer, deserialized_manual_transfer);
+    }
+
+    #[rstest]
+    fn manual_transfer_limit_storable_impl() {
+        let limit = fixtures::manual_transfer_limit();
+        let serialized_limit = limit.to_bytes();
+        let deserialized_limit = ManualTransferLimit::from_bytes(serialized_limit);
+
+        assert_eq!(limit, deserialized_limit);
+    }
+
+    #[rstest]
+    #[case::processing(ManualTransferState::Processing, true)]
+    #[case::completed(ManualTransferState::Completed { block_index: Nat::from(1u64) }, true)]
+    #[case::call_failed(
+        ManualTransferState::Failed {
+            error: ApiError::ledger_call_failed(Principal::anonymous(), "canister stopped"),
+        },
+        true
+    )]
+    #[case::rejected_by_ledger(
+        ManualTransferState::Failed {
+            error: ApiError::ledger_transfer_rejected(
+                Principal::anonymous(),
+                backend_api::LedgerTransferRejection::TooOld,
+            ),
+        },
+        false
+    )]
+    #[case::pending_approval(ManualTransferState::PendingApproval, false)]
+    #[case::denied(ManualTransferState::Denied { error: ApiError::too_many_requests("limit") }, false)]
+    #[case::rejected(ManualTransferState::Rejected { rejected_by_user_id: fixtures::uuid_a() }, false)]
+    fn manual_transfer_is_possibly_transferred(
+        #[case] state: ManualTransferState,
+        #[case] expected: bool,
+    ) {
+        let manual_transfer = ManualTransfer {
+            state,
+            ..fixtures::manual_transfer()
+        };
+
+        assert_eq!(manual_transfer.is_possibly_transferred(), expected);
+    }
+
+    #[rstest]
+    #[case::recent(fixtures::date_time_a(), false)]
+    #[case::expired(
+        fixtures::date_time_a().sub(chrono::Duration::seconds(MANUAL_TRANSFER_APPROVAL_EXPIRY_SECONDS as i64 + 1)),
+        true
+    )]
+    fn manual_transfer_is_approval_expired(#[case] created_at: DateTime, #[case] expected: bool) {
+        let mut manual_transfer = fixtures::manual_transfer_pending_approval();
+        manual_transfer.timestamps.created_at = created_at;
+
+        assert_eq!(
+            manual_transfer.is_approval_expired(&fixtures::date_time_a()),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case::no_threshold(None, 1_000, false)]
+    #[case::below(Some(1_000), 1_000, false)]
+    #[case::above(Some(1_000), 1_001, true)]
+    fn manual_transfer_limit_requires_approval(
+        #[case] approval_threshold: Option<u128>,
+        #[case] amount: u128,
+        #[case] expected: bool,
+    ) {
+        let limit = ManualTransferLimit {
+            approval_threshold,
+            ..fixtures::manual_transfer_limit()
+        };
+
+        assert_eq!(limit.requires_approval(amount), expected);
+    }
+
+    #[rstest]
+    #[case::no_limit(None, 1_000_000, true)]
+    #[case::within(Some(1_000), 500, true)]
+    #[case::exceeded(Some(1_000), 501, false)]
+    fn manual_transfer_limit_assert_within_daily_limit(
+        #[case] daily_limit: Option<u128>,
+        #[case] amount: u128,
+        #[case] expected: bool,
+    ) {
+        let now = fixtures::date_time_a();
+        let transferred_today = ManualTransfer {
+            amount: 500,
+            timestamps: TimestampFields {
+                created_at: now,
+                updated_at: now,
+            },
+            ..fixtures::manual_transfer()
+        };
+        // transfers of previous days and transfers not executed are not counted
+        let transferred_yesterday = ManualTransfer {
+            timestamps: TimestampFields {
+                created_at: now.sub(chrono::Duration::days(1)),
+                updated_at: now.sub(chrono::Duration::days(1)),
+            },
+            ..transferred_today.clone()
+        };
+        let denied_today = ManualTransfer {
+            state: ManualTransferState::Denied {
+                error: ApiError::too_many_requests("limit"),
+            },
+            ..transferred_today.clone()
+        };
+        let limit = ManualTransferLimit {
+            daily_limit,
+            ..fixtures::manual_transfer_limit()
+        };
+
+        assert_eq!(
+            limit
+                .assert_within_daily_limit(
+                    amount,
+                    &[transferred_yesterday, transferred_today, denied_today],
+                    &now,
+                )
+                .is_ok(),
+            expected
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::borrow::Cow;
+
+use backend_api::ApiError;
+use candid::{CandidType, Decode, Deserialize, Encode};
+use ic_stable_structures::{storable::Bound, Storable};
+use sha2::{Digest, Sha256};
+
+use super::DateTime;
+
+/// The failed attempts of a principal are counted in windows of 1 hour.
+const SELF_SPIN_FAILED_ATTEMPTS_WINDOW_SECONDS: u64 = 60 * 60;
+
+/// The SHA-256 hash of a normalized self-spin or invite code, so that the codes are never stored in clear.
+pub type SelfSpinCodeHash = [u8; 32];
+
+/// Self-spin and invite codes are compared ignoring the case, the spaces and the dashes,
+/// so that they can be easily typed by the players.
+pub fn hash_code(code: &str) -> SelfSpinCodeHash {
+    let normalized_code = code
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .flat_map(char::to_uppercase)
+        .collect::<String>();
+
+    Sha256::digest(normalized_code.as_bytes()).into()
+}
+
+/// The wrong codes submitted by a principal trying to spin the wheel for themselves,
+/// counted to prevent guessing the codes.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct SelfSpinFailedAttempts {
+    pub count: u32,
+    pub window_started_at: DateTime,
+}
+
+impl SelfSpinFailedAttempts {
+    /// Records a failed attempt, starting a new window if the previous one is over.
+    pub fn record_failed_attempt(previous: Option<Self>, now: &DateTime) -> Self {
+        match previous {
+            Some(previous) if !previous.is_window_over(now) => Self {
+                count: previous.count.saturating_add(1),
+                window_started_at: previous.window_started_at,
+            },
+            _ => Self {
+                count: 1,
+                window_started_at: *now,
+            },
+        }
+    }
+
+    pub fn assert_not_locked_out(
+        &self,
+        max_failed_attempts: u32,
+        now: &DateTime,
+    ) -> Result<(), ApiError> {
+        if self.count >= max_failed_attempts && !self.is_window_over(now) {
+            let retry_after_seconds = (self.window_started_at.timestamp_seconds()
+                + SELF_SPIN_FAILED_ATTEMPTS_WINDOW_SECONDS)
+                .saturating_sub(now.timestamp_seconds());
+            return Err(ApiError::too_many_requests(&format!(
+                "Too many wrong self-spin codes, retry in {} seconds",
+                retry_after_seconds
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn is_window_over(&self, now: &DateTime) -> bool {
+        now.timestamp_seconds()
+            .saturating_sub(self.window_started_at.timestamp_seconds())
+            >= SELF_SPIN_FAILED_ATTEMPTS_WINDOW_SECONDS
+    }
+}
+
+impl Storable for SelfSpinFailedAttempts {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use rstest::*;
+
+    #[rstest]
+    fn storable_impl() {
+        let failed_attempts = fixtures::self_spin_failed_attempts();
+        let serialized_failed_attempts = failed_attempts.to_bytes();
+        let deserialized_failed_attempts =
+            SelfSpinFailedAttempts::from_bytes(serialized_failed_attempts);
+
+        assert_eq!(failed_attempts, deserialized_failed_attempts);
+    }
+
+    #[rstest]
+    #[case::same("ABCD-1234", "ABCD-1234", true)]
+    #[case::lowercase("ABCD-1234", "abcd-1234", true)]
+    #[case::spaces_and_dashes("ABCD-1234", " abcd 12-34 ", true)]
+    #[case::different("ABCD-1234", "ABCD-1235", false)]
+    fn hash_code_normalization(
+        #[case] code: &str,
+        #[case] other_code: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(hash_code(code) == hash_code(other_code), expected);
+    }
+
+    #[rstest]
+    fn record_failed_attempt() {
+        let now = fixtures::date_time_a();
+        let failed_attempts = SelfSpinFailedAttempts::record_failed_attempt(None, &now);
+        assert_eq!(
+            failed_attempts,
+            SelfSpinFailedAttempts {
+                count: 1,
+                window_started_at: now,
+            }
+        );
+
+        let later = now.add(chrono::Duration::minutes(30));
+        let failed_attempts =
+            SelfSpinFailedAttempts::record_failed_attempt(Some(failed_attempts), &later);
+        assert_eq!(
+            failed_attempts,
+            SelfSpinFailedAttempts {
+                count: 2,
+                window_started_at: now,
+            }
+        );
+
+        let next_window = now.add(chrono::Duration::hours(1));
+        let failed_attempts =
+            SelfSpinFailedAttempts::record_failed_attempt(Some(failed_attempts), &next_window);
+        assert_eq!(
+            failed_attempts,
+            SelfSpinFailedAttempts {
+                count: 1,
+                window_started_at: next_window,
+            }
+        );
+    }
+
+    #[rstest]
+    #[case::below_max(2, 0, true)]
+    #[case::max_reached(3, 10, false)]
+    #[case::window_over(3, 60, true)]
+    fn assert_not_locked_out(
+        #[case] count: u32,
+        #[case] elapsed_minutes: i64,
+        #[case] expected_ok: bool,
+    ) {
+        let window_started_at = fixtures::date_time_a();
+        let failed_attempts = SelfSpinFailedAttempts {
+            count,
+            window_started_at,
+        };
+        let now = window_started_at.add(chrono::Duration::minutes(elapsed_minutes));
+
+        let result = failed_attempts.assert_not_locked_out(3, &now);
+
+        match expected_ok {
+            true => assert_eq!(result, Ok(())),
+            false => assert_eq!(
+                result,
+                Err(ApiError::too_many_requests(
+                    "Too many wrong self-spin codes, retry in 3000 seconds"
+                ))
+            ),
+        }
+    }
+}
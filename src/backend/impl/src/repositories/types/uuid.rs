@@ -30,6 +30,21 @@ impl Uuid {
         Self(UuidImpl::max())
     }
 
+    /// The lowest UUID that can be generated at the given time.
+    pub fn min_at(timestamp_millis: u64) -> Self {
+        Self(Builder::from_unix_timestamp_millis(timestamp_millis, &[0; UUID_RNG_SIZE]).into_uuid())
+    }
+
+    pub fn timestamp_millis(&self) -> u64 {
+        self.0
+            .get_timestamp()
+            .map(|timestamp| {
+                let (seconds, nanos) = timestamp.to_unix();
+                seconds * 1_000 + u64::from(nanos) / 1_000_000
+            })
+            .unwrap_or(0)
+    }
+
     pub fn min() -> Self {
         Self::nil()
     }
@@ -112,6 +127,16 @@ mod tests {
         assert_eq!(deserialized_uuid, uuid);
     }
 
+    #[rstest]
+    fn min_at() {
+        let uuid = fixtures::uuid_a();
+        let min_uuid = Uuid::min_at(uuid.timestamp_millis());
+
+        assert!(min_uuid < uuid);
+        assert!(Uuid::min_at(uuid.timestamp_millis() + 1) > uuid);
+        assert_eq!(min_uuid.timestamp_millis(), uuid.timestamp_millis());
+    }
+
     #[rstest]
     fn try_from() {
         let uuid = fixtures::uuid();
@@ -1,15 +1,19 @@
 use std::{borrow::Cow, ops::RangeBounds};
 
 use backend_api::ApiError;
-use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use candid::{CandidType, Decode, Deserialize, Encode, Nat, Principal};
 use ic_stable_structures::{
     storable::{Blob, Bound},
     Storable,
 };
+use icrc_ledger_types::icrc1::account::{Account, Subaccount};
 
 use crate::FRONTEND_ASSETS_DIR;
 
-use super::{get_current_date_time, DateTime, HttpAssetPath, TimestampFields, Timestamped, Uuid};
+use super::{
+    get_current_date_time, DateTime, EventId, HttpAssetPath, LedgerMetadata, SentLedgerTransfer,
+    TimestampFields, Timestamped, Uuid,
+};
 
 pub type WheelAssetId = Uuid;
 
@@ -52,10 +56,40 @@ impl WheelAssetTokenBalance {
     }
 }
 
+/// The consecutive failures to fetch a token data, e.g. the USD price or the balance.
+/// It is cleared as soon as a fetch succeeds.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct WheelAssetTokenFetchStatus {
+    pub failed_attempts: u32,
+    pub last_error: ApiError,
+    pub last_failed_at: DateTime,
+}
+
+impl WheelAssetTokenFetchStatus {
+    fn failed(previous: Option<&Self>, error: ApiError) -> Self {
+        Self {
+            failed_attempts: previous
+                .map(|status| status.failed_attempts)
+                .unwrap_or(0)
+                .saturating_add(1),
+            last_error: error,
+            last_failed_at: get_current_date_time(),
+        }
+    }
+}
+
 #[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
 pub struct WheelAssetTokenLedgerConfig {
     pub ledger_canister_id: Principal,
+    /// Validated against the ledger's decimals, see [LedgerMetadata].
     pub decimals: u8,
+    /// The last fetched transfer fee of the ledger, in token base units.
+    /// `None` until the fee is fetched for the first time.
+    pub fee: Option<u128>,
+    /// The symbol of the token, `None` until fetched from the ledger.
+    pub symbol: Option<String>,
+    /// The name of the token, `None` until fetched from the ledger.
+    pub name: Option<String>,
 }
 
 impl WheelAssetTokenLedgerConfig {
@@ -66,9 +100,57 @@ impl WheelAssetTokenLedgerConfig {
     fn unit_amount_float(&self) -> f64 {
         self.unit_amount() as f64
     }
+
+    /// The fee paid on top of each prize transfer, 0 if not fetched yet.
+    pub fn transfer_fee(&self) -> u128 {
+        self.fee.unwrap_or(0)
+    }
+
+    /// Sets the metadata fetched from the ledger.
+    pub fn set_ledger_metadata(&mut self, ledger_metadata: &LedgerMetadata) {
+        self.decimals = ledger_metadata.decimals;
+        self.fee = Some(ledger_metadata.fee);
+        if ledger_metadata.symbol.is_some() {
+            self.symbol.clone_from(&ledger_metadata.symbol);
+        }
+        if ledger_metadata.name.is_some() {
+            self.name.clone_from(&ledger_metadata.name);
+        }
+    }
+}
+
+/// Where the prizes of a token asset are paid from.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub enum WheelAssetTokenFundingSource {
+    /// The canister's own default account,
+    /// which must be funded by transferring tokens to it.
+    CanisterAccount,
+    /// The sponsor's account, from which the canister pays the prizes
+    /// with `icrc2_transfer_from`, within the allowance approved by the sponsor.
+    SponsorAllowance { sponsor_account: Account },
 }
 
+/// The canister's subaccount holding the funds of a token asset.
 #[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub enum WheelAssetTokenSubaccount {
+    /// The canister's default subaccount, shared by the token assets
+    /// created before each asset had its own subaccount.
+    Default,
+    /// The subaccount derived from the asset id, see [wheel_asset_subaccount].
+    Derived,
+}
+
+/// The canister's subaccount of the token asset with the given id:
+/// the bytes of the id, padded with zeros.
+pub fn wheel_asset_subaccount(wheel_asset_id: &WheelAssetId) -> Subaccount {
+    let mut subaccount = [0; 32];
+    let id_bytes = wheel_asset_id.to_bytes();
+    subaccount[..id_bytes.len()].copy_from_slice(&id_bytes);
+    subaccount
+}
+
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 pub enum WheelAssetType {
     Token {
         ledger_config: WheelAssetTokenLedgerConfig,
@@ -77,19 +159,59 @@ pub enum WheelAssetType {
         exchange_rate_symbol: Option<String>,
         /// The last fetched USD price, if any.
         usd_price: Option<WheelAssetTokenPrice>,
-        /// The last fetched token balance, if any.
+        /// The failures to fetch the USD price since the last successful fetch, if any.
+        usd_price_fetch_status: Option<WheelAssetTokenFetchStatus>,
+        /// Where the prizes are paid from.
+        /// If not provided, the prizes are paid from the canister account.
+        funding_source: Option<WheelAssetTokenFundingSource>,
+        /// The canister's subaccount used to deposit the funds of the asset,
+        /// or to receive the sponsor's allowance.
+        /// Assets created before subaccounts were introduced don't have it
+        /// and use the default subaccount.
+        subaccount: Option<WheelAssetTokenSubaccount>,
+        /// The last fetched funds available for the prizes, if any:
+        /// the canister's balance or the remaining sponsor's allowance,
+        /// depending on the funding source.
         balance: Option<WheelAssetTokenBalance>,
-        /// The amount of USD to be paid per prize.
+        /// The failures to fetch the balance since the last successful fetch, if any.
+        balance_fetch_status: Option<WheelAssetTokenFetchStatus>,
+        /// The amount of USD to be paid per prize,
+        /// converted to tokens with the last fetched USD price.
+        /// Ignored if `prize_token_amount` is set.
         prize_usd_amount: f64,
+        /// The fixed amount of token base units to be paid per prize, if any.
+        /// The USD price is then only used to estimate the USD value of the prize.
+        prize_token_amount: Option<u128>,
     },
     Gadget {
         article_type: Option<String>,
+        /// The variants of the gadget, e.g. sizes or colors, each with its own stock.
+        /// The variant is picked when the prize is handed over.
+        /// Gadgets created before variants were introduced don't have them.
+        variants: Option<Vec<WheelAssetGadgetVariant>>,
     },
     Jackpot {
         wheel_asset_ids: Vec<WheelAssetId>,
     },
 }
 
+/// A variant of a gadget, e.g. a T-shirt size.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct WheelAssetGadgetVariant {
+    /// Unique within the gadget, used to pick the variant.
+    pub name: String,
+    pub size: Option<String>,
+    pub color: Option<String>,
+    pub total_amount: u32,
+    pub handed_over_amount: u32,
+}
+
+impl WheelAssetGadgetVariant {
+    pub fn available_amount(&self) -> u32 {
+        self.total_amount.saturating_sub(self.handed_over_amount)
+    }
+}
+
 impl From<&WheelAssetType> for u8 {
     fn from(asset_type: &WheelAssetType) -> u8 {
         match asset_type {
@@ -106,17 +228,71 @@ impl WheelAssetType {
             ledger_config: WheelAssetTokenLedgerConfig {
                 ledger_canister_id: Principal::from_slice(&[0]),
                 decimals: 0,
+                fee: None,
+                symbol: None,
+                name: None,
             },
             exchange_rate_symbol: None,
             usd_price: None,
+            usd_price_fetch_status: None,
+            funding_source: None,
+            subaccount: None,
             balance: None,
+            balance_fetch_status: None,
             prize_usd_amount: 0.0,
+            prize_token_amount: None,
         }
     }
 
     pub fn set_latest_price(&mut self, input_usd_price: WheelAssetTokenPrice) {
-        if let WheelAssetType::Token { usd_price, .. } = self {
+        if let WheelAssetType::Token {
+            usd_price,
+            usd_price_fetch_status,
+            ..
+        } = self
+        {
             *usd_price = Some(input_usd_price);
+            *usd_price_fetch_status = None;
+        }
+    }
+
+    pub fn usd_price_last_fetched_at(&self) -> Option<DateTime> {
+        match self {
+            WheelAssetType::Token { usd_price, .. } => {
+                usd_price.as_ref().map(|price| price.last_fetched_at)
+            }
+            WheelAssetType::Gadget { .. } | WheelAssetType::Jackpot { .. } => None,
+        }
+    }
+
+    /// Whether the fetched USD price is older than the given age.
+    /// Tokens that don't fetch their price (e.g. stablecoins) never have a stale price,
+    /// while tokens that haven't fetched their price yet have no draws available anyway.
+    pub fn is_usd_price_stale(&self, max_age_seconds: u64, now: &DateTime) -> bool {
+        // the amount of fixed prizes doesn't depend on the price
+        if !self.should_fetch_usd_price() || self.is_fixed_token_amount_prize() {
+            return false;
+        }
+
+        self.usd_price_last_fetched_at()
+            .map(|last_fetched_at| {
+                now.timestamp_seconds()
+                    .saturating_sub(last_fetched_at.timestamp_seconds())
+                    > max_age_seconds
+            })
+            .unwrap_or(false)
+    }
+
+    pub fn set_usd_price_fetch_failed(&mut self, error: ApiError) {
+        if let WheelAssetType::Token {
+            usd_price_fetch_status,
+            ..
+        } = self
+        {
+            *usd_price_fetch_status = Some(WheelAssetTokenFetchStatus::failed(
+                usd_price_fetch_status.as_ref(),
+                error,
+            ));
         }
     }
 
@@ -131,52 +307,77 @@ impl WheelAssetType {
     }
 
     pub fn set_latest_balance(&mut self, input_balance: WheelAssetTokenBalance) {
-        if let WheelAssetType::Token { balance, .. } = self {
+        if let WheelAssetType::Token {
+            balance,
+            balance_fetch_status,
+            ..
+        } = self
+        {
             *balance = Some(input_balance);
+            *balance_fetch_status = None;
         }
     }
 
-    fn token_balance(&self) -> f64 {
-        match self {
-            WheelAssetType::Token {
-                balance,
-                ledger_config,
-                ..
-            } => balance
-                .as_ref()
-                .map(|el| ((el.balance as f64) / ledger_config.unit_amount_float()))
-                .unwrap_or(0f64),
-            WheelAssetType::Gadget { .. } | WheelAssetType::Jackpot { .. } => 0f64,
+    pub fn set_ledger_metadata(&mut self, ledger_metadata: &LedgerMetadata) {
+        if let WheelAssetType::Token { ledger_config, .. } = self {
+            ledger_config.set_ledger_metadata(ledger_metadata);
         }
     }
 
-    fn token_total_usd_amount(&self) -> Option<f64> {
-        let balance = self.token_balance();
-        match self {
-            WheelAssetType::Token { usd_price, .. } => {
-                usd_price.as_ref().map(|el| balance * el.usd_price)
-            }
-            WheelAssetType::Gadget { .. } | WheelAssetType::Jackpot { .. } => None,
+    pub fn set_balance_fetch_failed(&mut self, error: ApiError) {
+        if let WheelAssetType::Token {
+            balance_fetch_status,
+            ..
+        } = self
+        {
+            *balance_fetch_status = Some(WheelAssetTokenFetchStatus::failed(
+                balance_fetch_status.as_ref(),
+                error,
+            ));
         }
     }
 
-    pub fn available_token_draws_count(&self) -> Option<u32> {
-        match self {
+    fn is_fixed_token_amount_prize(&self) -> bool {
+        matches!(
+            self,
             WheelAssetType::Token {
-                prize_usd_amount, ..
-            } => self.token_total_usd_amount().map(|total_usd_amount| {
-                if &total_usd_amount < prize_usd_amount {
-                    return 0;
-                }
+                prize_token_amount: Some(_),
+                ..
+            }
+        )
+    }
 
-                (total_usd_amount / prize_usd_amount).trunc() as u32
-            }),
-            WheelAssetType::Gadget { .. } | WheelAssetType::Jackpot { .. } => None,
-        }
+    /// How many prizes can be paid with the balance, `None` if the prize amount is unknown.
+    /// Each prize transfer also pays the ledger fee, which is taken from the balance as well.
+    pub fn available_token_draws_count(&self) -> Option<u32> {
+        let WheelAssetType::Token {
+            balance,
+            ledger_config,
+            ..
+        } = self
+        else {
+            return None;
+        };
+
+        let prize_amount = self.token_prize_amount()?;
+        let balance = balance.as_ref().map(|el| el.balance).unwrap_or(0);
+        let draw_cost = prize_amount.saturating_add(ledger_config.transfer_fee());
+
+        Some(
+            balance
+                .checked_div(draw_cost)
+                .unwrap_or(0)
+                .try_into()
+                .unwrap_or(u32::MAX),
+        )
     }
 
     pub fn token_prize_amount(&self) -> Option<u128> {
         match self {
+            WheelAssetType::Token {
+                prize_token_amount: Some(prize_token_amount),
+                ..
+            } => Some(*prize_token_amount),
             WheelAssetType::Token {
                 prize_usd_amount,
                 usd_price,
@@ -190,8 +391,18 @@ impl WheelAssetType {
         }
     }
 
+    /// The USD value of a prize, estimated with the last fetched USD price
+    /// for fixed token amount prizes.
     pub fn token_prize_usd_amount(&self) -> Option<f64> {
         match self {
+            WheelAssetType::Token {
+                prize_token_amount: Some(prize_token_amount),
+                usd_price,
+                ledger_config,
+                ..
+            } => usd_price.as_ref().map(|p| {
+                (*prize_token_amount as f64 / ledger_config.unit_amount_float()) * p.usd_price
+            }),
             WheelAssetType::Token {
                 prize_usd_amount, ..
             } => Some(*prize_usd_amount),
@@ -199,6 +410,39 @@ impl WheelAssetType {
         }
     }
 
+    pub fn token_funding_source(&self) -> Option<&WheelAssetTokenFundingSource> {
+        match self {
+            WheelAssetType::Token { funding_source, .. } => Some(
+                funding_source
+                    .as_ref()
+                    .unwrap_or(&WheelAssetTokenFundingSource::CanisterAccount),
+            ),
+            WheelAssetType::Gadget { .. } | WheelAssetType::Jackpot { .. } => None,
+        }
+    }
+
+    /// The canister's subaccount holding the funds of the token asset with the given id,
+    /// `None` for the default subaccount and for the other asset types.
+    pub fn token_subaccount(&self, wheel_asset_id: &WheelAssetId) -> Option<Subaccount> {
+        match self {
+            WheelAssetType::Token {
+                subaccount: Some(WheelAssetTokenSubaccount::Derived),
+                ..
+            } => Some(wheel_asset_subaccount(wheel_asset_id)),
+            _ => None,
+        }
+    }
+
+    /// The sponsor's account the prizes are paid from, if the token is funded by an allowance.
+    pub fn token_sponsor_account(&self) -> Option<Account> {
+        match self.token_funding_source() {
+            Some(WheelAssetTokenFundingSource::SponsorAllowance { sponsor_account }) => {
+                Some(*sponsor_account)
+            }
+            _ => None,
+        }
+    }
+
     pub fn ledger_config(&self) -> Option<&WheelAssetTokenLedgerConfig> {
         match self {
             WheelAssetType::Token { ledger_config, .. } => Some(ledger_config),
@@ -207,6 +451,22 @@ impl WheelAssetType {
     }
 }
 
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub enum WheelAssetDrawWeight {
+    /// The asset is drawn with a fixed relative weight.
+    Fixed { weight: u32 },
+    /// The asset is drawn with a weight equal to its available quantity,
+    /// so that its probability decreases as it gets extracted.
+    AvailableQuantity,
+}
+
+impl Default for WheelAssetDrawWeight {
+    /// All assets have the same probability of being drawn.
+    fn default() -> Self {
+        Self::Fixed { weight: 1 }
+    }
+}
+
 #[derive(Debug, Clone, Copy, CandidType, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 // We're explicit about the enum values here because they are serialized
 // to u8 when the state is used as a key and we want to make sure that the
@@ -228,6 +488,71 @@ pub struct WheelAssetUiSettings {
     pub background_color_hex: String,
 }
 
+/// The transfer of the remaining balance of a token asset that is being retired.
+///
+/// It's stored before calling the ledger, so that an interrupted retirement can be resumed
+/// with the same deduplication parameters (`memo` and `created_at_time`)
+/// without transferring the balance twice.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct WheelAssetRetirement {
+    /// The canister's subaccount holding the funds of the asset.
+    pub from_subaccount: Option<Subaccount>,
+    pub to: Account,
+    /// The balance minus the transfer fee.
+    pub amount: u128,
+    pub fee: u128,
+    pub memo: Vec<u8>,
+    pub created_at_time: u64,
+    /// Set once the ledger has executed the transfer.
+    pub block_index: Option<Nat>,
+    /// Whether a call to the ledger for this transfer had an unknown outcome,
+    /// so that the ledger may have executed it: while set, the retirement must not be
+    /// discarded, and a `TooOld` rejection means that the transfer must be looked up
+    /// in the ledger's transactions instead of being sent again.
+    /// `None` for retirements stored before this flag existed.
+    pub outcome_unknown: Option<bool>,
+}
+
+impl WheelAssetRetirement {
+    /// Returns `None` if the balance doesn't cover the fee, i.e. there is nothing to transfer.
+    pub fn new(
+        wheel_asset_id: WheelAssetId,
+        from_subaccount: Option<Subaccount>,
+        to: Account,
+        balance: u128,
+        fee: u128,
+        created_at_time: u64,
+    ) -> Option<Self> {
+        let amount = balance.checked_sub(fee).filter(|amount| *amount > 0)?;
+
+        Some(Self {
+            from_subaccount,
+            to,
+            amount,
+            fee,
+            memo: wheel_asset_id.to_bytes().to_vec(),
+            created_at_time,
+            block_index: None,
+            outcome_unknown: Some(false),
+        })
+    }
+
+    pub fn is_outcome_unknown(&self) -> bool {
+        self.outcome_unknown.unwrap_or(false)
+    }
+
+    /// The transfer of the remaining balance, to look it up in the ledger's transactions.
+    pub fn sent_transfer(&self, ledger_canister_id: Principal) -> SentLedgerTransfer {
+        SentLedgerTransfer {
+            ledger_canister_id,
+            to: self.to,
+            amount: self.amount,
+            memo: self.memo.clone(),
+            created_at_time: self.created_at_time,
+        }
+    }
+}
+
 #[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
 pub struct WheelAsset {
     pub name: String,
@@ -239,6 +564,15 @@ pub struct WheelAsset {
     pub modal_image_path: Option<HttpAssetPath>,
     pub wheel_ui_settings: WheelAssetUiSettings,
     pub timestamps: TimestampFields,
+    /// The weight used to draw this asset in a wheel prize extraction.
+    /// Assets created before weights were introduced don't have it,
+    /// see [WheelAsset::draw_weight].
+    pub draw_weight: Option<WheelAssetDrawWeight>,
+    /// The event this asset belongs to.
+    /// Assets without an event are used when no event is active.
+    pub event_id: Option<EventId>,
+    /// The withdrawal of the remaining balance, set while the token asset is being retired.
+    pub retirement: Option<WheelAssetRetirement>,
 }
 
 impl WheelAsset {
@@ -247,6 +581,8 @@ impl WheelAsset {
         asset_type: WheelAssetType,
         total_amount: u32,
         wheel_ui_settings: Option<WheelAssetUiSettings>,
+        draw_weight: Option<WheelAssetDrawWeight>,
+        event_id: Option<EventId>,
     ) -> Self {
         Self {
             name,
@@ -261,6 +597,9 @@ impl WheelAsset {
                 background_color_hex: "#29ABE2".to_string(),
             }),
             timestamps: TimestampFields::new(),
+            draw_weight: Some(draw_weight.unwrap_or_default()),
+            event_id,
+            retirement: None,
         }
     }
 
@@ -272,41 +611,137 @@ impl WheelAsset {
         self.asset_type.set_latest_balance(input_balance);
     }
 
+    pub fn set_usd_price_fetch_failed(&mut self, error: ApiError) {
+        self.asset_type.set_usd_price_fetch_failed(error);
+    }
+
+    pub fn set_balance_fetch_failed(&mut self, error: ApiError) {
+        self.asset_type.set_balance_fetch_failed(error);
+    }
+
+    pub fn set_ledger_metadata(&mut self, ledger_metadata: &LedgerMetadata) {
+        self.asset_type.set_ledger_metadata(ledger_metadata);
+    }
+
     pub fn is_token(&self) -> bool {
         matches!(self.asset_type, WheelAssetType::Token { .. })
     }
 
+    pub fn is_gadget(&self) -> bool {
+        matches!(self.asset_type, WheelAssetType::Gadget { .. })
+    }
+
     pub fn is_enabled(&self) -> bool {
         self.state == WheelAssetState::Enabled
     }
 
     pub fn prize_usd_amount(&self) -> Option<f64> {
-        match self.asset_type {
-            WheelAssetType::Token {
-                prize_usd_amount, ..
-            } => Some(prize_usd_amount),
-            WheelAssetType::Gadget { .. } | WheelAssetType::Jackpot { .. } => None,
+        self.asset_type.token_prize_usd_amount()
+    }
+
+    /// The ids of the assets paid out by this jackpot, empty for the other asset types.
+    pub fn jackpot_wheel_asset_ids(&self) -> &[WheelAssetId] {
+        match &self.asset_type {
+            WheelAssetType::Jackpot { wheel_asset_ids } => wheel_asset_ids,
+            WheelAssetType::Token { .. } | WheelAssetType::Gadget { .. } => &[],
         }
     }
 
-    pub fn available_quantity(&self) -> u32 {
+    /// The quantity of this asset that can still be drawn.
+    ///
+    /// A jackpot is limited by the draws left of each of the `jackpot_components`,
+    /// which must contain all the assets referenced by the jackpot. If any of them is missing,
+    /// the jackpot is not available. The components are ignored for the other asset types.
+    pub fn available_quantity(&self, jackpot_components: &[WheelAsset]) -> u32 {
         let available_qty = self.total_amount.saturating_sub(self.used_amount);
-        match self.asset_type {
+        match &self.asset_type {
             WheelAssetType::Token { .. } => {
                 let token_qty = self.asset_type.available_token_draws_count().unwrap_or(0);
                 std::cmp::min(token_qty, available_qty)
             }
-            WheelAssetType::Gadget { .. } | WheelAssetType::Jackpot { .. } => available_qty,
+            WheelAssetType::Gadget { .. } => available_qty,
+            WheelAssetType::Jackpot { wheel_asset_ids } => {
+                if jackpot_components.len() < wheel_asset_ids.len() {
+                    return 0;
+                }
+
+                jackpot_components
+                    .iter()
+                    .map(|component| {
+                        component
+                            .asset_type
+                            .available_token_draws_count()
+                            .unwrap_or(0)
+                    })
+                    .fold(available_qty, std::cmp::min)
+            }
+        }
+    }
+
+    pub fn draw_weight(&self) -> WheelAssetDrawWeight {
+        self.draw_weight.clone().unwrap_or_default()
+    }
+
+    /// The weight with which this asset participates in a wheel prize extraction.
+    /// Assets that have no available quantity have a weight of 0.
+    pub fn effective_draw_weight(&self, jackpot_components: &[WheelAsset]) -> u32 {
+        let available_quantity = self.available_quantity(jackpot_components);
+        if available_quantity == 0 {
+            return 0;
+        }
+
+        match self.draw_weight() {
+            WheelAssetDrawWeight::Fixed { weight } => weight,
+            WheelAssetDrawWeight::AvailableQuantity => available_quantity,
         }
     }
 
-    pub fn use_one(&mut self) -> Result<(), ApiError> {
-        if self.available_quantity() == 0 {
+    pub fn use_one(&mut self, jackpot_components: &[WheelAsset]) -> Result<(), ApiError> {
+        if self.available_quantity(jackpot_components) == 0 {
             return Err(ApiError::internal("Asset available quantity is 0"));
         }
         self.used_amount += 1;
         Ok(())
     }
+
+    /// Takes one item of the variant out of the stock of the gadget, when it's handed over.
+    /// The variant is required if the gadget has variants, and must not be set otherwise.
+    pub fn hand_over_gadget_variant(&mut self, variant_name: Option<&str>) -> Result<(), ApiError> {
+        let WheelAssetType::Gadget { variants, .. } = &mut self.asset_type else {
+            return Err(ApiError::invalid_argument("Asset is not a gadget"));
+        };
+
+        let variants = variants.as_deref_mut().unwrap_or_default();
+        match (variants.is_empty(), variant_name) {
+            (true, None) => Ok(()),
+            (true, Some(_)) => Err(ApiError::invalid_argument("Gadget has no variants")),
+            (false, None) => Err(ApiError::invalid_argument(
+                "Gadget variant must be provided",
+            )),
+            (false, Some(variant_name)) => {
+                let variant = variants
+                    .iter_mut()
+                    .find(|variant| variant.name == variant_name)
+                    .ok_or_else(|| {
+                        ApiError::not_found(&format!("Gadget variant {} not found", variant_name))
+                    })?;
+                if variant.available_amount() == 0 {
+                    return Err(ApiError::conflict(&format!(
+                        "Gadget variant {} is out of stock",
+                        variant_name
+                    )));
+                }
+                variant.handed_over_amount += 1;
+                Ok(())
+            }
+        }
+    }
+
+    /// Counts one more use of the asset without checking its available quantity,
+    /// e.g. for a prize that has already been paid out.
+    pub fn mark_one_used(&mut self) {
+        self.used_amount = self.used_amount.saturating_add(1);
+    }
 }
 
 impl Timestamped for WheelAsset {
@@ -457,11 +892,19 @@ pub fn icp_wheel_asset() -> (WheelAsset, Vec<u8>) {
                     ledger_canister_id: Principal::from_text("ryjl3-tyaaa-aaaaa-aaaba-cai")
                         .unwrap(),
                     decimals: 8,
+                    fee: None,
+                    symbol: None,
+                    name: None,
                 },
                 exchange_rate_symbol: Some("ICP".to_string()),
                 usd_price: None,
+                usd_price_fetch_status: None,
+                funding_source: None,
+                subaccount: Some(WheelAssetTokenSubaccount::Derived),
                 balance: None,
+                balance_fetch_status: None,
                 prize_usd_amount: 1.0,
+                prize_token_amount: None,
             },
             total_amount: 0,
             used_amount: 0,
@@ -472,6 +915,9 @@ pub fn icp_wheel_asset() -> (WheelAsset, Vec<u8>) {
                 background_color_hex: "#29ABE2".to_string(),
             },
             timestamps: TimestampFields::new(),
+            draw_weight: Some(WheelAssetDrawWeight::default()),
+            event_id: None,
+            retirement: None,
         },
         FRONTEND_ASSETS_DIR
             .get_file("images/tokens/icp.png")
@@ -490,11 +936,19 @@ pub fn ckbtc_wheel_asset() -> (WheelAsset, Vec<u8>) {
                     ledger_canister_id: Principal::from_text("mxzaz-hqaaa-aaaar-qaada-cai")
                         .unwrap(),
                     decimals: 8,
+                    fee: None,
+                    symbol: None,
+                    name: None,
                 },
                 exchange_rate_symbol: Some("BTC".to_string()),
                 usd_price: None,
+                usd_price_fetch_status: None,
+                funding_source: None,
+                subaccount: Some(WheelAssetTokenSubaccount::Derived),
                 balance: None,
+                balance_fetch_status: None,
                 prize_usd_amount: 1.0,
+                prize_token_amount: None,
             },
             total_amount: 0,
             used_amount: 0,
@@ -505,6 +959,9 @@ pub fn ckbtc_wheel_asset() -> (WheelAsset, Vec<u8>) {
                 background_color_hex: "#F15A24".to_string(),
             },
             timestamps: TimestampFields::new(),
+            draw_weight: Some(WheelAssetDrawWeight::default()),
+            event_id: None,
+            retirement: None,
         },
         FRONTEND_ASSETS_DIR
             .get_file("images/tokens/ckbtc.png")
@@ -523,11 +980,19 @@ pub fn cketh_wheel_asset() -> (WheelAsset, Vec<u8>) {
                     ledger_canister_id: Principal::from_text("ss2fx-dyaaa-aaaar-qacoq-cai")
                         .unwrap(),
                     decimals: 18,
+                    fee: None,
+                    symbol: None,
+                    name: None,
                 },
                 exchange_rate_symbol: Some("ETH".to_string()),
                 usd_price: None,
+                usd_price_fetch_status: None,
+                funding_source: None,
+                subaccount: Some(WheelAssetTokenSubaccount::Derived),
                 balance: None,
+                balance_fetch_status: None,
                 prize_usd_amount: 1.0,
+                prize_token_amount: None,
             },
             total_amount: 0,
             used_amount: 0,
@@ -538,6 +1003,9 @@ pub fn cketh_wheel_asset() -> (WheelAsset, Vec<u8>) {
                 background_color_hex: "#ED1E79".to_string(),
             },
             timestamps: TimestampFields::new(),
+            draw_weight: Some(WheelAssetDrawWeight::default()),
+            event_id: None,
+            retirement: None,
         },
         FRONTEND_ASSETS_DIR
             .get_file("images/tokens/cketh.png")
@@ -556,11 +1024,19 @@ pub fn ckusdc_wheel_asset() -> (WheelAsset, Vec<u8>) {
                     ledger_canister_id: Principal::from_text("xevnm-gaaaa-aaaar-qafnq-cai")
                         .unwrap(),
                     decimals: 6,
+                    fee: None,
+                    symbol: None,
+                    name: None,
                 },
                 exchange_rate_symbol: None,
                 usd_price: Some(WheelAssetTokenPrice::default_price()),
+                usd_price_fetch_status: None,
+                funding_source: None,
+                subaccount: Some(WheelAssetTokenSubaccount::Derived),
                 balance: None,
+                balance_fetch_status: None,
                 prize_usd_amount: 1.0,
+                prize_token_amount: None,
             },
             total_amount: 0,
             used_amount: 0,
@@ -571,6 +1047,9 @@ pub fn ckusdc_wheel_asset() -> (WheelAsset, Vec<u8>) {
                 background_color_hex: "#522785".to_string(),
             },
             timestamps: TimestampFields::new(),
+            draw_weight: Some(WheelAssetDrawWeight::default()),
+            event_id: None,
+            retirement: None,
         },
         FRONTEND_ASSETS_DIR
             .get_file("images/tokens/ckusdc.png")
@@ -589,6 +1068,7 @@ mod tests {
     #[rstest]
     #[case::token(fixtures::wheel_asset_token())]
     #[case::gadget(fixtures::wheel_asset_gadget())]
+    #[case::gadget_with_variants(fixtures::wheel_asset_gadget_with_variants())]
     #[case::jackpot(fixtures::wheel_asset_jackpot())]
     fn storable_impl(#[case] wheel_asset: WheelAsset) {
         let serialized_wheel_asset = wheel_asset.to_bytes();
@@ -610,6 +1090,49 @@ mod tests {
         assert_eq!(key, deserialized_key);
     }
 
+    #[rstest]
+    #[case::balance_covers_fee(1_000, 10, Some(990))]
+    #[case::balance_equals_fee(10, 10, None)]
+    #[case::balance_below_fee(5, 10, None)]
+    #[case::empty_balance(0, 10, None)]
+    fn wheel_asset_retirement_new(
+        #[case] balance: u128,
+        #[case] fee: u128,
+        #[case] expected_amount: Option<u128>,
+    ) {
+        let wheel_asset_id = fixtures::uuid();
+        let to = account();
+
+        let from_subaccount = Some(wheel_asset_subaccount(&wheel_asset_id));
+
+        let retirement =
+            WheelAssetRetirement::new(wheel_asset_id, from_subaccount, to, balance, fee, 42);
+
+        assert_eq!(
+            retirement.as_ref().map(|retirement| retirement.amount),
+            expected_amount
+        );
+        if let Some(retirement) = retirement {
+            assert_eq!(retirement.from_subaccount, from_subaccount);
+            assert_eq!(retirement.to, to);
+            assert_eq!(retirement.fee, fee);
+            assert_eq!(retirement.memo, wheel_asset_id.to_bytes().to_vec());
+            assert_eq!(retirement.created_at_time, 42);
+            assert_eq!(retirement.block_index, None);
+            assert!(!retirement.is_outcome_unknown());
+            assert_eq!(
+                retirement.sent_transfer(fixtures::principal()),
+                SentLedgerTransfer {
+                    ledger_canister_id: fixtures::principal(),
+                    to,
+                    amount: balance - fee,
+                    memo: wheel_asset_id.to_bytes().to_vec(),
+                    created_at_time: 42,
+                }
+            );
+        }
+    }
+
     #[rstest]
     #[case::token(fixtures::wheel_asset_token())]
     #[case::gadget(fixtures::wheel_asset_gadget())]
@@ -638,6 +1161,43 @@ mod tests {
         assert_eq!(new_usd_price.usd_price, 42.42);
     }
 
+    #[rstest]
+    #[case::fresh(Some("BTC"), true, 60, false)]
+    #[case::at_max_age(Some("BTC"), true, 120, false)]
+    #[case::stale(Some("BTC"), true, 121, true)]
+    #[case::not_fetched_yet(Some("BTC"), false, 1_000, false)]
+    #[case::fixed_price(None, true, 1_000, false)]
+    fn wheel_asset_type_is_usd_price_stale(
+        #[case] exchange_rate_symbol: Option<&str>,
+        #[case] has_usd_price: bool,
+        #[case] seconds_since_fetch: i64,
+        #[case] expected_stale: bool,
+    ) {
+        const MAX_AGE_SECONDS: u64 = 120;
+        let mut wheel_asset = fixtures::wheel_asset_token();
+        if let WheelAssetType::Token {
+            exchange_rate_symbol: symbol,
+            ..
+        } = &mut wheel_asset.asset_type
+        {
+            *symbol = exchange_rate_symbol.map(|s| s.to_string());
+        }
+        let now = get_current_date_time();
+        if has_usd_price {
+            wheel_asset.set_latest_price(WheelAssetTokenPrice {
+                usd_price: 42.42,
+                last_fetched_at: now.sub(chrono::Duration::seconds(seconds_since_fetch)),
+            });
+        }
+
+        assert_eq!(
+            wheel_asset
+                .asset_type
+                .is_usd_price_stale(MAX_AGE_SECONDS, &now),
+            expected_stale
+        );
+    }
+
     #[rstest]
     fn wheel_asset_type_set_latest_balance() {
         let mut wheel_asset = fixtures::wheel_asset_token();
@@ -650,6 +1210,43 @@ mod tests {
         assert_eq!(new_balance.balance, 42);
     }
 
+    #[rstest]
+    fn wheel_asset_type_fetch_failed() {
+        let mut wheel_asset = fixtures::wheel_asset_token();
+        let usd_price_fetch_status = |wheel_asset: &WheelAsset| match &wheel_asset.asset_type {
+            WheelAssetType::Token {
+                usd_price_fetch_status,
+                ..
+            } => usd_price_fetch_status.clone(),
+            _ => unreachable!(),
+        };
+        let balance_fetch_status = |wheel_asset: &WheelAsset| match &wheel_asset.asset_type {
+            WheelAssetType::Token {
+                balance_fetch_status,
+                ..
+            } => balance_fetch_status.clone(),
+            _ => unreachable!(),
+        };
+
+        wheel_asset.set_usd_price_fetch_failed(ApiError::internal("first"));
+        wheel_asset.set_usd_price_fetch_failed(ApiError::internal("second"));
+        wheel_asset.set_balance_fetch_failed(ApiError::internal("balance"));
+        let status = usd_price_fetch_status(&wheel_asset).unwrap();
+        assert_eq!(status.failed_attempts, 2);
+        assert_eq!(status.last_error, ApiError::internal("second"));
+        assert_eq!(
+            balance_fetch_status(&wheel_asset).unwrap().failed_attempts,
+            1
+        );
+
+        wheel_asset.set_latest_price(WheelAssetTokenPrice::new(42.42));
+        assert_eq!(usd_price_fetch_status(&wheel_asset), None);
+        assert!(balance_fetch_status(&wheel_asset).is_some());
+
+        wheel_asset.set_latest_balance(WheelAssetTokenBalance::new(42));
+        assert_eq!(balance_fetch_status(&wheel_asset), None);
+    }
+
     #[rstest]
     #[case::token((fixtures::wheel_asset_token(), true))]
     #[case::gadget((fixtures::wheel_asset_gadget(), false))]
@@ -658,6 +1255,95 @@ mod tests {
         assert_eq!(wheel_asset.is_token(), expected_is_token);
     }
 
+    #[rstest]
+    #[case::default(None, false)]
+    #[case::canister_account(Some(WheelAssetTokenFundingSource::CanisterAccount), false)]
+    #[case::sponsor_allowance(
+        Some(WheelAssetTokenFundingSource::SponsorAllowance {
+            sponsor_account: account(),
+        }),
+        true
+    )]
+    fn wheel_asset_type_token_funding_source(
+        #[case] funding_source: Option<WheelAssetTokenFundingSource>,
+        #[case] expected_sponsor: bool,
+    ) {
+        let mut wheel_asset = fixtures::wheel_asset_token();
+        if let WheelAssetType::Token {
+            funding_source: existing_funding_source,
+            ..
+        } = &mut wheel_asset.asset_type
+        {
+            *existing_funding_source = funding_source.clone();
+        }
+
+        assert_eq!(
+            wheel_asset.asset_type.token_funding_source(),
+            Some(
+                funding_source
+                    .as_ref()
+                    .unwrap_or(&WheelAssetTokenFundingSource::CanisterAccount)
+            )
+        );
+        assert_eq!(
+            wheel_asset.asset_type.token_sponsor_account(),
+            expected_sponsor.then(account)
+        );
+    }
+
+    #[rstest]
+    #[case::gadget(fixtures::wheel_asset_gadget())]
+    #[case::jackpot(fixtures::wheel_asset_jackpot())]
+    fn wheel_asset_type_token_funding_source_others(#[case] wheel_asset: WheelAsset) {
+        assert_eq!(wheel_asset.asset_type.token_funding_source(), None);
+        assert_eq!(wheel_asset.asset_type.token_sponsor_account(), None);
+    }
+
+    #[rstest]
+    fn wheel_asset_subaccount_from_id() {
+        let wheel_asset_id = fixtures::uuid();
+        let id_bytes = wheel_asset_id.to_bytes();
+
+        let subaccount = wheel_asset_subaccount(&wheel_asset_id);
+
+        assert_eq!(&subaccount[..id_bytes.len()], &id_bytes[..]);
+        assert!(subaccount[id_bytes.len()..].iter().all(|byte| *byte == 0));
+    }
+
+    #[rstest]
+    #[case::legacy(None, false)]
+    #[case::default(Some(WheelAssetTokenSubaccount::Default), false)]
+    #[case::derived(Some(WheelAssetTokenSubaccount::Derived), true)]
+    fn wheel_asset_type_token_subaccount(
+        #[case] subaccount: Option<WheelAssetTokenSubaccount>,
+        #[case] expected_derived: bool,
+    ) {
+        let wheel_asset_id = fixtures::uuid();
+        let mut wheel_asset = fixtures::wheel_asset_token();
+        if let WheelAssetType::Token {
+            subaccount: existing_subaccount,
+            ..
+        } = &mut wheel_asset.asset_type
+        {
+            *existing_subaccount = subaccount;
+        }
+
+        assert_eq!(
+            wheel_asset.asset_type.token_subaccount(&wheel_asset_id),
+            expected_derived.then(|| wheel_asset_subaccount(&wheel_asset_id))
+        );
+    }
+
+    #[rstest]
+    #[case::gadget(fixtures::wheel_asset_gadget())]
+    #[case::jackpot(fixtures::wheel_asset_jackpot())]
+    fn wheel_asset_type_token_subaccount_others(#[case] wheel_asset: WheelAsset) {
+        assert_eq!(
+            wheel_asset.asset_type.token_subaccount(&fixtures::uuid()),
+            None
+        );
+    }
+
     #[rstest]
     #[case((100_000_000, 8, 1.0, 1.0, 1))]
     #[case((100_000_000, 8, 1.9, 1.0, 1))]
@@ -698,6 +1384,61 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn wheel_asset_type_set_ledger_metadata() {
+        let ledger_metadata = fixtures::ledger_metadata();
+        let mut wheel_asset = fixtures::wheel_asset_token();
+
+        wheel_asset.set_ledger_metadata(&ledger_metadata);
+
+        let ledger_config = wheel_asset.asset_type.ledger_config().unwrap();
+        assert_eq!(ledger_config.decimals, ledger_metadata.decimals);
+        assert_eq!(ledger_config.fee, Some(ledger_metadata.fee));
+        assert_eq!(ledger_config.transfer_fee(), ledger_metadata.fee);
+        assert_eq!(ledger_config.symbol, ledger_metadata.symbol);
+        assert_eq!(ledger_config.name, ledger_metadata.name);
+    }
+
+    #[rstest]
+    #[case::usd_fee_not_fetched((100_000_000, None, None, 2))]
+    #[case::usd_fee((100_000_000, None, Some(10_000), 1))]
+    #[case::usd_fee_covered((100_020_000, None, Some(10_000), 2))]
+    #[case::fixed_fee_not_fetched((330, Some(100), None, 3))]
+    #[case::fixed_fee((330, Some(100), Some(10), 3))]
+    #[case::fixed_fee_not_covered((329, Some(100), Some(10), 2))]
+    #[case::fixed_fee_only((10, Some(100), Some(10), 0))]
+    fn available_token_draws_count_with_fee(
+        #[case] (initial_balance, initial_prize_token_amount, initial_fee, expected_draws): (
+            u128,
+            Option<u128>,
+            Option<u128>,
+            u32,
+        ),
+    ) {
+        let mut wheel_asset = fixtures::wheel_asset_token();
+        wheel_asset.set_latest_balance(WheelAssetTokenBalance::new(initial_balance));
+        wheel_asset.set_latest_price(WheelAssetTokenPrice::new(1.0));
+        match &mut wheel_asset.asset_type {
+            WheelAssetType::Token {
+                ledger_config,
+                prize_usd_amount,
+                prize_token_amount,
+                ..
+            } => {
+                ledger_config.decimals = 8;
+                ledger_config.fee = initial_fee;
+                *prize_usd_amount = 0.5;
+                *prize_token_amount = initial_prize_token_amount;
+            }
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            wheel_asset.asset_type.available_token_draws_count(),
+            Some(expected_draws)
+        );
+    }
+
     #[rstest]
     #[case::gadget(fixtures::wheel_asset_gadget())]
     #[case::jackpot(fixtures::wheel_asset_jackpot())]
@@ -705,6 +1446,59 @@ mod tests {
         assert_eq!(wheel_asset.asset_type.available_token_draws_count(), None);
     }
 
+    #[rstest]
+    #[case::exact((300, Some(1.0), 3, Some(0.000001)))]
+    #[case::remainder((250, Some(2.0), 2, Some(0.000002)))]
+    #[case::insufficient((99, Some(1.0), 0, Some(0.000001)))]
+    #[case::no_price((300, None, 3, None))]
+    fn token_fixed_amount_prize(
+        #[case] (initial_balance, initial_usd_price, expected_draws, expected_prize_usd_amount): (
+            u128,
+            Option<f64>,
+            u32,
+            Option<f64>,
+        ),
+    ) {
+        let mut wheel_asset = fixtures::wheel_asset_token();
+        match &mut wheel_asset.asset_type {
+            WheelAssetType::Token {
+                ledger_config,
+                usd_price,
+                prize_token_amount,
+                ..
+            } => {
+                ledger_config.decimals = 8;
+                *usd_price = None;
+                *prize_token_amount = Some(100);
+            }
+            _ => unreachable!(),
+        };
+        wheel_asset.set_latest_balance(WheelAssetTokenBalance::new(initial_balance));
+        if let Some(initial_usd_price) = initial_usd_price {
+            wheel_asset.set_latest_price(WheelAssetTokenPrice {
+                usd_price: initial_usd_price,
+                last_fetched_at: get_current_date_time().sub(chrono::Duration::days(1)),
+            });
+        }
+
+        assert_eq!(
+            wheel_asset.asset_type.available_token_draws_count(),
+            Some(expected_draws)
+        );
+        assert_eq!(wheel_asset.asset_type.token_prize_amount(), Some(100));
+        match (
+            wheel_asset.asset_type.token_prize_usd_amount(),
+            expected_prize_usd_amount,
+        ) {
+            (Some(actual), Some(expected)) => assert!((actual - expected).abs() < 1e-12),
+            (actual, expected) => assert_eq!(actual, expected),
+        }
+        // the paid amount does not depend on the price, which is therefore never stale
+        assert!(!wheel_asset
+            .asset_type
+            .is_usd_price_stale(60, &get_current_date_time()));
+    }
+
     #[rstest]
     #[case((1, 10, 0, 1))]
     #[case((100, 0, 0, 0))]
@@ -741,14 +1535,14 @@ mod tests {
 
                 // just to check if our parameters are correct
                 assert_eq!(
-                    wheel_asset.asset_type.token_total_usd_amount().unwrap(),
-                    avail_token_draws as f64 * PRICE_USD_AMOUNT
+                    wheel_asset.asset_type.available_token_draws_count(),
+                    Some(avail_token_draws as u32)
                 );
             }
             _ => unreachable!(),
         };
 
-        assert_eq!(wheel_asset.available_quantity(), expected_quantity);
+        assert_eq!(wheel_asset.available_quantity(&[]), expected_quantity);
     }
 
     #[rstest]
@@ -768,7 +1562,37 @@ mod tests {
     ) {
         wheel_asset.total_amount = total_amount;
         wheel_asset.used_amount = used_amount;
-        assert_eq!(wheel_asset.available_quantity(), expected_quantity);
+        let jackpot_components = jackpot_components(&[1_000, 1_000, 1_000, 1_000]);
+        assert_eq!(
+            wheel_asset.available_quantity(&jackpot_components),
+            expected_quantity
+        );
+    }
+
+    #[rstest]
+    #[case::components_available((vec![10, 10, 10, 10], 100, 0, 10))]
+    #[case::one_component_limited((vec![10, 3, 10, 10], 100, 0, 3))]
+    #[case::jackpot_limited((vec![10, 10, 10, 10], 100, 95, 5))]
+    #[case::one_component_empty((vec![10, 0, 10, 10], 100, 0, 0))]
+    #[case::one_component_missing((vec![10, 10, 10], 100, 0, 0))]
+    fn available_quantity_jackpot(
+        #[case] (components_draws, total_amount, used_amount, expected_quantity): (
+            Vec<u128>,
+            u32,
+            u32,
+            u32,
+        ),
+    ) {
+        let mut wheel_asset = fixtures::wheel_asset_jackpot();
+        wheel_asset.total_amount = total_amount;
+        wheel_asset.used_amount = used_amount;
+        let jackpot_components = jackpot_components(&components_draws);
+
+        assert_eq!(
+            wheel_asset.available_quantity(&jackpot_components),
+            expected_quantity
+        );
+        assert_eq!(wheel_asset.available_quantity(&[]), 0);
     }
 
     #[rstest]
@@ -807,18 +1631,18 @@ mod tests {
 
                 // just to check if our parameters are correct
                 assert_eq!(
-                    wheel_asset.asset_type.token_total_usd_amount().unwrap(),
-                    avail_token_draws as f64 * PRICE_USD_AMOUNT
+                    wheel_asset.asset_type.available_token_draws_count(),
+                    Some(avail_token_draws as u32)
                 );
             }
             _ => unreachable!(),
         };
 
         if expected_quantity > 0 {
-            wheel_asset.use_one().unwrap();
+            wheel_asset.use_one(&[]).unwrap();
             assert_eq!(wheel_asset.used_amount, used_amount + 1);
         } else {
-            let err = wheel_asset.use_one().unwrap_err();
+            let err = wheel_asset.use_one(&[]).unwrap_err();
             assert_eq!(wheel_asset.used_amount, used_amount);
             assert_eq!(err.message(), "Asset available quantity is 0");
         }
@@ -831,19 +1655,148 @@ mod tests {
         const TOTAL_AMOUNT: u32 = 2;
         wheel_asset.total_amount = TOTAL_AMOUNT;
         wheel_asset.used_amount = 0;
-        wheel_asset.use_one().unwrap();
+        let jackpot_components = jackpot_components(&[1_000, 1_000, 1_000, 1_000]);
+        wheel_asset.use_one(&jackpot_components).unwrap();
         assert_eq!(wheel_asset.total_amount, TOTAL_AMOUNT);
         assert_eq!(wheel_asset.used_amount, 1);
-        wheel_asset.use_one().unwrap();
+        wheel_asset.use_one(&jackpot_components).unwrap();
         assert_eq!(wheel_asset.total_amount, TOTAL_AMOUNT);
         assert_eq!(wheel_asset.used_amount, 2);
-        let err = wheel_asset.use_one().unwrap_err();
+        let err = wheel_asset.use_one(&jackpot_components).unwrap_err();
         assert_eq!(wheel_asset.total_amount, TOTAL_AMOUNT);
         assert_eq!(wheel_asset.used_amount, 2);
         assert_eq!(err.message(), "Asset available quantity is 0");
         wheel_asset.total_amount = 0;
-        let err = wheel_asset.use_one().unwrap_err();
+        let err = wheel_asset.use_one(&jackpot_components).unwrap_err();
         assert_eq!(wheel_asset.total_amount, 0);
         assert_eq!(err.message(), "Asset available quantity is 0");
     }
+
+    #[rstest]
+    #[case::token(fixtures::wheel_asset_token())]
+    #[case::gadget(fixtures::wheel_asset_gadget())]
+    #[case::jackpot(fixtures::wheel_asset_jackpot())]
+    fn mark_one_used(#[case] mut wheel_asset: WheelAsset) {
+        wheel_asset.total_amount = 1;
+        wheel_asset.used_amount = 1;
+
+        wheel_asset.mark_one_used();
+
+        assert_eq!(wheel_asset.used_amount, 2);
+        assert_eq!(wheel_asset.available_quantity(&[]), 0);
+    }
+
+    #[rstest]
+    #[case::without_variants(fixtures::wheel_asset_gadget(), None, None)]
+    #[case::variant_not_needed(
+        fixtures::wheel_asset_gadget(),
+        Some("M"),
+        Some("Gadget has no variants")
+    )]
+    #[case::variant(fixtures::wheel_asset_gadget_with_variants(), Some("M"), None)]
+    #[case::variant_missing(
+        fixtures::wheel_asset_gadget_with_variants(),
+        None,
+        Some("Gadget variant must be provided")
+    )]
+    #[case::variant_not_found(
+        fixtures::wheel_asset_gadget_with_variants(),
+        Some("XL"),
+        Some("Gadget variant XL not found")
+    )]
+    #[case::variant_out_of_stock(
+        fixtures::wheel_asset_gadget_with_variants(),
+        Some("L"),
+        Some("Gadget variant L is out of stock")
+    )]
+    #[case::not_a_gadget(fixtures::wheel_asset_token(), None, Some("Asset is not a gadget"))]
+    fn hand_over_gadget_variant(
+        #[case] mut wheel_asset: WheelAsset,
+        #[case] variant_name: Option<&str>,
+        #[case] expected_error: Option<&str>,
+    ) {
+        let original_wheel_asset = wheel_asset.clone();
+
+        let result = wheel_asset.hand_over_gadget_variant(variant_name);
+
+        match expected_error {
+            Some(expected_error) => {
+                assert_eq!(result.unwrap_err().message(), expected_error);
+                assert_eq!(wheel_asset, original_wheel_asset);
+            }
+            None => {
+                result.unwrap();
+                let handed_over_amounts = |wheel_asset: &WheelAsset| match &wheel_asset.asset_type {
+                    WheelAssetType::Gadget { variants, .. } => variants
+                        .iter()
+                        .flatten()
+                        .map(|variant| (variant.name.clone(), variant.handed_over_amount))
+                        .collect::<Vec<_>>(),
+                    _ => unreachable!(),
+                };
+                let expected_amounts = handed_over_amounts(&original_wheel_asset)
+                    .into_iter()
+                    .map(|(name, amount)| {
+                        let amount = if Some(name.as_str()) == variant_name {
+                            amount + 1
+                        } else {
+                            amount
+                        };
+                        (name, amount)
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(handed_over_amounts(&wheel_asset), expected_amounts);
+                // the draws are counted when the prize is won, not when it's handed over
+                assert_eq!(wheel_asset.used_amount, original_wheel_asset.used_amount);
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::default((None, 100, 0, 1))]
+    #[case::fixed((Some(WheelAssetDrawWeight::Fixed { weight: 5 }), 100, 0, 5))]
+    #[case::fixed_unavailable((Some(WheelAssetDrawWeight::Fixed { weight: 5 }), 100, 100, 0))]
+    #[case::available_quantity((Some(WheelAssetDrawWeight::AvailableQuantity), 100, 20, 80))]
+    #[case::available_quantity_unavailable((Some(WheelAssetDrawWeight::AvailableQuantity), 10, 10, 0))]
+    fn effective_draw_weight(
+        #[case] (draw_weight, total_amount, used_amount, expected_weight): (
+            Option<WheelAssetDrawWeight>,
+            u32,
+            u32,
+            u32,
+        ),
+    ) {
+        let mut wheel_asset = fixtures::wheel_asset_gadget();
+        wheel_asset.total_amount = total_amount;
+        wheel_asset.used_amount = used_amount;
+        wheel_asset.draw_weight = draw_weight;
+
+        assert_eq!(wheel_asset.effective_draw_weight(&[]), expected_weight);
+    }
+
+    /// Token assets worth the given number of prizes each.
+    fn account() -> Account {
+        Account {
+            owner: Principal::from_text("mxzaz-hqaaa-aaaar-qaada-cai").unwrap(),
+            subaccount: Some([1; 32]),
+        }
+    }
+
+    fn jackpot_components(draws: &[u128]) -> Vec<WheelAsset> {
+        draws
+            .iter()
+            .map(|draws| {
+                let mut wheel_asset = fixtures::wheel_asset_token();
+                wheel_asset.set_latest_balance(WheelAssetTokenBalance::new(*draws));
+                wheel_asset.set_latest_price(WheelAssetTokenPrice::new(1.0));
+                if let WheelAssetType::Token {
+                    prize_usd_amount, ..
+                } = &mut wheel_asset.asset_type
+                {
+                    *prize_usd_amount = 1.0;
+                }
+                wheel_asset
+            })
+            .collect()
+    }
 }
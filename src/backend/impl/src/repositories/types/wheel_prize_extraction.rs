@@ -1,13 +1,17 @@
 use std::{borrow::Cow, fmt::Display, ops::RangeBounds};
 
-use backend_api::ApiError;
-use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use backend_api::{draw_wheel_prize_index, ApiError, WHEEL_PRIZE_DRAW_SEED_LENGTH};
+use candid::{CandidType, Decode, Deserialize, Encode, Nat, Principal};
 use ic_stable_structures::{
     storable::{Blob, Bound},
     Storable,
 };
+use icrc_ledger_types::icrc1::account::{Account, Subaccount};
 
-use super::{TimestampFields, Timestamped, UserId, Uuid, WheelAssetId};
+use super::{
+    DateTime, EventId, InviteCodeId, SentLedgerTransfer, TimestampFields, Timestamped, UserId,
+    Uuid, WheelAssetId, WheelAssetTokenLedgerConfig,
+};
 
 pub type WheelPrizeExtractionId = Uuid;
 
@@ -55,10 +59,18 @@ impl From<WheelPrizeExtractionOld> for WheelPrizeExtraction {
 
         Self {
             extracted_for_principal: old.extracted_for_principal,
+            extracted_for_subaccount: None,
             state: old.state.into(),
             extracted_by_user_id: old.extracted_by_user_id,
             timestamps: old.timestamps,
             wheel_asset_id,
+            draw_proof: None,
+            event_id: None,
+            payouts: None,
+            stale_prices: None,
+            gadget_claim: None,
+            self_spin: None,
+            invite_code_id: None,
         }
     }
 }
@@ -78,8 +90,18 @@ impl Storable for WheelPrizeExtractionOld {
 #[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
 pub enum WheelPrizeExtractionState {
     Processing,
-    Completed { prize_usd_amount: Option<f64> },
-    Failed { error: ApiError },
+    Completed {
+        prize_usd_amount: Option<f64>,
+    },
+    Failed {
+        error: ApiError,
+    },
+    /// Some payouts of a jackpot succeeded and some failed.
+    /// The failed payouts can be retried.
+    PartiallyCompleted {
+        prize_usd_amount: Option<f64>,
+        error: ApiError,
+    },
 }
 
 impl From<&WheelPrizeExtractionState> for u8 {
@@ -88,6 +110,7 @@ impl From<&WheelPrizeExtractionState> for u8 {
             WheelPrizeExtractionState::Processing => 1,
             WheelPrizeExtractionState::Completed { .. } => 2,
             WheelPrizeExtractionState::Failed { .. } => 3,
+            WheelPrizeExtractionState::PartiallyCompleted { .. } => 4,
         }
     }
 }
@@ -100,6 +123,14 @@ impl Display for WheelPrizeExtractionState {
                 write!(f, "Completed (prize_usd_amount:{:?})", prize_usd_amount)
             }
             WheelPrizeExtractionState::Failed { error } => write!(f, "Failed (error:{error})",),
+            WheelPrizeExtractionState::PartiallyCompleted {
+                prize_usd_amount,
+                error,
+            } => write!(
+                f,
+                "PartiallyCompleted (prize_usd_amount:{:?}, error:{error})",
+                prize_usd_amount
+            ),
         }
     }
 }
@@ -110,28 +141,275 @@ impl WheelPrizeExtractionState {
             prize_usd_amount: None,
         }
     }
+
+    /// A partially completed state to look up the state index, where only the variant matters.
+    pub fn default_partially_completed() -> Self {
+        Self::PartiallyCompleted {
+            prize_usd_amount: None,
+            error: ApiError::internal("Some payouts failed"),
+        }
+    }
+
+    /// A failed state to look up the state index, where only the variant matters.
+    pub fn default_failed() -> Self {
+        Self::Failed {
+            error: ApiError::internal("Extraction failed"),
+        }
+    }
+}
+
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct WheelPrizeDrawCandidate {
+    pub wheel_asset_id: WheelAssetId,
+    pub weight: u32,
+}
+
+/// A draw whose wheel asset has been excluded because its token price couldn't be refreshed.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct WheelPrizeExcludedDraw {
+    pub candidates: Vec<WheelPrizeDrawCandidate>,
+    pub chosen_index: u32,
+}
+
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct WheelPrizeDrawProof {
+    pub seed: [u8; WHEEL_PRIZE_DRAW_SEED_LENGTH],
+    pub candidates: Vec<WheelPrizeDrawCandidate>,
+    pub chosen_index: u32,
+    /// The draws that preceded this one with the same seed, in order.
+    /// Proofs stored before the excluded draws were recorded don't have them.
+    pub excluded_draws: Option<Vec<WheelPrizeExcludedDraw>>,
+}
+
+impl WheelPrizeDrawProof {
+    /// Draws one of the candidates using the seed.
+    pub fn draw(
+        seed: [u8; WHEEL_PRIZE_DRAW_SEED_LENGTH],
+        candidates: Vec<WheelPrizeDrawCandidate>,
+    ) -> Result<Self, ApiError> {
+        let weights: Vec<u32> = candidates.iter().map(|c| c.weight).collect();
+        let chosen_index = draw_wheel_prize_index(seed, &weights)
+            .ok_or_else(|| ApiError::internal("No wheel assets with a positive weight to draw"))?;
+
+        Ok(Self {
+            seed,
+            candidates,
+            chosen_index: chosen_index as u32,
+            excluded_draws: Some(vec![]),
+        })
+    }
+
+    /// Draws again with the same seed among the given candidates,
+    /// recording the current draw as excluded.
+    pub fn redraw(self, candidates: Vec<WheelPrizeDrawCandidate>) -> Result<Self, ApiError> {
+        let mut excluded_draws = self.excluded_draws.unwrap_or_default();
+        excluded_draws.push(WheelPrizeExcludedDraw {
+            candidates: self.candidates,
+            chosen_index: self.chosen_index,
+        });
+
+        Ok(Self {
+            excluded_draws: Some(excluded_draws),
+            ..Self::draw(self.seed, candidates)?
+        })
+    }
+
+    /// Returns the drawn wheel asset, if `chosen_index` points to one of the candidates.
+    pub fn chosen_wheel_asset_id(&self) -> Option<WheelAssetId> {
+        self.candidates
+            .get(self.chosen_index as usize)
+            .map(|candidate| candidate.wheel_asset_id)
+    }
+}
+
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub enum WheelPrizePayoutState {
+    Pending,
+    Completed { block_index: Nat },
+    Failed { error: ApiError },
+}
+
+/// A token transfer to the extracted principal.
+///
+/// It's stored before calling the ledger, so that the transfer can be retried
+/// with the same deduplication parameters (`memo` and `created_at_time`)
+/// without paying the prize twice.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct WheelPrizePayout {
+    /// The token wheel asset being paid out,
+    /// which is one of the jackpot's assets for jackpot prizes.
+    pub wheel_asset_id: WheelAssetId,
+    pub ledger_canister_id: Principal,
+    /// The sponsor's account the payout is transferred from with `icrc2_transfer_from`.
+    /// If not provided, the payout is transferred from the canister account.
+    pub from_account: Option<Account>,
+    /// The canister's subaccount holding the funds of the asset: the subaccount the payout
+    /// is transferred from, or the spender's subaccount if `from_account` is set.
+    /// Payouts created before subaccounts were introduced don't have it
+    /// and use the default subaccount.
+    pub from_subaccount: Option<Subaccount>,
+    pub amount: u128,
+    /// The ledger fee when the payout was created, set explicitly in the transfer
+    /// so that a retried transfer is deduplicated even if the ledger fee changes meanwhile.
+    /// Payouts created before the fee was fetched don't have it and use the ledger's fee.
+    pub fee: Option<u128>,
+    pub memo: Vec<u8>,
+    pub created_at_time: u64,
+    pub state: WheelPrizePayoutState,
+    /// Whether a call to the ledger for this payout had an unknown outcome,
+    /// so that the ledger may have executed it: while set, the deduplication parameters
+    /// must not change, and a `TooOld` rejection means that the payout must be looked up
+    /// in the ledger's transactions instead of being sent again.
+    /// `None` for payouts stored before this flag existed.
+    pub outcome_unknown: Option<bool>,
+}
+
+impl WheelPrizePayout {
+    pub fn new_pending(
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+        wheel_asset_id: WheelAssetId,
+        ledger_config: &WheelAssetTokenLedgerConfig,
+        from_account: Option<Account>,
+        from_subaccount: Option<Subaccount>,
+        amount: u128,
+        created_at_time: u64,
+    ) -> Self {
+        Self {
+            wheel_asset_id,
+            ledger_canister_id: ledger_config.ledger_canister_id,
+            from_account,
+            from_subaccount,
+            amount,
+            fee: ledger_config.fee,
+            memo: wheel_prize_extraction_id.to_bytes().to_vec(),
+            created_at_time,
+            state: WheelPrizePayoutState::Pending,
+            outcome_unknown: Some(false),
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        matches!(self.state, WheelPrizePayoutState::Pending)
+    }
+
+    pub fn is_outcome_unknown(&self) -> bool {
+        self.outcome_unknown.unwrap_or(false)
+    }
+
+    /// The transfer of this payout to the account, to look it up in the ledger's transactions.
+    pub fn sent_transfer(&self, to: Account) -> SentLedgerTransfer {
+        SentLedgerTransfer {
+            ledger_canister_id: self.ledger_canister_id,
+            to,
+            amount: self.amount,
+            memo: self.memo.clone(),
+            created_at_time: self.created_at_time,
+        }
+    }
+}
+
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub enum StaleWheelAssetPriceResolution {
+    /// The price has been fetched again before the draw.
+    Refreshed { usd_price: f64 },
+    /// The price could not be fetched again, so the asset and the jackpots
+    /// paying it out have been excluded from the draw.
+    Excluded { error: ApiError },
+}
+
+/// A token price that was older than the maximum age allowed by the policy
+/// when the extraction started.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct StaleWheelAssetPrice {
+    pub wheel_asset_id: WheelAssetId,
+    pub last_fetched_at: DateTime,
+    pub resolution: StaleWheelAssetPriceResolution,
+}
+
+/// The collection of a gadget prize by the winner.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub enum WheelPrizeGadgetClaim {
+    Won,
+    HandedOver {
+        handed_over_at: DateTime,
+        /// The staff member who handed the gadget over.
+        handed_over_by_user_id: UserId,
+        /// The variant picked by the staff member, if the gadget has variants.
+        gadget_variant_name: Option<String>,
+    },
+}
+
+/// The code with which the winner spun the wheel for themselves.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub enum WheelPrizeSelfSpin {
+    /// The self-spin code of the event, which each principal can use once per event.
+    EventCode,
+    InviteCode {
+        invite_code_id: InviteCodeId,
+    },
 }
 
 #[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
 pub struct WheelPrizeExtraction {
+    /// The owner of the account the prize is paid out to,
+    /// which is also the principal the extraction policy is checked against.
     pub extracted_for_principal: Principal,
+    /// Extractions paid out to the default account don't have it.
+    pub extracted_for_subaccount: Option<Subaccount>,
     pub state: WheelPrizeExtractionState,
     pub extracted_by_user_id: UserId,
     pub timestamps: TimestampFields,
     pub wheel_asset_id: Option<WheelAssetId>,
+    /// Extractions created before draw proofs were introduced,
+    /// or that failed before the draw, don't have it.
+    pub draw_proof: Option<WheelPrizeDrawProof>,
+    /// The event in which the extraction happened, if any.
+    pub event_id: Option<EventId>,
+    /// The token transfers of the prize, stored before executing them.
+    /// Extractions created before payouts were introduced don't have them.
+    pub payouts: Option<Vec<WheelPrizePayout>>,
+    /// The token prices that were too old to draw the prize.
+    /// Extractions created before the price age was checked don't have them.
+    pub stale_prices: Option<Vec<StaleWheelAssetPrice>>,
+    /// Set when a gadget is won.
+    /// Gadgets won before claims were tracked don't have it.
+    pub gadget_claim: Option<WheelPrizeGadgetClaim>,
+    /// Set when the winner spun the wheel for themselves,
+    /// instead of being extracted by an admin or a scanner.
+    pub self_spin: Option<WheelPrizeSelfSpin>,
+    /// The invite code with which an admin or a scanner extracted the winner,
+    /// proving that the winner attended.
+    pub invite_code_id: Option<InviteCodeId>,
 }
 
 impl WheelPrizeExtraction {
     pub fn new_processing(
-        extracted_for_principal: Principal,
+        extracted_for: Account,
         extracted_by_user_id: UserId,
+        event_id: Option<EventId>,
     ) -> Self {
         Self {
-            extracted_for_principal,
+            extracted_for_principal: extracted_for.owner,
+            extracted_for_subaccount: extracted_for.subaccount,
             state: WheelPrizeExtractionState::Processing,
             extracted_by_user_id,
             timestamps: TimestampFields::new(),
             wheel_asset_id: None,
+            draw_proof: None,
+            event_id,
+            payouts: None,
+            stale_prices: None,
+            gadget_claim: None,
+            self_spin: None,
+            invite_code_id: None,
+        }
+    }
+
+    /// The account the prize is paid out to.
+    pub fn extracted_for_account(&self) -> Account {
+        Account {
+            owner: self.extracted_for_principal,
+            subaccount: self.extracted_for_subaccount,
         }
     }
 
@@ -145,9 +423,134 @@ impl WheelPrizeExtraction {
         self.wheel_asset_id = wheel_asset_id;
     }
 
+    pub fn set_partially_completed(
+        &mut self,
+        wheel_asset_id: WheelAssetId,
+        prize_usd_amount: Option<f64>,
+        error: ApiError,
+    ) {
+        self.state = WheelPrizeExtractionState::PartiallyCompleted {
+            prize_usd_amount,
+            error,
+        };
+        self.wheel_asset_id = Some(wheel_asset_id);
+    }
+
+    pub fn payout_state(&self, payout_index: usize) -> Option<&WheelPrizePayoutState> {
+        self.payouts
+            .as_ref()
+            .and_then(|payouts| payouts.get(payout_index))
+            .map(|payout| &payout.state)
+    }
+
+    pub fn set_payout_state(&mut self, payout_index: usize, state: WheelPrizePayoutState) {
+        if let Some(payout) = self
+            .payouts
+            .as_mut()
+            .and_then(|payouts| payouts.get_mut(payout_index))
+        {
+            payout.state = state;
+        }
+    }
+
+    pub fn set_payout_outcome_unknown(&mut self, payout_index: usize, outcome_unknown: bool) {
+        if let Some(payout) = self
+            .payouts
+            .as_mut()
+            .and_then(|payouts| payouts.get_mut(payout_index))
+        {
+            payout.outcome_unknown = Some(outcome_unknown);
+        }
+    }
+
+    /// Sets the failed payouts back to pending.
+    /// The payouts rejected by the ledger get a new `created_at_time`, as the ledger didn't
+    /// execute them, unless a previous call for them had an unknown outcome.
+    /// The others keep their deduplication parameters because the ledger may have executed them.
+    /// Returns the number of payouts to retry.
+    pub fn reset_failed_payouts(&mut self, created_at_time: u64) -> usize {
+        let mut failed_payouts_count = 0;
+        for payout in self.payouts.iter_mut().flatten() {
+            if let WheelPrizePayoutState::Failed { error } = &payout.state {
+                if error.is_ledger_transfer_rejected() && !payout.is_outcome_unknown() {
+                    payout.created_at_time = created_at_time;
+                }
+                payout.state = WheelPrizePayoutState::Pending;
+                failed_payouts_count += 1;
+            }
+        }
+        failed_payouts_count
+    }
+
+    pub fn has_pending_payouts(&self) -> bool {
+        self.payouts
+            .iter()
+            .flatten()
+            .any(|payout| payout.is_pending())
+    }
+
+    /// Whether a payout of the given token asset may still be transferred,
+    /// either because it's pending or because it failed and can be retried.
+    pub fn has_unsettled_payout(&self, wheel_asset_id: &WheelAssetId) -> bool {
+        self.payouts.iter().flatten().any(|payout| {
+            &payout.wheel_asset_id == wheel_asset_id
+                && !matches!(payout.state, WheelPrizePayoutState::Completed { .. })
+        })
+    }
+
+    pub fn is_processing(&self) -> bool {
+        matches!(self.state, WheelPrizeExtractionState::Processing)
+    }
+
     pub fn is_failed(&self) -> bool {
         matches!(self.state, WheelPrizeExtractionState::Failed { .. })
     }
+
+    pub fn is_completed(&self) -> bool {
+        matches!(self.state, WheelPrizeExtractionState::Completed { .. })
+    }
+
+    pub fn set_gadget_won(&mut self) {
+        self.gadget_claim = Some(WheelPrizeGadgetClaim::Won);
+    }
+
+    /// Whether the extraction won a gadget that has not been handed over yet.
+    pub fn is_gadget_unclaimed(&self) -> bool {
+        self.is_completed() && matches!(self.gadget_claim, Some(WheelPrizeGadgetClaim::Won))
+    }
+
+    pub fn set_gadget_handed_over(
+        &mut self,
+        handed_over_at: DateTime,
+        handed_over_by_user_id: UserId,
+        gadget_variant_name: Option<String>,
+    ) -> Result<(), ApiError> {
+        if let Some(WheelPrizeGadgetClaim::HandedOver { handed_over_at, .. }) = &self.gadget_claim {
+            return Err(ApiError::conflict(&format!(
+                "Gadget prize has already been handed over at {}",
+                handed_over_at
+            )));
+        }
+        if !self.is_gadget_unclaimed() {
+            return Err(ApiError::conflict(
+                "Extraction has no gadget prize to hand over",
+            ));
+        }
+
+        self.gadget_claim = Some(WheelPrizeGadgetClaim::HandedOver {
+            handed_over_at,
+            handed_over_by_user_id,
+            gadget_variant_name,
+        });
+        Ok(())
+    }
+
+    pub fn is_partially_completed(&self) -> bool {
+        matches!(
+            self.state,
+            WheelPrizeExtractionState::PartiallyCompleted { .. }
+        )
+    }
 }
 
 impl Timestamped for WheelPrizeExtraction {
@@ -162,15 +565,131 @@ impl Storable for WheelPrizeExtraction {
     }
 
     fn from_bytes(bytes: Cow<[u8]>) -> Self {
-        match Decode!(bytes.as_ref(), WheelPrizeExtractionOld) {
-            Ok(old) => old.into(),
-            Err(_) => Decode!(bytes.as_ref(), Self).unwrap(),
+        match Decode!(bytes.as_ref(), Self) {
+            // completed extractions always have a wheel asset id,
+            // unless they were stored with the old format, which had it in the state
+            Ok(extraction) if !extraction.is_completed() || extraction.wheel_asset_id.is_some() => {
+                extraction
+            }
+            _ => Decode!(bytes.as_ref(), WheelPrizeExtractionOld)
+                .unwrap()
+                .into(),
         }
     }
 
     const BOUND: Bound = Bound::Unbounded;
 }
 
+/// The criteria to list the extractions, all of which must match.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WheelPrizeExtractionFilter {
+    /// Only the variant of the state is matched.
+    pub state: Option<WheelPrizeExtractionState>,
+    pub wheel_asset_id: Option<WheelAssetId>,
+    pub extracted_by_user_id: Option<UserId>,
+    /// Included. Matched by [WheelPrizeExtractionIdRange], as the ids contain the creation time.
+    pub created_from: Option<DateTime>,
+    /// Excluded. Matched by [WheelPrizeExtractionIdRange], as the ids contain the creation time.
+    pub created_before: Option<DateTime>,
+}
+
+impl WheelPrizeExtractionFilter {
+    pub fn matches(&self, wheel_prize_extraction: &WheelPrizeExtraction) -> bool {
+        self.state
+            .as_ref()
+            .is_none_or(|state| u8::from(state) == u8::from(&wheel_prize_extraction.state))
+            && self.wheel_asset_id.is_none_or(|wheel_asset_id| {
+                wheel_prize_extraction.wheel_asset_id == Some(wheel_asset_id)
+            })
+            && self
+                .extracted_by_user_id
+                .is_none_or(|extracted_by_user_id| {
+                    wheel_prize_extraction.extracted_by_user_id == extracted_by_user_id
+                })
+    }
+}
+
+/// The ids of the extractions created in the time range of the filter.
+/// The extractions are listed newest first, so the range ends
+/// before the `start_after` extraction if provided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WheelPrizeExtractionIdRange {
+    start_bound: WheelPrizeExtractionId,
+    end_bound: WheelPrizeExtractionId,
+    end_excluded: bool,
+}
+
+impl WheelPrizeExtractionIdRange {
+    pub fn new(
+        filter: &WheelPrizeExtractionFilter,
+        start_after: Option<WheelPrizeExtractionId>,
+    ) -> Self {
+        // the ids can't encode pre-epoch timestamps, so the bounds are clamped to the epoch
+        let start_bound = filter.created_from.map_or_else(Uuid::min, |created_from| {
+            Uuid::min_at(created_from.max(DateTime::min()).timestamp_millis())
+        });
+        let end_bound = filter
+            .created_before
+            .map(|created_before| {
+                Uuid::min_at(created_before.max(DateTime::min()).timestamp_millis())
+            })
+            .into_iter()
+            .chain(start_after)
+            .min();
+
+        match end_bound {
+            Some(end_bound) => Self {
+                start_bound,
+                end_bound,
+                end_excluded: true,
+            },
+            None => Self {
+                start_bound,
+                end_bound: Uuid::max(),
+                end_excluded: false,
+            },
+        }
+    }
+
+    /// The ids of the extractions created from the given date time (included).
+    pub fn created_from(created_from: DateTime) -> Self {
+        Self::new(
+            &WheelPrizeExtractionFilter {
+                created_from: Some(created_from),
+                ..Default::default()
+            },
+            None,
+        )
+    }
+
+    pub fn all() -> Self {
+        Self {
+            start_bound: Uuid::min(),
+            end_bound: Uuid::max(),
+            end_excluded: false,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start_bound > self.end_bound
+            || (self.end_excluded && self.start_bound == self.end_bound)
+    }
+}
+
+impl RangeBounds<WheelPrizeExtractionId> for WheelPrizeExtractionIdRange {
+    fn start_bound(&self) -> std::ops::Bound<&WheelPrizeExtractionId> {
+        std::ops::Bound::Included(&self.start_bound)
+    }
+
+    fn end_bound(&self) -> std::ops::Bound<&WheelPrizeExtractionId> {
+        if self.end_excluded {
+            std::ops::Bound::Excluded(&self.end_bound)
+        } else {
+            std::ops::Bound::Included(&self.end_bound)
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct WheelPrizeExtractionStateKey(Blob<{ Self::MAX_SIZE as usize }>);
 
@@ -215,13 +734,22 @@ impl Storable for WheelPrizeExtractionStateKey {
 pub struct WheelPrizeExtractionStateRange {
     start_bound: WheelPrizeExtractionStateKey,
     end_bound: WheelPrizeExtractionStateKey,
+    end_excluded: bool,
 }
 
 impl WheelPrizeExtractionStateRange {
     pub fn new(state: &WheelPrizeExtractionState) -> Result<Self, ApiError> {
+        Self::with_ids(state, &WheelPrizeExtractionIdRange::all())
+    }
+
+    pub fn with_ids(
+        state: &WheelPrizeExtractionState,
+        id_range: &WheelPrizeExtractionIdRange,
+    ) -> Result<Self, ApiError> {
         Ok(Self {
-            start_bound: WheelPrizeExtractionStateKey::new(state, Uuid::min())?,
-            end_bound: WheelPrizeExtractionStateKey::new(state, Uuid::max())?,
+            start_bound: WheelPrizeExtractionStateKey::new(state, id_range.start_bound)?,
+            end_bound: WheelPrizeExtractionStateKey::new(state, id_range.end_bound)?,
+            end_excluded: id_range.end_excluded,
         })
     }
 }
@@ -232,7 +760,11 @@ impl RangeBounds<WheelPrizeExtractionStateKey> for WheelPrizeExtractionStateRang
     }
 
     fn end_bound(&self) -> std::ops::Bound<&WheelPrizeExtractionStateKey> {
-        std::ops::Bound::Included(&self.end_bound)
+        if self.end_excluded {
+            std::ops::Bound::Excluded(&self.end_bound)
+        } else {
+            std::ops::Bound::Included(&self.end_bound)
+        }
     }
 }
 
@@ -277,20 +809,38 @@ impl Storable for WheelPrizeExtractionAssetIdKey {
     };
 }
 
-// TODO: implement filters for asset id range in API
-// pub struct WheelPrizeExtractionAssetIdRange {
-//     start_bound: WheelPrizeExtractionAssetIdKey,
-//     end_bound: WheelPrizeExtractionAssetIdKey,
-// }
+pub struct WheelPrizeExtractionAssetIdRange {
+    start_bound: WheelPrizeExtractionAssetIdKey,
+    end_bound: WheelPrizeExtractionAssetIdKey,
+    end_excluded: bool,
+}
+
+impl WheelPrizeExtractionAssetIdRange {
+    pub fn new(
+        wheel_asset_id: WheelAssetId,
+        id_range: &WheelPrizeExtractionIdRange,
+    ) -> Result<Self, ApiError> {
+        Ok(Self {
+            start_bound: WheelPrizeExtractionAssetIdKey::new(wheel_asset_id, id_range.start_bound)?,
+            end_bound: WheelPrizeExtractionAssetIdKey::new(wheel_asset_id, id_range.end_bound)?,
+            end_excluded: id_range.end_excluded,
+        })
+    }
+}
+
+impl RangeBounds<WheelPrizeExtractionAssetIdKey> for WheelPrizeExtractionAssetIdRange {
+    fn start_bound(&self) -> std::ops::Bound<&WheelPrizeExtractionAssetIdKey> {
+        std::ops::Bound::Included(&self.start_bound)
+    }
 
-// impl WheelPrizeExtractionAssetIdRange {
-//     pub fn new(wheel_asset_id: WheelAssetId) -> Result<Self, ApiError> {
-//         Ok(Self {
-//             start_bound: WheelPrizeExtractionAssetIdKey::new(wheel_asset_id, Uuid::min())?,
-//             end_bound: WheelPrizeExtractionAssetIdKey::new(wheel_asset_id, Uuid::max())?,
-//         })
-//     }
-// }
+    fn end_bound(&self) -> std::ops::Bound<&WheelPrizeExtractionAssetIdKey> {
+        if self.end_excluded {
+            std::ops::Bound::Excluded(&self.end_bound)
+        } else {
+            std::ops::Bound::Included(&self.end_bound)
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct WheelPrizeExtractionUserIdKey(Blob<{ Self::MAX_SIZE as usize }>);
@@ -330,29 +880,217 @@ impl Storable for WheelPrizeExtractionUserIdKey {
     };
 }
 
-// TODO: implement filters for user id range in API
-// pub struct WheelPrizeExtractionUserIdRange {
-//     start_bound: WheelPrizeExtractionUserIdKey,
-//     end_bound: WheelPrizeExtractionUserIdKey,
-// }
+pub struct WheelPrizeExtractionUserIdRange {
+    start_bound: WheelPrizeExtractionUserIdKey,
+    end_bound: WheelPrizeExtractionUserIdKey,
+    end_excluded: bool,
+}
+
+impl WheelPrizeExtractionUserIdRange {
+    pub fn new(user_id: UserId, id_range: &WheelPrizeExtractionIdRange) -> Result<Self, ApiError> {
+        Ok(Self {
+            start_bound: WheelPrizeExtractionUserIdKey::new(user_id, id_range.start_bound)?,
+            end_bound: WheelPrizeExtractionUserIdKey::new(user_id, id_range.end_bound)?,
+            end_excluded: id_range.end_excluded,
+        })
+    }
+}
+
+impl RangeBounds<WheelPrizeExtractionUserIdKey> for WheelPrizeExtractionUserIdRange {
+    fn start_bound(&self) -> std::ops::Bound<&WheelPrizeExtractionUserIdKey> {
+        std::ops::Bound::Included(&self.start_bound)
+    }
+
+    fn end_bound(&self) -> std::ops::Bound<&WheelPrizeExtractionUserIdKey> {
+        if self.end_excluded {
+            std::ops::Bound::Excluded(&self.end_bound)
+        } else {
+            std::ops::Bound::Included(&self.end_bound)
+        }
+    }
+}
+
+/// Extractions that don't belong to any event are indexed under the nil UUID,
+/// which is never generated for an event.
+fn event_id_index_key(event_id: Option<EventId>) -> Uuid {
+    event_id.unwrap_or_else(Uuid::nil)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WheelPrizeExtractionEventIdKey(Blob<{ Self::MAX_SIZE as usize }>);
+
+impl WheelPrizeExtractionEventIdKey {
+    const MAX_SIZE: u32 = <(EventId, WheelPrizeExtractionId)>::BOUND.max_size();
+
+    pub fn new(
+        event_id: Option<EventId>,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+    ) -> Result<Self, ApiError> {
+        Ok(Self(
+            Blob::try_from(
+                (event_id_index_key(event_id), wheel_prize_extraction_id)
+                    .to_bytes()
+                    .as_ref(),
+            )
+            .map_err(|_| {
+                ApiError::internal(&format!(
+                    "Failed to convert event id {:?}, wheel prize extraction id {:?} to bytes.",
+                    event_id, wheel_prize_extraction_id
+                ))
+            })?,
+        ))
+    }
+}
+
+impl Storable for WheelPrizeExtractionEventIdKey {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        self.0.to_bytes()
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(Blob::from_bytes(bytes))
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: Self::MAX_SIZE,
+        is_fixed_size: true,
+    };
+}
+
+pub struct WheelPrizeExtractionEventIdRange {
+    start_bound: WheelPrizeExtractionEventIdKey,
+    end_bound: WheelPrizeExtractionEventIdKey,
+    end_excluded: bool,
+}
+
+impl WheelPrizeExtractionEventIdRange {
+    pub fn new(
+        event_id: Option<EventId>,
+        id_range: &WheelPrizeExtractionIdRange,
+    ) -> Result<Self, ApiError> {
+        Ok(Self {
+            start_bound: WheelPrizeExtractionEventIdKey::new(event_id, id_range.start_bound)?,
+            end_bound: WheelPrizeExtractionEventIdKey::new(event_id, id_range.end_bound)?,
+            end_excluded: id_range.end_excluded,
+        })
+    }
+}
+
+impl RangeBounds<WheelPrizeExtractionEventIdKey> for WheelPrizeExtractionEventIdRange {
+    fn start_bound(&self) -> std::ops::Bound<&WheelPrizeExtractionEventIdKey> {
+        std::ops::Bound::Included(&self.start_bound)
+    }
+
+    fn end_bound(&self) -> std::ops::Bound<&WheelPrizeExtractionEventIdKey> {
+        if self.end_excluded {
+            std::ops::Bound::Excluded(&self.end_bound)
+        } else {
+            std::ops::Bound::Included(&self.end_bound)
+        }
+    }
+}
+
+pub(super) const PRINCIPAL_MAX_SIZE: usize = 29;
+
+/// Indexes the extractions by principal, event and id.
+///
+/// The principal is stored as its length followed by its bytes padded to the maximum principal size,
+/// so that all the keys have the same size and the extractions of a principal are sorted by event and id.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WheelPrizeExtractionPrincipalKey(Blob<{ Self::MAX_SIZE as usize }>);
+
+impl WheelPrizeExtractionPrincipalKey {
+    const MAX_SIZE: u32 =
+        1 + PRINCIPAL_MAX_SIZE as u32 + <(EventId, WheelPrizeExtractionId)>::BOUND.max_size();
+
+    pub fn new(
+        principal: &Principal,
+        event_id: Option<EventId>,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+    ) -> Result<Self, ApiError> {
+        let principal_bytes = principal.as_slice();
+        let mut bytes = Vec::with_capacity(Self::MAX_SIZE as usize);
+        bytes.push(principal_bytes.len() as u8);
+        bytes.extend_from_slice(principal_bytes);
+        bytes.resize(1 + PRINCIPAL_MAX_SIZE, 0);
+        bytes.extend_from_slice(
+            &(event_id_index_key(event_id), wheel_prize_extraction_id).to_bytes(),
+        );
+
+        Ok(Self(Blob::try_from(bytes.as_slice()).map_err(|_| {
+            ApiError::internal(&format!(
+                "Failed to convert principal {}, event id {:?}, wheel prize extraction id {:?} to bytes.",
+                principal, event_id, wheel_prize_extraction_id
+            ))
+        })?))
+    }
+}
+
+impl Storable for WheelPrizeExtractionPrincipalKey {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        self.0.to_bytes()
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(Blob::from_bytes(bytes))
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: Self::MAX_SIZE,
+        is_fixed_size: true,
+    };
+}
+
+pub struct WheelPrizeExtractionPrincipalRange {
+    start_bound: WheelPrizeExtractionPrincipalKey,
+    end_bound: WheelPrizeExtractionPrincipalKey,
+}
+
+impl WheelPrizeExtractionPrincipalRange {
+    /// The range of the extractions of the principal in the given event.
+    pub fn new(principal: &Principal, event_id: Option<EventId>) -> Result<Self, ApiError> {
+        Ok(Self {
+            start_bound: WheelPrizeExtractionPrincipalKey::new(principal, event_id, Uuid::min())?,
+            end_bound: WheelPrizeExtractionPrincipalKey::new(principal, event_id, Uuid::max())?,
+        })
+    }
+
+    /// The range of all the extractions of the principal, in and outside of any event.
+    pub fn all_events(principal: &Principal) -> Result<Self, ApiError> {
+        Ok(Self {
+            start_bound: WheelPrizeExtractionPrincipalKey::new(principal, None, Uuid::min())?,
+            end_bound: WheelPrizeExtractionPrincipalKey::new(
+                principal,
+                Some(Uuid::max()),
+                Uuid::max(),
+            )?,
+        })
+    }
+}
+
+impl RangeBounds<WheelPrizeExtractionPrincipalKey> for WheelPrizeExtractionPrincipalRange {
+    fn start_bound(&self) -> std::ops::Bound<&WheelPrizeExtractionPrincipalKey> {
+        std::ops::Bound::Included(&self.start_bound)
+    }
 
-// impl WheelPrizeExtractionUserIdRange {
-//     pub fn new(user_id: UserId) -> Result<Self, ApiError> {
-//         Ok(Self {
-//             start_bound: WheelPrizeExtractionUserIdKey::new(user_id, Uuid::min())?,
-//             end_bound: WheelPrizeExtractionUserIdKey::new(user_id, Uuid::max())?,
-//         })
-//     }
-// }
+    fn end_bound(&self) -> std::ops::Bound<&WheelPrizeExtractionPrincipalKey> {
+        std::ops::Bound::Included(&self.end_bound)
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::fixtures;
+    use backend_api::LedgerTransferRejection;
     use rstest::*;
 
     #[rstest]
-    #[case(fixtures::wheel_prize_extraction())]
+    #[case::completed(fixtures::wheel_prize_extraction())]
+    #[case::processing(fixtures::wheel_prize_extraction_processing())]
+    #[case::failed(fixtures::wheel_prize_extraction_failed())]
+    #[case::partially_completed(fixtures::wheel_prize_extraction_partially_completed())]
+    #[case::gadget_handed_over(fixtures::wheel_prize_extraction_gadget_handed_over())]
     fn storable_impl(#[case] wheel_prize_extraction: WheelPrizeExtraction) {
         let serialized_wheel_prize_extraction = wheel_prize_extraction.to_bytes();
         let deserialized_wheel_prize_extraction =
@@ -361,6 +1099,100 @@ mod tests {
         assert_eq!(wheel_prize_extraction, deserialized_wheel_prize_extraction);
     }
 
+    #[rstest]
+    #[case::default_account(None)]
+    #[case::subaccount(Some([1; 32]))]
+    fn extracted_for_account(#[case] subaccount: Option<Subaccount>) {
+        let account = Account {
+            owner: fixtures::principal(),
+            subaccount,
+        };
+        let wheel_prize_extraction =
+            WheelPrizeExtraction::new_processing(account, fixtures::uuid_a(), None);
+
+        assert_eq!(
+            wheel_prize_extraction.extracted_for_principal,
+            fixtures::principal()
+        );
+        assert_eq!(wheel_prize_extraction.extracted_for_subaccount, subaccount);
+        assert_eq!(wheel_prize_extraction.extracted_for_account(), account);
+    }
+
+    #[rstest]
+    #[case::won(fixtures::wheel_prize_extraction_gadget(), true)]
+    #[case::handed_over(fixtures::wheel_prize_extraction_gadget_handed_over(), false)]
+    #[case::not_tracked(
+        WheelPrizeExtraction {
+            gadget_claim: None,
+            ..fixtures::wheel_prize_extraction_gadget()
+        },
+        false
+    )]
+    #[case::failed(
+        WheelPrizeExtraction {
+            state: WheelPrizeExtractionState::Failed {
+                error: ApiError::internal("error"),
+            },
+            ..fixtures::wheel_prize_extraction_gadget()
+        },
+        false
+    )]
+    #[case::token(fixtures::wheel_prize_extraction(), false)]
+    fn is_gadget_unclaimed(
+        #[case] wheel_prize_extraction: WheelPrizeExtraction,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(wheel_prize_extraction.is_gadget_unclaimed(), expected);
+    }
+
+    #[rstest]
+    fn set_gadget_handed_over() {
+        let mut wheel_prize_extraction = fixtures::wheel_prize_extraction_gadget();
+        let handed_over_by_user_id = fixtures::uuid_a();
+
+        wheel_prize_extraction
+            .set_gadget_handed_over(
+                fixtures::date_time_a(),
+                handed_over_by_user_id,
+                Some("M".to_string()),
+            )
+            .unwrap();
+
+        assert_eq!(
+            wheel_prize_extraction.gadget_claim,
+            Some(WheelPrizeGadgetClaim::HandedOver {
+                handed_over_at: fixtures::date_time_a(),
+                handed_over_by_user_id,
+                gadget_variant_name: Some("M".to_string()),
+            })
+        );
+        assert!(!wheel_prize_extraction.is_gadget_unclaimed());
+    }
+
+    #[rstest]
+    #[case::handed_over(
+        fixtures::wheel_prize_extraction_gadget_handed_over(),
+        "Gadget prize has already been handed over at"
+    )]
+    #[case::token(
+        fixtures::wheel_prize_extraction(),
+        "Extraction has no gadget prize to hand over"
+    )]
+    fn set_gadget_handed_over_conflict(
+        #[case] mut wheel_prize_extraction: WheelPrizeExtraction,
+        #[case] expected_error: &str,
+    ) {
+        let original_gadget_claim = wheel_prize_extraction.gadget_claim.clone();
+
+        let err = wheel_prize_extraction
+            .set_gadget_handed_over(fixtures::date_time_b(), fixtures::uuid_b(), None)
+            .unwrap_err();
+
+        assert_eq!(err.code(), 409);
+        assert!(err.message().starts_with(expected_error));
+        assert_eq!(wheel_prize_extraction.gadget_claim, original_gadget_claim);
+    }
+
     #[rstest]
     #[case::completed(fixtures::old_wheel_prize_extraction_completed())]
     #[case::pending(fixtures::old_wheel_prize_extraction_pending())]
@@ -417,10 +1249,231 @@ mod tests {
         }
     }
 
+    #[rstest]
+    fn set_payout_state() {
+        let mut wheel_prize_extraction = fixtures::wheel_prize_extraction_processing();
+        let original_payouts = wheel_prize_extraction.payouts.clone().unwrap();
+        let block_index = Nat::from(7u64);
+
+        wheel_prize_extraction.set_payout_state(
+            0,
+            WheelPrizePayoutState::Completed {
+                block_index: block_index.clone(),
+            },
+        );
+        // out of bounds indexes are ignored
+        wheel_prize_extraction.set_payout_state(1, WheelPrizePayoutState::Pending);
+
+        let payouts = wheel_prize_extraction.payouts.unwrap();
+        assert_eq!(payouts.len(), 1);
+        assert_eq!(
+            payouts[0].state,
+            WheelPrizePayoutState::Completed { block_index }
+        );
+        assert_eq!(payouts[0].memo, original_payouts[0].memo);
+    }
+
+    #[rstest]
+    fn reset_failed_payouts() {
+        let mut wheel_prize_extraction = fixtures::wheel_prize_extraction_partially_completed();
+        let original_payouts = wheel_prize_extraction.payouts.clone().unwrap();
+        let created_at_time = original_payouts[1].created_at_time + 1;
+
+        let failed_payouts_count = wheel_prize_extraction.reset_failed_payouts(created_at_time);
+
+        let payouts = wheel_prize_extraction.payouts.unwrap();
+        assert_eq!(failed_payouts_count, 1);
+        // completed payouts are left untouched
+        assert_eq!(payouts[0], original_payouts[0]);
+        assert_eq!(payouts[1].state, WheelPrizePayoutState::Pending);
+        assert_eq!(payouts[1].created_at_time, created_at_time);
+        assert_eq!(payouts[1].memo, original_payouts[1].memo);
+    }
+
+    #[rstest]
+    #[case::call_failed(ApiError::ledger_call_failed(
+        fixtures::wheel_prize_payout().ledger_canister_id,
+        "canister stopped",
+    ))]
+    #[case::legacy(ApiError::internal("Call to ledger canister failed"))]
+    fn reset_failed_payouts_not_rejected(#[case] error: ApiError) {
+        let mut wheel_prize_extraction = fixtures::wheel_prize_extraction_partially_completed();
+        wheel_prize_extraction.set_payout_state(1, WheelPrizePayoutState::Failed { error });
+        let original_payouts = wheel_prize_extraction.payouts.clone().unwrap();
+
+        let failed_payouts_count =
+            wheel_prize_extraction.reset_failed_payouts(original_payouts[1].created_at_time + 1);
+
+        let payouts = wheel_prize_extraction.payouts.unwrap();
+        assert_eq!(failed_payouts_count, 1);
+        assert_eq!(payouts[1].state, WheelPrizePayoutState::Pending);
+        // the ledger may have executed the payout, so it's retried with the same parameters
+        assert_eq!(
+            payouts[1].created_at_time,
+            original_payouts[1].created_at_time
+        );
+    }
+
+    #[rstest]
+    // a call failed, then the retry with the same parameters was too old for the ledger
+    // and the payout could not be found in the ledger's transactions
+    #[case::call_failed_then_too_old(true, false)]
+    // the payout has not been found in the ledger's transactions, so it can be sent again
+    #[case::too_old_not_executed(false, true)]
+    fn reset_failed_payouts_too_old(
+        #[case] outcome_unknown: bool,
+        #[case] expected_new_created_at_time: bool,
+    ) {
+        let mut wheel_prize_extraction = fixtures::wheel_prize_extraction_partially_completed();
+        let ledger_canister_id = fixtures::wheel_prize_payout().ledger_canister_id;
+        wheel_prize_extraction.set_payout_state(
+            1,
+            WheelPrizePayoutState::Failed {
+                error: ApiError::ledger_transfer_rejected(
+                    ledger_canister_id,
+                    LedgerTransferRejection::TooOld,
+                ),
+            },
+        );
+        wheel_prize_extraction.set_payout_outcome_unknown(1, outcome_unknown);
+        let original_payouts = wheel_prize_extraction.payouts.clone().unwrap();
+        let created_at_time = original_payouts[1].created_at_time + 1;
+
+        let failed_payouts_count = wheel_prize_extraction.reset_failed_payouts(created_at_time);
+
+        let payouts = wheel_prize_extraction.payouts.unwrap();
+        assert_eq!(failed_payouts_count, 1);
+        assert_eq!(payouts[1].state, WheelPrizePayoutState::Pending);
+        assert_eq!(payouts[1].outcome_unknown, Some(outcome_unknown));
+        assert_eq!(
+            payouts[1].created_at_time,
+            if expected_new_created_at_time {
+                created_at_time
+            } else {
+                original_payouts[1].created_at_time
+            }
+        );
+    }
+
+    #[rstest]
+    #[case::processing(fixtures::wheel_prize_extraction_processing(), true)]
+    #[case::partially_completed(fixtures::wheel_prize_extraction_partially_completed(), false)]
+    #[case::completed(fixtures::wheel_prize_extraction(), false)]
+    fn has_pending_payouts(
+        #[case] wheel_prize_extraction: WheelPrizeExtraction,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(wheel_prize_extraction.has_pending_payouts(), expected);
+    }
+
+    #[rstest]
+    #[case::pending(fixtures::wheel_prize_extraction_processing(), true, true)]
+    #[case::failed(fixtures::wheel_prize_extraction_partially_completed(), true, true)]
+    #[case::completed(fixtures::wheel_prize_extraction(), true, false)]
+    #[case::other_asset(fixtures::wheel_prize_extraction_processing(), false, false)]
+    fn has_unsettled_payout(
+        #[case] wheel_prize_extraction: WheelPrizeExtraction,
+        #[case] same_asset: bool,
+        #[case] expected: bool,
+    ) {
+        // the ids generated by the fixtures depend on the current time,
+        // so the asset id is taken from the extraction's last payout, which is the failed one
+        let payout_wheel_asset_id = wheel_prize_extraction
+            .payouts
+            .as_ref()
+            .and_then(|payouts| payouts.last())
+            .map(|payout| payout.wheel_asset_id)
+            .unwrap_or_else(fixtures::uuid);
+        let wheel_asset_id = if same_asset {
+            payout_wheel_asset_id
+        } else {
+            fixtures::uuid_a()
+        };
+
+        assert_eq!(
+            wheel_prize_extraction.has_unsettled_payout(&wheel_asset_id),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn wheel_prize_draw_proof_draw() {
+        let candidates = vec![
+            WheelPrizeDrawCandidate {
+                wheel_asset_id: fixtures::uuid(),
+                weight: 0,
+            },
+            WheelPrizeDrawCandidate {
+                wheel_asset_id: fixtures::uuid(),
+                weight: 2,
+            },
+        ];
+        let draw_proof = WheelPrizeDrawProof::draw([1; 32], candidates.clone()).unwrap();
+
+        assert_eq!(draw_proof.chosen_index, 1);
+        assert_eq!(
+            draw_proof.chosen_wheel_asset_id(),
+            Some(candidates[1].wheel_asset_id)
+        );
+        assert!(WheelPrizeDrawProof::draw([1; 32], candidates[..1].to_vec()).is_err());
+
+        let inconsistent_proof = WheelPrizeDrawProof {
+            chosen_index: 2,
+            ..draw_proof
+        };
+        assert_eq!(inconsistent_proof.chosen_wheel_asset_id(), None);
+    }
+
+    #[rstest]
+    fn wheel_prize_draw_proof_redraw() {
+        let candidates = vec![
+            WheelPrizeDrawCandidate {
+                wheel_asset_id: fixtures::uuid_a(),
+                weight: 1,
+            },
+            WheelPrizeDrawCandidate {
+                wheel_asset_id: fixtures::uuid_b(),
+                weight: 1,
+            },
+        ];
+        let draw_proof = WheelPrizeDrawProof::draw([1; 32], candidates.clone()).unwrap();
+        let remaining_candidates = candidates
+            .iter()
+            .filter(|candidate| {
+                Some(candidate.wheel_asset_id) != draw_proof.chosen_wheel_asset_id()
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let redraw_proof = draw_proof
+            .clone()
+            .redraw(remaining_candidates.clone())
+            .unwrap();
+
+        assert_eq!(redraw_proof.seed, draw_proof.seed);
+        assert_eq!(redraw_proof.candidates, remaining_candidates);
+        assert_eq!(
+            redraw_proof.chosen_wheel_asset_id(),
+            Some(remaining_candidates[0].wheel_asset_id)
+        );
+        assert_eq!(
+            redraw_proof.excluded_draws,
+            Some(vec![WheelPrizeExcludedDraw {
+                candidates,
+                chosen_index: draw_proof.chosen_index,
+            }])
+        );
+        assert!(backend_api::WheelPrizeDrawProof::from(redraw_proof).verify());
+    }
+
     #[rstest]
     #[case::processing(WheelPrizeExtractionState::Processing)]
     #[case::completed(WheelPrizeExtractionState::Completed { prize_usd_amount: Some(1.5) })]
     #[case::failed(WheelPrizeExtractionState::Failed { error: ApiError::internal("error") })]
+    #[case::partially_completed(WheelPrizeExtractionState::PartiallyCompleted {
+        prize_usd_amount: Some(1.5),
+        error: ApiError::internal("error"),
+    })]
     fn wheel_prize_extraction_state_key_storable_impl(#[case] state: WheelPrizeExtractionState) {
         let wheel_prize_extraction_id = fixtures::uuid();
         let key = WheelPrizeExtractionStateKey::new(&state, wheel_prize_extraction_id).unwrap();
@@ -452,4 +1505,273 @@ mod tests {
 
         assert_eq!(key, deserialized_key);
     }
+
+    #[rstest]
+    #[case::event(Some(fixtures::uuid_a()))]
+    #[case::no_event(None)]
+    fn wheel_prize_extraction_event_id_key_storable_impl(#[case] event_id: Option<EventId>) {
+        let wheel_prize_extraction_id = fixtures::uuid();
+        let key = WheelPrizeExtractionEventIdKey::new(event_id, wheel_prize_extraction_id).unwrap();
+        let serialized_key = key.to_bytes();
+        let deserialized_key = WheelPrizeExtractionEventIdKey::from_bytes(serialized_key);
+
+        assert_eq!(key, deserialized_key);
+    }
+
+    #[rstest]
+    #[case::event(Some(fixtures::uuid_a()))]
+    #[case::no_event(None)]
+    fn wheel_prize_extraction_principal_key_storable_impl(#[case] event_id: Option<EventId>) {
+        let principal = fixtures::principal();
+        let wheel_prize_extraction_id = fixtures::uuid();
+        let key =
+            WheelPrizeExtractionPrincipalKey::new(&principal, event_id, wheel_prize_extraction_id)
+                .unwrap();
+        let serialized_key = key.to_bytes();
+        let deserialized_key = WheelPrizeExtractionPrincipalKey::from_bytes(serialized_key);
+
+        assert_eq!(key, deserialized_key);
+    }
+
+    #[rstest]
+    fn wheel_prize_extraction_filter_matches() {
+        let wheel_prize_extraction = fixtures::wheel_prize_extraction();
+
+        assert!(WheelPrizeExtractionFilter::default().matches(&wheel_prize_extraction));
+        assert!(WheelPrizeExtractionFilter {
+            state: Some(WheelPrizeExtractionState::default_completed()),
+            wheel_asset_id: wheel_prize_extraction.wheel_asset_id,
+            extracted_by_user_id: Some(wheel_prize_extraction.extracted_by_user_id),
+            ..Default::default()
+        }
+        .matches(&wheel_prize_extraction));
+        assert!(!WheelPrizeExtractionFilter {
+            state: Some(WheelPrizeExtractionState::default_failed()),
+            ..Default::default()
+        }
+        .matches(&wheel_prize_extraction));
+        assert!(!WheelPrizeExtractionFilter {
+            wheel_asset_id: Some(Uuid::max()),
+            ..Default::default()
+        }
+        .matches(&wheel_prize_extraction));
+        assert!(!WheelPrizeExtractionFilter {
+            extracted_by_user_id: Some(Uuid::max()),
+            ..Default::default()
+        }
+        .matches(&wheel_prize_extraction));
+    }
+
+    #[rstest]
+    #[case::all(None, None, None, true)]
+    #[case::created_in_range(
+        Some(fixtures::date_time_a()),
+        Some(fixtures::date_time_future()),
+        None,
+        true
+    )]
+    #[case::created_before_range(Some(fixtures::date_time_future()), None, None, false)]
+    #[case::created_after_range(None, Some(fixtures::date_time_a()), None, false)]
+    #[case::created_from_pre_epoch(Some(pre_epoch_date_time()), None, None, true)]
+    #[case::created_before_pre_epoch(None, Some(pre_epoch_date_time()), None, false)]
+    #[case::before_start_after(None, None, Some(fixtures::uuid_b()), true)]
+    #[case::start_after_itself(None, None, Some(fixtures::uuid_a()), false)]
+    fn wheel_prize_extraction_id_range(
+        #[case] created_from: Option<DateTime>,
+        #[case] created_before: Option<DateTime>,
+        #[case] start_after: Option<WheelPrizeExtractionId>,
+        #[case] expected: bool,
+    ) {
+        let filter = WheelPrizeExtractionFilter {
+            created_from,
+            created_before,
+            ..Default::default()
+        };
+
+        let range = WheelPrizeExtractionIdRange::new(&filter, start_after);
+
+        assert_eq!(range.contains(&fixtures::uuid_a()), expected);
+    }
+
+    fn pre_epoch_date_time() -> DateTime {
+        DateTime::try_from("1969-12-31T00:00:00Z").unwrap()
+    }
+
+    #[rstest]
+    fn wheel_prize_extraction_id_range_created_at() {
+        let wheel_prize_extraction_id = fixtures::uuid_a();
+        let created_at =
+            DateTime::from_timestamp_micros(wheel_prize_extraction_id.timestamp_millis() * 1_000)
+                .unwrap();
+        let filter = WheelPrizeExtractionFilter {
+            created_from: Some(created_at),
+            created_before: Some(created_at.add(chrono::Duration::seconds(1))),
+            ..Default::default()
+        };
+
+        let range = WheelPrizeExtractionIdRange::new(&filter, None);
+
+        assert!(range.contains(&wheel_prize_extraction_id));
+        assert!(!range.contains(&fixtures::uuid_b()));
+    }
+
+    #[rstest]
+    fn wheel_prize_extraction_id_range_is_empty() {
+        assert!(!WheelPrizeExtractionIdRange::all().is_empty());
+        assert!(WheelPrizeExtractionIdRange::new(
+            &WheelPrizeExtractionFilter {
+                created_from: Some(fixtures::date_time_future()),
+                created_before: Some(fixtures::date_time_a()),
+                ..Default::default()
+            },
+            None,
+        )
+        .is_empty());
+        assert!(WheelPrizeExtractionIdRange::new(
+            &WheelPrizeExtractionFilter {
+                created_from: Some(fixtures::date_time_future()),
+                ..Default::default()
+            },
+            Some(fixtures::uuid_a()),
+        )
+        .is_empty());
+    }
+
+    #[rstest]
+    fn wheel_prize_extraction_state_range_with_ids() {
+        let state = WheelPrizeExtractionState::Processing;
+        let id_range = WheelPrizeExtractionIdRange::new(
+            &WheelPrizeExtractionFilter::default(),
+            Some(fixtures::uuid_b()),
+        );
+        let range = WheelPrizeExtractionStateRange::with_ids(&state, &id_range).unwrap();
+
+        assert!(
+            range.contains(&WheelPrizeExtractionStateKey::new(&state, fixtures::uuid_a()).unwrap())
+        );
+        assert!(!range
+            .contains(&WheelPrizeExtractionStateKey::new(&state, fixtures::uuid_b()).unwrap()));
+        assert!(!range.contains(
+            &WheelPrizeExtractionStateKey::new(
+                &WheelPrizeExtractionState::default_completed(),
+                fixtures::uuid_a()
+            )
+            .unwrap()
+        ));
+    }
+
+    #[rstest]
+    fn wheel_prize_extraction_asset_id_range() {
+        let wheel_asset_id = fixtures::uuid();
+        let id_range = WheelPrizeExtractionIdRange::new(
+            &WheelPrizeExtractionFilter::default(),
+            Some(fixtures::uuid_b()),
+        );
+        let range = WheelPrizeExtractionAssetIdRange::new(wheel_asset_id, &id_range).unwrap();
+
+        assert!(range.contains(
+            &WheelPrizeExtractionAssetIdKey::new(wheel_asset_id, fixtures::uuid_a()).unwrap()
+        ));
+        assert!(!range.contains(
+            &WheelPrizeExtractionAssetIdKey::new(wheel_asset_id, fixtures::uuid_b()).unwrap()
+        ));
+        assert!(!range.contains(
+            &WheelPrizeExtractionAssetIdKey::new(Uuid::max(), fixtures::uuid_a()).unwrap()
+        ));
+    }
+
+    #[rstest]
+    #[case(None)]
+    #[case(Some(fixtures::uuid()))]
+    fn wheel_prize_extraction_event_id_range(#[case] event_id: Option<EventId>) {
+        let uuid_b = fixtures::uuid_b();
+        let created_at =
+            DateTime::from_timestamp_micros(uuid_b.timestamp_millis() * 1_000).unwrap();
+        let id_range = WheelPrizeExtractionIdRange::created_from(created_at);
+        let range = WheelPrizeExtractionEventIdRange::new(event_id, &id_range).unwrap();
+
+        assert!(range.contains(&WheelPrizeExtractionEventIdKey::new(event_id, uuid_b).unwrap()));
+        assert!(!range
+            .contains(&WheelPrizeExtractionEventIdKey::new(event_id, fixtures::uuid_a()).unwrap()));
+        assert!(!range
+            .contains(&WheelPrizeExtractionEventIdKey::new(Some(Uuid::max()), uuid_b).unwrap()));
+    }
+
+    #[rstest]
+    fn wheel_prize_extraction_user_id_range() {
+        let user_id = fixtures::uuid();
+        let range =
+            WheelPrizeExtractionUserIdRange::new(user_id, &WheelPrizeExtractionIdRange::all())
+                .unwrap();
+
+        assert!(range
+            .contains(&WheelPrizeExtractionUserIdKey::new(user_id, fixtures::uuid_a()).unwrap()));
+        assert!(range
+            .contains(&WheelPrizeExtractionUserIdKey::new(user_id, fixtures::uuid_b()).unwrap()));
+        assert!(!range.contains(
+            &WheelPrizeExtractionUserIdKey::new(Uuid::max(), fixtures::uuid_a()).unwrap()
+        ));
+    }
+
+    #[rstest]
+    fn wheel_prize_extraction_principal_range() {
+        let principal = fixtures::principal();
+        let other_principal = Principal::from_slice(&[0, 0]);
+        let event_id = fixtures::uuid_a();
+        let wheel_prize_extraction_id = fixtures::uuid();
+        let range = WheelPrizeExtractionPrincipalRange::new(&principal, Some(event_id)).unwrap();
+
+        assert!(range.contains(
+            &WheelPrizeExtractionPrincipalKey::new(
+                &principal,
+                Some(event_id),
+                wheel_prize_extraction_id
+            )
+            .unwrap()
+        ));
+        assert!(!range.contains(
+            &WheelPrizeExtractionPrincipalKey::new(&principal, None, wheel_prize_extraction_id)
+                .unwrap()
+        ));
+        assert!(!range.contains(
+            &WheelPrizeExtractionPrincipalKey::new(
+                &other_principal,
+                Some(event_id),
+                wheel_prize_extraction_id
+            )
+            .unwrap()
+        ));
+    }
+
+    #[rstest]
+    fn wheel_prize_extraction_principal_range_all_events() {
+        let principal = fixtures::principal();
+        let other_principal = Principal::from_slice(&[0, 0]);
+        let wheel_prize_extraction_id = fixtures::uuid();
+        let range = WheelPrizeExtractionPrincipalRange::all_events(&principal).unwrap();
+
+        assert!(range.contains(
+            &WheelPrizeExtractionPrincipalKey::new(
+                &principal,
+                Some(fixtures::uuid_a()),
+                wheel_prize_extraction_id
+            )
+            .unwrap()
+        ));
+        assert!(range.contains(
+            &WheelPrizeExtractionPrincipalKey::new(&principal, None, wheel_prize_extraction_id)
+                .unwrap()
+        ));
+        assert!(!range.contains(
+            &WheelPrizeExtractionPrincipalKey::new(&other_principal, None, Uuid::min()).unwrap()
+        ));
+        assert!(!range.contains(
+            &WheelPrizeExtractionPrincipalKey::new(
+                &other_principal,
+                Some(fixtures::uuid_a()),
+                wheel_prize_extraction_id
+            )
+            .unwrap()
+        ));
+    }
 }
@@ -0,0 +1,429 @@
+use std::borrow::Cow;
+
+use backend_api::{ApiError, ExtractionPolicyRejection};
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+
+use super::{DateTime, TimestampFields, Timestamped, WheelPrizeExtraction};
+
+/// Token prices are fetched every hour, so a price older than 2 hours
+/// means that at least one fetch has failed.
+const DEFAULT_MAX_TOKEN_PRICE_AGE_SECONDS: u64 = 2 * 60 * 60;
+const DEFAULT_SELF_SPIN_MAX_FAILED_ATTEMPTS: u32 = 5;
+
+/// The rules that a principal must satisfy to be extracted.
+/// There's only one policy, configured by the admins.
+///
+/// Wins are the extractions that did not fail, counted in the scope of the active event
+/// (or outside of any event if there's no active event). Days are UTC days.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq)]
+pub struct WheelPrizeExtractionPolicy {
+    pub max_wins_per_principal: Option<u32>,
+    pub max_wins_per_principal_per_day: Option<u32>,
+    pub max_wins_per_day: Option<u32>,
+    pub spin_cooldown_seconds: u64,
+    pub failed_spin_cooldown_seconds: u64,
+    /// If set, only these principals can be extracted.
+    pub allowed_principals: Option<Vec<Principal>>,
+    pub denied_principals: Vec<Principal>,
+    /// Token prices older than this are refreshed before the draw,
+    /// or their assets are excluded from the draw if the refresh fails.
+    /// `None` for policies stored before this setting existed, see [Self::max_token_price_age_seconds].
+    pub max_token_price_age_seconds: Option<u64>,
+    /// Whether the players can spin the wheel for themselves with a self-spin code.
+    /// `None` for policies stored before this setting existed, which means disabled.
+    pub self_spin_enabled: Option<bool>,
+    /// The wrong self-spin codes that a principal can submit in an hour.
+    /// `None` means the default, see [Self::self_spin_max_failed_attempts].
+    pub self_spin_max_failed_attempts: Option<u32>,
+    /// Whether each extraction must consume an invite code, proving that the winner attended.
+    /// `None` for policies stored before this setting existed, which means not required.
+    pub invite_code_required: Option<bool>,
+    pub timestamps: TimestampFields,
+}
+
+impl Default for WheelPrizeExtractionPolicy {
+    /// Each principal can be extracted only once, and can be extracted again
+    /// 30 seconds after a failed extraction.
+    fn default() -> Self {
+        Self {
+            max_wins_per_principal: Some(1),
+            max_wins_per_principal_per_day: None,
+            max_wins_per_day: None,
+            spin_cooldown_seconds: 0,
+            failed_spin_cooldown_seconds: 30,
+            allowed_principals: None,
+            denied_principals: vec![],
+            max_token_price_age_seconds: Some(DEFAULT_MAX_TOKEN_PRICE_AGE_SECONDS),
+            self_spin_enabled: Some(false),
+            self_spin_max_failed_attempts: None,
+            invite_code_required: Some(false),
+            timestamps: TimestampFields::new(),
+        }
+    }
+}
+
+impl WheelPrizeExtractionPolicy {
+    pub fn max_token_price_age_seconds(&self) -> u64 {
+        self.max_token_price_age_seconds
+            .unwrap_or(DEFAULT_MAX_TOKEN_PRICE_AGE_SECONDS)
+    }
+
+    pub fn is_self_spin_enabled(&self) -> bool {
+        self.self_spin_enabled.unwrap_or(false)
+    }
+
+    pub fn self_spin_max_failed_attempts(&self) -> u32 {
+        self.self_spin_max_failed_attempts
+            .unwrap_or(DEFAULT_SELF_SPIN_MAX_FAILED_ATTEMPTS)
+    }
+
+    pub fn is_invite_code_required(&self) -> bool {
+        self.invite_code_required.unwrap_or(false)
+    }
+
+    pub fn assert_principal_is_allowed(&self, principal: &Principal) -> Result<(), ApiError> {
+        if self.denied_principals.contains(principal) {
+            return Err(ApiError::extraction_policy_rejected(
+                ExtractionPolicyRejection::PrincipalDenied,
+                None,
+            ));
+        }
+
+        if let Some(allowed_principals) = &self.allowed_principals {
+            if !allowed_principals.contains(principal) {
+                return Err(ApiError::extraction_policy_rejected(
+                    ExtractionPolicyRejection::PrincipalNotAllowed,
+                    None,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks the limits of a principal, given all its extractions
+    /// in the current scope sorted by creation time.
+    pub fn assert_principal_can_be_extracted(
+        &self,
+        principal_extractions: &[WheelPrizeExtraction],
+        now: &DateTime,
+    ) -> Result<(), ApiError> {
+        let wins = principal_extractions
+            .iter()
+            .filter(|extraction| !extraction.is_failed());
+
+        if let Some(max_wins) = self.max_wins_per_principal {
+            if wins.clone().count() >= max_wins as usize {
+                return Err(ApiError::extraction_policy_rejected(
+                    ExtractionPolicyRejection::MaxWinsPerPrincipal { max_wins },
+                    None,
+                ));
+            }
+        }
+
+        if let Some(max_wins) = self.max_wins_per_principal_per_day {
+            let start_of_day = now.start_of_day();
+            if wins
+                .filter(|extraction| extraction.timestamps.created_at >= start_of_day)
+                .count()
+                >= max_wins as usize
+            {
+                return Err(ApiError::extraction_policy_rejected(
+                    ExtractionPolicyRejection::MaxWinsPerPrincipalPerDay { max_wins },
+                    Some(seconds_until_next_day(now)),
+                ));
+            }
+        }
+
+        if let Some(last_extraction) = principal_extractions.last() {
+            let (cooldown_seconds, rejection) = if last_extraction.is_failed() {
+                (
+                    self.failed_spin_cooldown_seconds,
+                    ExtractionPolicyRejection::FailedSpinCooldown,
+                )
+            } else {
+                (
+                    self.spin_cooldown_seconds,
+                    ExtractionPolicyRejection::SpinCooldown,
+                )
+            };
+            let elapsed_seconds = now
+                .timestamp_seconds()
+                .saturating_sub(last_extraction.timestamps.created_at.timestamp_seconds());
+            if elapsed_seconds < cooldown_seconds {
+                return Err(ApiError::extraction_policy_rejected(
+                    rejection,
+                    Some(cooldown_seconds - elapsed_seconds),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn assert_daily_wins_available(
+        &self,
+        wins_today: usize,
+        now: &DateTime,
+    ) -> Result<(), ApiError> {
+        if let Some(max_wins) = self.max_wins_per_day {
+            if wins_today >= max_wins as usize {
+                return Err(ApiError::extraction_policy_rejected(
+                    ExtractionPolicyRejection::MaxWinsPerDay { max_wins },
+                    Some(seconds_until_next_day(now)),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The daily limits are reset at midnight (UTC).
+fn seconds_until_next_day(now: &DateTime) -> u64 {
+    let next_day = now.start_of_day().add(chrono::Duration::days(1));
+    next_day.timestamp_seconds() - now.timestamp_seconds()
+}
+
+impl Timestamped for WheelPrizeExtractionPolicy {
+    fn update_timestamp(&mut self) {
+        self.timestamps.update();
+    }
+}
+
+impl Storable for WheelPrizeExtractionPolicy {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use rstest::*;
+
+    #[rstest]
+    #[case::default(WheelPrizeExtractionPolicy::default())]
+    #[case::custom(fixtures::wheel_prize_extraction_policy())]
+    fn storable_impl(#[case] policy: WheelPrizeExtractionPolicy) {
+        let serialized_policy = policy.to_bytes();
+        let deserialized_policy = WheelPrizeExtractionPolicy::from_bytes(serialized_policy);
+
+        assert_eq!(policy, deserialized_policy);
+    }
+
+    #[rstest]
+    #[case::default(WheelPrizeExtractionPolicy::default(), fixtures::principal(), None)]
+    #[case::denied(
+        fixtures::wheel_prize_extraction_policy(),
+        fixtures::principal(),
+        Some(ExtractionPolicyRejection::PrincipalDenied)
+    )]
+    #[case::allowed(
+        fixtures::wheel_prize_extraction_policy(),
+        Principal::from_slice(&[1]),
+        None
+    )]
+    #[case::not_allowed(
+        fixtures::wheel_prize_extraction_policy(),
+        Principal::from_slice(&[2]),
+        Some(ExtractionPolicyRejection::PrincipalNotAllowed)
+    )]
+    fn assert_principal_is_allowed(
+        #[case] policy: WheelPrizeExtractionPolicy,
+        #[case] principal: Principal,
+        #[case] expected_rejection: Option<ExtractionPolicyRejection>,
+    ) {
+        let result = policy.assert_principal_is_allowed(&principal);
+
+        assert_eq!(
+            result.err(),
+            expected_rejection
+                .map(|rejection| ApiError::extraction_policy_rejected(rejection, None))
+        );
+    }
+
+    #[rstest]
+    #[case::no_extractions(WheelPrizeExtractionPolicy::default(), vec![], 0, None)]
+    #[case::already_won(
+        WheelPrizeExtractionPolicy::default(),
+        vec![won_at(fixtures::date_time_a())],
+        3600,
+        Some((ExtractionPolicyRejection::MaxWinsPerPrincipal { max_wins: 1 }, None))
+    )]
+    #[case::failed_cooldown(
+        WheelPrizeExtractionPolicy::default(),
+        vec![failed_at(fixtures::date_time_a())],
+        10,
+        Some((ExtractionPolicyRejection::FailedSpinCooldown, Some(20)))
+    )]
+    #[case::failed_cooldown_elapsed(
+        WheelPrizeExtractionPolicy::default(),
+        vec![failed_at(fixtures::date_time_a())],
+        30,
+        None
+    )]
+    #[case::spin_cooldown(
+        fixtures::wheel_prize_extraction_policy(),
+        vec![won_at(fixtures::date_time_a())],
+        60,
+        Some((ExtractionPolicyRejection::SpinCooldown, Some(60)))
+    )]
+    #[case::daily_cap(
+        fixtures::wheel_prize_extraction_policy(),
+        vec![won_at(fixtures::date_time_a()), won_at(fixtures::date_time_a())],
+        3600,
+        Some((
+            ExtractionPolicyRejection::MaxWinsPerPrincipalPerDay { max_wins: 2 },
+            Some(45_594)
+        ))
+    )]
+    #[case::daily_cap_ignores_failed(
+        fixtures::wheel_prize_extraction_policy(),
+        vec![won_at(fixtures::date_time_a()), failed_at(fixtures::date_time_a())],
+        3600,
+        None
+    )]
+    #[case::daily_cap_previous_day(
+        fixtures::wheel_prize_extraction_policy(),
+        vec![won_at(fixtures::date_time_b()), won_at(fixtures::date_time_b())],
+        3600,
+        None
+    )]
+    #[case::max_wins(
+        fixtures::wheel_prize_extraction_policy(),
+        vec![
+            won_at(fixtures::date_time_c()),
+            won_at(fixtures::date_time_b()),
+            won_at(fixtures::date_time_a()),
+        ],
+        3600,
+        Some((ExtractionPolicyRejection::MaxWinsPerPrincipal { max_wins: 3 }, None))
+    )]
+    fn assert_principal_can_be_extracted(
+        #[case] policy: WheelPrizeExtractionPolicy,
+        #[case] principal_extractions: Vec<WheelPrizeExtraction>,
+        #[case] seconds_after_date_time_a: u64,
+        #[case] expected_rejection: Option<(ExtractionPolicyRejection, Option<u64>)>,
+    ) {
+        let now = date_time_a_plus_seconds(seconds_after_date_time_a);
+
+        let result = policy.assert_principal_can_be_extracted(&principal_extractions, &now);
+
+        assert_eq!(
+            result.err(),
+            expected_rejection.map(|(rejection, retry_after_seconds)| {
+                ApiError::extraction_policy_rejected(rejection, retry_after_seconds)
+            })
+        );
+    }
+
+    #[rstest]
+    #[case::unlimited(WheelPrizeExtractionPolicy::default(), 1000, None)]
+    #[case::below_cap(fixtures::wheel_prize_extraction_policy(), 99, None)]
+    #[case::cap_reached(
+        fixtures::wheel_prize_extraction_policy(),
+        100,
+        Some(ApiError::extraction_policy_rejected(
+            ExtractionPolicyRejection::MaxWinsPerDay { max_wins: 100 },
+            Some(49_194)
+        ))
+    )]
+    fn assert_daily_wins_available(
+        #[case] policy: WheelPrizeExtractionPolicy,
+        #[case] wins_today: usize,
+        #[case] expected_error: Option<ApiError>,
+    ) {
+        let now = date_time_a_plus_seconds(0);
+
+        assert_eq!(
+            policy.assert_daily_wins_available(wins_today, &now).err(),
+            expected_error
+        );
+    }
+
+    #[rstest]
+    #[case::default(WheelPrizeExtractionPolicy::default(), 7_200)]
+    #[case::custom(fixtures::wheel_prize_extraction_policy(), 600)]
+    #[case::stored_before_setting(
+        WheelPrizeExtractionPolicy {
+            max_token_price_age_seconds: None,
+            ..fixtures::wheel_prize_extraction_policy()
+        },
+        7_200
+    )]
+    fn max_token_price_age_seconds(
+        #[case] policy: WheelPrizeExtractionPolicy,
+        #[case] expected_seconds: u64,
+    ) {
+        assert_eq!(policy.max_token_price_age_seconds(), expected_seconds);
+    }
+
+    #[rstest]
+    #[case::default(WheelPrizeExtractionPolicy::default(), false, 5)]
+    #[case::custom(fixtures::wheel_prize_extraction_policy(), true, 3)]
+    #[case::stored_before_settings(
+        WheelPrizeExtractionPolicy {
+            self_spin_enabled: None,
+            self_spin_max_failed_attempts: None,
+            ..fixtures::wheel_prize_extraction_policy()
+        },
+        false,
+        5
+    )]
+    fn self_spin_settings(
+        #[case] policy: WheelPrizeExtractionPolicy,
+        #[case] expected_enabled: bool,
+        #[case] expected_max_failed_attempts: u32,
+    ) {
+        assert_eq!(policy.is_self_spin_enabled(), expected_enabled);
+        assert_eq!(
+            policy.self_spin_max_failed_attempts(),
+            expected_max_failed_attempts
+        );
+    }
+
+    #[rstest]
+    #[case::default(WheelPrizeExtractionPolicy::default(), false)]
+    #[case::custom(fixtures::wheel_prize_extraction_policy(), true)]
+    #[case::stored_before_setting(
+        WheelPrizeExtractionPolicy {
+            invite_code_required: None,
+            ..fixtures::wheel_prize_extraction_policy()
+        },
+        false
+    )]
+    fn is_invite_code_required(
+        #[case] policy: WheelPrizeExtractionPolicy,
+        #[case] expected_required: bool,
+    ) {
+        assert_eq!(policy.is_invite_code_required(), expected_required);
+    }
+
+    fn date_time_a_plus_seconds(seconds: u64) -> DateTime {
+        DateTime::from_timestamp_micros(
+            fixtures::date_time_a().timestamp_micros() + seconds * 1_000_000,
+        )
+        .unwrap()
+    }
+
+    fn won_at(created_at: DateTime) -> WheelPrizeExtraction {
+        let mut extraction = fixtures::wheel_prize_extraction();
+        extraction.timestamps.created_at = created_at;
+        extraction
+    }
+
+    fn failed_at(created_at: DateTime) -> WheelPrizeExtraction {
+        let mut extraction = fixtures::wheel_prize_extraction_failed();
+        extraction.timestamps.created_at = created_at;
+        extraction
+    }
+}
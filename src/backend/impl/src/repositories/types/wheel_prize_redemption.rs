@@ -0,0 +1,176 @@
+use std::borrow::Cow;
+
+use backend_api::ApiError;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL, Engine};
+use candid::{CandidType, Decode, Deserialize, Encode};
+use hmac::{Hmac, Mac};
+use ic_stable_structures::{storable::Bound, Storable};
+use qrcode::{render::svg, QrCode};
+use sha2::Sha256;
+
+use super::{TimestampFields, Timestamped, WheelPrizeExtractionId};
+
+/// The number of bytes of the HMAC kept in the redemption code.
+const REDEMPTION_CODE_BYTES_SIZE: usize = 16;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The secret with which the redemption codes of the gadget prizes are signed.
+/// It's generated once from the randomness of the management canister and never leaves the canister.
+#[derive(Debug, CandidType, Deserialize, Clone, PartialEq, Eq)]
+pub struct WheelPrizeRedemptionSecret {
+    pub secret: [u8; 32],
+    pub timestamps: TimestampFields,
+}
+
+impl WheelPrizeRedemptionSecret {
+    pub fn new(secret: [u8; 32]) -> Self {
+        Self {
+            secret,
+            timestamps: TimestampFields::new(),
+        }
+    }
+
+    /// The code that the winner shows at the booth to redeem the gadget won with the extraction.
+    pub fn redemption_code(&self, wheel_prize_extraction_id: &WheelPrizeExtractionId) -> String {
+        let tag = self.mac(wheel_prize_extraction_id).finalize().into_bytes();
+        BASE64_URL.encode(&tag[..REDEMPTION_CODE_BYTES_SIZE])
+    }
+
+    /// Checks the code in constant time.
+    pub fn verify_redemption_code(
+        &self,
+        wheel_prize_extraction_id: &WheelPrizeExtractionId,
+        redemption_code: &str,
+    ) -> Result<(), ApiError> {
+        let invalid_code_error = || ApiError::permission_denied("Invalid redemption code");
+
+        let tag = BASE64_URL
+            .decode(redemption_code)
+            .map_err(|_| invalid_code_error())?;
+        if tag.len() != REDEMPTION_CODE_BYTES_SIZE {
+            return Err(invalid_code_error());
+        }
+
+        self.mac(wheel_prize_extraction_id)
+            .verify_truncated_left(&tag)
+            .map_err(|_| invalid_code_error())
+    }
+
+    fn mac(&self, wheel_prize_extraction_id: &WheelPrizeExtractionId) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        mac.update(wheel_prize_extraction_id.to_string().as_bytes());
+        mac
+    }
+}
+
+impl Timestamped for WheelPrizeRedemptionSecret {
+    fn update_timestamp(&mut self) {
+        self.timestamps.update();
+    }
+}
+
+impl Storable for WheelPrizeRedemptionSecret {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// Renders the QR code that the scanners read to redeem the gadget won with the extraction.
+/// It encodes the arguments of the `redeem_prize` endpoint as JSON.
+pub fn wheel_prize_redemption_qr_code_svg(
+    wheel_prize_extraction_id: &WheelPrizeExtractionId,
+    redemption_code: &str,
+) -> Result<String, ApiError> {
+    let content = format!(
+        "{{\"wheel_prize_extraction_id\":\"{}\",\"redemption_code\":\"{}\"}}",
+        wheel_prize_extraction_id, redemption_code
+    );
+    let qr_code = QrCode::new(content.as_bytes()).map_err(|err| {
+        ApiError::internal(&format!("Failed to generate redemption QR code: {}", err))
+    })?;
+
+    Ok(qr_code
+        .render::<svg::Color>()
+        .min_dimensions(256, 256)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use rstest::*;
+
+    #[rstest]
+    fn storable_impl() {
+        let secret = fixtures::wheel_prize_redemption_secret();
+        let serialized_secret = secret.to_bytes();
+        let deserialized_secret = WheelPrizeRedemptionSecret::from_bytes(serialized_secret);
+
+        assert_eq!(secret, deserialized_secret);
+    }
+
+    #[rstest]
+    fn redemption_code() {
+        let secret = fixtures::wheel_prize_redemption_secret();
+        let id = fixtures::uuid_a();
+
+        let redemption_code = secret.redemption_code(&id);
+
+        assert_eq!(redemption_code.len(), 22);
+        assert_eq!(redemption_code, secret.redemption_code(&id));
+        assert_ne!(redemption_code, secret.redemption_code(&fixtures::uuid_b()));
+        assert_ne!(
+            redemption_code,
+            WheelPrizeRedemptionSecret::new([2; 32]).redemption_code(&id)
+        );
+        assert!(secret.verify_redemption_code(&id, &redemption_code).is_ok());
+    }
+
+    #[rstest]
+    #[case::other_extraction(fixtures::uuid_b(), None)]
+    #[case::other_secret(fixtures::uuid_a(), Some([2; 32]))]
+    fn verify_redemption_code_mismatch(
+        #[case] id: WheelPrizeExtractionId,
+        #[case] other_secret: Option<[u8; 32]>,
+    ) {
+        let secret = fixtures::wheel_prize_redemption_secret();
+        let redemption_code = other_secret
+            .map(WheelPrizeRedemptionSecret::new)
+            .unwrap_or_else(|| secret.clone())
+            .redemption_code(&fixtures::uuid_a());
+
+        assert_eq!(
+            secret.verify_redemption_code(&id, &redemption_code),
+            Err(ApiError::permission_denied("Invalid redemption code"))
+        );
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::not_base64("not a code!")]
+    #[case::truncated("AAAA")]
+    fn verify_redemption_code_malformed(#[case] redemption_code: &str) {
+        let secret = fixtures::wheel_prize_redemption_secret();
+
+        assert_eq!(
+            secret.verify_redemption_code(&fixtures::uuid_a(), redemption_code),
+            Err(ApiError::permission_denied("Invalid redemption code"))
+        );
+    }
+
+    #[rstest]
+    fn redemption_qr_code_svg() {
+        let svg = wheel_prize_redemption_qr_code_svg(&fixtures::uuid_a(), "code").unwrap();
+
+        assert!(svg.contains("<svg"));
+    }
+}
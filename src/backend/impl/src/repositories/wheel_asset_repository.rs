@@ -32,6 +32,10 @@ pub trait WheelAssetRepository {
 
     fn list_wheel_assets(&self) -> Vec<(WheelAssetId, WheelAsset)>;
 
+    /// Lists the existing assets referenced by the given jackpot,
+    /// or nothing if the asset is not a jackpot.
+    fn list_jackpot_components(&self, wheel_asset: &WheelAsset) -> Vec<WheelAsset>;
+
     fn get_wheel_prizes_order(&self) -> Vec<WheelAssetId>;
 
     fn update_wheel_prizes_order(&self, order: Vec<WheelAssetId>) -> Result<(), ApiError>;
@@ -158,6 +162,16 @@ impl WheelAssetRepository for WheelAssetRepositoryImpl {
         STATE.with_borrow(|s| s.wheel_assets.iter().collect())
     }
 
+    fn list_jackpot_components(&self, wheel_asset: &WheelAsset) -> Vec<WheelAsset> {
+        STATE.with_borrow(|s| {
+            wheel_asset
+                .jackpot_wheel_asset_ids()
+                .iter()
+                .filter_map(|id| s.wheel_assets.get(id))
+                .collect()
+        })
+    }
+
     fn get_wheel_prizes_order(&self) -> Vec<WheelAssetId> {
         STATE.with_borrow(|s| s.wheel_prize_order_index.values().collect())
     }
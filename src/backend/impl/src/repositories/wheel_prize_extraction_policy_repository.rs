@@ -0,0 +1,74 @@
+use std::cell::RefCell;
+
+use backend_api::ApiError;
+
+use super::{
+    init_wheel_prize_extraction_policy, Timestamped, WheelPrizeExtractionPolicy,
+    WheelPrizeExtractionPolicyMemory,
+};
+
+#[cfg_attr(test, mockall::automock)]
+pub trait WheelPrizeExtractionPolicyRepository {
+    fn get_wheel_prize_extraction_policy(&self) -> WheelPrizeExtractionPolicy;
+
+    fn update_wheel_prize_extraction_policy(
+        &self,
+        policy: WheelPrizeExtractionPolicy,
+    ) -> Result<(), ApiError>;
+}
+
+pub struct WheelPrizeExtractionPolicyRepositoryImpl {}
+
+impl Default for WheelPrizeExtractionPolicyRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WheelPrizeExtractionPolicyRepository for WheelPrizeExtractionPolicyRepositoryImpl {
+    fn get_wheel_prize_extraction_policy(&self) -> WheelPrizeExtractionPolicy {
+        STATE.with_borrow(|s| s.wheel_prize_extraction_policy.get().clone())
+    }
+
+    fn update_wheel_prize_extraction_policy(
+        &self,
+        mut policy: WheelPrizeExtractionPolicy,
+    ) -> Result<(), ApiError> {
+        policy.update_timestamp();
+
+        STATE.with_borrow_mut(|s| {
+            s.wheel_prize_extraction_policy
+                .set(policy)
+                .map(|_| ())
+                .map_err(|err| {
+                    ApiError::internal(&format!(
+                        "Failed to update wheel prize extraction policy: {:?}",
+                        err
+                    ))
+                })
+        })
+    }
+}
+
+impl WheelPrizeExtractionPolicyRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+struct WheelPrizeExtractionPolicyState {
+    wheel_prize_extraction_policy: WheelPrizeExtractionPolicyMemory,
+}
+
+impl Default for WheelPrizeExtractionPolicyState {
+    fn default() -> Self {
+        Self {
+            wheel_prize_extraction_policy: init_wheel_prize_extraction_policy(),
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<WheelPrizeExtractionPolicyState> =
+        RefCell::new(WheelPrizeExtractionPolicyState::default());
+}
@@ -4,15 +4,18 @@ use backend_api::ApiError;
 use candid::Principal;
 
 use super::{
-    init_wheel_prize_extraction_asset_id_index, init_wheel_prize_extraction_principal_index,
-    init_wheel_prize_extraction_state_index, init_wheel_prize_extraction_user_id_index,
-    init_wheel_prize_extractions, Timestamped, WheelPrizeExtraction,
-    WheelPrizeExtractionAssetIdIndexMemory, WheelPrizeExtractionAssetIdKey, WheelPrizeExtractionId,
-    WheelPrizeExtractionMemory, WheelPrizeExtractionPrincipalIndexMemory,
-    WheelPrizeExtractionState as WheelPrizeExtractionStateEnum,
+    init_wheel_prize_extraction_asset_id_index, init_wheel_prize_extraction_event_id_index,
+    init_wheel_prize_extraction_principal_index, init_wheel_prize_extraction_state_index,
+    init_wheel_prize_extraction_user_id_index, init_wheel_prize_extractions, EventId, Timestamped,
+    WheelPrizeExtraction, WheelPrizeExtractionAssetIdIndexMemory, WheelPrizeExtractionAssetIdKey,
+    WheelPrizeExtractionAssetIdRange, WheelPrizeExtractionEventIdIndexMemory,
+    WheelPrizeExtractionEventIdKey, WheelPrizeExtractionEventIdRange, WheelPrizeExtractionFilter,
+    WheelPrizeExtractionId, WheelPrizeExtractionIdRange, WheelPrizeExtractionMemory,
+    WheelPrizeExtractionPrincipalIndexMemory, WheelPrizeExtractionPrincipalKey,
+    WheelPrizeExtractionPrincipalRange, WheelPrizeExtractionState as WheelPrizeExtractionStateEnum,
     WheelPrizeExtractionStateIndexMemory, WheelPrizeExtractionStateKey,
     WheelPrizeExtractionStateRange, WheelPrizeExtractionUserIdIndexMemory,
-    WheelPrizeExtractionUserIdKey,
+    WheelPrizeExtractionUserIdKey, WheelPrizeExtractionUserIdRange,
 };
 
 #[cfg_attr(test, mockall::automock)]
@@ -28,13 +31,31 @@ pub trait WheelPrizeExtractionRepository {
         state: Option<&'a WheelPrizeExtractionStateEnum>,
     ) -> Option<(WheelPrizeExtractionId, WheelPrizeExtraction)>;
 
-    fn get_wheel_prize_extraction_by_principal(
+    /// Lists the extractions of the principal in the given event,
+    /// or outside of any event if `event_id` is `None`, oldest first.
+    fn list_wheel_prize_extractions_by_principal(
         &self,
         principal: &Principal,
-    ) -> Option<(WheelPrizeExtractionId, WheelPrizeExtraction)>;
+        event_id: Option<EventId>,
+    ) -> Result<Vec<(WheelPrizeExtractionId, WheelPrizeExtraction)>, ApiError>;
+
+    /// Lists all the extractions of the principal, in and outside of any event, newest first.
+    fn list_all_wheel_prize_extractions_by_principal(
+        &self,
+        principal: &Principal,
+    ) -> Result<Vec<(WheelPrizeExtractionId, WheelPrizeExtraction)>, ApiError>;
 
     fn list_wheel_prize_extractions(&self) -> Vec<(WheelPrizeExtractionId, WheelPrizeExtraction)>;
 
+    /// Lists the extractions matching the filter, newest first,
+    /// starting after the given extraction if provided.
+    fn list_wheel_prize_extractions_page(
+        &self,
+        filter: &WheelPrizeExtractionFilter,
+        start_after: Option<WheelPrizeExtractionId>,
+        limit: usize,
+    ) -> Result<Vec<(WheelPrizeExtractionId, WheelPrizeExtraction)>, ApiError>;
+
     fn create_wheel_prize_extraction(
         &self,
         wheel_prize_extraction: WheelPrizeExtraction,
@@ -46,10 +67,21 @@ pub trait WheelPrizeExtractionRepository {
         wheel_prize_extraction: WheelPrizeExtraction,
     ) -> Result<(), ApiError>;
 
+    /// Lists the extractions of the given event in the given id range,
+    /// or the ones outside of any event if `event_id` is `None`.
+    fn list_wheel_prize_extractions_by_event(
+        &self,
+        event_id: Option<EventId>,
+        id_range: &WheelPrizeExtractionIdRange,
+    ) -> Result<Vec<(WheelPrizeExtractionId, WheelPrizeExtraction)>, ApiError>;
+
     fn list_wheel_prize_extractions_by_state(
         &self,
         state: &WheelPrizeExtractionStateEnum,
     ) -> Vec<(WheelPrizeExtractionId, WheelPrizeExtraction)>;
+
+    /// Populates the indexes that were introduced after some extractions were already stored.
+    fn init_indexes(&self) -> Result<(), ApiError>;
 }
 
 pub struct WheelPrizeExtractionRepositoryImpl {}
@@ -86,22 +118,113 @@ impl WheelPrizeExtractionRepository for WheelPrizeExtractionRepositoryImpl {
         })
     }
 
-    fn get_wheel_prize_extraction_by_principal(
+    fn list_wheel_prize_extractions_by_principal(
         &self,
         principal: &Principal,
-    ) -> Option<(WheelPrizeExtractionId, WheelPrizeExtraction)> {
-        STATE.with_borrow(|s| {
+        event_id: Option<EventId>,
+    ) -> Result<Vec<(WheelPrizeExtractionId, WheelPrizeExtraction)>, ApiError> {
+        let range = WheelPrizeExtractionPrincipalRange::new(principal, event_id)?;
+
+        // items are indexed by uuid v7, which already has the timestamp included
+        Ok(STATE.with_borrow(|s| {
             s.wheel_prize_extraction_principal_index
-                .get(principal)
-                // wheel prize extraction with this id should always exist
-                .map(|id| (id, s.wheel_prize_extractions.get(&id).unwrap()))
-        })
+                .range(range)
+                .map(|(_, id)| {
+                    // SAFETY: wheel prize extraction with this id should always exist
+                    (id, s.wheel_prize_extractions.get(&id).unwrap())
+                })
+                .collect()
+        }))
+    }
+
+    fn list_all_wheel_prize_extractions_by_principal(
+        &self,
+        principal: &Principal,
+    ) -> Result<Vec<(WheelPrizeExtractionId, WheelPrizeExtraction)>, ApiError> {
+        let range = WheelPrizeExtractionPrincipalRange::all_events(principal)?;
+
+        let mut wheel_prize_extractions = STATE.with_borrow(|s| {
+            s.wheel_prize_extraction_principal_index
+                .range(range)
+                .map(|(_, id)| {
+                    // SAFETY: wheel prize extraction with this id should always exist
+                    (id, s.wheel_prize_extractions.get(&id).unwrap())
+                })
+                .collect::<Vec<_>>()
+        });
+        // the index is sorted by event first, while uuid v7 ids are sorted by creation time
+        wheel_prize_extractions.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        Ok(wheel_prize_extractions)
     }
 
     fn list_wheel_prize_extractions(&self) -> Vec<(WheelPrizeExtractionId, WheelPrizeExtraction)> {
         STATE.with_borrow(|s| s.wheel_prize_extractions.iter().rev().collect())
     }
 
+    fn list_wheel_prize_extractions_page(
+        &self,
+        filter: &WheelPrizeExtractionFilter,
+        start_after: Option<WheelPrizeExtractionId>,
+        limit: usize,
+    ) -> Result<Vec<(WheelPrizeExtractionId, WheelPrizeExtraction)>, ApiError> {
+        let id_range = WheelPrizeExtractionIdRange::new(filter, start_after);
+        if id_range.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // items are indexed by uuid v7, which already has the timestamp included,
+        // and the most selective index is used to find the candidates
+        STATE.with_borrow(|s| {
+            let get_wheel_prize_extraction = |id: WheelPrizeExtractionId| {
+                // SAFETY: wheel prize extraction with this id should always exist
+                (id, s.wheel_prize_extractions.get(&id).unwrap())
+            };
+            let wheel_prize_extractions: Box<
+                dyn Iterator<Item = (WheelPrizeExtractionId, WheelPrizeExtraction)>,
+            > = match (
+                filter.extracted_by_user_id,
+                filter.wheel_asset_id,
+                filter.state.as_ref(),
+            ) {
+                (Some(extracted_by_user_id), _, _) => {
+                    let range =
+                        WheelPrizeExtractionUserIdRange::new(extracted_by_user_id, &id_range)?;
+                    Box::new(
+                        s.wheel_prize_extraction_user_id_index
+                            .range(range)
+                            .rev()
+                            .map(|(_, id)| get_wheel_prize_extraction(id)),
+                    )
+                }
+                (None, Some(wheel_asset_id), _) => {
+                    let range = WheelPrizeExtractionAssetIdRange::new(wheel_asset_id, &id_range)?;
+                    Box::new(
+                        s.wheel_prize_extraction_asset_id_index
+                            .range(range)
+                            .rev()
+                            .map(|(_, id)| get_wheel_prize_extraction(id)),
+                    )
+                }
+                (None, None, Some(state)) => {
+                    let range = WheelPrizeExtractionStateRange::with_ids(state, &id_range)?;
+                    Box::new(
+                        s.wheel_prize_extraction_state_index
+                            .range(range)
+                            .rev()
+                            .map(|(_, id)| get_wheel_prize_extraction(id)),
+                    )
+                }
+                (None, None, None) => Box::new(s.wheel_prize_extractions.range(id_range).rev()),
+            };
+
+            Ok(wheel_prize_extractions
+                .filter(|(_, wheel_prize_extraction)| filter.matches(wheel_prize_extraction))
+                .take(limit)
+                .collect())
+        })
+    }
+
     fn create_wheel_prize_extraction(
         &self,
         wheel_prize_extraction: WheelPrizeExtraction,
@@ -135,9 +258,17 @@ impl WheelPrizeExtractionRepository for WheelPrizeExtractionRepositoryImpl {
                 )?;
                 s.wheel_prize_extraction_user_id_index
                     .remove(&old_user_id_key);
-                let old_principal_key = old_wheel_prize_extraction.extracted_for_principal;
+                let old_principal_key = WheelPrizeExtractionPrincipalKey::new(
+                    &old_wheel_prize_extraction.extracted_for_principal,
+                    old_wheel_prize_extraction.event_id,
+                    id,
+                )?;
                 s.wheel_prize_extraction_principal_index
                     .remove(&old_principal_key);
+                let old_event_id_key =
+                    WheelPrizeExtractionEventIdKey::new(old_wheel_prize_extraction.event_id, id)?;
+                s.wheel_prize_extraction_event_id_index
+                    .remove(&old_event_id_key);
                 if let Some(old_asset_id) = old_wheel_prize_extraction.wheel_asset_id {
                     let old_asset_id_key = WheelPrizeExtractionAssetIdKey::new(old_asset_id, id)?;
                     s.wheel_prize_extraction_asset_id_index
@@ -153,6 +284,24 @@ impl WheelPrizeExtractionRepository for WheelPrizeExtractionRepositoryImpl {
         })
     }
 
+    fn list_wheel_prize_extractions_by_event(
+        &self,
+        event_id: Option<EventId>,
+        id_range: &WheelPrizeExtractionIdRange,
+    ) -> Result<Vec<(WheelPrizeExtractionId, WheelPrizeExtraction)>, ApiError> {
+        let range = WheelPrizeExtractionEventIdRange::new(event_id, id_range)?;
+
+        Ok(STATE.with_borrow(|s| {
+            s.wheel_prize_extraction_event_id_index
+                .range(range)
+                .map(|(_, id)| {
+                    // SAFETY: wheel prize extraction with this id should always exist
+                    (id, s.wheel_prize_extractions.get(&id).unwrap())
+                })
+                .collect()
+        }))
+    }
+
     fn list_wheel_prize_extractions_by_state(
         &self,
         state: &WheelPrizeExtractionStateEnum,
@@ -168,6 +317,22 @@ impl WheelPrizeExtractionRepository for WheelPrizeExtractionRepositoryImpl {
                 .collect()
         })
     }
+
+    fn init_indexes(&self) -> Result<(), ApiError> {
+        STATE.with_borrow_mut(|s| {
+            // the principal and event id indexes are always populated together
+            if !s.wheel_prize_extraction_principal_index.is_empty() {
+                return Ok(());
+            }
+
+            let wheel_prize_extractions = s.wheel_prize_extractions.iter().collect::<Vec<_>>();
+            for (id, wheel_prize_extraction) in wheel_prize_extractions {
+                self.set_principal_and_event_id_indexes(s, id, &wheel_prize_extraction)?;
+            }
+
+            Ok(())
+        })
+    }
 }
 
 impl WheelPrizeExtractionRepositoryImpl {
@@ -190,6 +355,30 @@ impl WheelPrizeExtractionRepositoryImpl {
         Ok(())
     }
 
+    fn set_principal_and_event_id_indexes(
+        &self,
+        state: &mut WheelPrizeExtractionState,
+        id: WheelPrizeExtractionId,
+        wheel_prize_extraction: &WheelPrizeExtraction,
+    ) -> Result<(), ApiError> {
+        let principal_key = WheelPrizeExtractionPrincipalKey::new(
+            &wheel_prize_extraction.extracted_for_principal,
+            wheel_prize_extraction.event_id,
+            id,
+        )?;
+        let event_id_key =
+            WheelPrizeExtractionEventIdKey::new(wheel_prize_extraction.event_id, id)?;
+
+        state
+            .wheel_prize_extraction_principal_index
+            .insert(principal_key, id);
+        state
+            .wheel_prize_extraction_event_id_index
+            .insert(event_id_key, id);
+
+        Ok(())
+    }
+
     fn insert_wheel_prize_extraction(
         &self,
         state: &mut WheelPrizeExtractionState,
@@ -199,7 +388,6 @@ impl WheelPrizeExtractionRepositoryImpl {
         let state_key = WheelPrizeExtractionStateKey::new(&wheel_prize_extraction.state, id)?;
         let user_id_key =
             WheelPrizeExtractionUserIdKey::new(wheel_prize_extraction.extracted_by_user_id, id)?;
-        let principal_key = wheel_prize_extraction.extracted_for_principal;
 
         state
             .wheel_prize_extractions
@@ -210,10 +398,8 @@ impl WheelPrizeExtractionRepositoryImpl {
         state
             .wheel_prize_extraction_user_id_index
             .insert(user_id_key, id);
-        state
-            .wheel_prize_extraction_principal_index
-            .insert(principal_key, id);
 
+        self.set_principal_and_event_id_indexes(state, id, &wheel_prize_extraction)?;
         self.set_asset_id_index(state, id, &wheel_prize_extraction)?;
 
         Ok(())
@@ -226,6 +412,7 @@ struct WheelPrizeExtractionState {
     wheel_prize_extraction_asset_id_index: WheelPrizeExtractionAssetIdIndexMemory,
     wheel_prize_extraction_user_id_index: WheelPrizeExtractionUserIdIndexMemory,
     wheel_prize_extraction_principal_index: WheelPrizeExtractionPrincipalIndexMemory,
+    wheel_prize_extraction_event_id_index: WheelPrizeExtractionEventIdIndexMemory,
 }
 
 impl Default for WheelPrizeExtractionState {
@@ -236,6 +423,7 @@ impl Default for WheelPrizeExtractionState {
             wheel_prize_extraction_asset_id_index: init_wheel_prize_extraction_asset_id_index(),
             wheel_prize_extraction_user_id_index: init_wheel_prize_extraction_user_id_index(),
             wheel_prize_extraction_principal_index: init_wheel_prize_extraction_principal_index(),
+            wheel_prize_extraction_event_id_index: init_wheel_prize_extraction_event_id_index(),
         }
     }
 }
@@ -0,0 +1,74 @@
+use std::cell::RefCell;
+
+use backend_api::ApiError;
+
+use super::{
+    init_wheel_prize_redemption_secret, Timestamped, WheelPrizeRedemptionSecret,
+    WheelPrizeRedemptionSecretMemory,
+};
+
+#[cfg_attr(test, mockall::automock)]
+pub trait WheelPrizeRedemptionRepository {
+    fn get_wheel_prize_redemption_secret(&self) -> Option<WheelPrizeRedemptionSecret>;
+
+    fn set_wheel_prize_redemption_secret(
+        &self,
+        secret: WheelPrizeRedemptionSecret,
+    ) -> Result<(), ApiError>;
+}
+
+pub struct WheelPrizeRedemptionRepositoryImpl {}
+
+impl Default for WheelPrizeRedemptionRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WheelPrizeRedemptionRepository for WheelPrizeRedemptionRepositoryImpl {
+    fn get_wheel_prize_redemption_secret(&self) -> Option<WheelPrizeRedemptionSecret> {
+        STATE.with_borrow(|s| s.wheel_prize_redemption_secret.get().clone())
+    }
+
+    fn set_wheel_prize_redemption_secret(
+        &self,
+        mut secret: WheelPrizeRedemptionSecret,
+    ) -> Result<(), ApiError> {
+        secret.update_timestamp();
+
+        STATE.with_borrow_mut(|s| {
+            s.wheel_prize_redemption_secret
+                .set(Some(secret))
+                .map(|_| ())
+                .map_err(|err| {
+                    ApiError::internal(&format!(
+                        "Failed to set wheel prize redemption secret: {:?}",
+                        err
+                    ))
+                })
+        })
+    }
+}
+
+impl WheelPrizeRedemptionRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+struct WheelPrizeRedemptionState {
+    wheel_prize_redemption_secret: WheelPrizeRedemptionSecretMemory,
+}
+
+impl Default for WheelPrizeRedemptionState {
+    fn default() -> Self {
+        Self {
+            wheel_prize_redemption_secret: init_wheel_prize_redemption_secret(),
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<WheelPrizeRedemptionState> =
+        RefCell::new(WheelPrizeRedemptionState::default());
+}
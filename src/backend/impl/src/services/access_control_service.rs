@@ -13,6 +13,8 @@ pub trait AccessControlService {
         &self,
         calling_principal: &Principal,
     ) -> Result<(), ApiError>;
+
+    fn assert_principal_is_scanner(&self, calling_principal: &Principal) -> Result<(), ApiError>;
 }
 
 pub struct AccessControlServiceImpl<T: UserProfileRepository> {
@@ -81,6 +83,27 @@ impl<T: UserProfileRepository> AccessControlService for AccessControlServiceImpl
 
         Ok(())
     }
+
+    fn assert_principal_is_scanner(&self, calling_principal: &Principal) -> Result<(), ApiError> {
+        let (_id, profile) = self
+            .user_profile_repository
+            .get_user_by_principal(calling_principal)
+            .ok_or_else(|| {
+                ApiError::not_found(&format!(
+                    "Principal {} must have a profile to call this endpoint",
+                    calling_principal.to_text()
+                ))
+            })?;
+
+        if !profile.is_scanner() {
+            return Err(ApiError::permission_denied(&format!(
+                "Principal {} must be a scanner to call this endpoint",
+                calling_principal.to_text()
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 impl<T: UserProfileRepository> AccessControlServiceImpl<T> {
@@ -0,0 +1,208 @@
+use backend_api::{
+    ApiError, CreateEventRequest, CreateEventResponse, DeleteEventRequest, GetActiveEventResponse,
+    ListEventsResponse, UpdateEventRequest,
+};
+
+use crate::{
+    mappings::map_event,
+    repositories::{
+        DateTime, Event, EventId, EventRepository, EventRepositoryImpl, WheelAssetRepository,
+        WheelAssetRepositoryImpl, WheelPrizeExtractionIdRange, WheelPrizeExtractionRepository,
+        WheelPrizeExtractionRepositoryImpl,
+    },
+};
+
+const EVENT_NAME_MAX_LENGTH: usize = 100;
+
+#[cfg_attr(test, mockall::automock)]
+pub trait EventService {
+    fn create_event(&self, request: CreateEventRequest) -> Result<CreateEventResponse, ApiError>;
+
+    fn update_event(&self, request: UpdateEventRequest) -> Result<(), ApiError>;
+
+    fn delete_event(&self, request: DeleteEventRequest) -> Result<(), ApiError>;
+
+    fn list_events(&self) -> Result<ListEventsResponse, ApiError>;
+
+    fn get_active_event(&self) -> Result<GetActiveEventResponse, ApiError>;
+}
+
+pub struct EventServiceImpl<
+    E: EventRepository,
+    A: WheelAssetRepository,
+    P: WheelPrizeExtractionRepository,
+> {
+    event_repository: E,
+    wheel_asset_repository: A,
+    wheel_prize_extraction_repository: P,
+}
+
+impl Default
+    for EventServiceImpl<
+        EventRepositoryImpl,
+        WheelAssetRepositoryImpl,
+        WheelPrizeExtractionRepositoryImpl,
+    >
+{
+    fn default() -> Self {
+        Self::new(
+            EventRepositoryImpl::default(),
+            WheelAssetRepositoryImpl::default(),
+            WheelPrizeExtractionRepositoryImpl::default(),
+        )
+    }
+}
+
+impl<E: EventRepository, A: WheelAssetRepository, P: WheelPrizeExtractionRepository> EventService
+    for EventServiceImpl<E, A, P>
+{
+    fn create_event(&self, request: CreateEventRequest) -> Result<CreateEventResponse, ApiError> {
+        self.validate_event_name(&request.name)?;
+        let start_date = DateTime::try_from(request.start_date.as_str())?;
+        let end_date = DateTime::try_from(request.end_date.as_str())?;
+        self.validate_event_dates(&start_date, &end_date)?;
+
+        let event = Event::new_inactive(request.name, start_date, end_date);
+        let event_id = self.event_repository.create_event(event.clone())?;
+
+        Ok(map_event(event_id, event))
+    }
+
+    fn update_event(&self, request: UpdateEventRequest) -> Result<(), ApiError> {
+        let event_id = EventId::try_from(request.id.as_str())?;
+        let mut event = self.get_event(&event_id)?;
+
+        if let Some(name) = request.name {
+            self.validate_event_name(&name)?;
+            event.name = name;
+        }
+
+        if let Some(start_date) = request.start_date {
+            event.start_date = DateTime::try_from(start_date.as_str())?;
+        }
+
+        if let Some(end_date) = request.end_date {
+            event.end_date = DateTime::try_from(end_date.as_str())?;
+        }
+
+        self.validate_event_dates(&event.start_date, &event.end_date)?;
+
+        if let Some(active) = request.active {
+            if active {
+                if let Some((active_event_id, _)) = self
+                    .event_repository
+                    .get_active_event()
+                    .filter(|(id, _)| *id != event_id)
+                {
+                    return Err(ApiError::conflict(&format!(
+                        "Event with id {} is already active, deactivate it first",
+                        active_event_id
+                    )));
+                }
+            }
+            event.active = active;
+        }
+
+        self.event_repository.update_event(event_id, event)
+    }
+
+    fn delete_event(&self, request: DeleteEventRequest) -> Result<(), ApiError> {
+        let event_id = EventId::try_from(request.id.as_str())?;
+        let event = self.get_event(&event_id)?;
+
+        if event.active {
+            return Err(ApiError::conflict("Cannot delete an active event"));
+        }
+
+        if self
+            .wheel_asset_repository
+            .list_wheel_assets()
+            .iter()
+            .any(|(_, wheel_asset)| wheel_asset.event_id == Some(event_id))
+        {
+            return Err(ApiError::conflict(
+                "Cannot delete an event that has wheel assets",
+            ));
+        }
+
+        if !self
+            .wheel_prize_extraction_repository
+            .list_wheel_prize_extractions_by_event(
+                Some(event_id),
+                &WheelPrizeExtractionIdRange::all(),
+            )?
+            .is_empty()
+        {
+            return Err(ApiError::conflict(
+                "Cannot delete an event that has wheel prize extractions",
+            ));
+        }
+
+        self.event_repository.delete_event(&event_id)
+    }
+
+    fn list_events(&self) -> Result<ListEventsResponse, ApiError> {
+        Ok(self
+            .event_repository
+            .list_events()
+            .into_iter()
+            .map(|(id, event)| map_event(id, event))
+            .collect())
+    }
+
+    fn get_active_event(&self) -> Result<GetActiveEventResponse, ApiError> {
+        Ok(self
+            .event_repository
+            .get_active_event()
+            .map(|(id, event)| map_event(id, event)))
+    }
+}
+
+impl<E: EventRepository, A: WheelAssetRepository, P: WheelPrizeExtractionRepository>
+    EventServiceImpl<E, A, P>
+{
+    fn new(
+        event_repository: E,
+        wheel_asset_repository: A,
+        wheel_prize_extraction_repository: P,
+    ) -> Self {
+        Self {
+            event_repository,
+            wheel_asset_repository,
+            wheel_prize_extraction_repository,
+        }
+    }
+
+    fn get_event(&self, id: &EventId) -> Result<Event, ApiError> {
+        self.event_repository
+            .get_event(id)
+            .ok_or_else(|| ApiError::not_found(&format!("Event with id {} not found", id)))
+    }
+
+    fn validate_event_name(&self, name: &str) -> Result<(), ApiError> {
+        if name.is_empty() {
+            return Err(ApiError::invalid_argument("Name must not be empty"));
+        }
+        if name.chars().count() > EVENT_NAME_MAX_LENGTH {
+            return Err(ApiError::invalid_argument(&format!(
+                "Name must be at most {EVENT_NAME_MAX_LENGTH} characters"
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn validate_event_dates(
+        &self,
+        start_date: &DateTime,
+        end_date: &DateTime,
+    ) -> Result<(), ApiError> {
+        if start_date >= end_date {
+            return Err(ApiError::invalid_argument(
+                "Start date must be before end date",
+            ));
+        }
+
+        Ok(())
+    }
+}
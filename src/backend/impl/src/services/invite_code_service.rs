@@ -0,0 +1,302 @@
+use backend_api::{
+    ApiError, ExportInviteCodesResponse, GenerateInviteCodesRequest, GenerateInviteCodesResponse,
+    GeneratedInviteCode, ListInviteCodesRequest, ListInviteCodesResponse, RevokeInviteCodeRequest,
+};
+use candid::Principal;
+
+use crate::{
+    mappings::{map_invite_code, map_invite_codes_csv},
+    repositories::{
+        derive_invite_code, get_current_date_time, hash_code, EventId, EventRepository,
+        EventRepositoryImpl, InviteCode, InviteCodeId, InviteCodePurpose, InviteCodeRepository,
+        InviteCodeRepositoryImpl, UserId, UserProfileRepository, UserProfileRepositoryImpl,
+        WheelPrizeExtractionId, WheelPrizeExtractionRepository, WheelPrizeExtractionRepositoryImpl,
+    },
+    system_api::random_seed,
+};
+
+const MAX_INVITE_CODES_PER_BATCH: u32 = 1_000;
+
+#[cfg_attr(test, mockall::automock)]
+#[allow(clippy::needless_lifetimes)]
+pub trait InviteCodeService {
+    /// Generates a batch of single-use invite codes, of which only the hashes are stored.
+    async fn generate_invite_codes<'a>(
+        &self,
+        calling_principal: &'a Principal,
+        request: GenerateInviteCodesRequest,
+    ) -> Result<GenerateInviteCodesResponse, ApiError>;
+
+    fn list_invite_codes(
+        &self,
+        request: ListInviteCodesRequest,
+    ) -> Result<ListInviteCodesResponse, ApiError>;
+
+    fn revoke_invite_code(
+        &self,
+        calling_principal: &Principal,
+        request: RevokeInviteCodeRequest,
+    ) -> Result<(), ApiError>;
+
+    /// Lists the invite codes as CSV for auditing. The codes themselves can't be included,
+    /// as they're returned only once by [InviteCodeService::generate_invite_codes].
+    fn export_invite_codes(
+        &self,
+        request: ListInviteCodesRequest,
+    ) -> Result<ExportInviteCodesResponse, ApiError>;
+
+    /// Checks that the invite code can be used for the purpose by the principal in the given event.
+    /// Returns `None` if there is no such code for the purpose, so that the callers can count the wrong codes.
+    fn authorize_invite_code(
+        &self,
+        principal: &Principal,
+        event_id: Option<EventId>,
+        invite_code: &str,
+        purpose: InviteCodePurpose,
+    ) -> Result<Option<InviteCodeId>, ApiError>;
+
+    /// Binds the invite code to the principal and the extraction.
+    /// Must be called in the same message of [InviteCodeService::authorize_invite_code],
+    /// so that the same code can't be used by concurrent calls.
+    fn consume_invite_code(
+        &self,
+        principal: &Principal,
+        invite_code_id: &InviteCodeId,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+    ) -> Result<(), ApiError>;
+}
+
+pub struct InviteCodeServiceImpl<
+    I: InviteCodeRepository,
+    P: WheelPrizeExtractionRepository,
+    E: EventRepository,
+    U: UserProfileRepository,
+> {
+    invite_code_repository: I,
+    wheel_prize_extraction_repository: P,
+    event_repository: E,
+    user_profile_repository: U,
+}
+
+impl Default
+    for InviteCodeServiceImpl<
+        InviteCodeRepositoryImpl,
+        WheelPrizeExtractionRepositoryImpl,
+        EventRepositoryImpl,
+        UserProfileRepositoryImpl,
+    >
+{
+    fn default() -> Self {
+        Self::new(
+            InviteCodeRepositoryImpl::default(),
+            WheelPrizeExtractionRepositoryImpl::default(),
+            EventRepositoryImpl::default(),
+            UserProfileRepositoryImpl::default(),
+        )
+    }
+}
+
+impl<
+        I: InviteCodeRepository,
+        P: WheelPrizeExtractionRepository,
+        E: EventRepository,
+        U: UserProfileRepository,
+    > InviteCodeService for InviteCodeServiceImpl<I, P, E, U>
+{
+    async fn generate_invite_codes(
+        &self,
+        calling_principal: &Principal,
+        request: GenerateInviteCodesRequest,
+    ) -> Result<GenerateInviteCodesResponse, ApiError> {
+        if request.count == 0 || request.count > MAX_INVITE_CODES_PER_BATCH {
+            return Err(ApiError::invalid_argument(&format!(
+                "Count must be between 1 and {}",
+                MAX_INVITE_CODES_PER_BATCH
+            )));
+        }
+        if request.principal == Some(Principal::anonymous()) {
+            return Err(ApiError::invalid_argument(
+                "Invite code principal cannot be anonymous",
+            ));
+        }
+        let event_id = self.get_requested_event_id(request.event_id.as_deref())?;
+        let purpose = request
+            .purpose
+            .map_or(InviteCodePurpose::Attendance, Into::into);
+        let created_by_user_id = self.get_user_id(calling_principal)?;
+
+        let seed = random_seed().await?;
+
+        (0..request.count)
+            .map(|index| {
+                let invite_code = derive_invite_code(&seed, index);
+                let invite_code_id =
+                    self.invite_code_repository
+                        .create_invite_code(InviteCode::new_available(
+                            hash_code(&invite_code),
+                            request.principal,
+                            event_id,
+                            purpose,
+                            created_by_user_id,
+                        ))?;
+
+                Ok(GeneratedInviteCode {
+                    invite_code_id: invite_code_id.to_string(),
+                    invite_code,
+                })
+            })
+            .collect()
+    }
+
+    fn list_invite_codes(
+        &self,
+        request: ListInviteCodesRequest,
+    ) -> Result<ListInviteCodesResponse, ApiError> {
+        let invite_codes = self
+            .list_requested_invite_codes(request)?
+            .into_iter()
+            .map(|(id, invite_code)| map_invite_code(id, invite_code))
+            .collect();
+        Ok(invite_codes)
+    }
+
+    fn revoke_invite_code(
+        &self,
+        calling_principal: &Principal,
+        request: RevokeInviteCodeRequest,
+    ) -> Result<(), ApiError> {
+        let invite_code_id = InviteCodeId::try_from(request.invite_code_id.as_str())?;
+        let mut invite_code = self.get_invite_code(&invite_code_id)?;
+        let revoked_by_user_id = self.get_user_id(calling_principal)?;
+
+        invite_code.revoke(revoked_by_user_id, get_current_date_time())?;
+
+        self.invite_code_repository
+            .update_invite_code(invite_code_id, invite_code)
+    }
+
+    fn export_invite_codes(
+        &self,
+        request: ListInviteCodesRequest,
+    ) -> Result<ExportInviteCodesResponse, ApiError> {
+        let invite_codes = self.list_requested_invite_codes(request)?;
+        Ok(map_invite_codes_csv(invite_codes))
+    }
+
+    fn authorize_invite_code(
+        &self,
+        principal: &Principal,
+        event_id: Option<EventId>,
+        invite_code: &str,
+        purpose: InviteCodePurpose,
+    ) -> Result<Option<InviteCodeId>, ApiError> {
+        let Some((invite_code_id, invite_code)) = self
+            .invite_code_repository
+            .get_invite_code_by_hash(&hash_code(invite_code))
+            .filter(|(_, invite_code)| invite_code.has_purpose(purpose))
+        else {
+            return Ok(None);
+        };
+
+        invite_code.assert_can_be_used(principal, event_id, |wheel_prize_extraction_id| {
+            self.wheel_prize_extraction_repository
+                .get_wheel_prize_extraction(wheel_prize_extraction_id)
+                .is_some_and(|extraction| extraction.is_failed())
+        })?;
+
+        Ok(Some(invite_code_id))
+    }
+
+    fn consume_invite_code(
+        &self,
+        principal: &Principal,
+        invite_code_id: &InviteCodeId,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+    ) -> Result<(), ApiError> {
+        let mut invite_code = self.get_invite_code(invite_code_id)?;
+        invite_code.set_used(
+            *principal,
+            wheel_prize_extraction_id,
+            get_current_date_time(),
+        );
+
+        self.invite_code_repository
+            .update_invite_code(*invite_code_id, invite_code)
+    }
+}
+
+impl<
+        I: InviteCodeRepository,
+        P: WheelPrizeExtractionRepository,
+        E: EventRepository,
+        U: UserProfileRepository,
+    > InviteCodeServiceImpl<I, P, E, U>
+{
+    fn new(
+        invite_code_repository: I,
+        wheel_prize_extraction_repository: P,
+        event_repository: E,
+        user_profile_repository: U,
+    ) -> Self {
+        Self {
+            invite_code_repository,
+            wheel_prize_extraction_repository,
+            event_repository,
+            user_profile_repository,
+        }
+    }
+
+    fn get_requested_event_id(&self, event_id: Option<&str>) -> Result<Option<EventId>, ApiError> {
+        let Some(event_id) = event_id else {
+            return Ok(None);
+        };
+
+        let event_id = EventId::try_from(event_id)?;
+        if self.event_repository.get_event(&event_id).is_none() {
+            return Err(ApiError::not_found(&format!(
+                "Event with id {} not found",
+                event_id
+            )));
+        }
+        Ok(Some(event_id))
+    }
+
+    fn list_requested_invite_codes(
+        &self,
+        request: ListInviteCodesRequest,
+    ) -> Result<Vec<(InviteCodeId, InviteCode)>, ApiError> {
+        let event_id = self.get_requested_event_id(request.event_id.as_deref())?;
+        let purpose = request.purpose.map(InviteCodePurpose::from);
+
+        let invite_codes = self
+            .invite_code_repository
+            .list_invite_codes()
+            .into_iter()
+            .filter(|(_, invite_code)| {
+                (event_id.is_none() || invite_code.event_id == event_id)
+                    && (purpose.is_none() || invite_code.purpose == purpose)
+            })
+            .collect();
+        Ok(invite_codes)
+    }
+
+    fn get_invite_code(&self, invite_code_id: &InviteCodeId) -> Result<InviteCode, ApiError> {
+        self.invite_code_repository
+            .get_invite_code(invite_code_id)
+            .ok_or_else(|| {
+                ApiError::not_found(&format!("Invite code with id {} not found", invite_code_id))
+            })
+    }
+
+    fn get_user_id(&self, calling_principal: &Principal) -> Result<UserId, ApiError> {
+        self.user_profile_repository
+            .get_user_by_principal(calling_principal)
+            .map(|(user_id, _)| user_id)
+            .ok_or_else(|| {
+                ApiError::not_found(&format!(
+                    "User profile for principal {} not found",
+                    calling_principal.to_text()
+                ))
+            })
+    }
+}
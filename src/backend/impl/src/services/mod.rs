@@ -1,17 +1,25 @@
 mod access_control_service;
 mod custom_domain_record_service;
+mod event_service;
 mod http_asset_service;
 mod init_service;
+mod invite_code_service;
+mod self_spin_service;
 mod user_profile_service;
 mod wallet_service;
 mod wheel_asset_service;
 mod wheel_prize_extraction_service;
+mod wheel_prize_redemption_service;
 
 pub use access_control_service::*;
 pub use custom_domain_record_service::*;
+pub use event_service::*;
 pub use http_asset_service::*;
 pub use init_service::*;
+pub use invite_code_service::*;
+pub use self_spin_service::*;
 pub use user_profile_service::*;
 pub use wallet_service::*;
 pub use wheel_asset_service::*;
 pub use wheel_prize_extraction_service::*;
+pub use wheel_prize_redemption_service::*;
@@ -0,0 +1,326 @@
+use backend_api::{
+    ApiError, CreateSelfSpinInviteRequest, CreateSelfSpinInviteResponse,
+    GenerateInviteCodesRequest, SetEventSelfSpinCodeRequest,
+};
+use candid::Principal;
+
+use crate::{
+    repositories::{
+        get_current_date_time, hash_code, Event, EventId, EventRepository, EventRepositoryImpl,
+        InviteCodePurpose, InviteCodeRepositoryImpl, SelfSpinFailedAttempts, SelfSpinRepository,
+        SelfSpinRepositoryImpl, UserProfileRepositoryImpl, WheelPrizeExtractionId,
+        WheelPrizeExtractionIdRange, WheelPrizeExtractionPolicyRepository,
+        WheelPrizeExtractionPolicyRepositoryImpl, WheelPrizeExtractionRepository,
+        WheelPrizeExtractionRepositoryImpl, WheelPrizeSelfSpin,
+    },
+    services::{InviteCodeService, InviteCodeServiceImpl},
+};
+
+const EVENT_SELF_SPIN_CODE_MIN_LENGTH: usize = 6;
+
+#[cfg_attr(test, mockall::automock)]
+#[allow(clippy::needless_lifetimes)]
+pub trait SelfSpinService {
+    async fn create_self_spin_invite<'a>(
+        &self,
+        calling_principal: &'a Principal,
+        request: CreateSelfSpinInviteRequest,
+    ) -> Result<CreateSelfSpinInviteResponse, ApiError>;
+
+    fn set_event_self_spin_code(
+        &self,
+        request: SetEventSelfSpinCodeRequest,
+    ) -> Result<(), ApiError>;
+
+    /// Checks the self-spin code submitted by the principal, counting the wrong codes
+    /// to lock the principal out after too many of them.
+    fn authorize_self_spin(
+        &self,
+        principal: &Principal,
+        event_id: Option<EventId>,
+        self_spin_code: &str,
+    ) -> Result<WheelPrizeSelfSpin, ApiError>;
+
+    /// Binds the invite code used for the self-spin, if any, to the extraction.
+    /// Must be called in the same message of [SelfSpinService::authorize_self_spin],
+    /// so that the same code can't be used by concurrent calls.
+    fn consume_self_spin(
+        &self,
+        principal: &Principal,
+        self_spin: &WheelPrizeSelfSpin,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+    ) -> Result<(), ApiError>;
+}
+
+pub struct SelfSpinServiceImpl<
+    IC: InviteCodeService,
+    S: SelfSpinRepository,
+    P: WheelPrizeExtractionRepository,
+    E: EventRepository,
+    PR: WheelPrizeExtractionPolicyRepository,
+> {
+    invite_code_service: IC,
+    self_spin_repository: S,
+    wheel_prize_extraction_repository: P,
+    event_repository: E,
+    wheel_prize_extraction_policy_repository: PR,
+}
+
+impl Default
+    for SelfSpinServiceImpl<
+        InviteCodeServiceImpl<
+            InviteCodeRepositoryImpl,
+            WheelPrizeExtractionRepositoryImpl,
+            EventRepositoryImpl,
+            UserProfileRepositoryImpl,
+        >,
+        SelfSpinRepositoryImpl,
+        WheelPrizeExtractionRepositoryImpl,
+        EventRepositoryImpl,
+        WheelPrizeExtractionPolicyRepositoryImpl,
+    >
+{
+    fn default() -> Self {
+        Self::new(
+            InviteCodeServiceImpl::default(),
+            SelfSpinRepositoryImpl::default(),
+            WheelPrizeExtractionRepositoryImpl::default(),
+            EventRepositoryImpl::default(),
+            WheelPrizeExtractionPolicyRepositoryImpl::default(),
+        )
+    }
+}
+
+impl<
+        IC: InviteCodeService,
+        S: SelfSpinRepository,
+        P: WheelPrizeExtractionRepository,
+        E: EventRepository,
+        PR: WheelPrizeExtractionPolicyRepository,
+    > SelfSpinService for SelfSpinServiceImpl<IC, S, P, E, PR>
+{
+    async fn create_self_spin_invite(
+        &self,
+        calling_principal: &Principal,
+        request: CreateSelfSpinInviteRequest,
+    ) -> Result<CreateSelfSpinInviteResponse, ApiError> {
+        let generated_invite_code = self
+            .invite_code_service
+            .generate_invite_codes(
+                calling_principal,
+                GenerateInviteCodesRequest {
+                    count: 1,
+                    event_id: request.event_id,
+                    principal: request.principal,
+                    purpose: Some(backend_api::InviteCodePurpose::SelfSpin),
+                },
+            )
+            .await?
+            .pop()
+            .ok_or_else(|| ApiError::internal("No invite code has been generated"))?;
+
+        Ok(CreateSelfSpinInviteResponse {
+            invite_code_id: generated_invite_code.invite_code_id,
+            invite_code: generated_invite_code.invite_code,
+        })
+    }
+
+    fn set_event_self_spin_code(
+        &self,
+        request: SetEventSelfSpinCodeRequest,
+    ) -> Result<(), ApiError> {
+        let event_id = EventId::try_from(request.event_id.as_str())?;
+        let mut event = self
+            .event_repository
+            .get_event(&event_id)
+            .ok_or_else(|| ApiError::not_found(&format!("Event with id {} not found", event_id)))?;
+
+        match request.self_spin_code {
+            Some(self_spin_code) => {
+                let normalized_length = self_spin_code
+                    .chars()
+                    .filter(|c| !c.is_whitespace() && *c != '-')
+                    .count();
+                if normalized_length < EVENT_SELF_SPIN_CODE_MIN_LENGTH {
+                    return Err(ApiError::invalid_argument(&format!(
+                        "Self-spin code must be at least {} characters long",
+                        EVENT_SELF_SPIN_CODE_MIN_LENGTH
+                    )));
+                }
+                let max_spins = request
+                    .max_spins
+                    .filter(|max_spins| *max_spins > 0)
+                    .ok_or_else(|| {
+                        ApiError::invalid_argument("Max spins must be set to a positive number")
+                    })?;
+                if request.max_failed_attempts == Some(0) {
+                    return Err(ApiError::invalid_argument(
+                        "Max failed attempts must be a positive number",
+                    ));
+                }
+                event.set_self_spin_code(
+                    Some(hash_code(&self_spin_code)),
+                    Some(max_spins),
+                    request.max_failed_attempts,
+                );
+            }
+            None => event.set_self_spin_code(None, None, None),
+        };
+
+        self.event_repository.update_event(event_id, event)
+    }
+
+    fn authorize_self_spin(
+        &self,
+        principal: &Principal,
+        event_id: Option<EventId>,
+        self_spin_code: &str,
+    ) -> Result<WheelPrizeSelfSpin, ApiError> {
+        let policy = self
+            .wheel_prize_extraction_policy_repository
+            .get_wheel_prize_extraction_policy();
+        let now = get_current_date_time();
+
+        let failed_attempts = self
+            .self_spin_repository
+            .get_self_spin_failed_attempts(principal);
+        if let Some(failed_attempts) = &failed_attempts {
+            failed_attempts.assert_not_locked_out(policy.self_spin_max_failed_attempts(), &now)?;
+        }
+
+        let code_hash = hash_code(self_spin_code);
+
+        // the event code is checked only if it can still be used, so that the players
+        // can't find out whether they guessed it after it has been disabled
+        let event = event_id.and_then(|event_id| {
+            self.event_repository
+                .get_event(&event_id)
+                .filter(|event| event.self_spin_code_hash.is_some())
+                .map(|event| (event_id, event))
+        });
+        let is_event_code_disabled = event
+            .as_ref()
+            .is_some_and(|(_, event)| event.is_self_spin_code_disabled());
+        let result = match &event {
+            Some((event_id, event))
+                if !is_event_code_disabled && event.self_spin_code_hash == Some(code_hash) =>
+            {
+                self.assert_event_code_can_be_used(principal, *event_id, event)
+                    .map(|_| Some(WheelPrizeSelfSpin::EventCode))
+            }
+            _ => self
+                .invite_code_service
+                .authorize_invite_code(
+                    principal,
+                    event_id,
+                    self_spin_code,
+                    InviteCodePurpose::SelfSpin,
+                )
+                .map(|invite_code_id| {
+                    invite_code_id
+                        .map(|invite_code_id| WheelPrizeSelfSpin::InviteCode { invite_code_id })
+                }),
+        };
+
+        match result? {
+            Some(self_spin) => {
+                if failed_attempts.is_some() {
+                    self.self_spin_repository
+                        .clear_self_spin_failed_attempts(principal);
+                }
+                Ok(self_spin)
+            }
+            None => {
+                self.self_spin_repository.set_self_spin_failed_attempts(
+                    *principal,
+                    SelfSpinFailedAttempts::record_failed_attempt(failed_attempts, &now),
+                );
+                // the wrong codes are counted for the whole event too,
+                // so that the event code can't be guessed with many principals
+                if let Some((event_id, mut event)) = event {
+                    event.record_self_spin_failed_attempt();
+                    self.event_repository.update_event(event_id, event)?;
+                }
+
+                if is_event_code_disabled {
+                    return Err(ApiError::too_many_requests(
+                        "The self-spin code of this event has been disabled after too many wrong codes",
+                    ));
+                }
+                Err(ApiError::permission_denied("Invalid self-spin code"))
+            }
+        }
+    }
+
+    fn consume_self_spin(
+        &self,
+        principal: &Principal,
+        self_spin: &WheelPrizeSelfSpin,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+    ) -> Result<(), ApiError> {
+        match self_spin {
+            // the extraction itself marks the event code as used by the principal
+            WheelPrizeSelfSpin::EventCode => Ok(()),
+            WheelPrizeSelfSpin::InviteCode { invite_code_id } => self
+                .invite_code_service
+                .consume_invite_code(principal, invite_code_id, wheel_prize_extraction_id),
+        }
+    }
+}
+
+impl<
+        IC: InviteCodeService,
+        S: SelfSpinRepository,
+        P: WheelPrizeExtractionRepository,
+        E: EventRepository,
+        PR: WheelPrizeExtractionPolicyRepository,
+    > SelfSpinServiceImpl<IC, S, P, E, PR>
+{
+    fn new(
+        invite_code_service: IC,
+        self_spin_repository: S,
+        wheel_prize_extraction_repository: P,
+        event_repository: E,
+        wheel_prize_extraction_policy_repository: PR,
+    ) -> Self {
+        Self {
+            invite_code_service,
+            self_spin_repository,
+            wheel_prize_extraction_repository,
+            event_repository,
+            wheel_prize_extraction_policy_repository,
+        }
+    }
+
+    /// Each principal can spin the wheel with the event code once, or again if the extraction failed,
+    /// within the maximum number of spins of the event code.
+    fn assert_event_code_can_be_used(
+        &self,
+        principal: &Principal,
+        event_id: EventId,
+        event: &Event,
+    ) -> Result<(), ApiError> {
+        let event_code_extractions = self
+            .wheel_prize_extraction_repository
+            .list_wheel_prize_extractions_by_event(
+                Some(event_id),
+                &WheelPrizeExtractionIdRange::all(),
+            )?
+            .into_iter()
+            .filter(|(_, extraction)| {
+                !extraction.is_failed()
+                    && matches!(extraction.self_spin, Some(WheelPrizeSelfSpin::EventCode))
+            })
+            .collect::<Vec<_>>();
+        if event_code_extractions
+            .iter()
+            .any(|(_, extraction)| extraction.extracted_for_principal == *principal)
+        {
+            return Err(ApiError::conflict(
+                "The self-spin code of this event has already been used by this principal",
+            ));
+        }
+
+        event.assert_self_spin_code_spins_available(event_code_extractions.len())
+    }
+}
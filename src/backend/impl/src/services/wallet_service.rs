@@ -1,97 +1,891 @@
-use backend_api::{ApiError, TransferTokenRequest};
+use backend_api::{
+    ApiError, ApproveManualTransferRequest, LedgerTransferRejection, ListLedgerTransfersRequest,
+    ListLedgerTransfersResponse, ListManualTransferLimitsResponse, ListManualTransfersResponse,
+    RejectManualTransferRequest, SetManualTransferLimitRequest, SetManualTransferLimitResponse,
+    TransferTokenRequest, TransferTokenResponse,
+};
 use candid::{Nat, Principal};
-use external_canisters::ledger::LedgerCanisterService;
+use external_canisters::ledger::{get_archived_transactions, LedgerCanisterService};
 use ic_cdk::println;
 use ic_stable_structures::Storable;
-use icrc_ledger_types::icrc1::{account::Account, transfer::TransferArg};
+use icrc_ledger_types::{
+    icrc1::{
+        account::Account,
+        transfer::{TransferArg, TransferError},
+    },
+    icrc2::transfer_from::{TransferFromArgs, TransferFromError},
+    icrc3::transactions::{GetTransactionsRequest, Transaction},
+};
 
-use crate::repositories::{UserProfileRepository, UserProfileRepositoryImpl};
+use crate::{
+    mappings::{
+        into_recipient_account, into_subaccount, map_ledger_transfer, map_manual_transfer,
+        map_manual_transfer_limit,
+    },
+    repositories::{
+        get_current_date_time, LedgerTransfer, LedgerTransferFilter, LedgerTransferId,
+        LedgerTransferRepository, LedgerTransferRepositoryImpl, ManualTransfer, ManualTransferId,
+        ManualTransferLimit, ManualTransferRepository, ManualTransferRepositoryImpl,
+        ManualTransferState, SentLedgerTransfer, UserId, UserProfileRepository,
+        UserProfileRepositoryImpl, WheelPrizeExtractionId, WheelPrizePayout,
+        MANUAL_TRANSFER_APPROVAL_EXPIRY_SECONDS,
+    },
+};
+
+const DEFAULT_LEDGER_TRANSFERS_PAGE_SIZE: u32 = 100;
+const MAX_LEDGER_TRANSFERS_PAGE_SIZE: u32 = 1_000;
+const MAX_MANUAL_TRANSFER_REASON_LENGTH: usize = 500;
+/// How far in the future of the ledger's time a transfer can be created, with a margin
+/// over the permitted drift of the ICRC-1 ledgers.
+const LEDGER_PERMITTED_DRIFT_NANOS: u64 = 5 * 60 * 1_000_000_000;
+const LEDGER_TRANSACTIONS_PAGE_SIZE: u64 = 1_000;
+/// The lookup of a payout gives up after this many transactions,
+/// so that a busy ledger can't make it run out of instructions or cycles.
+const MAX_LOOKED_UP_LEDGER_TRANSACTIONS: u64 = 50_000;
 
 #[cfg_attr(test, mockall::automock)]
 pub trait WalletService {
+    /// Transfers the tokens from the canister account, within the daily limit of the ledger.
+    /// If the amount is above the approval threshold of the ledger, the transfer is stored
+    /// until a second admin approves it instead.
+    /// Every attempt is recorded as a manual transfer, including the invalid ones.
     async fn transfer_token(
         &self,
         calling_principal: Principal,
         request: TransferTokenRequest,
+    ) -> Result<TransferTokenResponse, ApiError>;
+
+    /// Executes a transfer pending approval, which must be approved by another admin
+    /// than the one who requested it.
+    async fn approve_manual_transfer(
+        &self,
+        calling_principal: Principal,
+        request: ApproveManualTransferRequest,
+    ) -> Result<Nat, ApiError>;
+
+    fn reject_manual_transfer(
+        &self,
+        calling_principal: Principal,
+        request: RejectManualTransferRequest,
+    ) -> Result<(), ApiError>;
+
+    fn list_manual_transfers(&self) -> ListManualTransfersResponse;
+
+    fn set_manual_transfer_limit(
+        &self,
+        request: SetManualTransferLimitRequest,
+    ) -> Result<SetManualTransferLimitResponse, ApiError>;
+
+    fn list_manual_transfer_limits(&self) -> ListManualTransferLimitsResponse;
+
+    /// Transfers the payout to the account, using the payout's deduplication parameters.
+    /// The payout is transferred from the sponsor's account with `icrc2_transfer_from`
+    /// if it has one, from the canister account otherwise.
+    /// Returns the block index of the transfer, which is the one of the original transfer
+    /// if the ledger already executed the payout.
+    async fn transfer_wheel_prize_payout(
+        &self,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+        extracted_by_user_id: UserId,
+        to: Account,
+        payout: WheelPrizePayout,
     ) -> Result<Nat, ApiError>;
+
+    /// Looks up a transfer in the ledger's transactions, see [find_ledger_transfer].
+    async fn find_ledger_transfer(
+        &self,
+        sent_transfer: SentLedgerTransfer,
+    ) -> Result<Option<Nat>, ApiError>;
+
+    fn list_ledger_transfers(
+        &self,
+        request: ListLedgerTransfersRequest,
+    ) -> Result<ListLedgerTransfersResponse, ApiError>;
 }
 
-pub struct WalletServiceImpl<U: UserProfileRepository> {
+pub struct WalletServiceImpl<
+    U: UserProfileRepository,
+    T: LedgerTransferRepository,
+    M: ManualTransferRepository,
+> {
     user_profile_repository: U,
+    ledger_transfer_repository: T,
+    manual_transfer_repository: M,
 }
 
-impl Default for WalletServiceImpl<UserProfileRepositoryImpl> {
+impl Default
+    for WalletServiceImpl<
+        UserProfileRepositoryImpl,
+        LedgerTransferRepositoryImpl,
+        ManualTransferRepositoryImpl,
+    >
+{
     fn default() -> Self {
-        Self::new(UserProfileRepositoryImpl::default())
+        Self::new(
+            UserProfileRepositoryImpl::default(),
+            LedgerTransferRepositoryImpl::default(),
+            ManualTransferRepositoryImpl::default(),
+        )
     }
 }
 
-impl<U: UserProfileRepository> WalletService for WalletServiceImpl<U> {
+impl<U: UserProfileRepository, T: LedgerTransferRepository, M: ManualTransferRepository>
+    WalletService for WalletServiceImpl<U, T, M>
+{
     async fn transfer_token(
         &self,
         calling_principal: Principal,
         request: TransferTokenRequest,
+    ) -> Result<TransferTokenResponse, ApiError> {
+        let user_id = self.get_user_id(&calling_principal)?;
+
+        println!("Transferring token. Request: {}", request);
+
+        let reason = request.reason.as_deref().unwrap_or_default().trim();
+        let to = into_recipient_account(request.to, request.to_account.clone());
+        let amount = u128::try_from(request.amount.0.clone())
+            .ok()
+            .filter(|amount| *amount > 0);
+        let from_subaccount = request
+            .from_subaccount
+            .as_deref()
+            .map(into_subaccount)
+            .transpose();
+
+        // the invalid requests are recorded too, with an anonymous recipient
+        // and a zero amount if they can't be parsed
+        let mut manual_transfer = ManualTransfer::new_processing(
+            request.ledger_canister_id,
+            from_subaccount.clone().ok().flatten(),
+            to.clone().unwrap_or(Account {
+                owner: Principal::anonymous(),
+                subaccount: None,
+            }),
+            amount.unwrap_or_default(),
+            reason
+                .chars()
+                .take(MAX_MANUAL_TRANSFER_REASON_LENGTH)
+                .collect(),
+            user_id,
+        );
+
+        let manual_transfer_limit = self
+            .manual_transfer_repository
+            .get_manual_transfer_limit(&request.ledger_canister_id)
+            .unwrap_or_default();
+        let validation = validate_transfer_token_request(reason, &to, amount, &from_subaccount)
+            .and_then(|_| self.assert_within_daily_limit(&manual_transfer_limit, &manual_transfer));
+        if let Err(err) = validation {
+            manual_transfer.state = ManualTransferState::Denied { error: err.clone() };
+            self.manual_transfer_repository
+                .create_manual_transfer(manual_transfer)?;
+            return Err(err);
+        }
+
+        if manual_transfer_limit.requires_approval(manual_transfer.amount) {
+            manual_transfer.state = ManualTransferState::PendingApproval;
+            let id = self
+                .manual_transfer_repository
+                .create_manual_transfer(manual_transfer)?;
+            println!("Manual transfer {} is pending approval", id);
+            return Ok(TransferTokenResponse::PendingApproval {
+                manual_transfer_id: id.to_string(),
+            });
+        }
+
+        let id = self
+            .manual_transfer_repository
+            .create_manual_transfer(manual_transfer.clone())?;
+
+        let block_index = self.execute_manual_transfer(id, manual_transfer).await?;
+
+        Ok(TransferTokenResponse::Completed { block_index })
+    }
+
+    async fn approve_manual_transfer(
+        &self,
+        calling_principal: Principal,
+        request: ApproveManualTransferRequest,
     ) -> Result<Nat, ApiError> {
-        let (user_id, _) = self
-            .user_profile_repository
-            .get_user_by_principal(&calling_principal)
+        let user_id = self.get_user_id(&calling_principal)?;
+        let (id, mut manual_transfer) =
+            self.get_manual_transfer_pending_approval(&request.manual_transfer_id)?;
+
+        if manual_transfer.requested_by_user_id == user_id {
+            return Err(ApiError::permission_denied(
+                "Manual transfer must be approved by another admin",
+            ));
+        }
+
+        let denial = if manual_transfer.is_approval_expired(&get_current_date_time()) {
+            Some(ApiError::conflict(&format!(
+                "Manual transfer {} has not been approved within {} hours",
+                id,
+                MANUAL_TRANSFER_APPROVAL_EXPIRY_SECONDS / 3_600
+            )))
+        } else {
+            let manual_transfer_limit = self
+                .manual_transfer_repository
+                .get_manual_transfer_limit(&manual_transfer.ledger_canister_id)
+                .unwrap_or_default();
+            self.assert_within_daily_limit(&manual_transfer_limit, &manual_transfer)
+                .err()
+        };
+        if let Some(err) = denial {
+            manual_transfer.state = ManualTransferState::Denied { error: err.clone() };
+            self.manual_transfer_repository
+                .update_manual_transfer(id, manual_transfer)?;
+            return Err(err);
+        }
+
+        println!("Manual transfer {} approved by user {}", id, user_id);
+
+        manual_transfer.approved_by_user_id = Some(user_id);
+        manual_transfer.state = ManualTransferState::Processing;
+
+        self.execute_manual_transfer(id, manual_transfer).await
+    }
+
+    fn reject_manual_transfer(
+        &self,
+        calling_principal: Principal,
+        request: RejectManualTransferRequest,
+    ) -> Result<(), ApiError> {
+        let user_id = self.get_user_id(&calling_principal)?;
+        let (id, mut manual_transfer) =
+            self.get_manual_transfer_pending_approval(&request.manual_transfer_id)?;
+
+        manual_transfer.state = ManualTransferState::Rejected {
+            rejected_by_user_id: user_id,
+        };
+        self.manual_transfer_repository
+            .update_manual_transfer(id, manual_transfer)
+    }
+
+    fn list_manual_transfers(&self) -> ListManualTransfersResponse {
+        self.manual_transfer_repository
+            .list_manual_transfers()
+            .into_iter()
+            .map(|(id, manual_transfer)| map_manual_transfer(id, manual_transfer))
+            .collect()
+    }
+
+    fn set_manual_transfer_limit(
+        &self,
+        request: SetManualTransferLimitRequest,
+    ) -> Result<SetManualTransferLimitResponse, ApiError> {
+        let into_amount = |amount: Nat, field: &str| {
+            u128::try_from(amount.0)
+                .map_err(|_| ApiError::invalid_argument(&format!("Invalid {}", field)))
+        };
+
+        let mut manual_transfer_limit = self
+            .manual_transfer_repository
+            .get_manual_transfer_limit(&request.ledger_canister_id)
+            .unwrap_or_default();
+        manual_transfer_limit.daily_limit = request
+            .daily_limit
+            .map(|daily_limit| into_amount(daily_limit, "daily limit"))
+            .transpose()?;
+        manual_transfer_limit.approval_threshold = request
+            .approval_threshold
+            .map(|approval_threshold| into_amount(approval_threshold, "approval threshold"))
+            .transpose()?;
+
+        self.manual_transfer_repository
+            .set_manual_transfer_limit(request.ledger_canister_id, manual_transfer_limit);
+
+        // SAFETY: the limit has just been set
+        let manual_transfer_limit = self
+            .manual_transfer_repository
+            .get_manual_transfer_limit(&request.ledger_canister_id)
+            .unwrap();
+
+        Ok(map_manual_transfer_limit(
+            request.ledger_canister_id,
+            manual_transfer_limit,
+        ))
+    }
+
+    fn list_manual_transfer_limits(&self) -> ListManualTransferLimitsResponse {
+        self.manual_transfer_repository
+            .list_manual_transfer_limits()
+            .into_iter()
+            .map(|(ledger_canister_id, manual_transfer_limit)| {
+                map_manual_transfer_limit(ledger_canister_id, manual_transfer_limit)
+            })
+            .collect()
+    }
+
+    async fn transfer_wheel_prize_payout(
+        &self,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+        extracted_by_user_id: UserId,
+        to: Account,
+        payout: WheelPrizePayout,
+    ) -> Result<Nat, ApiError> {
+        println!(
+            "Transferring wheel prize payout. Ledger canister id: {}, From: {}, To: {}, Amount: {}, Created at time: {}",
+            payout.ledger_canister_id,
+            payout
+                .from_account
+                .map(|account| account.to_string())
+                .unwrap_or_else(|| "canister account".to_string()),
+            to,
+            payout.amount,
+            payout.created_at_time
+        );
+
+        let ledger_canister = LedgerCanisterService(payout.ledger_canister_id);
+
+        let result = match payout.from_account {
+            Some(from) => ledger_canister
+                .icrc2_transfer_from(TransferFromArgs {
+                    spender_subaccount: payout.from_subaccount,
+                    from,
+                    to,
+                    amount: payout.amount.into(),
+                    fee: payout.fee.map(Into::into),
+                    memo: Some(payout.memo.clone().into()),
+                    created_at_time: Some(payout.created_at_time),
+                })
+                .await
+                .map(|res| res.map_err(LedgerTransferError::from)),
+            None => ledger_canister
+                .icrc1_transfer(TransferArg {
+                    amount: payout.amount.into(),
+                    to,
+                    created_at_time: Some(payout.created_at_time),
+                    from_subaccount: payout.from_subaccount,
+                    fee: payout.fee.map(Into::into),
+                    memo: Some(payout.memo.clone().into()),
+                })
+                .await
+                .map(|res| res.map_err(LedgerTransferError::from)),
+        };
+
+        let block_index = match result {
+            Ok(Ok(block_index)) => {
+                println!(
+                    "Wheel prize payout transferred successfully. Block index: {}",
+                    block_index
+                );
+                block_index
+            }
+            Ok(Err(LedgerTransferError::Duplicate { duplicate_of })) => {
+                println!(
+                    "Wheel prize payout was already transferred. Block index: {}",
+                    duplicate_of
+                );
+                duplicate_of
+            }
+            Ok(Err(LedgerTransferError::Rejected(rejection))) => {
+                let err = ApiError::ledger_transfer_rejected(payout.ledger_canister_id, rejection);
+                println!("Wheel prize payout failed. Error: {}", err);
+                return Err(err);
+            }
+            Err(e) => {
+                let err = ApiError::ledger_call_failed(payout.ledger_canister_id, &e.to_string());
+                println!("Call to ledger canister failed. Error: {}", err);
+                return Err(err);
+            }
+        };
+
+        let mut ledger_transfer = LedgerTransfer::new(
+            payout.ledger_canister_id,
+            payout
+                .from_account
+                .unwrap_or_else(|| canister_account(payout.from_subaccount)),
+            to,
+            payout.amount.into(),
+            payout.fee.map(Into::into),
+            Some(payout.memo),
+            block_index.clone(),
+        );
+        ledger_transfer.initiated_by_user_id = Some(extracted_by_user_id);
+        ledger_transfer.wheel_prize_extraction_id = Some(wheel_prize_extraction_id);
+        ledger_transfer.wheel_asset_id = Some(payout.wheel_asset_id);
+        self.log_ledger_transfer(ledger_transfer);
+
+        Ok(block_index)
+    }
+
+    async fn find_ledger_transfer(
+        &self,
+        sent_transfer: SentLedgerTransfer,
+    ) -> Result<Option<Nat>, ApiError> {
+        find_ledger_transfer(&sent_transfer).await
+    }
+
+    fn list_ledger_transfers(
+        &self,
+        request: ListLedgerTransfersRequest,
+    ) -> Result<ListLedgerTransfersResponse, ApiError> {
+        let limit = request.limit.unwrap_or(DEFAULT_LEDGER_TRANSFERS_PAGE_SIZE);
+        if limit == 0 || limit > MAX_LEDGER_TRANSFERS_PAGE_SIZE {
+            return Err(ApiError::invalid_argument(&format!(
+                "Limit must be between 1 and {}",
+                MAX_LEDGER_TRANSFERS_PAGE_SIZE
+            )));
+        }
+
+        let filter = LedgerTransferFilter {
+            ledger_canister_id: request.ledger_canister_id,
+            principal: request.principal,
+            wheel_prize_extraction_id: request
+                .wheel_prize_extraction_id
+                .as_deref()
+                .map(WheelPrizeExtractionId::try_from)
+                .transpose()?,
+        };
+        let start_after = request
+            .start_after
+            .as_deref()
+            .map(LedgerTransferId::try_from)
+            .transpose()?;
+
+        let ledger_transfers = self.ledger_transfer_repository.list_ledger_transfers(
+            &filter,
+            start_after,
+            limit as usize,
+        )?;
+        let next_start_after = ledger_transfers
+            .last()
+            .filter(|_| ledger_transfers.len() == limit as usize)
+            .map(|(id, _)| id.to_string());
+
+        Ok(ListLedgerTransfersResponse {
+            transfers: ledger_transfers
+                .into_iter()
+                .map(|(id, ledger_transfer)| map_ledger_transfer(id, ledger_transfer))
+                .collect(),
+            next_start_after,
+        })
+    }
+}
+
+impl<U: UserProfileRepository, T: LedgerTransferRepository, M: ManualTransferRepository>
+    WalletServiceImpl<U, T, M>
+{
+    pub fn new(
+        user_profile_repository: U,
+        ledger_transfer_repository: T,
+        manual_transfer_repository: M,
+    ) -> Self {
+        Self {
+            user_profile_repository,
+            ledger_transfer_repository,
+            manual_transfer_repository,
+        }
+    }
+
+    fn get_user_id(&self, calling_principal: &Principal) -> Result<UserId, ApiError> {
+        self.user_profile_repository
+            .get_user_by_principal(calling_principal)
+            .map(|(user_id, _)| user_id)
             .ok_or_else(|| {
                 ApiError::not_found(&format!(
                     "User id for principal {} not found",
                     calling_principal.to_text()
                 ))
+            })
+    }
+
+    fn get_manual_transfer_pending_approval(
+        &self,
+        manual_transfer_id: &str,
+    ) -> Result<(ManualTransferId, ManualTransfer), ApiError> {
+        let id = ManualTransferId::try_from(manual_transfer_id)?;
+        let manual_transfer = self
+            .manual_transfer_repository
+            .get_manual_transfer(&id)
+            .ok_or_else(|| {
+                ApiError::not_found(&format!("Manual transfer with id {} not found", id))
             })?;
 
-        let display_request = request.to_string();
-        println!("Transferring token. Request: {}", display_request);
+        if !manual_transfer.is_pending_approval() {
+            return Err(ApiError::conflict(&format!(
+                "Manual transfer {} is not pending approval, current state: {}",
+                id, manual_transfer.state
+            )));
+        }
+
+        Ok((id, manual_transfer))
+    }
+
+    fn assert_within_daily_limit(
+        &self,
+        manual_transfer_limit: &ManualTransferLimit,
+        manual_transfer: &ManualTransfer,
+    ) -> Result<(), ApiError> {
+        if manual_transfer_limit.daily_limit.is_none() {
+            return Ok(());
+        }
+
+        // transfers count on the day they are executed, which can be the day after
+        // they have been requested if they had to be approved
+        let now = get_current_date_time();
+        let created_after = now.start_of_day().sub(chrono::Duration::seconds(
+            MANUAL_TRANSFER_APPROVAL_EXPIRY_SECONDS as i64,
+        ));
+        let ledger_transfers = self
+            .manual_transfer_repository
+            .list_manual_transfers_created_after(&created_after)
+            .into_iter()
+            .map(|(_, transfer)| transfer)
+            .filter(|transfer| transfer.ledger_canister_id == manual_transfer.ledger_canister_id)
+            .collect::<Vec<_>>();
+
+        manual_transfer_limit.assert_within_daily_limit(
+            manual_transfer.amount,
+            &ledger_transfers,
+            &now,
+        )
+    }
+
+    /// Sends the manual transfer to the ledger and records the outcome.
+    /// The `created_at_time` of the transfer is stored before it is sent.
+    async fn execute_manual_transfer(
+        &self,
+        id: ManualTransferId,
+        mut manual_transfer: ManualTransfer,
+    ) -> Result<Nat, ApiError> {
+        let ledger_canister_id = manual_transfer.ledger_canister_id;
+        let ledger_canister = LedgerCanisterService(ledger_canister_id);
+        let from_subaccount = manual_transfer.from_subaccount;
+        let to = manual_transfer.to_account();
+        let memo = id.to_bytes().to_vec();
+        let created_at_time = ic_cdk::api::time();
 
-        let ledger_canister = LedgerCanisterService(request.ledger_canister_id);
+        manual_transfer.created_at_time = Some(created_at_time);
+        self.manual_transfer_repository
+            .update_manual_transfer(id, manual_transfer.clone())?;
 
-        match ledger_canister
+        let result = match ledger_canister
             .icrc1_transfer(TransferArg {
-                amount: request.amount,
-                to: Account {
-                    owner: request.to,
-                    subaccount: None,
-                },
-                created_at_time: Some(ic_cdk::api::time()),
-                from_subaccount: None,
+                amount: Nat::from(manual_transfer.amount),
+                to,
+                created_at_time: Some(created_at_time),
+                from_subaccount,
                 fee: None,
-                memo: Some(user_id.to_bytes().to_vec().into()),
+                memo: Some(memo.clone().into()),
             })
             .await
+            .map(|res| res.map_err(LedgerTransferError::from))
         {
             Ok(Ok(block_index)) => {
                 println!(
-                    "Token transferred successfully. Request: {}, Block index: {}",
-                    display_request, block_index
+                    "Token transferred successfully. Manual transfer: {}, Block index: {}",
+                    id, block_index
                 );
                 Ok(block_index)
             }
-            Ok(Err(e)) => {
-                let error_message = format!("Transfer failed: {e}");
+            Ok(Err(LedgerTransferError::Duplicate { duplicate_of })) => {
                 println!(
-                    "Transfer failed. Request: {}, Error: {}",
-                    display_request, error_message
+                    "Token was already transferred. Manual transfer: {}, Block index: {}",
+                    id, duplicate_of
                 );
-                Err(ApiError::internal(&error_message))
+                Ok(duplicate_of)
+            }
+            Ok(Err(LedgerTransferError::Rejected(rejection))) => {
+                let err = ApiError::ledger_transfer_rejected(ledger_canister_id, rejection);
+                println!("Transfer failed. Manual transfer: {}, Error: {}", id, err);
+                Err(err)
             }
             Err(e) => {
-                let error_message = format!("Call to ledger canister failed: {:?}", e);
+                let err = ApiError::ledger_call_failed(ledger_canister_id, &e.to_string());
                 println!(
-                    "Call to ledger canister failed. Request: {}, Error: {}",
-                    display_request, error_message
+                    "Call to ledger canister failed. Manual transfer: {}, Error: {}",
+                    id, err
                 );
-                Err(ApiError::internal(&error_message))
+                Err(err)
             }
+        };
+
+        manual_transfer.state = match &result {
+            Ok(block_index) => ManualTransferState::Completed {
+                block_index: block_index.clone(),
+            },
+            Err(err) => ManualTransferState::Failed { error: err.clone() },
+        };
+        let requested_by_user_id = manual_transfer.requested_by_user_id;
+        let amount = manual_transfer.amount;
+        // the outcome of the transfer can't be changed anymore, so the error is only logged
+        if let Err(err) = self
+            .manual_transfer_repository
+            .update_manual_transfer(id, manual_transfer)
+        {
+            println!(
+                "Error: failed to record the outcome of manual transfer {}: {}",
+                id, err
+            );
+        }
+
+        let block_index = result?;
+        let mut ledger_transfer = LedgerTransfer::new(
+            ledger_canister_id,
+            canister_account(from_subaccount),
+            to,
+            Nat::from(amount),
+            None,
+            Some(memo),
+            block_index.clone(),
+        );
+        ledger_transfer.initiated_by_user_id = Some(requested_by_user_id);
+        self.log_ledger_transfer(ledger_transfer);
+
+        Ok(block_index)
+    }
+
+    fn log_ledger_transfer(&self, ledger_transfer: LedgerTransfer) {
+        let block_index = ledger_transfer.block_index.clone();
+        let ledger_canister_id = ledger_transfer.ledger_canister_id;
+
+        // the transfer has been executed anyway, so the error is only logged
+        if let Err(err) = self
+            .ledger_transfer_repository
+            .create_ledger_transfer(ledger_transfer)
+        {
+            println!(
+                "Error: failed to log the transfer of block {} on ledger {}: {}",
+                block_index, ledger_canister_id, err
+            );
         }
     }
 }
 
-impl<U: UserProfileRepository> WalletServiceImpl<U> {
-    pub fn new(user_profile_repository: U) -> Self {
-        Self {
-            user_profile_repository,
+fn validate_transfer_token_request(
+    reason: &str,
+    to: &Result<Account, ApiError>,
+    amount: Option<u128>,
+    from_subaccount: &Result<Option<[u8; 32]>, ApiError>,
+) -> Result<(), ApiError> {
+    if reason.is_empty() {
+        return Err(ApiError::invalid_argument("Reason is required"));
+    }
+    if reason.chars().count() > MAX_MANUAL_TRANSFER_REASON_LENGTH {
+        return Err(ApiError::invalid_argument(&format!(
+            "Reason must be at most {} characters long",
+            MAX_MANUAL_TRANSFER_REASON_LENGTH
+        )));
+    }
+    let to = to.as_ref().map_err(Clone::clone)?;
+    if to.owner == Principal::anonymous() {
+        return Err(ApiError::invalid_argument("Recipient cannot be anonymous"));
+    }
+    if amount.is_none() {
+        return Err(ApiError::invalid_argument("Invalid amount"));
+    }
+    from_subaccount.as_ref().map_err(Clone::clone)?;
+
+    Ok(())
+}
+
+/// Looks up a transfer in the ledger's transactions, newest first,
+/// for the transfers that the ledger can't deduplicate anymore.
+/// Returns the block index of the transfer, or `None` if the ledger didn't execute it.
+/// Fails if the transactions could not be fetched, or if there are too many transactions
+/// since the transfer was created to look it up.
+pub(crate) async fn find_ledger_transfer(
+    sent_transfer: &SentLedgerTransfer,
+) -> Result<Option<Nat>, ApiError> {
+    let ledger_canister = LedgerCanisterService(sent_transfer.ledger_canister_id);
+    // the ledger executes a transfer only once its time reaches
+    // the `created_at_time` of the transfer minus the permitted drift
+    let min_timestamp = sent_transfer
+        .created_at_time
+        .saturating_sub(LEDGER_PERMITTED_DRIFT_NANOS);
+
+    let log_length = ledger_canister
+        .get_transactions(GetTransactionsRequest {
+            start: 0u64.into(),
+            length: 0u64.into(),
+        })
+        .await
+        .map_err(|e| {
+            ApiError::ledger_call_failed(sent_transfer.ledger_canister_id, &e.to_string())
+        })?
+        .log_length;
+    let mut end = u64::try_from(log_length.0).map_err(|_| {
+        ApiError::internal(&format!(
+            "Invalid log length returned by ledger canister {}",
+            sent_transfer.ledger_canister_id
+        ))
+    })?;
+    let min_start = end.saturating_sub(MAX_LOOKED_UP_LEDGER_TRANSACTIONS);
+
+    while end > 0 {
+        if end <= min_start {
+            return Err(ApiError::conflict(&format!(
+                "Transfer not found in the last {} transactions of ledger canister {}, it must be reconciled manually",
+                MAX_LOOKED_UP_LEDGER_TRANSACTIONS, sent_transfer.ledger_canister_id
+            )));
+        }
+
+        let start = end
+            .saturating_sub(LEDGER_TRANSACTIONS_PAGE_SIZE)
+            .max(min_start);
+        let transactions = get_ledger_transactions(&ledger_canister, start, end - start).await?;
+        if transactions.is_empty() {
+            return Err(ApiError::internal(&format!(
+                "Ledger canister {} returned no transactions from block {}",
+                sent_transfer.ledger_canister_id, start
+            )));
         }
+
+        if let Some((block_index, _)) = transactions
+            .iter()
+            .rev()
+            .find(|(_, transaction)| sent_transfer.matches(transaction))
+        {
+            println!(
+                "Transfer found in the ledger transactions. Block index: {}",
+                block_index
+            );
+            return Ok(Some((*block_index).into()));
+        }
+
+        // the transactions are sorted by timestamp, so the older ones can't be the transfer
+        if transactions
+            .first()
+            .is_some_and(|(_, transaction)| transaction.timestamp < min_timestamp)
+        {
+            break;
+        }
+        end = start;
+    }
+
+    println!("Transfer not found in the ledger transactions");
+    Ok(None)
+}
+
+/// Fetches the ledger transactions in the range, including the archived ones,
+/// sorted by block index.
+async fn get_ledger_transactions(
+    ledger_canister: &LedgerCanisterService,
+    start: u64,
+    length: u64,
+) -> Result<Vec<(u64, Transaction)>, ApiError> {
+    let ledger_canister_id = ledger_canister.0;
+    let call_failed =
+        |e: ic_cdk::call::Error| ApiError::ledger_call_failed(ledger_canister_id, &e.to_string());
+    let invalid_block_index = |_| {
+        ApiError::internal(&format!(
+            "Invalid block index returned by ledger canister {}",
+            ledger_canister_id
+        ))
+    };
+
+    let response = ledger_canister
+        .get_transactions(GetTransactionsRequest {
+            start: start.into(),
+            length: length.into(),
+        })
+        .await
+        .map_err(call_failed)?;
+
+    let mut transactions = Vec::new();
+    for archived_range in response.archived_transactions {
+        let archived_start =
+            u64::try_from(archived_range.start.0.clone()).map_err(invalid_block_index)?;
+        let archived_transactions = get_archived_transactions(
+            &archived_range.callback,
+            GetTransactionsRequest {
+                start: archived_range.start,
+                length: archived_range.length,
+            },
+        )
+        .await
+        .map_err(call_failed)?
+        .transactions;
+        transactions.extend((archived_start..).zip(archived_transactions));
+    }
+    let first_index = u64::try_from(response.first_index.0).map_err(invalid_block_index)?;
+    transactions.extend((first_index..).zip(response.transactions));
+
+    transactions.sort_by_key(|(block_index, _)| *block_index);
+    Ok(transactions)
+}
+
+fn canister_account(subaccount: Option<[u8; 32]>) -> Account {
+    Account {
+        owner: ic_cdk::api::canister_self(),
+        subaccount,
+    }
+}
+
+/// The errors of `icrc1_transfer` and `icrc2_transfer_from`,
+/// to handle the duplicates and the rejections of both in the same way.
+pub(crate) enum LedgerTransferError {
+    Duplicate { duplicate_of: Nat },
+    Rejected(LedgerTransferRejection),
+}
+
+impl From<TransferError> for LedgerTransferError {
+    fn from(err: TransferError) -> Self {
+        let rejection = match err {
+            TransferError::Duplicate { duplicate_of } => return Self::Duplicate { duplicate_of },
+            TransferError::BadFee { expected_fee } => {
+                LedgerTransferRejection::BadFee { expected_fee }
+            }
+            TransferError::BadBurn { min_burn_amount } => {
+                LedgerTransferRejection::BadBurn { min_burn_amount }
+            }
+            TransferError::InsufficientFunds { balance } => {
+                LedgerTransferRejection::InsufficientFunds { balance }
+            }
+            TransferError::TooOld => LedgerTransferRejection::TooOld,
+            TransferError::CreatedInFuture { ledger_time } => {
+                LedgerTransferRejection::CreatedInFuture { ledger_time }
+            }
+            TransferError::TemporarilyUnavailable => {
+                LedgerTransferRejection::TemporarilyUnavailable
+            }
+            TransferError::GenericError {
+                error_code,
+                message,
+            } => LedgerTransferRejection::GenericError {
+                error_code,
+                message,
+            },
+        };
+        Self::Rejected(rejection)
+    }
+}
+
+impl From<TransferFromError> for LedgerTransferError {
+    fn from(err: TransferFromError) -> Self {
+        let rejection = match err {
+            TransferFromError::Duplicate { duplicate_of } => {
+                return Self::Duplicate { duplicate_of }
+            }
+            TransferFromError::BadFee { expected_fee } => {
+                LedgerTransferRejection::BadFee { expected_fee }
+            }
+            TransferFromError::BadBurn { min_burn_amount } => {
+                LedgerTransferRejection::BadBurn { min_burn_amount }
+            }
+            TransferFromError::InsufficientFunds { balance } => {
+                LedgerTransferRejection::InsufficientFunds { balance }
+            }
+            TransferFromError::InsufficientAllowance { allowance } => {
+                LedgerTransferRejection::InsufficientAllowance { allowance }
+            }
+            TransferFromError::TooOld => LedgerTransferRejection::TooOld,
+            TransferFromError::CreatedInFuture { ledger_time } => {
+                LedgerTransferRejection::CreatedInFuture { ledger_time }
+            }
+            TransferFromError::TemporarilyUnavailable => {
+                LedgerTransferRejection::TemporarilyUnavailable
+            }
+            TransferFromError::GenericError {
+                error_code,
+                message,
+            } => LedgerTransferRejection::GenericError {
+                error_code,
+                message,
+            },
+        };
+        Self::Rejected(rejection)
     }
 }
@@ -2,27 +2,45 @@ use std::{collections::HashSet, path::Path, time::Duration};
 
 use backend_api::{
     ApiError, CreateWheelAssetRequest, CreateWheelAssetResponse, CreateWheelAssetTypeConfig,
-    DeleteWheelAssetRequest, ListWheelAssetsRequest, ListWheelAssetsResponse,
-    ListWheelPrizesResponse, UpdateWheelAssetImageConfig, UpdateWheelAssetImageRequest,
+    DeleteWheelAssetRequest, LedgerTransferRejection, ListWheelAssetsRequest,
+    ListWheelAssetsResponse, ListWheelPrizesResponse, RetireWheelAssetRequest,
+    RetireWheelAssetResponse, UpdateWheelAssetImageConfig, UpdateWheelAssetImageRequest,
     UpdateWheelAssetRequest, UpdateWheelAssetTypeConfig, UpdateWheelPrizesOrderRequest,
-    WheelAssetImageConfig, WheelAssetUiSettings,
+    WheelAssetDrawWeight, WheelAssetGadgetVariantConfig, WheelAssetImageConfig,
+    WheelAssetUiSettings,
 };
+use candid::{Nat, Principal};
 use external_canisters::{ledger::LedgerCanisterService, xrc::ExchangeRateCanisterService};
-use ic_cdk::{futures::spawn, println};
-use ic_cdk_timers::set_timer;
+use ic_cdk::println;
 use ic_xrc_types::{Asset, AssetClass, GetExchangeRateRequest};
-use icrc_ledger_types::icrc1::account::Account;
+use icrc_ledger_types::{
+    icrc1::{
+        account::{Account, Subaccount},
+        transfer::TransferArg,
+    },
+    icrc2::allowance::AllowanceArgs,
+};
 use lazy_static::lazy_static;
 use regex::Regex;
 
 use crate::{
-    mappings::{into_wheel_asset_ids, map_wheel_asset, map_wheel_prize},
+    mappings::{
+        into_account, into_wheel_asset_gadget_variants, into_wheel_asset_ids, map_wheel_asset,
+        map_wheel_prize,
+    },
     repositories::{
-        ckbtc_wheel_asset, cketh_wheel_asset, ckusdc_wheel_asset, icp_wheel_asset, HttpAsset,
-        HttpAssetRepository, HttpAssetRepositoryImpl, WheelAsset, WheelAssetId,
-        WheelAssetRepository, WheelAssetRepositoryImpl, WheelAssetState, WheelAssetTokenBalance,
-        WheelAssetTokenPrice, WheelAssetType, CACHE_CONTROL_HEADER_NAME, ONE_WEEK_CACHE_CONTROL,
+        ckbtc_wheel_asset, cketh_wheel_asset, ckusdc_wheel_asset, get_current_date_time,
+        icp_wheel_asset, EventId, EventRepository, EventRepositoryImpl, HttpAsset,
+        HttpAssetRepository, HttpAssetRepositoryImpl, Icrc1MetadataEntries, LedgerMetadata,
+        LedgerMetadataRepository, LedgerMetadataRepositoryImpl, LedgerTransfer,
+        LedgerTransferRepository, LedgerTransferRepositoryImpl, WheelAsset, WheelAssetId,
+        WheelAssetRepository, WheelAssetRepositoryImpl, WheelAssetRetirement, WheelAssetState,
+        WheelAssetTokenBalance, WheelAssetTokenFundingSource, WheelAssetTokenPrice, WheelAssetType,
+        WheelPrizeExtractionRepository, WheelPrizeExtractionRepositoryImpl,
+        WheelPrizeExtractionState, CACHE_CONTROL_HEADER_NAME, ONE_WEEK_CACHE_CONTROL,
     },
+    services::{find_ledger_transfer, LedgerTransferError},
+    system_api::{spawn_with_retry, RetryPolicy},
 };
 
 const WHEEL_ASSET_NAME_MAX_LENGTH: usize = 100;
@@ -38,6 +56,21 @@ lazy_static! {
 }
 const MINIMUM_WHEEL_ASSET_JACKPOT_ASSET_IDS_COUNT: usize = 2;
 const MAXIMUM_WHEEL_ASSET_JACKPOT_ASSET_IDS_COUNT: usize = 4;
+/// The maximum number of variants of a gadget asset
+const MAXIMUM_WHEEL_ASSET_GADGET_VARIANTS_COUNT: usize = 50;
+/// Retries the token data fetchers for about 15 minutes,
+/// the data is fetched again by the periodic job anyway
+const TOKEN_DATA_FETCH_RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 5,
+    initial_delay: Duration::from_secs(60),
+};
+/// The minimum fixed draw weight for a wheel asset
+const MINIMUM_WHEEL_ASSET_DRAW_WEIGHT: u32 = 1;
+/// The maximum fixed draw weight for a wheel asset
+const MAXIMUM_WHEEL_ASSET_DRAW_WEIGHT: u32 = 10_000;
+/// The ledger fee and decimals rarely change, and the balance fetcher
+/// refreshes them every hour, so that each ledger is called once for all its assets
+const LEDGER_METADATA_MAX_AGE_SECONDS: u64 = 30 * 60;
 
 #[cfg_attr(test, mockall::automock)]
 pub trait WheelAssetService {
@@ -52,7 +85,13 @@ pub trait WheelAssetService {
 
     fn schedule_token_data_fetchers(&self, asset_id: WheelAssetId, asset_type: WheelAssetType);
 
-    fn create_wheel_asset(
+    /// Fetches and saves the USD price of the given token asset,
+    /// returning the new price.
+    async fn refresh_token_usd_price(&self, asset_id: WheelAssetId) -> Result<f64, ApiError>;
+
+    /// Token assets take their metadata from the ledger, rejecting decimals
+    /// that don't match the ledger's ones. The ledger's logo, if any, becomes the wheel image.
+    async fn create_wheel_asset(
         &self,
         asset: CreateWheelAssetRequest,
     ) -> Result<CreateWheelAssetResponse, ApiError>;
@@ -61,6 +100,14 @@ pub trait WheelAssetService {
 
     fn delete_wheel_asset(&self, request: DeleteWheelAssetRequest) -> Result<(), ApiError>;
 
+    /// Transfers the remaining balance of a token asset, minus the fee, to the given account
+    /// and then deletes the asset. The transfer is stored before calling the ledger,
+    /// so an interrupted retirement is resumed by calling this method again.
+    async fn retire_wheel_asset(
+        &self,
+        request: RetireWheelAssetRequest,
+    ) -> Result<RetireWheelAssetResponse, ApiError>;
+
     fn update_wheel_asset_image(
         &self,
         request: UpdateWheelAssetImageRequest,
@@ -74,22 +121,52 @@ pub trait WheelAssetService {
     ) -> Result<(), ApiError>;
 }
 
-pub struct WheelAssetServiceImpl<W: WheelAssetRepository, H: HttpAssetRepository> {
+pub struct WheelAssetServiceImpl<
+    W: WheelAssetRepository,
+    H: HttpAssetRepository,
+    E: EventRepository,
+    P: WheelPrizeExtractionRepository,
+    L: LedgerMetadataRepository,
+    T: LedgerTransferRepository,
+> {
     wheel_asset_repository: W,
     http_asset_repository: H,
+    event_repository: E,
+    wheel_prize_extraction_repository: P,
+    ledger_metadata_repository: L,
+    ledger_transfer_repository: T,
 }
 
-impl Default for WheelAssetServiceImpl<WheelAssetRepositoryImpl, HttpAssetRepositoryImpl> {
+impl Default
+    for WheelAssetServiceImpl<
+        WheelAssetRepositoryImpl,
+        HttpAssetRepositoryImpl,
+        EventRepositoryImpl,
+        WheelPrizeExtractionRepositoryImpl,
+        LedgerMetadataRepositoryImpl,
+        LedgerTransferRepositoryImpl,
+    >
+{
     fn default() -> Self {
         Self::new(
             WheelAssetRepositoryImpl::default(),
             HttpAssetRepositoryImpl::default(),
+            EventRepositoryImpl::default(),
+            WheelPrizeExtractionRepositoryImpl::default(),
+            LedgerMetadataRepositoryImpl::default(),
+            LedgerTransferRepositoryImpl::default(),
         )
     }
 }
 
-impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetService
-    for WheelAssetServiceImpl<W, H>
+impl<
+        W: WheelAssetRepository,
+        H: HttpAssetRepository,
+        E: EventRepository,
+        P: WheelPrizeExtractionRepository,
+        L: LedgerMetadataRepository,
+        T: LedgerTransferRepository,
+    > WheelAssetService for WheelAssetServiceImpl<W, H, E, P, L, T>
 {
     fn list_wheel_assets(
         &self,
@@ -104,7 +181,10 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetService
             None => self.wheel_asset_repository.list_wheel_assets(),
         }
         .into_iter()
-        .map(|(id, asset)| map_wheel_asset(id, asset))
+        .map(|(id, asset)| {
+            let jackpot_components = self.wheel_asset_repository.list_jackpot_components(&asset);
+            map_wheel_asset(id, asset, &jackpot_components)
+        })
         .collect();
 
         Ok(items)
@@ -166,49 +246,121 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetService
         self.schedule_price_fetcher(asset_id, asset_type);
     }
 
-    fn create_wheel_asset(
+    async fn refresh_token_usd_price(&self, asset_id: WheelAssetId) -> Result<f64, ApiError> {
+        let wheel_asset = self.get_wheel_asset(&asset_id)?;
+        if !wheel_asset.asset_type.should_fetch_usd_price() {
+            return Err(ApiError::invalid_argument(&format!(
+                "Wheel asset with id {} does not have an exchange rate symbol",
+                asset_id
+            )));
+        }
+
+        self.fetch_and_save_token_price(asset_id, wheel_asset.asset_type)
+            .await?;
+
+        match self.get_wheel_asset(&asset_id)?.asset_type {
+            WheelAssetType::Token {
+                usd_price: Some(usd_price),
+                ..
+            } => Ok(usd_price.usd_price),
+            _ => Err(ApiError::internal(&format!(
+                "Wheel asset with id {} has no USD price after fetching it",
+                asset_id
+            ))),
+        }
+    }
+
+    async fn create_wheel_asset(
         &self,
         request: CreateWheelAssetRequest,
     ) -> Result<CreateWheelAssetResponse, ApiError> {
         self.validate_create_wheel_asset_request(&request)?;
 
-        let wheel_asset_type = request.asset_type_config.try_into()?;
-
-        if let WheelAssetType::Token {
-            ref ledger_config, ..
-        } = wheel_asset_type
-        {
-            if self
-                .wheel_asset_repository
-                .list_wheel_assets_by_type(&wheel_asset_type)?
-                .iter()
-                .any(|(_, asset)| {
-                    asset
-                        .asset_type
-                        .ledger_config()
-                        .map(|config| config.ledger_canister_id == ledger_config.ledger_canister_id)
-                        .unwrap_or(false)
-                })
-            {
-                return Err(ApiError::invalid_argument(&format!(
-                    "Token asset with ledger canister ID {} already exists",
-                    ledger_config.ledger_canister_id
+        let event_id = request
+            .event_id
+            .map(|id| EventId::try_from(id.as_str()))
+            .transpose()?;
+        if let Some(event_id) = event_id {
+            if self.event_repository.get_event(&event_id).is_none() {
+                return Err(ApiError::not_found(&format!(
+                    "Event with id {} not found",
+                    event_id
                 )));
             }
         }
 
+        let requested_decimals = match &request.asset_type_config {
+            CreateWheelAssetTypeConfig::Token { ledger_config, .. }
+            | CreateWheelAssetTypeConfig::TokenFixedAmount { ledger_config, .. } => {
+                ledger_config.decimals
+            }
+            CreateWheelAssetTypeConfig::Gadget { .. }
+            | CreateWheelAssetTypeConfig::Jackpot { .. } => None,
+        };
+        let mut wheel_asset_type: WheelAssetType = request.asset_type_config.try_into()?;
+        let mut ledger_logo = None;
+
+        match wheel_asset_type {
+            // each new token asset pays from its own subaccount, so funds are never shared
+            WheelAssetType::Token {
+                ref ledger_config, ..
+            } => {
+                let ledger_canister_id = ledger_config.ledger_canister_id;
+                let ledger_metadata = self.get_ledger_metadata(ledger_canister_id).await?;
+                if let Some(requested_decimals) = requested_decimals {
+                    self.validate_wheel_asset_token_decimals(
+                        &ledger_canister_id,
+                        requested_decimals,
+                        &ledger_metadata,
+                    )?;
+                }
+                wheel_asset_type.set_ledger_metadata(&ledger_metadata);
+                ledger_logo = ledger_metadata.logo_image();
+            }
+            WheelAssetType::Jackpot {
+                ref wheel_asset_ids,
+            } => {
+                self.validate_jackpot_wheel_assets_event(wheel_asset_ids, event_id)?;
+                // jackpots are created enabled
+                self.validate_jackpot_wheel_assets_enabled(wheel_asset_ids)?;
+            }
+            WheelAssetType::Gadget { .. } => {}
+        }
+
         let wheel_asset = WheelAsset::new_enabled(
             request.name,
             wheel_asset_type,
             request.total_amount,
             request.wheel_ui_settings.map(Into::into),
+            request.draw_weight.map(Into::into),
+            event_id,
         );
 
         let id = self
             .wheel_asset_repository
-            .create_wheel_asset(wheel_asset.clone())?;
+            .create_wheel_asset(wheel_asset)?;
 
-        Ok(map_wheel_asset(id, wheel_asset))
+        if let Some((content_type, content_bytes)) = ledger_logo {
+            // the logo is just a default, so the asset is created even if the logo can't be used
+            if let Err(err) = self.update_wheel_asset_image(UpdateWheelAssetImageRequest {
+                id: id.to_string(),
+                image_config: UpdateWheelAssetImageConfig::Wheel(WheelAssetImageConfig {
+                    content_bytes,
+                    content_type,
+                }),
+            }) {
+                println!(
+                    "create_wheel_asset: Failed to set the ledger logo as the wheel image of asset {}: {}",
+                    id, err
+                );
+            }
+        }
+
+        let wheel_asset = self.get_wheel_asset(&id)?;
+        let jackpot_components = self
+            .wheel_asset_repository
+            .list_jackpot_components(&wheel_asset);
+        Ok(map_wheel_asset(id, wheel_asset, &jackpot_components))
     }
 
     fn update_wheel_asset(&self, request: UpdateWheelAssetRequest) -> Result<(), ApiError> {
@@ -233,44 +385,102 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetService
 
         if let Some(state) = request.state {
             existing_asset.state = state.into();
+            if !existing_asset.is_enabled() {
+                self.assert_wheel_asset_not_in_enabled_jackpot(&asset_id)?;
+            }
         }
 
+        let mut funding_source_changed = false;
         if let Some(asset_type_config) = request.asset_type_config {
             match (asset_type_config, &mut existing_asset.asset_type) {
                 (
                     UpdateWheelAssetTypeConfig::Token {
                         exchange_rate_symbol: new_exchange_rate_symbol,
                         prize_usd_amount: new_prize_usd_amount,
+                        prize_token_amount: new_prize_token_amount,
                         ledger_config: new_ledger_config,
+                        funding_source: new_funding_source,
                     },
                     WheelAssetType::Token {
                         prize_usd_amount: existing_prize_usd_amount,
+                        prize_token_amount: existing_prize_token_amount,
                         exchange_rate_symbol: existing_exchange_rate_symbol,
                         ledger_config: existing_ledger_config,
+                        funding_source: existing_funding_source,
+                        balance: existing_balance,
+                        balance_fetch_status: existing_balance_fetch_status,
                         ..
                     },
                 ) => {
+                    // setting one of the prize amounts switches the prize to that denomination
                     if let Some(new_prize_usd_amount) = new_prize_usd_amount {
                         *existing_prize_usd_amount = new_prize_usd_amount;
+                        *existing_prize_token_amount = None;
+                    }
+                    if let Some(new_prize_token_amount) = new_prize_token_amount {
+                        *existing_prize_token_amount = Some(new_prize_token_amount);
                     }
                     if let Some(new_exchange_rate_symbol) = new_exchange_rate_symbol {
                         *existing_exchange_rate_symbol = Some(new_exchange_rate_symbol);
                     }
                     if let Some(new_ledger_config) = new_ledger_config {
                         if let Some(new_decimals) = new_ledger_config.decimals {
+                            // the cached decimals are fetched again by the balance fetcher anyway
+                            if let Some(ledger_metadata) = self
+                                .ledger_metadata_repository
+                                .get_ledger_metadata(&existing_ledger_config.ledger_canister_id)
+                            {
+                                self.validate_wheel_asset_token_decimals(
+                                    &existing_ledger_config.ledger_canister_id,
+                                    new_decimals,
+                                    &ledger_metadata,
+                                )?;
+                            }
                             existing_ledger_config.decimals = new_decimals;
                         }
                     }
+                    if let Some(new_funding_source) = new_funding_source {
+                        let new_funding_source = new_funding_source.try_into()?;
+                        if existing_funding_source
+                            .as_ref()
+                            .unwrap_or(&WheelAssetTokenFundingSource::CanisterAccount)
+                            != &new_funding_source
+                        {
+                            *existing_funding_source = Some(new_funding_source);
+                            // the balance was fetched from the previous funding source
+                            *existing_balance = None;
+                            *existing_balance_fetch_status = None;
+                            funding_source_changed = true;
+                        }
+                    }
                 }
                 (
                     UpdateWheelAssetTypeConfig::Gadget {
                         article_type: new_article_type,
+                        variants: new_variants,
                     },
                     WheelAssetType::Gadget {
                         article_type: existing_article_type,
+                        variants: existing_variants,
                     },
                 ) => {
                     *existing_article_type = new_article_type;
+                    if let Some(new_variants) = new_variants {
+                        let new_variants = into_wheel_asset_gadget_variants(
+                            new_variants,
+                            existing_variants.as_deref().unwrap_or_default(),
+                        );
+                        if let Some(variant) = new_variants
+                            .iter()
+                            .find(|variant| variant.total_amount < variant.handed_over_amount)
+                        {
+                            return Err(ApiError::invalid_argument(&format!(
+                                "Total amount of gadget variant {} must be at least {}, the amount already handed over",
+                                variant.name, variant.handed_over_amount
+                            )));
+                        }
+                        *existing_variants = Some(new_variants);
+                    }
                 }
                 (
                     UpdateWheelAssetTypeConfig::Jackpot {
@@ -280,7 +490,12 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetService
                         wheel_asset_ids: existing_wheel_asset_ids,
                     },
                 ) => {
-                    *existing_wheel_asset_ids = into_wheel_asset_ids(new_wheel_asset_ids)?;
+                    let new_wheel_asset_ids = into_wheel_asset_ids(new_wheel_asset_ids)?;
+                    self.validate_jackpot_wheel_assets_event(
+                        &new_wheel_asset_ids,
+                        existing_asset.event_id,
+                    )?;
+                    *existing_wheel_asset_ids = new_wheel_asset_ids;
                 }
                 _ => {
                     return Err(ApiError::invalid_argument(
@@ -290,12 +505,31 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetService
             }
         }
 
+        if funding_source_changed {
+            self.assert_token_funds_not_shared(&asset_id, &existing_asset.asset_type)?;
+        }
+
+        if existing_asset.is_enabled() {
+            self.validate_jackpot_wheel_assets_enabled(existing_asset.jackpot_wheel_asset_ids())?;
+        }
+
         if let Some(wheel_ui_settings) = request.wheel_ui_settings {
             existing_asset.wheel_ui_settings = wheel_ui_settings.into();
         }
 
+        if let Some(draw_weight) = request.draw_weight {
+            existing_asset.draw_weight = Some(draw_weight.into());
+        }
+
+        let asset_type = existing_asset.asset_type.clone();
         self.wheel_asset_repository
-            .update_wheel_asset(asset_id, existing_asset)
+            .update_wheel_asset(asset_id, existing_asset)?;
+
+        if funding_source_changed {
+            self.schedule_balance_fetcher(asset_id, asset_type);
+        }
+
+        Ok(())
     }
 
     fn delete_wheel_asset(&self, request: DeleteWheelAssetRequest) -> Result<(), ApiError> {
@@ -304,19 +538,100 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetService
         let existing_asset = self.get_wheel_asset(&asset_id)?;
 
         if existing_asset.is_token() {
-            // TODO: implement token deletion once we know what to do with the remaining balance
-            return Err(ApiError::invalid_argument("Cannot delete token asset"));
+            // the remaining balance must be withdrawn first
+            return Err(ApiError::invalid_argument(
+                "Cannot delete token asset, retire it instead",
+            ));
         }
+        self.assert_wheel_asset_not_in_enabled_jackpot(&asset_id)?;
 
-        if let Some(path) = &existing_asset.modal_image_path {
-            self.http_asset_repository.delete_http_asset(path)?;
+        self.delete_wheel_asset_and_images(&asset_id, &existing_asset)
+    }
+
+    async fn retire_wheel_asset(
+        &self,
+        request: RetireWheelAssetRequest,
+    ) -> Result<RetireWheelAssetResponse, ApiError> {
+        let asset_id = WheelAssetId::try_from(request.id.as_str())?;
+        let to = into_account(request.to)?;
+
+        let mut existing_asset = self.get_wheel_asset(&asset_id)?;
+
+        let Some(ledger_canister_id) = existing_asset
+            .asset_type
+            .ledger_config()
+            .map(|config| config.ledger_canister_id)
+        else {
+            return Err(ApiError::invalid_argument(
+                "Only token assets can be retired, delete the asset instead",
+            ));
+        };
+        if let Some(retirement) = &existing_asset.retirement {
+            if retirement.to != to {
+                return Err(ApiError::conflict(&format!(
+                    "Wheel asset with id {} is already being retired to {}",
+                    asset_id, retirement.to
+                )));
+            }
         }
-        if let Some(path) = &existing_asset.wheel_image_path {
-            self.http_asset_repository.delete_http_asset(path)?;
+        self.assert_wheel_asset_not_in_enabled_jackpot(&asset_id)?;
+        self.assert_wheel_asset_has_no_unsettled_payouts(&asset_id)?;
+
+        // the asset must not be drawn while its balance is withdrawn
+        if existing_asset.is_enabled() {
+            existing_asset.state = WheelAssetState::Disabled;
+            self.wheel_asset_repository
+                .update_wheel_asset(asset_id, existing_asset.clone())?;
+        }
+
+        // the funds of a sponsored asset never left the sponsor's account
+        if existing_asset.asset_type.token_sponsor_account().is_some() {
+            self.delete_wheel_asset_and_images(&asset_id, &existing_asset)?;
+            return Ok(RetireWheelAssetResponse { block_index: None });
+        }
+
+        let retirement = match existing_asset.retirement {
+            // resume the interrupted retirement with the same transfer
+            Some(retirement) => retirement,
+            None => {
+                let from_subaccount = existing_asset.asset_type.token_subaccount(&asset_id);
+                let Some(retirement) = self
+                    .prepare_wheel_asset_retirement(
+                        asset_id,
+                        ledger_canister_id,
+                        from_subaccount,
+                        to,
+                    )
+                    .await?
+                else {
+                    println!(
+                        "retire_wheel_asset: nothing to transfer for asset {}, deleting it",
+                        asset_id
+                    );
+                    let existing_asset = self.get_wheel_asset(&asset_id)?;
+                    self.delete_wheel_asset_and_images(&asset_id, &existing_asset)?;
+                    return Ok(RetireWheelAssetResponse { block_index: None });
+                };
+                retirement
+            }
+        };
+
+        let block_index = match retirement.block_index.clone() {
+            Some(block_index) => block_index,
+            None => {
+                self.transfer_wheel_asset_retirement(asset_id, ledger_canister_id, retirement)
+                    .await?
+            }
+        };
+
+        // the asset may have been deleted by a concurrent retirement of the same asset
+        if let Some(existing_asset) = self.wheel_asset_repository.get_wheel_asset(&asset_id) {
+            self.delete_wheel_asset_and_images(&asset_id, &existing_asset)?;
         }
-        self.http_asset_repository.certify_all_assets()?;
 
-        self.wheel_asset_repository.delete_wheel_asset(&asset_id)
+        Ok(RetireWheelAssetResponse {
+            block_index: Some(block_index),
+        })
     }
 
     fn update_wheel_asset_image(
@@ -368,14 +683,34 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetService
     }
 
     fn list_wheel_prizes(&self) -> Result<ListWheelPrizesResponse, ApiError> {
+        let event_id = self.event_repository.get_active_event().map(|(id, _)| id);
+
+        // the extraction draws among all the enabled assets of the active event,
+        // proportionally to their weight
+        let total_draw_weight = self
+            .wheel_asset_repository
+            .list_wheel_assets_by_state(WheelAssetState::Enabled)?
+            .iter()
+            .filter(|(_, wheel_asset)| wheel_asset.event_id == event_id)
+            .map(|(_, wheel_asset)| self.effective_draw_weight(wheel_asset) as u64)
+            .sum::<u64>();
+
         let prizes = self
             .wheel_asset_repository
             .get_wheel_prizes_order()
             .iter()
-            .map(|id| {
+            .filter_map(|id| {
                 // SAFETY: wheel asset with this id should always exists
                 let wheel_asset = self.wheel_asset_repository.get_wheel_asset(id).unwrap();
-                map_wheel_prize(*id, wheel_asset)
+                if wheel_asset.event_id != event_id {
+                    return None;
+                }
+                let draw_probability = if total_draw_weight > 0 {
+                    self.effective_draw_weight(&wheel_asset) as f64 / total_draw_weight as f64
+                } else {
+                    0.0
+                };
+                Some(map_wheel_prize(*id, wheel_asset, draw_probability))
             })
             .collect();
 
@@ -386,40 +721,78 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetService
         &self,
         request: UpdateWheelPrizesOrderRequest,
     ) -> Result<(), ApiError> {
-        // check that the provided wheel asset IDs match all the existing enabled assets
-        // and create a vector of `WheelAssetId`s
-        let ordered_ids = {
-            let enabled_wheel_assets = self
-                .wheel_asset_repository
-                .list_wheel_assets_by_state(WheelAssetState::Enabled)?;
+        let enabled_wheel_assets = self
+            .wheel_asset_repository
+            .list_wheel_assets_by_state(WheelAssetState::Enabled)?;
 
-            let mut ordered_ids = Vec::new();
-            for id in &request.wheel_asset_ids {
-                let id = WheelAssetId::try_from(id.as_str())?;
-                if !enabled_wheel_assets
-                    .iter()
-                    .any(|(asset_id, _)| *asset_id == id)
-                {
-                    return Err(ApiError::invalid_argument(&format!(
+        // check that the provided wheel asset IDs match existing enabled assets of the same event
+        // and create a vector of `WheelAssetId`s
+        let mut event_id = None;
+        let mut ordered_ids = Vec::new();
+        for (i, id) in request.wheel_asset_ids.iter().enumerate() {
+            let id = WheelAssetId::try_from(id.as_str())?;
+            let (_, wheel_asset) = enabled_wheel_assets
+                .iter()
+                .find(|(asset_id, _)| *asset_id == id)
+                .ok_or_else(|| {
+                    ApiError::invalid_argument(&format!(
                         "Wheel asset with ID {} does not exist or is not enabled",
                         id
-                    )));
-                }
-                ordered_ids.push(id);
+                    ))
+                })?;
+            if i == 0 {
+                event_id = wheel_asset.event_id;
+            } else if wheel_asset.event_id != event_id {
+                return Err(ApiError::invalid_argument(
+                    "All wheel assets must belong to the same event",
+                ));
             }
-            ordered_ids
-        };
+            ordered_ids.push(id);
+        }
+        if request.wheel_asset_ids.is_empty() {
+            event_id = self.event_repository.get_active_event().map(|(id, _)| id);
+        }
+
+        // each event only orders its own prizes, the other ones are kept as they are
+        let other_events_ids = self
+            .wheel_asset_repository
+            .get_wheel_prizes_order()
+            .into_iter()
+            .filter(|id| {
+                enabled_wheel_assets
+                    .iter()
+                    .any(|(asset_id, asset)| asset_id == id && asset.event_id != event_id)
+            });
 
         self.wheel_asset_repository
-            .update_wheel_prizes_order(ordered_ids)
+            .update_wheel_prizes_order(other_events_ids.chain(ordered_ids).collect())
     }
 }
 
-impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetServiceImpl<W, H> {
-    fn new(wheel_asset_repository: W, http_asset_repository: H) -> Self {
+impl<
+        W: WheelAssetRepository,
+        H: HttpAssetRepository,
+        E: EventRepository,
+        P: WheelPrizeExtractionRepository,
+        L: LedgerMetadataRepository,
+        T: LedgerTransferRepository,
+    > WheelAssetServiceImpl<W, H, E, P, L, T>
+{
+    fn new(
+        wheel_asset_repository: W,
+        http_asset_repository: H,
+        event_repository: E,
+        wheel_prize_extraction_repository: P,
+        ledger_metadata_repository: L,
+        ledger_transfer_repository: T,
+    ) -> Self {
         Self {
             wheel_asset_repository,
             http_asset_repository,
+            event_repository,
+            wheel_prize_extraction_repository,
+            ledger_metadata_repository,
+            ledger_transfer_repository,
         }
     }
 
@@ -429,6 +802,303 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetServiceImpl<W, H
             .ok_or_else(|| ApiError::not_found(&format!("Wheel asset with id {} not found", id)))
     }
 
+    fn delete_wheel_asset_and_images(
+        &self,
+        asset_id: &WheelAssetId,
+        wheel_asset: &WheelAsset,
+    ) -> Result<(), ApiError> {
+        if let Some(path) = &wheel_asset.modal_image_path {
+            self.http_asset_repository.delete_http_asset(path)?;
+        }
+        if let Some(path) = &wheel_asset.wheel_image_path {
+            self.http_asset_repository.delete_http_asset(path)?;
+        }
+        self.http_asset_repository.certify_all_assets()?;
+
+        self.wheel_asset_repository.delete_wheel_asset(asset_id)
+    }
+
+    /// The payouts of the asset that may still be transferred
+    /// would fail once its balance has been withdrawn.
+    fn assert_wheel_asset_has_no_unsettled_payouts(
+        &self,
+        wheel_asset_id: &WheelAssetId,
+    ) -> Result<(), ApiError> {
+        let unsettled_extraction_id = [
+            WheelPrizeExtractionState::Processing,
+            WheelPrizeExtractionState::PartiallyCompleted {
+                prize_usd_amount: None,
+                error: ApiError::internal(""),
+            },
+        ]
+        .iter()
+        .flat_map(|state| {
+            self.wheel_prize_extraction_repository
+                .list_wheel_prize_extractions_by_state(state)
+        })
+        .find(|(_, extraction)| extraction.has_unsettled_payout(wheel_asset_id))
+        .map(|(id, _)| id);
+
+        match unsettled_extraction_id {
+            Some(extraction_id) => Err(ApiError::conflict(&format!(
+                "Wheel asset with id {} has unsettled payouts in the wheel prize extraction with id {}",
+                wheel_asset_id, extraction_id
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Fetches the balance and the fee of the token and stores the retirement transfer,
+    /// unless a concurrent call already did. Returns `None` if there is nothing to transfer.
+    async fn prepare_wheel_asset_retirement(
+        &self,
+        asset_id: WheelAssetId,
+        ledger_canister_id: Principal,
+        from_subaccount: Option<Subaccount>,
+        to: Account,
+    ) -> Result<Option<WheelAssetRetirement>, ApiError> {
+        let ledger_canister = LedgerCanisterService(ledger_canister_id);
+
+        let balance = ledger_canister
+            .icrc1_balance_of(Account {
+                owner: ic_cdk::api::canister_self(),
+                subaccount: from_subaccount,
+            })
+            .await
+            .map_err(|err| ApiError::ledger_call_failed(ledger_canister_id, &err.to_string()))?;
+        let fee = ledger_canister
+            .icrc1_fee()
+            .await
+            .map_err(|err| ApiError::ledger_call_failed(ledger_canister_id, &err.to_string()))?;
+        let fee = u128::try_from(fee.0).map_err(|_| {
+            ApiError::internal(&format!("Invalid fee from ledger {}", ledger_canister_id))
+        })?;
+
+        let mut existing_asset = self.get_wheel_asset(&asset_id)?;
+        if let Some(retirement) = existing_asset.retirement {
+            return Ok(Some(retirement));
+        }
+
+        let retirement = WheelAssetRetirement::new(
+            asset_id,
+            from_subaccount,
+            to,
+            balance,
+            fee,
+            ic_cdk::api::time(),
+        );
+        if let Some(retirement) = &retirement {
+            existing_asset.retirement = Some(retirement.clone());
+            self.wheel_asset_repository
+                .update_wheel_asset(asset_id, existing_asset)?;
+        }
+
+        Ok(retirement)
+    }
+
+    /// Executes the stored retirement transfer and records its block index.
+    /// If the ledger rejects the transfer, the retirement is discarded,
+    /// so that the next attempt fetches the balance and the fee again,
+    /// unless a previous call had an unknown outcome: then the retirement is kept,
+    /// and if the ledger can't deduplicate it anymore it's looked up in the ledger's transactions.
+    async fn transfer_wheel_asset_retirement(
+        &self,
+        asset_id: WheelAssetId,
+        ledger_canister_id: Principal,
+        retirement: WheelAssetRetirement,
+    ) -> Result<Nat, ApiError> {
+        println!(
+            "Transferring remaining balance of wheel asset {}. Ledger canister id: {}, To: {}, Amount: {}, Fee: {}",
+            asset_id, ledger_canister_id, retirement.to, retirement.amount, retirement.fee
+        );
+
+        let result = match LedgerCanisterService(ledger_canister_id)
+            .icrc1_transfer(TransferArg {
+                from_subaccount: retirement.from_subaccount,
+                to: retirement.to,
+                fee: Some(retirement.fee.into()),
+                created_at_time: Some(retirement.created_at_time),
+                memo: Some(retirement.memo.clone().into()),
+                amount: retirement.amount.into(),
+            })
+            .await
+        {
+            Ok(Ok(block_index)) => Ok(block_index),
+            Ok(Err(err)) => match LedgerTransferError::from(err) {
+                LedgerTransferError::Duplicate { duplicate_of } => Ok(duplicate_of),
+                LedgerTransferError::Rejected(LedgerTransferRejection::TooOld)
+                    if retirement.is_outcome_unknown() =>
+                {
+                    println!(
+                        "Looking up the retirement transfer of wheel asset {} in the ledger",
+                        asset_id
+                    );
+                    match find_ledger_transfer(&retirement.sent_transfer(ledger_canister_id))
+                        .await?
+                    {
+                        Some(block_index) => Ok(block_index),
+                        None => Err(ApiError::ledger_transfer_rejected(
+                            ledger_canister_id,
+                            LedgerTransferRejection::TooOld,
+                        )),
+                    }
+                }
+                LedgerTransferError::Rejected(rejection) => Err(
+                    ApiError::ledger_transfer_rejected(ledger_canister_id, rejection),
+                ),
+            },
+            Err(err) => Err(ApiError::ledger_call_failed(
+                ledger_canister_id,
+                &err.to_string(),
+            )),
+        };
+
+        let mut existing_asset = self.get_wheel_asset(&asset_id)?;
+        let is_same_retirement =
+            existing_asset
+                .retirement
+                .as_ref()
+                .is_some_and(|existing_retirement| {
+                    existing_retirement.created_at_time == retirement.created_at_time
+                });
+        let block_index = match result {
+            Ok(block_index) => block_index,
+            Err(err) if err.is_ledger_call_failed() => {
+                // the ledger may have executed the transfer,
+                // so it must be retried with the same deduplication parameters
+                if let (true, Some(existing_retirement)) =
+                    (is_same_retirement, existing_asset.retirement.as_mut())
+                {
+                    existing_retirement.outcome_unknown = Some(true);
+                    self.wheel_asset_repository
+                        .update_wheel_asset(asset_id, existing_asset)?;
+                }
+                return Err(err);
+            }
+            Err(err) => {
+                // the ledger did not execute the rejected transfer, unless a previous call
+                // had an unknown outcome: a too old transfer has been looked up in that case
+                let not_executed =
+                    !retirement.is_outcome_unknown() || err.is_ledger_transfer_too_old();
+                if is_same_retirement && not_executed {
+                    existing_asset.retirement = None;
+                    self.wheel_asset_repository
+                        .update_wheel_asset(asset_id, existing_asset)?;
+                }
+                return Err(err);
+            }
+        };
+
+        println!(
+            "Remaining balance of wheel asset {} transferred successfully. Block index: {}",
+            asset_id, block_index
+        );
+
+        let mut ledger_transfer = LedgerTransfer::new(
+            ledger_canister_id,
+            Account {
+                owner: ic_cdk::api::canister_self(),
+                subaccount: retirement.from_subaccount,
+            },
+            retirement.to,
+            retirement.amount.into(),
+            Some(retirement.fee.into()),
+            Some(retirement.memo.clone()),
+            block_index.clone(),
+        );
+        ledger_transfer.wheel_asset_id = Some(asset_id);
+        // the transfer has been executed anyway, so the error is only logged
+        if let Err(err) = self
+            .ledger_transfer_repository
+            .create_ledger_transfer(ledger_transfer)
+        {
+            println!(
+                "Error: failed to log the retirement transfer of wheel asset {}: {}",
+                asset_id, err
+            );
+        }
+
+        existing_asset.retirement = Some(WheelAssetRetirement {
+            block_index: Some(block_index.clone()),
+            ..retirement
+        });
+        self.wheel_asset_repository
+            .update_wheel_asset(asset_id, existing_asset)?;
+
+        Ok(block_index)
+    }
+
+    fn effective_draw_weight(&self, wheel_asset: &WheelAsset) -> u32 {
+        let jackpot_components = self
+            .wheel_asset_repository
+            .list_jackpot_components(wheel_asset);
+        wheel_asset.effective_draw_weight(&jackpot_components)
+    }
+
+    /// An enabled jackpot can be drawn at any time, so the assets it pays out
+    /// must stay enabled as long as the jackpot is.
+    fn assert_wheel_asset_not_in_enabled_jackpot(
+        &self,
+        wheel_asset_id: &WheelAssetId,
+    ) -> Result<(), ApiError> {
+        let jackpot = self
+            .wheel_asset_repository
+            .list_wheel_assets_by_type(&WheelAssetType::Jackpot {
+                wheel_asset_ids: vec![],
+            })?
+            .into_iter()
+            .find(|(_, jackpot)| {
+                jackpot.is_enabled() && jackpot.jackpot_wheel_asset_ids().contains(wheel_asset_id)
+            });
+
+        match jackpot {
+            Some((jackpot_id, _)) => Err(ApiError::conflict(&format!(
+                "Wheel asset with id {} is referenced by the enabled jackpot with id {}",
+                wheel_asset_id, jackpot_id
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Token assets paying from the same account on the same ledger would share their funds,
+    /// so only one of them is allowed. This can only happen for assets using the default subaccount.
+    fn assert_token_funds_not_shared(
+        &self,
+        wheel_asset_id: &WheelAssetId,
+        wheel_asset_type: &WheelAssetType,
+    ) -> Result<(), ApiError> {
+        let (Some(ledger_config), Some(funding_source)) = (
+            wheel_asset_type.ledger_config(),
+            wheel_asset_type.token_funding_source(),
+        ) else {
+            return Ok(());
+        };
+
+        if self
+            .wheel_asset_repository
+            .list_wheel_assets_by_type(wheel_asset_type)?
+            .iter()
+            .any(|(id, asset)| {
+                id != wheel_asset_id
+                    && asset
+                        .asset_type
+                        .ledger_config()
+                        .map(|config| config.ledger_canister_id == ledger_config.ledger_canister_id)
+                        .unwrap_or(false)
+                    && asset.asset_type.token_funding_source() == Some(funding_source)
+                    && asset.asset_type.token_subaccount(id)
+                        == wheel_asset_type.token_subaccount(wheel_asset_id)
+            })
+        {
+            return Err(ApiError::invalid_argument(&format!(
+                "Token asset with ledger canister ID {} and the same funding source already exists",
+                ledger_config.ledger_canister_id
+            )));
+        }
+
+        Ok(())
+    }
+
     fn validate_create_wheel_asset_request(
         &self,
         request: &CreateWheelAssetRequest,
@@ -446,14 +1116,25 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetServiceImpl<W, H
             self.validate_wheel_ui_settings(wheel_ui_settings)?;
         }
 
+        if let Some(draw_weight) = &request.draw_weight {
+            self.validate_wheel_asset_draw_weight(draw_weight)?;
+        }
+
         match &request.asset_type_config {
             CreateWheelAssetTypeConfig::Token {
                 prize_usd_amount, ..
             } => self.validate_wheel_asset_token_prize_usd_amount(prize_usd_amount)?,
+            CreateWheelAssetTypeConfig::TokenFixedAmount {
+                prize_token_amount, ..
+            } => self.validate_wheel_asset_token_prize_token_amount(prize_token_amount)?,
             CreateWheelAssetTypeConfig::Jackpot { wheel_asset_ids } => {
                 self.validate_wheel_asset_jackpot_asset_ids(wheel_asset_ids)?
             }
-            CreateWheelAssetTypeConfig::Gadget { .. } => {}
+            CreateWheelAssetTypeConfig::Gadget { variants, .. } => {
+                if let Some(variants) = variants {
+                    self.validate_wheel_asset_gadget_variants(variants)?;
+                }
+            }
         }
 
         Ok(())
@@ -482,16 +1163,30 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetServiceImpl<W, H
         if let Some(asset_type_config) = &request.asset_type_config {
             match asset_type_config {
                 UpdateWheelAssetTypeConfig::Token {
-                    prize_usd_amount, ..
+                    prize_usd_amount,
+                    prize_token_amount,
+                    ..
                 } => {
+                    if prize_usd_amount.is_some() && prize_token_amount.is_some() {
+                        return Err(ApiError::invalid_argument(
+                            "Only one of prize USD amount and prize token amount can be set",
+                        ));
+                    }
                     if let Some(prize_usd_amount) = prize_usd_amount {
                         self.validate_wheel_asset_token_prize_usd_amount(prize_usd_amount)?;
                     }
+                    if let Some(prize_token_amount) = prize_token_amount {
+                        self.validate_wheel_asset_token_prize_token_amount(prize_token_amount)?;
+                    }
                 }
                 UpdateWheelAssetTypeConfig::Jackpot { wheel_asset_ids } => {
                     self.validate_wheel_asset_jackpot_asset_ids(wheel_asset_ids)?
                 }
-                UpdateWheelAssetTypeConfig::Gadget { .. } => {}
+                UpdateWheelAssetTypeConfig::Gadget { variants, .. } => {
+                    if let Some(variants) = variants {
+                        self.validate_wheel_asset_gadget_variants(variants)?;
+                    }
+                }
             }
         }
 
@@ -499,6 +1194,10 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetServiceImpl<W, H
             self.validate_wheel_ui_settings(wheel_ui_settings)?;
         }
 
+        if let Some(draw_weight) = &request.draw_weight {
+            self.validate_wheel_asset_draw_weight(draw_weight)?;
+        }
+
         Ok(())
     }
 
@@ -514,6 +1213,21 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetServiceImpl<W, H
         Ok(())
     }
 
+    fn validate_wheel_asset_draw_weight(
+        &self,
+        draw_weight: &WheelAssetDrawWeight,
+    ) -> Result<(), ApiError> {
+        if let WheelAssetDrawWeight::Fixed { weight } = draw_weight {
+            if !(MINIMUM_WHEEL_ASSET_DRAW_WEIGHT..=MAXIMUM_WHEEL_ASSET_DRAW_WEIGHT).contains(weight)
+            {
+                return Err(ApiError::invalid_argument(&format!(
+                    "Draw weight must be between {MINIMUM_WHEEL_ASSET_DRAW_WEIGHT} and {MAXIMUM_WHEEL_ASSET_DRAW_WEIGHT}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
     fn validate_wheel_asset_amounts(
         &self,
         total_amount: u32,
@@ -561,6 +1275,31 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetServiceImpl<W, H
         Ok(())
     }
 
+    fn validate_wheel_asset_token_prize_token_amount(&self, amount: &u128) -> Result<(), ApiError> {
+        if *amount == 0 {
+            return Err(ApiError::invalid_argument(
+                "Prize token amount must be greater than 0",
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_wheel_asset_token_decimals(
+        &self,
+        ledger_canister_id: &Principal,
+        decimals: u8,
+        ledger_metadata: &LedgerMetadata,
+    ) -> Result<(), ApiError> {
+        if decimals != ledger_metadata.decimals {
+            return Err(ApiError::invalid_argument(&format!(
+                "Decimals {} do not match the decimals {} of ledger {}",
+                decimals, ledger_metadata.decimals, ledger_canister_id
+            )));
+        }
+
+        Ok(())
+    }
+
     fn validate_wheel_asset_jackpot_asset_ids(
         &self,
         wheel_asset_ids: &[String],
@@ -584,8 +1323,70 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetServiceImpl<W, H
         Ok(())
     }
 
+    fn validate_wheel_asset_gadget_variants(
+        &self,
+        variants: &[WheelAssetGadgetVariantConfig],
+    ) -> Result<(), ApiError> {
+        if variants.len() > MAXIMUM_WHEEL_ASSET_GADGET_VARIANTS_COUNT {
+            return Err(ApiError::invalid_argument(&format!(
+                "Gadget can have at most {MAXIMUM_WHEEL_ASSET_GADGET_VARIANTS_COUNT} variants"
+            )));
+        }
+        let mut seen = HashSet::new();
+        for variant in variants {
+            if variant.name.is_empty() {
+                return Err(ApiError::invalid_argument(
+                    "Gadget variant name must not be empty",
+                ));
+            }
+            if variant.name.chars().count() > WHEEL_ASSET_NAME_MAX_LENGTH {
+                return Err(ApiError::invalid_argument(&format!(
+                    "Gadget variant name must be at most {WHEEL_ASSET_NAME_MAX_LENGTH} characters"
+                )));
+            }
+            if !seen.insert(&variant.name) {
+                return Err(ApiError::invalid_argument(
+                    "Gadget cannot have duplicate variant names",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_jackpot_wheel_assets_event(
+        &self,
+        wheel_asset_ids: &[WheelAssetId],
+        event_id: Option<EventId>,
+    ) -> Result<(), ApiError> {
+        for wheel_asset_id in wheel_asset_ids {
+            let wheel_asset = self.get_wheel_asset(wheel_asset_id)?;
+            if wheel_asset.event_id != event_id {
+                return Err(ApiError::invalid_argument(&format!(
+                    "Jackpot wheel asset with id {} belongs to another event",
+                    wheel_asset_id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_jackpot_wheel_assets_enabled(
+        &self,
+        wheel_asset_ids: &[WheelAssetId],
+    ) -> Result<(), ApiError> {
+        for wheel_asset_id in wheel_asset_ids {
+            if !self.get_wheel_asset(wheel_asset_id)?.is_enabled() {
+                return Err(ApiError::invalid_argument(&format!(
+                    "Jackpot wheel asset with id {} is not enabled",
+                    wheel_asset_id
+                )));
+            }
+        }
+        Ok(())
+    }
+
     /// Immediately (= after 0 seconds) starts a task to fetch the price of the given asset,
-    /// if the asset has an exchange rate symbol.
+    /// if the asset has an exchange rate symbol. The task is retried with backoff if it fails.
     fn schedule_price_fetcher(&self, asset_id: WheelAssetId, asset_type: WheelAssetType) {
         if !asset_type.should_fetch_usd_price() {
             return;
@@ -596,16 +1397,25 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetServiceImpl<W, H
             asset_id
         );
 
-        set_timer(Duration::from_secs(0), move || {
-            spawn(async move {
-                WheelAssetServiceImpl::default()
-                    .fetch_and_save_token_price(asset_id, asset_type)
-                    .await
-            });
-        });
+        spawn_with_retry(
+            "fetch_and_save_token_price",
+            TOKEN_DATA_FETCH_RETRY_POLICY,
+            move |_| {
+                let asset_type = asset_type.clone();
+                async move {
+                    WheelAssetServiceImpl::default()
+                        .fetch_and_save_token_price(asset_id, asset_type)
+                        .await
+                }
+            },
+        );
     }
 
-    async fn fetch_and_save_token_price(&self, asset_id: WheelAssetId, asset_type: WheelAssetType) {
+    async fn fetch_and_save_token_price(
+        &self,
+        asset_id: WheelAssetId,
+        asset_type: WheelAssetType,
+    ) -> Result<(), ApiError> {
         println!(
             "fetch_and_save_token_price: Fetching price for asset {}",
             asset_id
@@ -613,13 +1423,13 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetServiceImpl<W, H
 
         let symbol = match asset_type {
             WheelAssetType::Token {
-                exchange_rate_symbol,
+                exchange_rate_symbol: Some(exchange_rate_symbol),
                 ..
-            } => exchange_rate_symbol.expect("asset should have an exchange rate symbol"),
+            } => exchange_rate_symbol,
             _ => {
                 // should never happen
                 println!("fetch_and_save_token_price: invalid asset type");
-                return;
+                return Ok(());
             }
         };
 
@@ -637,130 +1447,266 @@ impl<W: WheelAssetRepository, H: HttpAssetRepository> WheelAssetServiceImpl<W, H
             timestamp: None, // get the latest rate
         };
 
-        match xrc_canister.get_exchange_rate(request).await.unwrap() {
-            Ok(result) => {
-                let usd_price = result.rate as f64 / 10_f64.powi(result.metadata.decimals as i32);
+        let result = match xrc_canister.get_exchange_rate(request).await {
+            Ok(Ok(result)) => Ok(result.rate as f64 / 10_f64.powi(result.metadata.decimals as i32)),
+            Ok(Err(err)) => Err(ApiError::internal(&format!(
+                "Failed to get exchange rate for symbol {}: {:?}",
+                symbol, err
+            ))),
+            Err(err) => Err(ApiError::internal(&format!(
+                "Failed to call the exchange rate canister for symbol {}: {}",
+                symbol, err
+            ))),
+        };
 
-                let mut asset = match self.wheel_asset_repository.get_wheel_asset(&asset_id) {
-                    Some(asset) => asset,
-                    None => {
-                        println!(
-                            "fetch_and_save_token_price: asset with id {} not found, it may have been deleted",
-                            asset_id
-                        );
-                        return;
-                    }
-                };
+        let Some(mut asset) = self.wheel_asset_repository.get_wheel_asset(&asset_id) else {
+            println!(
+                "fetch_and_save_token_price: asset with id {} not found, it may have been deleted",
+                asset_id
+            );
+            return Ok(());
+        };
 
-                asset.set_latest_price(WheelAssetTokenPrice::new(usd_price));
+        match &result {
+            Ok(usd_price) => asset.set_latest_price(WheelAssetTokenPrice::new(*usd_price)),
+            Err(err) => asset.set_usd_price_fetch_failed(err.clone()),
+        }
 
-                if let Err(err) = self
-                    .wheel_asset_repository
-                    .update_wheel_asset(asset_id, asset)
-                {
-                    println!(
-                        "Error: fetch_and_save_token_price: failed to update asset with id {}: {}",
-                        asset_id, err,
-                    );
-                    return;
-                }
+        self.wheel_asset_repository
+            .update_wheel_asset(asset_id, asset)?;
+        result?;
 
-                println!(
-                    "fetch_and_save_token_price: Successfully fetched and saved price for asset {}",
-                    asset_id
-                );
-            }
-            Err(err) => {
-                // TODO: implement retry
-                println!(
-                    "Error: fetch_and_save_token_price: failed to get exchange rate for asset {}: symbol {}, error: {:?}",
-                    asset_id, symbol, err
-                )
-            }
+        println!(
+            "fetch_and_save_token_price: Successfully fetched and saved price for asset {}",
+            asset_id
+        );
+
+        Ok(())
+    }
+
+    /// Returns the cached metadata of the ledger, fetching it again if it's older
+    /// than [LEDGER_METADATA_MAX_AGE_SECONDS] or if it has never been fetched.
+    async fn get_ledger_metadata(
+        &self,
+        ledger_canister_id: Principal,
+    ) -> Result<LedgerMetadata, ApiError> {
+        if let Some(ledger_metadata) = self
+            .ledger_metadata_repository
+            .get_ledger_metadata(&ledger_canister_id)
+            .filter(|ledger_metadata| {
+                !ledger_metadata.is_stale(LEDGER_METADATA_MAX_AGE_SECONDS, &get_current_date_time())
+            })
+        {
+            return Ok(ledger_metadata);
         }
+
+        let ledger_canister = LedgerCanisterService(ledger_canister_id);
+        let ledger_call_error = |method: &str, err: ic_cdk::call::Error| {
+            ApiError::internal(&format!(
+                "Failed to call {} on ledger {}: {}",
+                method, ledger_canister_id, err
+            ))
+        };
+
+        let entries = ledger_canister
+            .icrc1_metadata()
+            .await
+            .map(|metadata| Icrc1MetadataEntries::new(&metadata))
+            .map_err(|err| ledger_call_error("icrc1_metadata", err))?;
+
+        // the ledgers are not required to include the standard entries in their metadata
+        let fee = match entries.fee {
+            Some(fee) => fee,
+            None => {
+                let fee = ledger_canister
+                    .icrc1_fee()
+                    .await
+                    .map_err(|err| ledger_call_error("icrc1_fee", err))?;
+                u128::try_from(fee.0).map_err(|_| {
+                    ApiError::internal(&format!("Invalid fee from ledger {}", ledger_canister_id))
+                })?
+            }
+        };
+        let decimals = match entries.decimals {
+            Some(decimals) => decimals,
+            None => ledger_canister
+                .icrc1_decimals()
+                .await
+                .map_err(|err| ledger_call_error("icrc1_decimals", err))?,
+        };
+        let symbol = match entries.symbol {
+            Some(symbol) => symbol,
+            None => ledger_canister
+                .icrc1_symbol()
+                .await
+                .map_err(|err| ledger_call_error("icrc1_symbol", err))?,
+        };
+        let name = match entries.name {
+            Some(name) => name,
+            None => ledger_canister
+                .icrc1_name()
+                .await
+                .map_err(|err| ledger_call_error("icrc1_name", err))?,
+        };
+
+        let ledger_metadata = LedgerMetadata::new(fee, decimals, symbol, name, entries.logo);
+        self.ledger_metadata_repository
+            .set_ledger_metadata(ledger_canister_id, ledger_metadata.clone());
+
+        Ok(ledger_metadata)
     }
 
     /// Immediately (= after 0 seconds) starts a task to fetch the balance of the given token asset.
+    /// The task is retried with backoff if it fails.
     fn schedule_balance_fetcher(&self, asset_id: WheelAssetId, asset_type: WheelAssetType) {
         println!(
             "schedule_balance_fetcher: Scheduling balance fetcher for asset {}",
             asset_id
         );
 
-        set_timer(Duration::from_secs(0), move || {
-            spawn(async move {
-                WheelAssetServiceImpl::default()
-                    .fetch_and_save_token_balance(asset_id, asset_type)
-                    .await
-            });
-        });
+        spawn_with_retry(
+            "fetch_and_save_token_balance",
+            TOKEN_DATA_FETCH_RETRY_POLICY,
+            move |_| {
+                let asset_type = asset_type.clone();
+                async move {
+                    WheelAssetServiceImpl::default()
+                        .fetch_and_save_token_balance(asset_id, asset_type)
+                        .await
+                }
+            },
+        );
     }
 
     async fn fetch_and_save_token_balance(
         &self,
         asset_id: WheelAssetId,
         asset_type: WheelAssetType,
-    ) {
+    ) -> Result<(), ApiError> {
         println!(
             "fetch_and_save_token_balance: Fetching balance for asset {}",
             asset_id
         );
 
-        let ledger_canister_id = match asset_type {
+        let ledger_canister_id = match &asset_type {
             WheelAssetType::Token { ledger_config, .. } => ledger_config.ledger_canister_id,
             _ => {
                 // should never happen
                 println!("fetch_and_save_token_balance: invalid asset type");
-                return;
+                return Ok(());
             }
         };
 
         let ledger_canister = LedgerCanisterService(ledger_canister_id);
+        let canister_account = Account {
+            owner: ic_cdk::api::canister_self(),
+            subaccount: asset_type.token_subaccount(&asset_id),
+        };
 
-        match ledger_canister
-            .icrc1_balance_of(Account {
-                owner: ic_cdk::api::canister_self(),
-                subaccount: None,
-            })
-            .await
-        {
-            Ok(balance) => {
-                let mut asset = match self.wheel_asset_repository.get_wheel_asset(&asset_id) {
-                    Some(asset) => asset,
-                    None => {
+        // the fee is needed to count the available draws of the balance
+        let ledger_metadata = self.get_ledger_metadata(ledger_canister_id).await;
+        let result = match ledger_metadata {
+            Ok(ledger_metadata) => match asset_type.token_sponsor_account() {
+                Some(sponsor_account) => {
+                    self.fetch_sponsor_allowance(
+                        &ledger_canister,
+                        sponsor_account,
+                        canister_account,
+                    )
+                    .await
+                }
+                None => ledger_canister
+                    .icrc1_balance_of(canister_account)
+                    .await
+                    .map_err(|err| {
+                        ApiError::internal(&format!(
+                            "Failed to get balance from ledger {}: {}",
+                            ledger_canister_id, err
+                        ))
+                    }),
+            }
+            .map(|balance| (balance, ledger_metadata)),
+            Err(err) => Err(err),
+        };
+
+        let Some(mut asset) = self.wheel_asset_repository.get_wheel_asset(&asset_id) else {
+            println!(
+                "fetch_and_save_token_balance: asset with id {} not found, it may have been deleted",
+                asset_id
+            );
+            return Ok(());
+        };
+
+        match &result {
+            Ok((balance, ledger_metadata)) => {
+                if let Some(ledger_config) = asset.asset_type.ledger_config() {
+                    if ledger_config.decimals != ledger_metadata.decimals {
                         println!(
-                            "fetch_and_save_token_balance: asset with id {} not found, it may have been deleted",
-                            asset_id
+                            "fetch_and_save_token_balance: correcting decimals of asset {} from {} to {}",
+                            asset_id, ledger_config.decimals, ledger_metadata.decimals
                         );
-                        return;
                     }
-                };
+                }
+                asset.set_ledger_metadata(ledger_metadata);
+                asset.set_latest_balance(WheelAssetTokenBalance::new(*balance));
+            }
+            Err(err) => asset.set_balance_fetch_failed(err.clone()),
+        }
 
-                asset.set_latest_balance(WheelAssetTokenBalance::new(balance));
+        self.wheel_asset_repository
+            .update_wheel_asset(asset_id, asset)?;
+        result?;
 
-                if let Err(err) = self
-                    .wheel_asset_repository
-                    .update_wheel_asset(asset_id, asset)
-                {
-                    println!(
-                        "Error: fetch_and_save_token_balance: failed to update asset with id {}: {}",
-                        asset_id, err,
-                    );
-                    return;
-                }
+        println!(
+            "fetch_and_save_token_balance: Successfully fetched and saved balance for asset {}",
+            asset_id
+        );
 
-                println!(
-                    "fetch_and_save_token_balance: Successfully fetched and saved balance for asset {}",
-                    asset_id
-                );
-            }
-            Err(err) => {
-                // TODO: implement retry
-                println!(
-                    "Error: fetch_and_save_token_balance: failed to get balance for asset {}: error: {:?}",
-                    asset_id, err
-                )
-            }
+        Ok(())
+    }
+
+    /// Returns how much the canister can still transfer from the sponsor's account,
+    /// which is the remaining allowance, capped by the sponsor's balance.
+    async fn fetch_sponsor_allowance(
+        &self,
+        ledger_canister: &LedgerCanisterService,
+        sponsor_account: Account,
+        canister_account: Account,
+    ) -> Result<u128, ApiError> {
+        let ledger_canister_id = ledger_canister.0;
+
+        let allowance = ledger_canister
+            .icrc2_allowance(AllowanceArgs {
+                account: sponsor_account,
+                spender: canister_account,
+            })
+            .await
+            .map_err(|err| {
+                ApiError::internal(&format!(
+                    "Failed to get allowance of {} from ledger {}: {}",
+                    sponsor_account, ledger_canister_id, err
+                ))
+            })?;
+
+        let is_expired = allowance
+            .expires_at
+            .map(|expires_at| expires_at <= ic_cdk::api::time())
+            .unwrap_or(false);
+        if is_expired {
+            return Ok(0);
         }
+        let allowance = u128::try_from(allowance.allowance.0).unwrap_or(u128::MAX);
+
+        let sponsor_balance = ledger_canister
+            .icrc1_balance_of(sponsor_account)
+            .await
+            .map_err(|err| {
+                ApiError::internal(&format!(
+                    "Failed to get balance of {} from ledger {}: {}",
+                    sponsor_account, ledger_canister_id, err
+                ))
+            })?;
+
+        Ok(allowance.min(sponsor_balance))
     }
 }
@@ -1,30 +1,52 @@
-use std::time::Duration;
-
 use backend_api::{
-    ApiError, CreateWheelPrizeExtractionRequest, GetLastWheelPrizeExtractionResponse,
-    GetWheelPrizeExtractionRequest, GetWheelPrizeExtractionResponse,
-    GetWheelPrizeExtractionsStatsResponse, ListWheelPrizeExtractionsResponse, TransferTokenRequest,
+    ApiError, CreateMyWheelPrizeExtractionRequest, CreateWheelPrizeExtractionRequest,
+    GetLastWheelPrizeExtractionResponse, GetMyWheelPrizeExtractionsResponse,
+    GetMyWheelPrizeRedemptionQrCodeRequest, GetMyWheelPrizeRedemptionQrCodeResponse,
+    GetWheelPrizeDrawProofRequest, GetWheelPrizeDrawProofResponse,
+    GetWheelPrizeExtractionPolicyResponse, GetWheelPrizeExtractionRequest,
+    GetWheelPrizeExtractionResponse, GetWheelPrizeExtractionsStatsRequest,
+    GetWheelPrizeExtractionsStatsResponse, HandOverWheelPrizeGadgetRequest,
+    ListUnclaimedWheelPrizeGadgetsRequest, ListUnclaimedWheelPrizeGadgetsResponse,
+    ListWheelPrizeExtractionsPageRequest, ListWheelPrizeExtractionsPageResponse,
+    ListWheelPrizeExtractionsResponse, RedeemPrizeRequest, RetryWheelPrizeExtractionPayoutsRequest,
+    UpdateWheelPrizeExtractionPolicyRequest,
 };
 use candid::Principal;
 use ic_cdk::println;
-use rand::prelude::*;
+use icrc_ledger_types::icrc1::account::Account;
 
 use crate::{
-    mappings::map_wheel_prize_extraction,
+    mappings::{
+        into_recipient_account, map_my_wheel_prize_extraction, map_wheel_prize_extraction,
+        map_wheel_prize_extraction_policy,
+    },
     repositories::{
-        elapsed_since, HttpAssetRepositoryImpl, UserProfileRepository, UserProfileRepositoryImpl,
-        WheelAssetId, WheelAssetRepository, WheelAssetRepositoryImpl, WheelAssetState,
-        WheelAssetType, WheelPrizeExtraction, WheelPrizeExtractionId,
-        WheelPrizeExtractionRepository, WheelPrizeExtractionRepositoryImpl,
-        WheelPrizeExtractionState,
+        get_current_date_time, DateTime, EventId, EventRepository, EventRepositoryImpl,
+        HttpAssetRepositoryImpl, InviteCodeId, InviteCodePurpose, InviteCodeRepositoryImpl,
+        LedgerMetadataRepositoryImpl, LedgerTransferRepositoryImpl, ManualTransferRepositoryImpl,
+        SelfSpinRepositoryImpl, StaleWheelAssetPrice, StaleWheelAssetPriceResolution, UserId,
+        UserProfileRepository, UserProfileRepositoryImpl, WheelAsset, WheelAssetId,
+        WheelAssetRepository, WheelAssetRepositoryImpl, WheelAssetState, WheelAssetType,
+        WheelPrizeDrawCandidate, WheelPrizeDrawProof, WheelPrizeExtraction,
+        WheelPrizeExtractionFilter, WheelPrizeExtractionId, WheelPrizeExtractionIdRange,
+        WheelPrizeExtractionPolicy, WheelPrizeExtractionPolicyRepository,
+        WheelPrizeExtractionPolicyRepositoryImpl, WheelPrizeExtractionRepository,
+        WheelPrizeExtractionRepositoryImpl, WheelPrizeExtractionState, WheelPrizePayout,
+        WheelPrizePayoutState, WheelPrizeRedemptionRepositoryImpl, WheelPrizeSelfSpin,
     },
-    services::{WalletService, WalletServiceImpl, WheelAssetService, WheelAssetServiceImpl},
-    system_api::chacha20_rng,
+    services::{
+        InviteCodeService, InviteCodeServiceImpl, SelfSpinService, SelfSpinServiceImpl,
+        WalletService, WalletServiceImpl, WheelAssetService, WheelAssetServiceImpl,
+        WheelPrizeRedemptionService, WheelPrizeRedemptionServiceImpl,
+    },
+    system_api::random_seed,
 };
 
-/// The minimum time duration to wait before enabling a new extraction for the same principal,
-/// if the previous extraction failed.
-const MINIMUM_FAILED_WHEEL_PRIZE_EXTRACTION_AGE_DURATION: Duration = Duration::from_secs(30);
+/// The time after which a processing extraction that didn't make any progress is considered stuck,
+/// e.g. because the canister trapped or the ledger call stalled in the middle of the payout.
+const STUCK_WHEEL_PRIZE_EXTRACTION_AGE_SECONDS: i64 = 10 * 60;
+const DEFAULT_WHEEL_PRIZE_EXTRACTIONS_PAGE_SIZE: u32 = 100;
+const MAX_WHEEL_PRIZE_EXTRACTIONS_PAGE_SIZE: u32 = 500;
 
 #[cfg_attr(test, mockall::automock)]
 #[allow(clippy::needless_lifetimes)]
@@ -40,15 +62,90 @@ pub trait WheelPrizeExtractionService {
 
     fn list_wheel_prize_extractions(&self) -> Result<ListWheelPrizeExtractionsResponse, ApiError>;
 
+    fn list_wheel_prize_extractions_page(
+        &self,
+        request: ListWheelPrizeExtractionsPageRequest,
+    ) -> Result<ListWheelPrizeExtractionsPageResponse, ApiError>;
+
+    /// Lists the extractions of the calling principal, in and outside of any event.
+    fn get_my_wheel_prize_extractions(
+        &self,
+        calling_principal: &Principal,
+    ) -> Result<GetMyWheelPrizeExtractionsResponse, ApiError>;
+
     async fn create_wheel_prize_extraction<'a>(
         &self,
         calling_principal: &'a Principal,
         request: CreateWheelPrizeExtractionRequest,
     ) -> Result<(), ApiError>;
 
+    /// Spins the wheel for the calling principal, paying the prize out to its default account,
+    /// if the self-spin is enabled by the policy and the self-spin code is valid.
+    async fn create_my_wheel_prize_extraction<'a>(
+        &self,
+        calling_principal: &'a Principal,
+        request: CreateMyWheelPrizeExtractionRequest,
+    ) -> Result<(), ApiError>;
+
     fn get_wheel_prize_extractions_stats(
         &self,
+        request: Option<GetWheelPrizeExtractionsStatsRequest>,
     ) -> Result<GetWheelPrizeExtractionsStatsResponse, ApiError>;
+
+    fn get_wheel_prize_draw_proof(
+        &self,
+        request: GetWheelPrizeDrawProofRequest,
+    ) -> Result<GetWheelPrizeDrawProofResponse, ApiError>;
+
+    fn get_wheel_prize_extraction_policy(
+        &self,
+    ) -> Result<GetWheelPrizeExtractionPolicyResponse, ApiError>;
+
+    fn update_wheel_prize_extraction_policy(
+        &self,
+        request: UpdateWheelPrizeExtractionPolicyRequest,
+    ) -> Result<(), ApiError>;
+
+    /// Retries the failed payouts of a partially completed extraction.
+    async fn retry_wheel_prize_extraction_payouts(
+        &self,
+        request: RetryWheelPrizeExtractionPayoutsRequest,
+    ) -> Result<(), ApiError>;
+
+    /// Records that the gadget won with an extraction has been handed over to the winner,
+    /// taking it out of the stock of the given variant.
+    fn hand_over_wheel_prize_gadget(
+        &self,
+        calling_principal: &Principal,
+        request: HandOverWheelPrizeGadgetRequest,
+    ) -> Result<(), ApiError>;
+
+    /// Hands over the gadget won with an extraction, if the redemption code
+    /// shown by the winner is valid for the extraction.
+    fn redeem_prize(
+        &self,
+        calling_principal: &Principal,
+        request: RedeemPrizeRequest,
+    ) -> Result<(), ApiError>;
+
+    /// Returns the redemption QR code of a gadget won by the calling principal
+    /// and not handed over yet, so that only the winner can show it at the booth.
+    fn get_my_wheel_prize_redemption_qr_code(
+        &self,
+        calling_principal: &Principal,
+        request: GetMyWheelPrizeRedemptionQrCodeRequest,
+    ) -> Result<GetMyWheelPrizeRedemptionQrCodeResponse, ApiError>;
+
+    fn list_unclaimed_wheel_prize_gadgets(
+        &self,
+        request: ListUnclaimedWheelPrizeGadgetsRequest,
+    ) -> Result<ListUnclaimedWheelPrizeGadgetsResponse, ApiError>;
+
+    /// Retries the pending payouts of the stuck extractions with the same deduplication parameters,
+    /// or marks the extractions as failed if they were interrupted before any payout.
+    async fn reconcile_wheel_prize_extractions(&self) -> Result<(), ApiError>;
+
+    fn init_indexes(&self) -> Result<(), ApiError>;
 }
 
 pub struct WheelPrizeExtractionServiceImpl<
@@ -57,12 +154,22 @@ pub struct WheelPrizeExtractionServiceImpl<
     U: UserProfileRepository,
     W: WalletService,
     WA: WheelAssetService,
+    E: EventRepository,
+    PR: WheelPrizeExtractionPolicyRepository,
+    RS: WheelPrizeRedemptionService,
+    S: SelfSpinService,
+    IC: InviteCodeService,
 > {
     wheel_asset_repository: A,
     wheel_prize_extraction_repository: P,
     user_profile_repository: U,
     wallet_service: W,
     wheel_asset_service: WA,
+    event_repository: E,
+    wheel_prize_extraction_policy_repository: PR,
+    wheel_prize_redemption_service: RS,
+    self_spin_service: S,
+    invite_code_service: IC,
 }
 
 impl Default
@@ -70,8 +177,40 @@ impl Default
         WheelAssetRepositoryImpl,
         WheelPrizeExtractionRepositoryImpl,
         UserProfileRepositoryImpl,
-        WalletServiceImpl<UserProfileRepositoryImpl>,
-        WheelAssetServiceImpl<WheelAssetRepositoryImpl, HttpAssetRepositoryImpl>,
+        WalletServiceImpl<
+            UserProfileRepositoryImpl,
+            LedgerTransferRepositoryImpl,
+            ManualTransferRepositoryImpl,
+        >,
+        WheelAssetServiceImpl<
+            WheelAssetRepositoryImpl,
+            HttpAssetRepositoryImpl,
+            EventRepositoryImpl,
+            WheelPrizeExtractionRepositoryImpl,
+            LedgerMetadataRepositoryImpl,
+            LedgerTransferRepositoryImpl,
+        >,
+        EventRepositoryImpl,
+        WheelPrizeExtractionPolicyRepositoryImpl,
+        WheelPrizeRedemptionServiceImpl<WheelPrizeRedemptionRepositoryImpl>,
+        SelfSpinServiceImpl<
+            InviteCodeServiceImpl<
+                InviteCodeRepositoryImpl,
+                WheelPrizeExtractionRepositoryImpl,
+                EventRepositoryImpl,
+                UserProfileRepositoryImpl,
+            >,
+            SelfSpinRepositoryImpl,
+            WheelPrizeExtractionRepositoryImpl,
+            EventRepositoryImpl,
+            WheelPrizeExtractionPolicyRepositoryImpl,
+        >,
+        InviteCodeServiceImpl<
+            InviteCodeRepositoryImpl,
+            WheelPrizeExtractionRepositoryImpl,
+            EventRepositoryImpl,
+            UserProfileRepositoryImpl,
+        >,
     >
 {
     fn default() -> Self {
@@ -81,6 +220,11 @@ impl Default
             UserProfileRepositoryImpl::default(),
             WalletServiceImpl::default(),
             WheelAssetServiceImpl::default(),
+            EventRepositoryImpl::default(),
+            WheelPrizeExtractionPolicyRepositoryImpl::default(),
+            WheelPrizeRedemptionServiceImpl::default(),
+            SelfSpinServiceImpl::default(),
+            InviteCodeServiceImpl::default(),
         )
     }
 }
@@ -91,7 +235,13 @@ impl<
         U: UserProfileRepository,
         W: WalletService,
         WA: WheelAssetService,
-    > WheelPrizeExtractionService for WheelPrizeExtractionServiceImpl<A, P, U, W, WA>
+        E: EventRepository,
+        PR: WheelPrizeExtractionPolicyRepository,
+        RS: WheelPrizeRedemptionService,
+        S: SelfSpinService,
+        IC: InviteCodeService,
+    > WheelPrizeExtractionService
+    for WheelPrizeExtractionServiceImpl<A, P, U, W, WA, E, PR, RS, S, IC>
 {
     fn get_wheel_prize_extraction(
         &self,
@@ -133,245 +283,194 @@ impl<
         Ok(wheel_prize_extractions)
     }
 
-    async fn create_wheel_prize_extraction(
+    fn list_wheel_prize_extractions_page(
         &self,
-        calling_principal: &Principal,
-        request: CreateWheelPrizeExtractionRequest,
-    ) -> Result<(), ApiError> {
-        self.validate_create_wheel_prize_extraction_request(&request)?;
+        request: ListWheelPrizeExtractionsPageRequest,
+    ) -> Result<ListWheelPrizeExtractionsPageResponse, ApiError> {
+        let limit = request
+            .limit
+            .unwrap_or(DEFAULT_WHEEL_PRIZE_EXTRACTIONS_PAGE_SIZE);
+        if limit == 0 || limit > MAX_WHEEL_PRIZE_EXTRACTIONS_PAGE_SIZE {
+            return Err(ApiError::invalid_argument(&format!(
+                "Limit must be between 1 and {}",
+                MAX_WHEEL_PRIZE_EXTRACTIONS_PAGE_SIZE
+            )));
+        }
 
-        let extracted_for_principal = request.extract_for_principal;
+        let filter = WheelPrizeExtractionFilter {
+            state: request.state.map(Into::into),
+            wheel_asset_id: request
+                .wheel_asset_id
+                .as_deref()
+                .map(WheelAssetId::try_from)
+                .transpose()?,
+            extracted_by_user_id: request
+                .extracted_by_user_id
+                .as_deref()
+                .map(UserId::try_from)
+                .transpose()?,
+            created_from: request
+                .created_from
+                .as_deref()
+                .map(DateTime::try_from)
+                .transpose()?,
+            created_before: request
+                .created_before
+                .as_deref()
+                .map(DateTime::try_from)
+                .transpose()?,
+        };
+        let start_after = request
+            .start_after
+            .as_deref()
+            .map(WheelPrizeExtractionId::try_from)
+            .transpose()?;
 
-        if let Some((_, existing_wheel_prize_extraction)) = self
+        let wheel_prize_extractions = self
             .wheel_prize_extraction_repository
-            .get_wheel_prize_extraction_by_principal(&extracted_for_principal)
-        {
-            if !existing_wheel_prize_extraction.is_failed() {
-                return Err(ApiError::invalid_argument(
-                    "This principal has already been extracted",
-                ));
-            } else if elapsed_since(&existing_wheel_prize_extraction.timestamps.created_at)
-                < MINIMUM_FAILED_WHEEL_PRIZE_EXTRACTION_AGE_DURATION
-            {
-                return Err(ApiError::invalid_argument(&format!(
-                    "You must wait {} seconds before extracting this principal again",
-                    MINIMUM_FAILED_WHEEL_PRIZE_EXTRACTION_AGE_DURATION.as_secs(),
-                )));
-            }
-        }
+            .list_wheel_prize_extractions_page(&filter, start_after, limit as usize)?;
+        let next_start_after = wheel_prize_extractions
+            .last()
+            .filter(|_| wheel_prize_extractions.len() == limit as usize)
+            .map(|(id, _)| id.to_string());
 
-        let extracted_by_user_id = self
-            .user_profile_repository
-            .get_user_by_principal(calling_principal)
-            .ok_or_else(|| {
-                ApiError::not_found(&format!(
-                    "User profile for principal {} not found",
-                    calling_principal.to_text()
-                ))
-            })?
-            .0;
+        Ok(ListWheelPrizeExtractionsPageResponse {
+            wheel_prize_extractions: wheel_prize_extractions
+                .into_iter()
+                .map(|(id, wheel_prize_extraction)| {
+                    map_wheel_prize_extraction(id, wheel_prize_extraction)
+                })
+                .collect(),
+            next_start_after,
+        })
+    }
 
-        let mut wheel_prize_extraction =
-            WheelPrizeExtraction::new_processing(extracted_for_principal, extracted_by_user_id);
-        let wheel_prize_extraction_id = self
+    fn get_my_wheel_prize_extractions(
+        &self,
+        calling_principal: &Principal,
+    ) -> Result<GetMyWheelPrizeExtractionsResponse, ApiError> {
+        let wheel_prize_extractions = self
             .wheel_prize_extraction_repository
-            .create_wheel_prize_extraction(wheel_prize_extraction.clone())?;
-
-        println!(
-            "Wheel prize extraction (id:{}): processing for principal {}",
-            wheel_prize_extraction_id, extracted_for_principal
-        );
+            .list_all_wheel_prize_extractions_by_principal(calling_principal)?
+            .into_iter()
+            .map(|(id, wheel_prize_extraction)| {
+                let prize_name = wheel_prize_extraction
+                    .wheel_asset_id
+                    .and_then(|wheel_asset_id| {
+                        self.wheel_asset_repository.get_wheel_asset(&wheel_asset_id)
+                    })
+                    .map(|wheel_asset| wheel_asset.name);
 
-        let (extracted_wheel_asset_id, mut extracted_wheel_asset) = self
-            .with_set_failed_on_error(
-                wheel_prize_extraction_id,
-                &mut wheel_prize_extraction,
-                None,
-                || async {
-                    let available_wheel_assets_ids = self
-                        .wheel_asset_repository
-                        .list_wheel_assets_by_state(WheelAssetState::Enabled)?
-                        .into_iter()
-                        .filter(|(_, wheel_asset)| wheel_asset.available_quantity() > 0)
-                        .collect::<Vec<_>>();
-
-                    let available_wheel_assets_count = available_wheel_assets_ids.len();
-                    if available_wheel_assets_count == 0 {
-                        return Err(ApiError::conflict(
-                            "No wheel assets available for extraction",
-                        ));
-                    }
+                map_my_wheel_prize_extraction(id, wheel_prize_extraction, prize_name)
+            })
+            .collect();
+        Ok(wheel_prize_extractions)
+    }
 
-                    let random_index = random_index(available_wheel_assets_count).await?;
+    async fn create_wheel_prize_extraction(
+        &self,
+        calling_principal: &Principal,
+        request: CreateWheelPrizeExtractionRequest,
+    ) -> Result<(), ApiError> {
+        let invite_code = request.invite_code.clone();
+        let extracted_for = self.validate_create_wheel_prize_extraction_request(request)?;
+        let event_id = self.get_running_event_id()?;
 
-                    available_wheel_assets_ids
-                        .get(random_index)
-                        .cloned()
-                        .ok_or_else(|| {
-                            ApiError::internal(&format!(
-                                "Wheel asset at index {} not found in available wheel assets list",
-                                random_index,
-                            ))
-                        })
-                },
-            )
-            .await?;
+        self.assert_principal_is_eligible(&extracted_for.owner, event_id)?;
 
-        let extracted_wheel_prize_usd_amount = self
-            .with_set_failed_on_error(
-                wheel_prize_extraction_id,
-                &mut wheel_prize_extraction,
-                Some(extracted_wheel_asset_id),
-                || async {
-                    let extracted_usd_amount = match &extracted_wheel_asset.asset_type {
-                        WheelAssetType::Token { ledger_config, .. } => {
-                            println!(
-                                "Wheel prize extraction (id:{}): Transferring token prize",
-                                wheel_prize_extraction_id,
-                            );
-
-                            self.wallet_service
-                                .transfer_token(
-                                    *calling_principal,
-                                    TransferTokenRequest {
-                                        ledger_canister_id: ledger_config.ledger_canister_id,
-                                        to: extracted_for_principal,
-                                        amount: extracted_wheel_asset
-                                            .asset_type
-                                            .token_prize_amount()
-                                            .unwrap_or(0)
-                                            .into(),
-                                    },
-                                )
-                                .await?;
-                            self.wheel_asset_service.schedule_token_data_fetchers(
-                                extracted_wheel_asset_id,
-                                extracted_wheel_asset.asset_type.clone(),
-                            );
-
-                            println!(
-                                "Wheel prize extraction (id:{}): Completed token transfer",
-                                wheel_prize_extraction_id,
-                            );
-
-                            extracted_wheel_asset.asset_type.token_prize_usd_amount()
-                        }
-                        WheelAssetType::Jackpot {
-                            wheel_asset_ids: jackpot_wheel_asset_ids,
-                        } => {
-                            println!(
-                                "Wheel prize extraction (id:{}): Transferring jackpot prize",
-                                wheel_prize_extraction_id,
-                            );
-
-                            let mut jackpot_usd_amount = 0.0;
-
-                            for jackpot_wheel_asset_id in jackpot_wheel_asset_ids {
-                                let jackpot_wheel_asset = self
-                                    .wheel_asset_repository
-                                    .get_wheel_asset(jackpot_wheel_asset_id)
-                                    .ok_or_else(|| {
-                                        ApiError::not_found(&format!(
-                                            "Wheel asset with id {} not found",
-                                            jackpot_wheel_asset_id
-                                        ))
-                                    })?;
-
-                                if let WheelAssetType::Token { ledger_config, .. } =
-                                    &jackpot_wheel_asset.asset_type
-                                {
-                                    self.wallet_service
-                                        .transfer_token(
-                                            *calling_principal,
-                                            TransferTokenRequest {
-                                                ledger_canister_id: ledger_config
-                                                    .ledger_canister_id,
-                                                to: extracted_for_principal,
-                                                amount: jackpot_wheel_asset
-                                                    .asset_type
-                                                    .token_prize_amount()
-                                                    .unwrap_or(0)
-                                                    .into(),
-                                            },
-                                        )
-                                        .await?;
-
-                                    jackpot_usd_amount += jackpot_wheel_asset
-                                        .asset_type
-                                        .token_prize_usd_amount()
-                                        .unwrap_or(0.0);
-                                } else {
-                                    // should never happen
-                                    return Err(ApiError::internal(&format!(
-                                        "Jackpot wheel asset with id {} is not a token",
-                                        jackpot_wheel_asset_id
-                                    )));
-                                }
-
-                                self.wheel_asset_service.schedule_token_data_fetchers(
-                                    *jackpot_wheel_asset_id,
-                                    jackpot_wheel_asset.asset_type.clone(),
-                                );
-                            }
-
-                            println!(
-                                "Wheel prize extraction (id:{}): Completed jackpot transfer",
-                                wheel_prize_extraction_id,
-                            );
-
-                            Some(jackpot_usd_amount)
-                        }
-                        WheelAssetType::Gadget { .. } => None,
-                    };
-
-                    extracted_wheel_asset.use_one()?;
-
-                    self.wheel_asset_repository.update_wheel_asset(
-                        extracted_wheel_asset_id,
-                        extracted_wheel_asset.clone(),
-                    )?;
+        let invite_code_id = match invite_code {
+            Some(invite_code) => Some(
+                self.invite_code_service
+                    .authorize_invite_code(
+                        &extracted_for.owner,
+                        event_id,
+                        &invite_code,
+                        InviteCodePurpose::Attendance,
+                    )?
+                    .ok_or_else(|| ApiError::permission_denied("Invalid invite code"))?,
+            ),
+            None => {
+                self.assert_invite_code_not_required()?;
+                None
+            }
+        };
 
-                    Ok(extracted_usd_amount)
-                },
-            )
-            .await?;
+        self.extract(
+            calling_principal,
+            extracted_for,
+            event_id,
+            None,
+            invite_code_id,
+        )
+        .await
+    }
 
-        wheel_prize_extraction
-            .set_completed(extracted_wheel_asset_id, extracted_wheel_prize_usd_amount);
+    async fn create_my_wheel_prize_extraction(
+        &self,
+        calling_principal: &Principal,
+        request: CreateMyWheelPrizeExtractionRequest,
+    ) -> Result<(), ApiError> {
+        if !self
+            .wheel_prize_extraction_policy_repository
+            .get_wheel_prize_extraction_policy()
+            .is_self_spin_enabled()
+        {
+            return Err(ApiError::permission_denied("Self-spin is not enabled"));
+        }
+        let event_id = self.get_running_event_id()?;
 
-        println!(
-            "Wheel prize extraction (id:{}, state:{}): wheel asset id {:?}",
-            wheel_prize_extraction_id,
-            wheel_prize_extraction.state,
-            wheel_prize_extraction.wheel_asset_id
-        );
+        self.assert_principal_is_eligible(calling_principal, event_id)?;
 
-        self.wheel_prize_extraction_repository
-            .update_wheel_prize_extraction(wheel_prize_extraction_id, wheel_prize_extraction)?;
+        let self_spin = self.self_spin_service.authorize_self_spin(
+            calling_principal,
+            event_id,
+            &request.self_spin_code,
+        )?;
+        if !matches!(self_spin, WheelPrizeSelfSpin::InviteCode { .. }) {
+            self.assert_invite_code_not_required()?;
+        }
+        let extracted_for = Account {
+            owner: *calling_principal,
+            subaccount: None,
+        };
 
-        Ok(())
+        self.extract(
+            calling_principal,
+            extracted_for,
+            event_id,
+            Some(self_spin),
+            None,
+        )
+        .await
     }
 
     fn get_wheel_prize_extractions_stats(
         &self,
+        request: Option<GetWheelPrizeExtractionsStatsRequest>,
     ) -> Result<GetWheelPrizeExtractionsStatsResponse, ApiError> {
-        let completed_state = WheelPrizeExtractionState::default_completed();
-        let completed_extractions = self
+        let event_id = self.get_requested_or_active_event_id(
+            request
+                .as_ref()
+                .and_then(|request| request.event_id.as_deref()),
+        )?;
+        let extractions = self
             .wheel_prize_extraction_repository
-            .list_wheel_prize_extractions_by_state(&completed_state);
+            .list_wheel_prize_extractions_by_event(event_id, &WheelPrizeExtractionIdRange::all())?;
 
-        let total_completed_extractions = completed_extractions.len() as u32;
-        let total_spent_usd = completed_extractions
+        let total_completed_extractions = extractions
+            .iter()
+            .filter(|(_, extraction)| extraction.is_completed())
+            .count() as u32;
+        // partially completed extractions have spent the amount of their successful payouts
+        let total_spent_usd = extractions
             .into_iter()
-            .filter_map(|(_, extraction)| {
-                if let WheelPrizeExtractionState::Completed {
+            .filter_map(|(_, extraction)| match extraction.state {
+                WheelPrizeExtractionState::Completed { prize_usd_amount }
+                | WheelPrizeExtractionState::PartiallyCompleted {
                     prize_usd_amount, ..
-                } = extraction.state
-                {
-                    prize_usd_amount
-                } else {
-                    None
-                }
+                } => prize_usd_amount,
+                WheelPrizeExtractionState::Processing
+                | WheelPrizeExtractionState::Failed { .. } => None,
             })
             .sum::<f64>()
             // summing no values returns -0 for some reasons
@@ -382,6 +481,214 @@ impl<
             total_spent_usd,
         })
     }
+
+    fn get_wheel_prize_draw_proof(
+        &self,
+        request: GetWheelPrizeDrawProofRequest,
+    ) -> Result<GetWheelPrizeDrawProofResponse, ApiError> {
+        let id = WheelPrizeExtractionId::try_from(request.wheel_prize_extraction_id.as_str())?;
+        let wheel_prize_extraction = self
+            .wheel_prize_extraction_repository
+            .get_wheel_prize_extraction(&id)
+            .ok_or_else(|| {
+                ApiError::not_found(&format!("Wheel prize extraction with id {} not found", id))
+            })?;
+
+        wheel_prize_extraction
+            .draw_proof
+            .map(|draw_proof| draw_proof.into())
+            .ok_or_else(|| {
+                ApiError::not_found(&format!(
+                    "Draw proof for wheel prize extraction with id {} not found",
+                    id
+                ))
+            })
+    }
+
+    fn get_wheel_prize_extraction_policy(
+        &self,
+    ) -> Result<GetWheelPrizeExtractionPolicyResponse, ApiError> {
+        let policy = self
+            .wheel_prize_extraction_policy_repository
+            .get_wheel_prize_extraction_policy();
+
+        Ok(map_wheel_prize_extraction_policy(policy))
+    }
+
+    fn update_wheel_prize_extraction_policy(
+        &self,
+        request: UpdateWheelPrizeExtractionPolicyRequest,
+    ) -> Result<(), ApiError> {
+        self.validate_update_wheel_prize_extraction_policy_request(&request)?;
+
+        let policy = WheelPrizeExtractionPolicy {
+            max_wins_per_principal: request.max_wins_per_principal,
+            max_wins_per_principal_per_day: request.max_wins_per_principal_per_day,
+            max_wins_per_day: request.max_wins_per_day,
+            spin_cooldown_seconds: request.spin_cooldown_seconds,
+            failed_spin_cooldown_seconds: request.failed_spin_cooldown_seconds,
+            allowed_principals: request.allowed_principals,
+            denied_principals: request.denied_principals,
+            max_token_price_age_seconds: request.max_token_price_age_seconds,
+            self_spin_enabled: request.self_spin_enabled,
+            self_spin_max_failed_attempts: request.self_spin_max_failed_attempts,
+            invite_code_required: request.invite_code_required,
+            timestamps: self
+                .wheel_prize_extraction_policy_repository
+                .get_wheel_prize_extraction_policy()
+                .timestamps,
+        };
+
+        self.wheel_prize_extraction_policy_repository
+            .update_wheel_prize_extraction_policy(policy)
+    }
+
+    async fn retry_wheel_prize_extraction_payouts(
+        &self,
+        request: RetryWheelPrizeExtractionPayoutsRequest,
+    ) -> Result<(), ApiError> {
+        let id = WheelPrizeExtractionId::try_from(request.wheel_prize_extraction_id.as_str())?;
+        let mut wheel_prize_extraction = self.get_wheel_prize_extraction_by_id(&id)?;
+
+        if !wheel_prize_extraction.is_partially_completed() {
+            return Err(ApiError::conflict(&format!(
+                "Wheel prize extraction with id {} is not partially completed",
+                id
+            )));
+        }
+
+        let failed_payouts_count = wheel_prize_extraction.reset_failed_payouts(ic_cdk::api::time());
+        if failed_payouts_count == 0 {
+            // the failed payouts are already being retried
+            return Err(ApiError::conflict(&format!(
+                "Wheel prize extraction with id {} has no failed payouts",
+                id
+            )));
+        }
+        println!(
+            "Wheel prize extraction (id:{}): Retrying {} failed payouts",
+            id, failed_payouts_count
+        );
+        self.wheel_prize_extraction_repository
+            .update_wheel_prize_extraction(id, wheel_prize_extraction.clone())?;
+
+        self.pay_out_wheel_prize_extraction(id, wheel_prize_extraction)
+            .await
+    }
+
+    fn hand_over_wheel_prize_gadget(
+        &self,
+        calling_principal: &Principal,
+        request: HandOverWheelPrizeGadgetRequest,
+    ) -> Result<(), ApiError> {
+        let id = WheelPrizeExtractionId::try_from(request.wheel_prize_extraction_id.as_str())?;
+
+        self.hand_over_gadget(calling_principal, id, request.gadget_variant_name)
+    }
+
+    fn redeem_prize(
+        &self,
+        calling_principal: &Principal,
+        request: RedeemPrizeRequest,
+    ) -> Result<(), ApiError> {
+        let id = WheelPrizeExtractionId::try_from(request.wheel_prize_extraction_id.as_str())?;
+        self.wheel_prize_redemption_service
+            .verify_redemption_code(&id, &request.redemption_code)?;
+
+        // the code can be used only once, because a gadget can't be handed over twice
+        self.hand_over_gadget(calling_principal, id, request.gadget_variant_name)
+    }
+
+    fn get_my_wheel_prize_redemption_qr_code(
+        &self,
+        calling_principal: &Principal,
+        request: GetMyWheelPrizeRedemptionQrCodeRequest,
+    ) -> Result<GetMyWheelPrizeRedemptionQrCodeResponse, ApiError> {
+        let id = WheelPrizeExtractionId::try_from(request.wheel_prize_extraction_id.as_str())?;
+        let wheel_prize_extraction = self
+            .wheel_prize_extraction_repository
+            .get_wheel_prize_extraction(&id)
+            // the extractions of the others are not found either, to not disclose them
+            .filter(|extraction| extraction.extracted_for_principal == *calling_principal)
+            .ok_or_else(|| {
+                ApiError::not_found(&format!("Wheel prize extraction with id {} not found", id))
+            })?;
+
+        if !wheel_prize_extraction.is_gadget_unclaimed() {
+            return Err(ApiError::conflict(&format!(
+                "Wheel prize extraction {} has no gadget to redeem",
+                id
+            )));
+        }
+
+        self.wheel_prize_redemption_service
+            .get_redemption_qr_code_svg(&id)
+    }
+
+    fn list_unclaimed_wheel_prize_gadgets(
+        &self,
+        request: ListUnclaimedWheelPrizeGadgetsRequest,
+    ) -> Result<ListUnclaimedWheelPrizeGadgetsResponse, ApiError> {
+        let event_id = request
+            .event_id
+            .map(|id| EventId::try_from(id.as_str()))
+            .transpose()?;
+
+        let wheel_prize_extractions = self
+            .wheel_prize_extraction_repository
+            .list_wheel_prize_extractions_by_state(&WheelPrizeExtractionState::default_completed())
+            .into_iter()
+            .filter(|(_, extraction)| {
+                extraction.is_gadget_unclaimed()
+                    && event_id.is_none_or(|event_id| extraction.event_id == Some(event_id))
+            })
+            .map(|(id, extraction)| map_wheel_prize_extraction(id, extraction))
+            .collect();
+        Ok(wheel_prize_extractions)
+    }
+
+    async fn reconcile_wheel_prize_extractions(&self) -> Result<(), ApiError> {
+        let stuck_before = get_current_date_time().sub(chrono::Duration::seconds(
+            STUCK_WHEEL_PRIZE_EXTRACTION_AGE_SECONDS,
+        ));
+        // partially completed extractions are stuck too if a retry left some payouts pending
+        let partially_completed_wheel_prize_extractions = self
+            .wheel_prize_extraction_repository
+            .list_wheel_prize_extractions_by_state(
+                &WheelPrizeExtractionState::default_partially_completed(),
+            )
+            .into_iter()
+            .filter(|(_, extraction)| extraction.has_pending_payouts());
+        let stuck_wheel_prize_extractions = self
+            .wheel_prize_extraction_repository
+            .list_wheel_prize_extractions_by_state(&WheelPrizeExtractionState::Processing)
+            .into_iter()
+            .chain(partially_completed_wheel_prize_extractions)
+            .filter(|(_, extraction)| extraction.timestamps.updated_at < stuck_before);
+
+        for (wheel_prize_extraction_id, wheel_prize_extraction) in stuck_wheel_prize_extractions {
+            println!(
+                "Wheel prize extraction (id:{}): Reconciling stuck extraction",
+                wheel_prize_extraction_id
+            );
+
+            if let Err(err) = self
+                .reconcile_wheel_prize_extraction(wheel_prize_extraction_id, wheel_prize_extraction)
+                .await
+            {
+                println!(
+                    "Wheel prize extraction (id:{}): Failed to reconcile: {}",
+                    wheel_prize_extraction_id, err
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn init_indexes(&self) -> Result<(), ApiError> {
+        self.wheel_prize_extraction_repository.init_indexes()
+    }
 }
 
 impl<
@@ -390,14 +697,25 @@ impl<
         U: UserProfileRepository,
         W: WalletService,
         WA: WheelAssetService,
-    > WheelPrizeExtractionServiceImpl<A, P, U, W, WA>
+        E: EventRepository,
+        PR: WheelPrizeExtractionPolicyRepository,
+        RS: WheelPrizeRedemptionService,
+        S: SelfSpinService,
+        IC: InviteCodeService,
+    > WheelPrizeExtractionServiceImpl<A, P, U, W, WA, E, PR, RS, S, IC>
 {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         wheel_asset_repository: A,
         wheel_prize_extraction_repository: P,
         user_profile_repository: U,
         wallet_service: W,
         wheel_asset_service: WA,
+        event_repository: E,
+        wheel_prize_extraction_policy_repository: PR,
+        wheel_prize_redemption_service: RS,
+        self_spin_service: S,
+        invite_code_service: IC,
     ) -> Self {
         Self {
             wheel_asset_repository,
@@ -405,27 +723,922 @@ impl<
             user_profile_repository,
             wallet_service,
             wheel_asset_service,
+            event_repository,
+            wheel_prize_extraction_policy_repository,
+            wheel_prize_redemption_service,
+            self_spin_service,
+            invite_code_service,
         }
     }
 
-    fn validate_create_wheel_prize_extraction_request(
+    /// Draws a prize for the account and pays it out, in the scope of the given event.
+    /// The owner of the account must have been checked against the extraction policy.
+    async fn extract(
         &self,
-        request: &CreateWheelPrizeExtractionRequest,
+        calling_principal: &Principal,
+        extracted_for: Account,
+        event_id: Option<EventId>,
+        self_spin: Option<WheelPrizeSelfSpin>,
+        invite_code_id: Option<InviteCodeId>,
     ) -> Result<(), ApiError> {
-        if request.extract_for_principal == Principal::anonymous() {
-            return Err(ApiError::invalid_argument(
-                "Extract for principal cannot be anonymous",
-            ));
-        }
+        let extracted_by_user_id = self
+            .user_profile_repository
+            .get_user_by_principal(calling_principal)
+            .ok_or_else(|| {
+                ApiError::not_found(&format!(
+                    "User profile for principal {} not found",
+                    calling_principal.to_text()
+                ))
+            })?
+            .0;
 
-        if request.extract_for_principal == ic_cdk::api::canister_self() {
-            return Err(ApiError::invalid_argument(
-                "Extract for principal cannot be this canister's principal",
-            ));
+        let mut wheel_prize_extraction =
+            WheelPrizeExtraction::new_processing(extracted_for, extracted_by_user_id, event_id);
+        wheel_prize_extraction.self_spin = self_spin;
+        wheel_prize_extraction.invite_code_id = invite_code_id;
+        let wheel_prize_extraction_id = self
+            .wheel_prize_extraction_repository
+            .create_wheel_prize_extraction(wheel_prize_extraction.clone())?;
+
+        println!(
+            "Wheel prize extraction (id:{}): processing for account {}",
+            wheel_prize_extraction_id, extracted_for
+        );
+
+        // the self-spin and invite codes are consumed before any await,
+        // so that they can't be used twice
+        let consume_result = wheel_prize_extraction
+            .self_spin
+            .as_ref()
+            .map_or(Ok(()), |self_spin| {
+                self.self_spin_service.consume_self_spin(
+                    &extracted_for.owner,
+                    self_spin,
+                    wheel_prize_extraction_id,
+                )
+            })
+            .and_then(|_| {
+                invite_code_id.map_or(Ok(()), |invite_code_id| {
+                    self.invite_code_service.consume_invite_code(
+                        &extracted_for.owner,
+                        &invite_code_id,
+                        wheel_prize_extraction_id,
+                    )
+                })
+            });
+        if let Err(error) = consume_result {
+            self.set_wheel_prize_extraction_failed(
+                wheel_prize_extraction_id,
+                &mut wheel_prize_extraction,
+                None,
+                error.clone(),
+            )?;
+            return Err(error);
         }
 
-        Ok(())
-    }
+        let max_token_price_age_seconds = self
+            .wheel_prize_extraction_policy_repository
+            .get_wheel_prize_extraction_policy()
+            .max_token_price_age_seconds();
+        let mut stale_prices = Vec::new();
+        let draw_result = self
+            .draw_wheel_prize(event_id, max_token_price_age_seconds, &mut stale_prices)
+            .await;
+        if !stale_prices.is_empty() {
+            println!(
+                "Wheel prize extraction (id:{}): {} stale token prices",
+                wheel_prize_extraction_id,
+                stale_prices.len()
+            );
+        }
+        wheel_prize_extraction.stale_prices = Some(stale_prices);
+
+        let (draw_proof, extracted_wheel_asset_id, extracted_wheel_asset) = self
+            .with_set_failed_on_error(
+                wheel_prize_extraction_id,
+                &mut wheel_prize_extraction,
+                None,
+                || async { draw_result },
+            )
+            .await?;
+
+        wheel_prize_extraction.draw_proof = Some(draw_proof);
+        self.wheel_prize_extraction_repository
+            .update_wheel_prize_extraction(
+                wheel_prize_extraction_id,
+                wheel_prize_extraction.clone(),
+            )?;
+
+        let payouts = self
+            .with_set_failed_on_error(
+                wheel_prize_extraction_id,
+                &mut wheel_prize_extraction,
+                Some(extracted_wheel_asset_id),
+                || async {
+                    self.build_wheel_prize_payouts(
+                        wheel_prize_extraction_id,
+                        extracted_wheel_asset_id,
+                        &extracted_wheel_asset,
+                    )
+                },
+            )
+            .await?;
+
+        // the payouts must be stored before calling the ledger,
+        // so that they can be retried with the same parameters if something goes wrong
+        wheel_prize_extraction.payouts = Some(payouts);
+        self.wheel_prize_extraction_repository
+            .update_wheel_prize_extraction(
+                wheel_prize_extraction_id,
+                wheel_prize_extraction.clone(),
+            )?;
+
+        self.pay_out_wheel_prize_extraction(wheel_prize_extraction_id, wheel_prize_extraction)
+            .await
+    }
+
+    /// Returns the id of the active event, failing if the event is not running.
+    /// Returns `None` if there's no active event.
+    fn get_running_event_id(&self) -> Result<Option<EventId>, ApiError> {
+        match self.event_repository.get_active_event() {
+            Some((event_id, event)) => {
+                if !event.is_running_at(&get_current_date_time()) {
+                    return Err(ApiError::conflict(&format!(
+                        "Event {} is not running",
+                        event.name
+                    )));
+                }
+                Ok(Some(event_id))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the requested event if any, or the active event otherwise.
+    fn get_requested_or_active_event_id(
+        &self,
+        event_id: Option<&str>,
+    ) -> Result<Option<EventId>, ApiError> {
+        match event_id {
+            Some(event_id) => {
+                let event_id = EventId::try_from(event_id)?;
+                if self.event_repository.get_event(&event_id).is_none() {
+                    return Err(ApiError::not_found(&format!(
+                        "Event with id {} not found",
+                        event_id
+                    )));
+                }
+                Ok(Some(event_id))
+            }
+            None => Ok(self.event_repository.get_active_event().map(|(id, _)| id)),
+        }
+    }
+
+    /// Returns the account to pay the prize out to.
+    fn validate_create_wheel_prize_extraction_request(
+        &self,
+        request: CreateWheelPrizeExtractionRequest,
+    ) -> Result<Account, ApiError> {
+        let extract_for =
+            into_recipient_account(request.extract_for_principal, request.extract_for_account)?;
+
+        if extract_for.owner == Principal::anonymous() {
+            return Err(ApiError::invalid_argument(
+                "Extract for principal cannot be anonymous",
+            ));
+        }
+
+        if extract_for.owner == ic_cdk::api::canister_self() {
+            return Err(ApiError::invalid_argument(
+                "Extract for principal cannot be this canister's principal",
+            ));
+        }
+
+        Ok(extract_for)
+    }
+
+    fn assert_invite_code_not_required(&self) -> Result<(), ApiError> {
+        if self
+            .wheel_prize_extraction_policy_repository
+            .get_wheel_prize_extraction_policy()
+            .is_invite_code_required()
+        {
+            return Err(ApiError::permission_denied(
+                "An invite code is required to be extracted",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks the extraction policy for the principal, in the scope of the given event.
+    fn assert_principal_is_eligible(
+        &self,
+        principal: &Principal,
+        event_id: Option<EventId>,
+    ) -> Result<(), ApiError> {
+        let policy = self
+            .wheel_prize_extraction_policy_repository
+            .get_wheel_prize_extraction_policy();
+        let now = get_current_date_time();
+
+        policy.assert_principal_is_allowed(principal)?;
+
+        let principal_extractions = self
+            .wheel_prize_extraction_repository
+            .list_wheel_prize_extractions_by_principal(principal, event_id)?
+            .into_iter()
+            .map(|(_, extraction)| extraction)
+            .collect::<Vec<_>>();
+        policy.assert_principal_can_be_extracted(&principal_extractions, &now)?;
+
+        if policy.max_wins_per_day.is_some() {
+            // only today's extractions are loaded, as the ids contain the creation time
+            let wins_today = self
+                .wheel_prize_extraction_repository
+                .list_wheel_prize_extractions_by_event(
+                    event_id,
+                    &WheelPrizeExtractionIdRange::created_from(now.start_of_day()),
+                )?
+                .into_iter()
+                .filter(|(_, extraction)| !extraction.is_failed())
+                .count();
+            policy.assert_daily_wins_available(wins_today, &now)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_update_wheel_prize_extraction_policy_request(
+        &self,
+        request: &UpdateWheelPrizeExtractionPolicyRequest,
+    ) -> Result<(), ApiError> {
+        for (name, limit) in [
+            ("max_wins_per_principal", request.max_wins_per_principal),
+            (
+                "max_wins_per_principal_per_day",
+                request.max_wins_per_principal_per_day,
+            ),
+            ("max_wins_per_day", request.max_wins_per_day),
+            (
+                "self_spin_max_failed_attempts",
+                request.self_spin_max_failed_attempts,
+            ),
+        ] {
+            if limit == Some(0) {
+                return Err(ApiError::invalid_argument(&format!(
+                    "{} must be greater than 0",
+                    name
+                )));
+            }
+        }
+
+        if request.max_token_price_age_seconds == Some(0) {
+            return Err(ApiError::invalid_argument(
+                "max_token_price_age_seconds must be greater than 0",
+            ));
+        }
+
+        let allowed_principals = request.allowed_principals.iter().flatten();
+        for principal in allowed_principals.clone().chain(&request.denied_principals) {
+            if *principal == Principal::anonymous() {
+                return Err(ApiError::invalid_argument(
+                    "Allowed and denied principals cannot be anonymous",
+                ));
+            }
+        }
+
+        if let Some(principal) = allowed_principals
+            .into_iter()
+            .find(|p| request.denied_principals.contains(p))
+        {
+            return Err(ApiError::invalid_argument(&format!(
+                "Principal {} cannot be both allowed and denied",
+                principal
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn build_wheel_prize_payouts(
+        &self,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+        wheel_asset_id: WheelAssetId,
+        wheel_asset: &WheelAsset,
+    ) -> Result<Vec<WheelPrizePayout>, ApiError> {
+        let created_at_time = ic_cdk::api::time();
+        let new_token_payout = |wheel_asset_id: WheelAssetId, wheel_asset_type: &WheelAssetType| {
+            let ledger_config = wheel_asset_type.ledger_config().ok_or_else(|| {
+                // should never happen
+                ApiError::internal(&format!(
+                    "Wheel asset with id {} is not a token",
+                    wheel_asset_id
+                ))
+            })?;
+
+            Ok(WheelPrizePayout::new_pending(
+                wheel_prize_extraction_id,
+                wheel_asset_id,
+                ledger_config,
+                wheel_asset_type.token_sponsor_account(),
+                wheel_asset_type.token_subaccount(&wheel_asset_id),
+                wheel_asset_type.token_prize_amount().unwrap_or(0),
+                created_at_time,
+            ))
+        };
+
+        match &wheel_asset.asset_type {
+            WheelAssetType::Token { .. } => Ok(vec![new_token_payout(
+                wheel_asset_id,
+                &wheel_asset.asset_type,
+            )?]),
+            WheelAssetType::Jackpot {
+                wheel_asset_ids: jackpot_wheel_asset_ids,
+            } => jackpot_wheel_asset_ids
+                .iter()
+                .map(|jackpot_wheel_asset_id| {
+                    let jackpot_wheel_asset = self.get_wheel_asset(jackpot_wheel_asset_id)?;
+                    new_token_payout(*jackpot_wheel_asset_id, &jackpot_wheel_asset.asset_type)
+                })
+                .collect(),
+            WheelAssetType::Gadget { .. } => Ok(vec![]),
+        }
+    }
+
+    /// Draws one of the wheel assets of the event that can be extracted.
+    ///
+    /// The token prices older than the given age are fetched again only for the drawn asset
+    /// and its jackpot components, so that an extraction calls the exchange rate canister
+    /// for one prize at most. If a price can't be fetched, all the assets with a stale price
+    /// are excluded and the prize is drawn again among the others with the same seed,
+    /// recording the excluded draw in the proof so that the whole draw can be replayed.
+    /// The stale prices and how they have been resolved are added to `stale_prices`.
+    ///
+    /// The drawn asset is checked again after the last await, because it may have been
+    /// used up or disabled in the meantime, in which case the draw fails.
+    async fn draw_wheel_prize(
+        &self,
+        event_id: Option<EventId>,
+        max_token_price_age_seconds: u64,
+        stale_prices: &mut Vec<StaleWheelAssetPrice>,
+    ) -> Result<(WheelPrizeDrawProof, WheelAssetId, WheelAsset), ApiError> {
+        let now = get_current_date_time();
+        let mut available_wheel_assets = self
+            .wheel_asset_repository
+            .list_wheel_assets_by_state(WheelAssetState::Enabled)?
+            .into_iter()
+            .filter(|(_, wheel_asset)| wheel_asset.event_id == event_id)
+            .filter_map(|(wheel_asset_id, wheel_asset)| {
+                let jackpot_components = self
+                    .wheel_asset_repository
+                    .list_jackpot_components(&wheel_asset);
+                let weight = wheel_asset.effective_draw_weight(&jackpot_components);
+                (weight > 0).then_some((wheel_asset_id, wheel_asset, weight))
+            })
+            .collect::<Vec<_>>();
+
+        let seed = random_seed().await?;
+        let mut excluded_draw_proof: Option<WheelPrizeDrawProof> = None;
+
+        loop {
+            if available_wheel_assets.is_empty() {
+                return Err(ApiError::conflict(
+                    "No wheel assets available for extraction",
+                ));
+            }
+
+            let candidates = available_wheel_assets
+                .iter()
+                .map(|(wheel_asset_id, _, weight)| WheelPrizeDrawCandidate {
+                    wheel_asset_id: *wheel_asset_id,
+                    weight: *weight,
+                })
+                .collect::<Vec<_>>();
+            let draw_proof = match excluded_draw_proof.take() {
+                Some(excluded_draw_proof) => excluded_draw_proof.redraw(candidates)?,
+                None => WheelPrizeDrawProof::draw(seed, candidates)?,
+            };
+
+            let (wheel_asset_id, wheel_asset, _) = available_wheel_assets
+                .get(draw_proof.chosen_index as usize)
+                .cloned()
+                .ok_or_else(|| {
+                    ApiError::internal(&format!(
+                        "Wheel asset at index {} not found in available wheel assets list",
+                        draw_proof.chosen_index,
+                    ))
+                })?;
+
+            let mut refresh_error = None;
+            for (stale_wheel_asset_id, stale_wheel_asset) in self.list_stale_price_wheel_assets(
+                wheel_asset_id,
+                &wheel_asset,
+                max_token_price_age_seconds,
+                &now,
+            ) {
+                let Some(last_fetched_at) =
+                    stale_wheel_asset.asset_type.usd_price_last_fetched_at()
+                else {
+                    continue;
+                };
+
+                let resolution = match self
+                    .wheel_asset_service
+                    .refresh_token_usd_price(stale_wheel_asset_id)
+                    .await
+                {
+                    Ok(usd_price) => StaleWheelAssetPriceResolution::Refreshed { usd_price },
+                    Err(error) => {
+                        refresh_error = Some(error.clone());
+                        StaleWheelAssetPriceResolution::Excluded { error }
+                    }
+                };
+                stale_prices.push(StaleWheelAssetPrice {
+                    wheel_asset_id: stale_wheel_asset_id,
+                    last_fetched_at,
+                    resolution,
+                });
+                if refresh_error.is_some() {
+                    break;
+                }
+            }
+
+            let Some(refresh_error) = refresh_error else {
+                // the prize amount is computed with the refreshed prices
+                let wheel_asset = self.get_wheel_asset(&wheel_asset_id)?;
+                self.assert_wheel_asset_can_be_drawn(&wheel_asset_id, &wheel_asset)?;
+                return Ok((draw_proof, wheel_asset_id, wheel_asset));
+            };
+
+            // the prices are not fetched again in this extraction,
+            // because the exchange rate canister is likely to fail again
+            available_wheel_assets.retain(|(id, asset, _)| {
+                let stale_wheel_assets = self.list_stale_price_wheel_assets(
+                    *id,
+                    asset,
+                    max_token_price_age_seconds,
+                    &now,
+                );
+                for (stale_wheel_asset_id, stale_wheel_asset) in &stale_wheel_assets {
+                    let already_resolved = stale_prices
+                        .iter()
+                        .any(|stale_price| &stale_price.wheel_asset_id == stale_wheel_asset_id);
+                    if let (false, Some(last_fetched_at)) = (
+                        already_resolved,
+                        stale_wheel_asset.asset_type.usd_price_last_fetched_at(),
+                    ) {
+                        stale_prices.push(StaleWheelAssetPrice {
+                            wheel_asset_id: *stale_wheel_asset_id,
+                            last_fetched_at,
+                            resolution: StaleWheelAssetPriceResolution::Excluded {
+                                error: refresh_error.clone(),
+                            },
+                        });
+                    }
+                }
+                stale_wheel_assets.is_empty()
+            });
+            excluded_draw_proof = Some(draw_proof);
+        }
+    }
+
+    /// Checks that the wheel asset and its jackpot components are still enabled
+    /// and that the asset can still be drawn.
+    fn assert_wheel_asset_can_be_drawn(
+        &self,
+        wheel_asset_id: &WheelAssetId,
+        wheel_asset: &WheelAsset,
+    ) -> Result<(), ApiError> {
+        let jackpot_components = self
+            .wheel_asset_repository
+            .list_jackpot_components(wheel_asset);
+        if !wheel_asset.is_enabled()
+            || jackpot_components
+                .iter()
+                .any(|component| !component.is_enabled())
+            || wheel_asset.effective_draw_weight(&jackpot_components) == 0
+        {
+            return Err(ApiError::conflict(&format!(
+                "Wheel asset with id {} is no longer available for extraction",
+                wheel_asset_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the wheel asset and its jackpot components
+    /// whose token price is older than the given age.
+    fn list_stale_price_wheel_assets(
+        &self,
+        wheel_asset_id: WheelAssetId,
+        wheel_asset: &WheelAsset,
+        max_token_price_age_seconds: u64,
+        now: &DateTime,
+    ) -> Vec<(WheelAssetId, WheelAsset)> {
+        let jackpot_components = wheel_asset
+            .jackpot_wheel_asset_ids()
+            .iter()
+            .filter_map(|id| {
+                self.wheel_asset_repository
+                    .get_wheel_asset(id)
+                    .map(|asset| (*id, asset))
+            });
+        std::iter::once((wheel_asset_id, wheel_asset.clone()))
+            .chain(jackpot_components)
+            .filter(|(_, asset)| {
+                asset
+                    .asset_type
+                    .is_usd_price_stale(max_token_price_age_seconds, now)
+            })
+            .collect()
+    }
+
+    /// Executes the pending payouts of the extraction, storing the outcome of each one,
+    /// and then completes the extraction according to the outcomes.
+    ///
+    /// The payouts that fail because the ledger is unavailable stay pending, so that the
+    /// reconciliation retries them with the same deduplication parameters, and the error
+    /// is returned without completing the extraction.
+    /// If a call for a payout had an unknown outcome and the ledger then rejects the retry
+    /// as too old, the payout is looked up in the ledger's transactions instead.
+    async fn pay_out_wheel_prize_extraction(
+        &self,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+        mut wheel_prize_extraction: WheelPrizeExtraction,
+    ) -> Result<(), ApiError> {
+        let wheel_asset_id = self.get_drawn_wheel_asset_id(&wheel_prize_extraction)?;
+        let payouts = wheel_prize_extraction.payouts.clone().unwrap_or_default();
+        let mut ledger_unavailable_error = None;
+
+        for (payout_index, payout) in payouts.into_iter().enumerate() {
+            if !payout.is_pending() {
+                continue;
+            }
+
+            println!(
+                "Wheel prize extraction (id:{}): Transferring payout of wheel asset {}",
+                wheel_prize_extraction_id, payout.wheel_asset_id,
+            );
+
+            let payout_wheel_asset_id = payout.wheel_asset_id;
+            let to = wheel_prize_extraction.extracted_for_account();
+            let mut outcome_unknown = payout.is_outcome_unknown();
+            let mut result = self
+                .wallet_service
+                .transfer_wheel_prize_payout(
+                    wheel_prize_extraction_id,
+                    wheel_prize_extraction.extracted_by_user_id,
+                    to,
+                    payout.clone(),
+                )
+                .await;
+
+            if outcome_unknown
+                && result
+                    .as_ref()
+                    .is_err_and(ApiError::is_ledger_transfer_too_old)
+            {
+                // the ledger can't tell anymore whether a previous call executed the payout,
+                // so it's looked up in the ledger's transactions instead of being sent again
+                println!(
+                    "Wheel prize extraction (id:{}): Looking up payout of wheel asset {} in the ledger",
+                    wheel_prize_extraction_id, payout_wheel_asset_id
+                );
+                match self
+                    .wallet_service
+                    .find_ledger_transfer(payout.sent_transfer(to))
+                    .await
+                {
+                    Ok(Some(block_index)) => result = Ok(block_index),
+                    // the payout can be retried with new deduplication parameters
+                    Ok(None) => outcome_unknown = false,
+                    Err(error) => result = Err(error),
+                }
+            }
+            if result.as_ref().is_err_and(ApiError::is_ledger_call_failed) {
+                outcome_unknown = true;
+            }
+
+            // the extraction may have been reconciled while waiting for the ledger
+            wheel_prize_extraction =
+                self.get_wheel_prize_extraction_by_id(&wheel_prize_extraction_id)?;
+            wheel_prize_extraction.set_payout_outcome_unknown(payout_index, outcome_unknown);
+
+            let payout_state = match result {
+                Ok(block_index) => {
+                    let already_completed = matches!(
+                        wheel_prize_extraction.payout_state(payout_index),
+                        Some(WheelPrizePayoutState::Completed { .. })
+                    );
+                    // the drawn asset is used when completing the extraction,
+                    // while the jackpot assets are used as soon as their payout succeeds
+                    if payout_wheel_asset_id != wheel_asset_id && !already_completed {
+                        self.mark_wheel_asset_used(payout_wheel_asset_id);
+                    }
+
+                    WheelPrizePayoutState::Completed { block_index }
+                }
+                Err(error) if error.is_ledger_unavailable() => {
+                    println!(
+                        "Wheel prize extraction (id:{}): Payout of wheel asset {} will be retried: {}",
+                        wheel_prize_extraction_id, payout_wheel_asset_id, error
+                    );
+
+                    self.wheel_prize_extraction_repository
+                        .update_wheel_prize_extraction(
+                            wheel_prize_extraction_id,
+                            wheel_prize_extraction.clone(),
+                        )?;
+                    ledger_unavailable_error = Some(error);
+                    continue;
+                }
+                Err(error) => {
+                    println!(
+                        "Wheel prize extraction (id:{}): Payout of wheel asset {} failed: {}",
+                        wheel_prize_extraction_id, payout_wheel_asset_id, error
+                    );
+
+                    WheelPrizePayoutState::Failed { error }
+                }
+            };
+
+            wheel_prize_extraction.set_payout_state(payout_index, payout_state);
+            self.wheel_prize_extraction_repository
+                .update_wheel_prize_extraction(
+                    wheel_prize_extraction_id,
+                    wheel_prize_extraction.clone(),
+                )?;
+        }
+
+        if let Some(error) = ledger_unavailable_error {
+            return Err(error);
+        }
+
+        self.complete_wheel_prize_extraction(wheel_prize_extraction_id, wheel_prize_extraction)
+    }
+
+    /// Sets the extraction as completed if all the payouts succeeded,
+    /// as failed if all of them failed, or as partially completed otherwise.
+    fn complete_wheel_prize_extraction(
+        &self,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+        mut wheel_prize_extraction: WheelPrizeExtraction,
+    ) -> Result<(), ApiError> {
+        if !wheel_prize_extraction.is_processing()
+            && !wheel_prize_extraction.is_partially_completed()
+        {
+            // already completed or failed by a concurrent reconciliation
+            return Ok(());
+        }
+
+        let wheel_asset_id = self.get_drawn_wheel_asset_id(&wheel_prize_extraction)?;
+        let payouts = wheel_prize_extraction.payouts.clone().unwrap_or_default();
+
+        if payouts.iter().any(|payout| payout.is_pending()) {
+            // a concurrent retry is still paying out, it will complete the extraction
+            return Ok(());
+        }
+
+        let completed_payouts = payouts
+            .iter()
+            .filter(|payout| matches!(payout.state, WheelPrizePayoutState::Completed { .. }))
+            .collect::<Vec<_>>();
+        let last_error = payouts
+            .iter()
+            .filter_map(|payout| match &payout.state {
+                WheelPrizePayoutState::Failed { error } => Some(error.clone()),
+                _ => None,
+            })
+            .next_back();
+
+        if let Some(error) = &last_error {
+            if completed_payouts.is_empty() {
+                self.set_wheel_prize_extraction_failed(
+                    wheel_prize_extraction_id,
+                    &mut wheel_prize_extraction,
+                    Some(wheel_asset_id),
+                    error.clone(),
+                )?;
+                return Err(error.clone());
+            }
+        }
+
+        // the drawn asset is used only once, when the extraction stops processing
+        if wheel_prize_extraction.is_processing() {
+            if payouts.is_empty() {
+                // nothing has been paid out yet, so the extraction can still fail
+                // if the asset has been used up in the meantime
+                if let Err(error) =
+                    self.get_wheel_asset(&wheel_asset_id)
+                        .and_then(|mut wheel_asset| {
+                            let jackpot_components = self
+                                .wheel_asset_repository
+                                .list_jackpot_components(&wheel_asset);
+                            wheel_asset.use_one(&jackpot_components)?;
+                            self.wheel_asset_repository
+                                .update_wheel_asset(wheel_asset_id, wheel_asset)
+                        })
+                {
+                    self.set_wheel_prize_extraction_failed(
+                        wheel_prize_extraction_id,
+                        &mut wheel_prize_extraction,
+                        Some(wheel_asset_id),
+                        error.clone(),
+                    )?;
+                    return Err(error);
+                }
+            } else {
+                self.mark_wheel_asset_used(wheel_asset_id);
+            }
+        }
+
+        // gadgets don't have payouts, and therefore no USD amount
+        let mut prize_usd_amount = None;
+        for payout in completed_payouts {
+            let Some(payout_wheel_asset) = self
+                .wheel_asset_repository
+                .get_wheel_asset(&payout.wheel_asset_id)
+            else {
+                continue;
+            };
+
+            *prize_usd_amount.get_or_insert(0.0) += payout_wheel_asset
+                .asset_type
+                .token_prize_usd_amount()
+                .unwrap_or(0.0);
+            self.wheel_asset_service
+                .schedule_token_data_fetchers(payout.wheel_asset_id, payout_wheel_asset.asset_type);
+        }
+
+        match &last_error {
+            Some(error) => wheel_prize_extraction.set_partially_completed(
+                wheel_asset_id,
+                prize_usd_amount,
+                error.clone(),
+            ),
+            None => wheel_prize_extraction.set_completed(wheel_asset_id, prize_usd_amount),
+        }
+        if wheel_prize_extraction.is_completed()
+            && self
+                .wheel_asset_repository
+                .get_wheel_asset(&wheel_asset_id)
+                .is_some_and(|wheel_asset| wheel_asset.is_gadget())
+        {
+            // the gadget still has to be handed over to the winner
+            wheel_prize_extraction.set_gadget_won();
+        }
+
+        println!(
+            "Wheel prize extraction (id:{}, state:{}): wheel asset id {:?}",
+            wheel_prize_extraction_id,
+            wheel_prize_extraction.state,
+            wheel_prize_extraction.wheel_asset_id
+        );
+
+        self.wheel_prize_extraction_repository
+            .update_wheel_prize_extraction(wheel_prize_extraction_id, wheel_prize_extraction)?;
+
+        match last_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Logs the error instead of returning it, because the prize has already been paid out.
+    fn mark_wheel_asset_used(&self, wheel_asset_id: WheelAssetId) {
+        if let Err(err) = self
+            .get_wheel_asset(&wheel_asset_id)
+            .and_then(|mut wheel_asset| {
+                wheel_asset.mark_one_used();
+                self.wheel_asset_repository
+                    .update_wheel_asset(wheel_asset_id, wheel_asset)
+            })
+        {
+            println!(
+                "Error: failed to update the used amount of wheel asset {}: {}",
+                wheel_asset_id, err
+            );
+        }
+    }
+
+    async fn reconcile_wheel_prize_extraction(
+        &self,
+        wheel_prize_extraction_id: WheelPrizeExtractionId,
+        mut wheel_prize_extraction: WheelPrizeExtraction,
+    ) -> Result<(), ApiError> {
+        // nothing has been transferred if the extraction was interrupted before storing the payouts
+        let Some(draw_proof) = &wheel_prize_extraction.draw_proof else {
+            return self.set_wheel_prize_extraction_failed(
+                wheel_prize_extraction_id,
+                &mut wheel_prize_extraction,
+                None,
+                ApiError::internal("Extraction interrupted before the draw"),
+            );
+        };
+        if wheel_prize_extraction.payouts.is_none() {
+            let wheel_asset_id = draw_proof.chosen_wheel_asset_id();
+            return self.set_wheel_prize_extraction_failed(
+                wheel_prize_extraction_id,
+                &mut wheel_prize_extraction,
+                wheel_asset_id,
+                ApiError::internal("Extraction interrupted before the payout"),
+            );
+        }
+
+        self.pay_out_wheel_prize_extraction(wheel_prize_extraction_id, wheel_prize_extraction)
+            .await
+    }
+
+    /// Takes the gadget out of the stock and marks it as handed over to the winner,
+    /// so that the redemption QR code is not valid anymore.
+    fn hand_over_gadget(
+        &self,
+        calling_principal: &Principal,
+        id: WheelPrizeExtractionId,
+        gadget_variant_name: Option<String>,
+    ) -> Result<(), ApiError> {
+        let handed_over_by_user_id = self
+            .user_profile_repository
+            .get_user_by_principal(calling_principal)
+            .ok_or_else(|| {
+                ApiError::not_found(&format!(
+                    "User profile for principal {} not found",
+                    calling_principal.to_text()
+                ))
+            })?
+            .0;
+        let mut wheel_prize_extraction = self.get_wheel_prize_extraction_by_id(&id)?;
+        let wheel_asset_id = self.get_drawn_wheel_asset_id(&wheel_prize_extraction)?;
+        let mut wheel_asset = self.get_wheel_asset(&wheel_asset_id)?;
+
+        wheel_prize_extraction.set_gadget_handed_over(
+            get_current_date_time(),
+            handed_over_by_user_id,
+            gadget_variant_name.clone(),
+        )?;
+        wheel_asset.hand_over_gadget_variant(gadget_variant_name.as_deref())?;
+
+        self.wheel_asset_repository
+            .update_wheel_asset(wheel_asset_id, wheel_asset)?;
+        self.wheel_prize_extraction_repository
+            .update_wheel_prize_extraction(id, wheel_prize_extraction)?;
+
+        println!(
+            "Wheel prize extraction (id:{}): Gadget handed over (variant:{:?})",
+            id, gadget_variant_name
+        );
+
+        Ok(())
+    }
+
+    fn get_wheel_prize_extraction_by_id(
+        &self,
+        id: &WheelPrizeExtractionId,
+    ) -> Result<WheelPrizeExtraction, ApiError> {
+        self.wheel_prize_extraction_repository
+            .get_wheel_prize_extraction(id)
+            .ok_or_else(|| {
+                ApiError::not_found(&format!("Wheel prize extraction with id {} not found", id))
+            })
+    }
+
+    fn get_wheel_asset(&self, id: &WheelAssetId) -> Result<WheelAsset, ApiError> {
+        self.wheel_asset_repository
+            .get_wheel_asset(id)
+            .ok_or_else(|| ApiError::not_found(&format!("Wheel asset with id {} not found", id)))
+    }
+
+    fn get_drawn_wheel_asset_id(
+        &self,
+        wheel_prize_extraction: &WheelPrizeExtraction,
+    ) -> Result<WheelAssetId, ApiError> {
+        wheel_prize_extraction
+            .draw_proof
+            .as_ref()
+            .ok_or_else(|| ApiError::internal("Wheel prize extraction has no draw proof"))?
+            .chosen_wheel_asset_id()
+            .ok_or_else(|| {
+                ApiError::internal("Wheel prize extraction has an inconsistent draw proof")
+            })
+    }
+
+    fn set_wheel_prize_extraction_failed(
+        &self,
+        extraction_id: WheelPrizeExtractionId,
+        extraction: &mut WheelPrizeExtraction,
+        wheel_asset_id: Option<WheelAssetId>,
+        error: ApiError,
+    ) -> Result<(), ApiError> {
+        extraction.set_failed(wheel_asset_id, error);
+        println!(
+            "Wheel prize extraction (id:{}): {}",
+            extraction_id, extraction.state
+        );
+        self.wheel_prize_extraction_repository
+            .update_wheel_prize_extraction(extraction_id, extraction.clone())
+    }
 
     async fn with_set_failed_on_error<F, Fut, T>(
         &self,
@@ -441,24 +1654,14 @@ impl<
         match f().await {
             Ok(result) => Ok(result),
             Err(error) => {
-                extraction.set_failed(wheel_asset_id, error.clone());
-                println!(
-                    "Wheel prize extraction (id:{}): {}",
-                    extraction_id, extraction.state
-                );
-                self.wheel_prize_extraction_repository
-                    .update_wheel_prize_extraction(extraction_id, extraction.clone())?;
+                self.set_wheel_prize_extraction_failed(
+                    extraction_id,
+                    extraction,
+                    wheel_asset_id,
+                    error.clone(),
+                )?;
                 Err(error)
             }
         }
     }
 }
-
-/// Extracts a random `usize` in the range `[0, max)`.
-async fn random_index(max: usize) -> Result<usize, ApiError> {
-    let mut rng = chacha20_rng().await?;
-    let random_index = (0..max)
-        .choose(&mut rng)
-        .ok_or_else(|| ApiError::internal("Failed to generate random index"))?;
-    Ok(random_index)
-}
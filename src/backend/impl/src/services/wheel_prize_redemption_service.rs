@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use backend_api::ApiError;
+use ic_cdk::println;
+
+use crate::{
+    repositories::{
+        wheel_prize_redemption_qr_code_svg, WheelPrizeExtractionId, WheelPrizeRedemptionRepository,
+        WheelPrizeRedemptionRepositoryImpl, WheelPrizeRedemptionSecret,
+    },
+    system_api::{random_seed, spawn_with_retry, RetryPolicy},
+};
+
+/// The redemption codes can't be generated without the secret,
+/// so the generation is retried for about an hour
+const REDEMPTION_SECRET_GENERATION_RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 7,
+    initial_delay: Duration::from_secs(60),
+};
+
+#[cfg_attr(test, mockall::automock)]
+pub trait WheelPrizeRedemptionService {
+    /// Generates the secret that signs the redemption codes in the background,
+    /// if it hasn't been generated yet.
+    fn init_redemption_secret(&self);
+
+    /// Renders the QR code with which the winner redeems the gadget won with the extraction,
+    /// as an SVG image. It's derived from the secret, so it's never stored.
+    fn get_redemption_qr_code_svg(
+        &self,
+        wheel_prize_extraction_id: &WheelPrizeExtractionId,
+    ) -> Result<String, ApiError>;
+
+    fn verify_redemption_code(
+        &self,
+        wheel_prize_extraction_id: &WheelPrizeExtractionId,
+        redemption_code: &str,
+    ) -> Result<(), ApiError>;
+}
+
+pub struct WheelPrizeRedemptionServiceImpl<R: WheelPrizeRedemptionRepository> {
+    wheel_prize_redemption_repository: R,
+}
+
+impl Default for WheelPrizeRedemptionServiceImpl<WheelPrizeRedemptionRepositoryImpl> {
+    fn default() -> Self {
+        Self::new(WheelPrizeRedemptionRepositoryImpl::default())
+    }
+}
+
+impl<R: WheelPrizeRedemptionRepository> WheelPrizeRedemptionService
+    for WheelPrizeRedemptionServiceImpl<R>
+{
+    fn init_redemption_secret(&self) {
+        if self
+            .wheel_prize_redemption_repository
+            .get_wheel_prize_redemption_secret()
+            .is_some()
+        {
+            return;
+        }
+
+        spawn_with_retry(
+            "generate_redemption_secret",
+            REDEMPTION_SECRET_GENERATION_RETRY_POLICY,
+            |_| async {
+                WheelPrizeRedemptionServiceImpl::default()
+                    .generate_redemption_secret()
+                    .await
+            },
+        );
+    }
+
+    fn get_redemption_qr_code_svg(
+        &self,
+        wheel_prize_extraction_id: &WheelPrizeExtractionId,
+    ) -> Result<String, ApiError> {
+        let redemption_code = self
+            .get_redemption_secret()?
+            .redemption_code(wheel_prize_extraction_id);
+
+        wheel_prize_redemption_qr_code_svg(wheel_prize_extraction_id, &redemption_code)
+    }
+
+    fn verify_redemption_code(
+        &self,
+        wheel_prize_extraction_id: &WheelPrizeExtractionId,
+        redemption_code: &str,
+    ) -> Result<(), ApiError> {
+        self.get_redemption_secret()?
+            .verify_redemption_code(wheel_prize_extraction_id, redemption_code)
+    }
+}
+
+impl<R: WheelPrizeRedemptionRepository> WheelPrizeRedemptionServiceImpl<R> {
+    fn new(wheel_prize_redemption_repository: R) -> Self {
+        Self {
+            wheel_prize_redemption_repository,
+        }
+    }
+
+    async fn generate_redemption_secret(&self) -> Result<(), ApiError> {
+        let secret = random_seed().await?;
+
+        // another generation may have completed while waiting for the randomness
+        if self
+            .wheel_prize_redemption_repository
+            .get_wheel_prize_redemption_secret()
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        self.wheel_prize_redemption_repository
+            .set_wheel_prize_redemption_secret(WheelPrizeRedemptionSecret::new(secret))?;
+        println!("generate_redemption_secret: Redemption secret generated");
+
+        Ok(())
+    }
+
+    fn get_redemption_secret(&self) -> Result<WheelPrizeRedemptionSecret, ApiError> {
+        self.wheel_prize_redemption_repository
+            .get_wheel_prize_redemption_secret()
+            .ok_or_else(|| ApiError::internal("Wheel prize redemption secret not generated yet"))
+    }
+}
@@ -1,5 +1,7 @@
 mod rand;
+mod retry;
 mod time;
 
 pub use rand::*;
+pub use retry::*;
 pub use time::*;
@@ -1,28 +1,24 @@
 use backend_api::ApiError;
 use fastrand::Rng;
 use ic_cdk::management_canister::raw_rand;
-use rand::prelude::*;
-use rand_chacha::ChaCha20Rng;
 use std::cell::RefCell;
 
 thread_local! {
   static RNG: RefCell<Rng> = create_rng();
 }
 
-pub async fn chacha20_rng() -> Result<ChaCha20Rng, ApiError> {
-    let seed: [u8; 32] = {
-        let seed = raw_rand().await.map_err(|err| {
-            ApiError::internal(&format!("System API call to `raw_rand` failed: {}", err))
-        })?;
-
-        seed.try_into().map_err(|err| {
-            ApiError::internal(&format!(
-                "System API call to `raw_rand` did not return 32 bytes: ({:?})",
-                err
-            ))
-        })
-    }?;
-    Ok(ChaCha20Rng::from_seed(seed))
+/// Returns 32 bytes of randomness from the management canister.
+pub async fn random_seed() -> Result<[u8; 32], ApiError> {
+    let seed = raw_rand().await.map_err(|err| {
+        ApiError::internal(&format!("System API call to `raw_rand` failed: {}", err))
+    })?;
+
+    seed.try_into().map_err(|err| {
+        ApiError::internal(&format!(
+            "System API call to `raw_rand` did not return 32 bytes: ({:?})",
+            err
+        ))
+    })
 }
 
 fn create_rng() -> RefCell<Rng> {
@@ -0,0 +1,98 @@
+use std::{future::Future, rc::Rc, time::Duration};
+
+use backend_api::ApiError;
+use ic_cdk::{futures::spawn, println};
+use ic_cdk_timers::set_timer;
+
+/// How many times and how often a failing task is retried.
+/// The delay between two attempts doubles after each failed attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// The delay between the first and the second attempt.
+    pub initial_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// The delay before the given attempt, starting from 1.
+    /// The first attempt runs immediately.
+    pub fn delay_before_attempt(&self, attempt: u32) -> Duration {
+        if attempt <= 1 {
+            return Duration::ZERO;
+        }
+
+        self.initial_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 2))
+    }
+}
+
+/// Immediately (= after 0 seconds) starts the task and, while it fails,
+/// schedules it again with an exponential backoff until the maximum number of attempts is reached.
+///
+/// The task receives the number of the current attempt, starting from 1.
+pub fn spawn_with_retry<F, Fut>(name: &'static str, policy: RetryPolicy, task: F)
+where
+    F: Fn(u32) -> Fut + 'static,
+    Fut: Future<Output = Result<(), ApiError>> + 'static,
+{
+    schedule_attempt(name, policy, Rc::new(task), 1);
+}
+
+fn schedule_attempt<F, Fut>(name: &'static str, policy: RetryPolicy, task: Rc<F>, attempt: u32)
+where
+    F: Fn(u32) -> Fut + 'static,
+    Fut: Future<Output = Result<(), ApiError>> + 'static,
+{
+    set_timer(policy.delay_before_attempt(attempt), move || {
+        spawn(async move {
+            let Err(err) = task(attempt).await else {
+                return;
+            };
+
+            if attempt >= policy.max_attempts {
+                println!(
+                    "Error: {}: attempt {}/{} failed, giving up: {}",
+                    name, attempt, policy.max_attempts, err
+                );
+                return;
+            }
+
+            let next_attempt = attempt + 1;
+            println!(
+                "Error: {}: attempt {}/{} failed, retrying in {}s: {}",
+                name,
+                attempt,
+                policy.max_attempts,
+                policy.delay_before_attempt(next_attempt).as_secs(),
+                err
+            );
+            schedule_attempt(name, policy, task, next_attempt);
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(1, 0)]
+    #[case(2, 30)]
+    #[case(3, 60)]
+    #[case(4, 120)]
+    #[case(5, 240)]
+    fn retry_policy_delay_before_attempt(#[case] attempt: u32, #[case] expected_delay_secs: u64) {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_delay: Duration::from_secs(30),
+        };
+
+        assert_eq!(
+            policy.delay_before_attempt(attempt),
+            Duration::from_secs(expected_delay_secs)
+        );
+    }
+}
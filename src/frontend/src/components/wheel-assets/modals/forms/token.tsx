@@ -67,13 +67,16 @@ type CreateAssetTokenFormSchemaType = Omit<
 > &
   Omit<
     Extract<CreateWheelAssetTypeConfig, { token: unknown }>['token'],
-    'ledger_config' | 'exchange_rate_symbol'
+    'ledger_config' | 'exchange_rate_symbol' | 'funding_source'
   > & {
     exchange_rate_symbol: string | undefined;
-  } & Extract<
-    CreateWheelAssetTypeConfig,
-    { token: unknown }
-  >['token']['ledger_config'] &
+  } & Omit<
+    Extract<
+      CreateWheelAssetTypeConfig,
+      { token: unknown }
+    >['token']['ledger_config'],
+    'decimals'
+  > &
   ImagesFormFieldsProps;
 
 const createAssetTokenFormSchema = z.object<
@@ -81,7 +84,6 @@ const createAssetTokenFormSchema = z.object<
 >({
   name: AssetNameSchema,
   ledger_canister_id: PrincipalSchema,
-  decimals: z.coerce.number().min(0).max(20),
   exchange_rate_symbol: z.string().optional(),
   prize_usd_amount: z.number().min(0.5).max(500),
   total_amount: AssetTotalAmountSchema,
@@ -113,8 +115,6 @@ export const AssetTokenForm: React.FC<AssetTokenFormProps> = ({
           return {
             name: existingWheelAsset.name,
             total_amount: existingWheelAsset.total_amount,
-            decimals:
-              existingWheelAsset.asset_type.token.ledger_config.decimals,
             exchange_rate_symbol:
               existingWheelAsset.asset_type.token.exchange_rate_symbol[0],
             prize_usd_amount:
@@ -153,7 +153,6 @@ export const AssetTokenForm: React.FC<AssetTokenFormProps> = ({
         shouldTouch: true,
       };
       form.setValue('name', metadata.symbol, validationSettings);
-      form.setValue('decimals', metadata.decimals, validationSettings);
 
       if (metadata.icon) {
         const iconFile = fileFromBase64(metadata.icon, 'iconFile');
@@ -161,7 +160,7 @@ export const AssetTokenForm: React.FC<AssetTokenFormProps> = ({
       }
     },
     onError: () => {
-      form.setError('decimals', {
+      form.setError('ledger_canister_id', {
         type: 'custom',
         message: 'Failed to fetch token metadata',
       });
@@ -181,12 +180,13 @@ export const AssetTokenForm: React.FC<AssetTokenFormProps> = ({
           asset_type_config: {
             token: {
               prize_usd_amount: candidOpt(data.prize_usd_amount),
+              prize_token_amount: [],
+              funding_source: [],
               exchange_rate_symbol: candidOpt(
                 data.exchange_rate_symbol || null,
               ),
-              ledger_config: candidOpt({
-                decimals: candidOpt(data.decimals),
-              }),
+              // the decimals are read from the ledger
+              ledger_config: [],
             },
           },
         })
@@ -197,12 +197,14 @@ export const AssetTokenForm: React.FC<AssetTokenFormProps> = ({
             token: {
               ledger_config: {
                 ledger_canister_id: data.ledger_canister_id,
-                decimals: data.decimals,
+                // the decimals are read from the ledger
+                decimals: [],
               },
               exchange_rate_symbol: candidOpt(
                 data.exchange_rate_symbol || null,
               ),
               prize_usd_amount: data.prize_usd_amount,
+              funding_source: [],
             },
           },
         });
@@ -231,7 +233,7 @@ export const AssetTokenForm: React.FC<AssetTokenFormProps> = ({
   const onSelectToken = useCallback(
     async (token: AvailableTokens) => {
       setSelectedToken(token);
-      form.clearErrors('decimals');
+      form.clearErrors('ledger_canister_id');
 
       if (token === 'custom') {
         const validationSettings = {
@@ -242,8 +244,6 @@ export const AssetTokenForm: React.FC<AssetTokenFormProps> = ({
         // @ts-expect-error The form expects a principal
         form.setValue('ledger_canister_id', '', validationSettings);
         form.setValue('name', '', validationSettings);
-        // @ts-expect-error The form expects a number
-        form.setValue('decimals', '', validationSettings);
         form.setValue('exchange_rate_symbol', '', validationSettings);
         form.setValue('wheel_image_file', undefined, validationSettings);
       } else {
@@ -259,11 +259,6 @@ export const AssetTokenForm: React.FC<AssetTokenFormProps> = ({
           validationSettings,
         );
         form.setValue('name', tokenData.name, validationSettings);
-        form.setValue(
-          'decimals',
-          tokenData.ledger_config.decimals,
-          validationSettings,
-        );
         form.setValue(
           'exchange_rate_symbol',
           tokenData.exchange_rate_symbol[0] || '',
@@ -332,7 +327,7 @@ export const AssetTokenForm: React.FC<AssetTokenFormProps> = ({
               control={form.control}
               name="name"
               render={({ field }) => (
-                <FormItem>
+                <FormItem className="col-span-full">
                   <FormLabel>Name *</FormLabel>
                   <FormControl>
                     <Input
@@ -349,28 +344,6 @@ export const AssetTokenForm: React.FC<AssetTokenFormProps> = ({
                 </FormItem>
               )}
             />
-            <FormField
-              control={form.control}
-              name="decimals"
-              render={({ field }) => (
-                <FormItem>
-                  <FormLabel>Decimals *</FormLabel>
-                  <FormControl>
-                    <Input
-                      type="number"
-                      placeholder={
-                        isFetchingLedgerCanisterMetadata
-                          ? 'Fetching...'
-                          : 'Decimals'
-                      }
-                      {...field}
-                      disabled={isFetchingLedgerCanisterMetadata}
-                    />
-                  </FormControl>
-                  <FormMessage />
-                </FormItem>
-              )}
-            />
             <FormField
               control={form.control}
               name="exchange_rate_symbol"
@@ -43,23 +43,31 @@ import { useForm, useWatch } from 'react-hook-form';
 import { z } from 'zod';
 import { useWheelAssetTokens } from '@/hooks/use-wheel-asset-tokens';
 import { useTransferToken } from '@/hooks/use-transfer-token';
+import { useToast } from '@/hooks/use-toast';
 
 const transferTokenFormSchema = z.object<
   ZodProperties<
-    Omit<TransferTokenRequest, 'amount'> & {
+    Omit<
+      TransferTokenRequest,
+      'amount' | 'to' | 'to_account' | 'from_subaccount' | 'reason'
+    > & {
+      to: Principal;
       amount: number;
+      reason: string;
     }
   >
 >({
   ledger_canister_id: PrincipalSchema,
   to: PrincipalSchema,
   amount: z.coerce.number().min(0),
+  reason: z.string().trim().min(1).max(500),
 });
 
 export const SendTokenModal = () => {
   const { tokenAssets } = useWheelAssetTokens();
   const { mutateAsync: transferToken, isPending: isTransferring } =
     useTransferToken();
+  const { toast } = useToast();
   const form = useForm<z.infer<typeof transferTokenFormSchema>>({
     resolver: zodResolver(transferTokenFormSchema),
     mode: 'onChange',
@@ -120,11 +128,20 @@ export const SendTokenModal = () => {
       return;
     }
     const amount = floatToBigInt(data.amount, selectedTokenAssetDecimals);
-    await transferToken({
+    const outcome = await transferToken({
       ledger_canister_id: data.ledger_canister_id,
-      to: data.to,
+      to: [data.to],
+      to_account: [],
+      from_subaccount: [],
       amount,
+      reason: [data.reason],
     });
+    if ('pending_approval' in outcome) {
+      toast({
+        title: 'Transfer pending approval',
+        description: `The amount requires the approval of a second admin (manual transfer ID: ${outcome.pending_approval.manual_transfer_id}).`,
+      });
+    }
     form.reset();
     setOpen(false);
   };
@@ -255,6 +272,25 @@ export const SendTokenModal = () => {
                   </FormItem>
                 )}
               />
+              <FormField
+                control={form.control}
+                name="reason"
+                render={({ field }) => (
+                  <FormItem>
+                    <FormLabel>Reason *</FormLabel>
+                    <FormControl>
+                      <Input
+                        placeholder="Why the transfer is needed"
+                        {...field}
+                      />
+                    </FormControl>
+                    <FormDescription>
+                      Kept in the audit trail of the transfer.
+                    </FormDescription>
+                    <FormMessage />
+                  </FormItem>
+                )}
+              />
             </div>
             <AlertDialogFooter>
               <AlertDialogCancel>Cancel</AlertDialogCancel>
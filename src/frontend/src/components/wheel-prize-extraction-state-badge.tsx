@@ -52,5 +52,24 @@ export function WheelPrizeExtractionStateBadge({
     );
   }
 
+  if ('partially_completed' in state) {
+    return (
+      <Popover>
+        <PopoverTrigger asChild>
+          <Badge variant="warning" className="cursor-pointer">
+            <AlertCircle />
+            Partially Completed
+          </Badge>
+        </PopoverTrigger>
+        <PopoverContent>
+          <div className="font-medium">Error Details</div>
+          <p className="text-muted-foreground mt-2 text-sm">
+            {renderError(state.partially_completed.error)}
+          </p>
+        </PopoverContent>
+      </Popover>
+    );
+  }
+
   return <Badge variant="secondary">Unknown State</Badge>;
 }
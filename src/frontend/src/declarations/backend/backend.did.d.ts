@@ -2,30 +2,79 @@ import type { Principal } from '@dfinity/principal';
 import type { ActorMethod } from '@dfinity/agent';
 import type { IDL } from '@dfinity/candid';
 
+export interface Account {
+  'owner' : Principal,
+  'subaccount' : [] | [Uint8Array | number[]],
+}
+export interface ApproveManualTransferRequest { 'manual_transfer_id' : string }
+export type ApproveManualTransferResponse = { 'ok' : bigint } |
+  { 'err' : Err };
 export interface CreateCustomDomainRecordRequest { 'domain_name' : string }
 export type CreateCustomDomainRecordResponse = { 'ok' : CustomDomainRecord } |
   { 'err' : Err };
+export interface CreateEventRequest {
+  'name' : string,
+  'end_date' : string,
+  'start_date' : string,
+}
+export type CreateEventResponse = { 'ok' : Event } |
+  { 'err' : Err };
 export type CreateMyUserProfileResponse = { 'ok' : UserProfile } |
   { 'err' : Err };
+export interface CreateMyWheelPrizeExtractionRequest {
+  'self_spin_code' : string,
+}
+export type CreateMyWheelPrizeExtractionResponse = { 'ok' : null } |
+  { 'err' : Err };
+export interface CreateSelfSpinInviteRequest {
+  'principal' : [] | [Principal],
+  'event_id' : [] | [string],
+}
+export type CreateSelfSpinInviteResponse = {
+    'ok' : { 'invite_code' : string, 'invite_code_id' : string }
+  } |
+  { 'err' : Err };
 export interface CreateWheelAssetRequest {
   'total_amount' : number,
   'asset_type_config' : CreateWheelAssetTypeConfig,
   'name' : string,
+  'event_id' : [] | [string],
   'wheel_ui_settings' : [] | [WheelAssetUiSettings],
+  'draw_weight' : [] | [WheelAssetDrawWeight],
 }
 export type CreateWheelAssetResponse = { 'ok' : WheelAsset } |
   { 'err' : Err };
+export interface CreateWheelAssetTokenLedgerConfig {
+  'decimals' : [] | [number],
+  'ledger_canister_id' : Principal,
+}
 export type CreateWheelAssetTypeConfig = {
     'token' : {
+      'funding_source' : [] | [WheelAssetTokenFundingSource],
       'exchange_rate_symbol' : [] | [string],
       'prize_usd_amount' : number,
-      'ledger_config' : WheelAssetTokenLedgerConfig,
+      'ledger_config' : CreateWheelAssetTokenLedgerConfig,
     }
   } |
   { 'jackpot' : { 'wheel_asset_ids' : Array<string> } } |
-  { 'gadget' : { 'article_type' : [] | [string] } };
+  {
+    'gadget' : {
+      'variants' : [] | [Array<WheelAssetGadgetVariantConfig>],
+      'article_type' : [] | [string],
+    }
+  } |
+  {
+    'token_fixed_amount' : {
+      'funding_source' : [] | [WheelAssetTokenFundingSource],
+      'exchange_rate_symbol' : [] | [string],
+      'prize_token_amount' : bigint,
+      'ledger_config' : CreateWheelAssetTokenLedgerConfig,
+    }
+  };
 export interface CreateWheelPrizeExtractionRequest {
-  'extract_for_principal' : Principal,
+  'invite_code' : [] | [string],
+  'extract_for_principal' : [] | [Principal],
+  'extract_for_account' : [] | [RecipientAccount],
 }
 export type CreateWheelPrizeExtractionResponse = { 'ok' : null } |
   { 'err' : Err };
@@ -45,30 +94,117 @@ export type CustomDomainRecordBnRegistrationState = {
 export interface DeleteCustomDomainRecordRequest { 'id' : string }
 export type DeleteCustomDomainRecordResponse = { 'ok' : null } |
   { 'err' : Err };
+export interface DeleteEventRequest { 'id' : string }
+export type DeleteEventResponse = { 'ok' : null } |
+  { 'err' : Err };
 export interface DeleteUserProfileRequest { 'user_id' : string }
 export type DeleteUserProfileResponse = { 'ok' : null } |
   { 'err' : Err };
 export interface DeleteWheelAssetRequest { 'id' : string }
 export type DeleteWheelAssetResponse = { 'ok' : null } |
   { 'err' : Err };
-export interface Err { 'code' : number, 'message' : string }
+export interface Err {
+  'code' : number,
+  'kind' : [] | [ErrKind],
+  'message' : string,
+}
+export type ErrKind = {
+    'extraction_policy_rejected' : {
+      'rejection' : ExtractionPolicyRejection,
+      'retry_after_seconds' : [] | [bigint],
+    }
+  } |
+  {
+    'ledger_transfer_rejected' : {
+      'rejection' : LedgerTransferRejection,
+      'ledger_canister_id' : Principal,
+    }
+  } |
+  { 'ledger_call_failed' : { 'ledger_canister_id' : Principal } };
+export interface Event {
+  'id' : string,
+  'has_self_spin_code' : boolean,
+  'updated_at' : string,
+  'active' : boolean,
+  'self_spin_max_failed_attempts' : number,
+  'name' : string,
+  'self_spin_code_max_spins' : [] | [number],
+  'end_date' : string,
+  'created_at' : string,
+  'start_date' : string,
+  'self_spin_failed_attempts' : number,
+}
+export type ExportInviteCodesResponse = { 'ok' : string } |
+  { 'err' : Err };
+export type ExtractionPolicyRejection = {
+    'max_wins_per_principal' : { 'max_wins' : number }
+  } |
+  { 'failed_spin_cooldown' : null } |
+  { 'principal_not_allowed' : null } |
+  { 'principal_denied' : null } |
+  { 'max_wins_per_principal_per_day' : { 'max_wins' : number } } |
+  { 'spin_cooldown' : null } |
+  { 'max_wins_per_day' : { 'max_wins' : number } };
 export type FetchTokensDataResponse = { 'ok' : null } |
   { 'err' : Err };
+export interface GenerateInviteCodesRequest {
+  'principal' : [] | [Principal],
+  'count' : number,
+  'event_id' : [] | [string],
+  'purpose' : [] | [InviteCodePurpose],
+}
+export type GenerateInviteCodesResponse = {
+    'ok' : Array<GeneratedInviteCode>
+  } |
+  { 'err' : Err };
+export interface GeneratedInviteCode {
+  'invite_code' : string,
+  'invite_code_id' : string,
+}
+export type GetActiveEventResponse = { 'ok' : [] | [Event] } |
+  { 'err' : Err };
 export type GetLastWheelPrizeExtractionResponse = {
     'ok' : [] | [WheelPrizeExtraction]
   } |
   { 'err' : Err };
 export type GetMyUserProfileResponse = { 'ok' : UserProfile } |
   { 'err' : Err };
+export type GetMyWheelPrizeExtractionsResponse = {
+    'ok' : Array<MyWheelPrizeExtraction>
+  } |
+  { 'err' : Err };
+export interface GetMyWheelPrizeRedemptionQrCodeRequest {
+  'wheel_prize_extraction_id' : string,
+}
+export type GetMyWheelPrizeRedemptionQrCodeResponse = { 'ok' : string } |
+  { 'err' : Err };
+export interface GetWheelPrizeDrawProofRequest {
+  'wheel_prize_extraction_id' : string,
+}
+export type GetWheelPrizeDrawProofResponse = { 'ok' : WheelPrizeDrawProof } |
+  { 'err' : Err };
+export type GetWheelPrizeExtractionPolicyResponse = {
+    'ok' : WheelPrizeExtractionPolicy
+  } |
+  { 'err' : Err };
 export interface GetWheelPrizeExtractionRequest {
   'wheel_prize_extraction_id' : string,
 }
 export type GetWheelPrizeExtractionResponse = { 'ok' : WheelPrizeExtraction } |
   { 'err' : Err };
+export interface GetWheelPrizeExtractionsStatsRequest {
+  'event_id' : [] | [string],
+}
 export type GetWheelPrizeExtractionsStatsResponse = {
     'ok' : WheelPrizeExtractionsStats
   } |
   { 'err' : Err };
+export interface HandOverWheelPrizeGadgetRequest {
+  'wheel_prize_extraction_id' : string,
+  'gadget_variant_name' : [] | [string],
+}
+export type HandOverWheelPrizeGadgetResponse = { 'ok' : null } |
+  { 'err' : Err };
 export type HeaderField = [string, string];
 export interface HttpRequest {
   'url' : string,
@@ -82,29 +218,221 @@ export interface HttpResponse {
   'headers' : Array<HeaderField>,
   'status_code' : number,
 }
+export interface InviteCode {
+  'id' : string,
+  'updated_at' : string,
+  'principal' : [] | [Principal],
+  'created_at' : string,
+  'state' : InviteCodeState,
+  'created_by_user_id' : string,
+  'event_id' : [] | [string],
+  'purpose' : [] | [InviteCodePurpose],
+}
+export type InviteCodePurpose = { 'attendance' : null } |
+  { 'self_spin' : null };
+export type InviteCodeState = {
+    'revoked' : { 'revoked_at' : string, 'revoked_by_user_id' : string }
+  } |
+  {
+    'used' : {
+      'principal' : Principal,
+      'wheel_prize_extraction_id' : string,
+      'used_at' : string,
+    }
+  } |
+  { 'available' : null };
+export interface LedgerTransfer {
+  'id' : string,
+  'to' : Account,
+  'fee' : [] | [bigint],
+  'wheel_prize_extraction_id' : [] | [string],
+  'block_index' : bigint,
+  'from' : Account,
+  'memo' : [] | [Uint8Array | number[]],
+  'initiated_by_user_id' : [] | [string],
+  'created_at' : string,
+  'ledger_canister_id' : Principal,
+  'wheel_asset_id' : [] | [string],
+  'amount' : bigint,
+}
+export type LedgerTransferRejection = {
+    'insufficient_funds' : { 'balance' : bigint }
+  } |
+  { 'created_in_future' : { 'ledger_time' : bigint } } |
+  { 'generic_error' : { 'message' : string, 'error_code' : bigint } } |
+  { 'temporarily_unavailable' : null } |
+  { 'too_old' : null } |
+  { 'bad_fee' : { 'expected_fee' : bigint } } |
+  { 'bad_burn' : { 'min_burn_amount' : bigint } } |
+  { 'insufficient_allowance' : { 'allowance' : bigint } };
+export interface LedgerTransfersPage {
+  'transfers' : Array<LedgerTransfer>,
+  'next_start_after' : [] | [string],
+}
 export type ListCustomDomainRecordsResponse = {
     'ok' : Array<CustomDomainRecord>
   } |
   { 'err' : Err };
+export type ListEventsResponse = { 'ok' : Array<Event> } |
+  { 'err' : Err };
+export interface ListInviteCodesRequest {
+  'event_id' : [] | [string],
+  'purpose' : [] | [InviteCodePurpose],
+}
+export type ListInviteCodesResponse = { 'ok' : Array<InviteCode> } |
+  { 'err' : Err };
+export interface ListLedgerTransfersRequest {
+  'principal' : [] | [Principal],
+  'wheel_prize_extraction_id' : [] | [string],
+  'start_after' : [] | [string],
+  'limit' : [] | [number],
+  'ledger_canister_id' : [] | [Principal],
+}
+export type ListLedgerTransfersResponse = { 'ok' : LedgerTransfersPage } |
+  { 'err' : Err };
+export type ListManualTransferLimitsResponse = {
+    'ok' : Array<ManualTransferLimit>
+  } |
+  { 'err' : Err };
+export type ListManualTransfersResponse = { 'ok' : Array<ManualTransfer> } |
+  { 'err' : Err };
+export interface ListUnclaimedWheelPrizeGadgetsRequest {
+  'event_id' : [] | [string],
+}
+export type ListUnclaimedWheelPrizeGadgetsResponse = {
+    'ok' : Array<WheelPrizeExtraction>
+  } |
+  { 'err' : Err };
 export type ListUsersResponse = { 'ok' : Array<UserProfile> } |
   { 'err' : Err };
 export interface ListWheelAssetsRequest { 'state' : [] | [WheelAssetState] }
 export type ListWheelAssetsResponse = { 'ok' : Array<WheelAsset> } |
   { 'err' : Err };
+export interface ListWheelPrizeExtractionsPageRequest {
+  'start_after' : [] | [string],
+  'limit' : [] | [number],
+  'state' : [] | [WheelPrizeExtractionStateFilter],
+  'extracted_by_user_id' : [] | [string],
+  'wheel_asset_id' : [] | [string],
+  'created_before' : [] | [string],
+  'created_from' : [] | [string],
+}
+export type ListWheelPrizeExtractionsPageResponse = {
+    'ok' : WheelPrizeExtractionsPage
+  } |
+  { 'err' : Err };
 export type ListWheelPrizeExtractionsResponse = {
     'ok' : Array<WheelPrizeExtraction>
   } |
   { 'err' : Err };
 export type ListWheelPrizesResponse = { 'ok' : Array<WheelPrize> } |
   { 'err' : Err };
+export interface ManualTransfer {
+  'id' : string,
+  'to' : Principal,
+  'updated_at' : string,
+  'to_subaccount' : [] | [Uint8Array | number[]],
+  'requested_by_user_id' : string,
+  'approved_by_user_id' : [] | [string],
+  'created_at' : string,
+  'from_subaccount' : [] | [Uint8Array | number[]],
+  'state' : ManualTransferState,
+  'ledger_canister_id' : Principal,
+  'created_at_time' : [] | [bigint],
+  'amount' : bigint,
+  'reason' : string,
+}
+export interface ManualTransferLimit {
+  'updated_at' : string,
+  'approval_threshold' : [] | [bigint],
+  'ledger_canister_id' : Principal,
+  'daily_limit' : [] | [bigint],
+}
+export type ManualTransferState = { 'completed' : { 'block_index' : bigint } } |
+  { 'pending_approval' : null } |
+  { 'denied' : { 'error' : Err } } |
+  { 'rejected' : { 'rejected_by_user_id' : string } } |
+  { 'processing' : null } |
+  { 'failed' : { 'error' : Err } };
+export interface MyWheelPrizeExtraction {
+  'id' : string,
+  'extracted_for_subaccount' : [] | [Uint8Array | number[]],
+  'created_at' : string,
+  'state' : WheelPrizeExtractionState,
+  'prize_name' : [] | [string],
+  'wheel_asset_id' : [] | [string],
+  'payouts' : Array<MyWheelPrizePayout>,
+  'gadget_claim' : [] | [WheelPrizeGadgetClaim],
+}
+export interface MyWheelPrizePayout {
+  'block_index' : [] | [bigint],
+  'ledger_canister_id' : Principal,
+  'amount' : bigint,
+}
+export type RecipientAccount = { 'text' : string } |
+  { 'account' : Account };
+export interface RedeemPrizeRequest {
+  'wheel_prize_extraction_id' : string,
+  'redemption_code' : string,
+  'gadget_variant_name' : [] | [string],
+}
+export type RedeemPrizeResponse = { 'ok' : null } |
+  { 'err' : Err };
+export interface RejectManualTransferRequest { 'manual_transfer_id' : string }
+export type RejectManualTransferResponse = { 'ok' : null } |
+  { 'err' : Err };
+export interface RetireWheelAssetRequest { 'id' : string, 'to' : Account }
+export type RetireWheelAssetResponse = {
+    'ok' : { 'block_index' : [] | [bigint] }
+  } |
+  { 'err' : Err };
+export interface RetryWheelPrizeExtractionPayoutsRequest {
+  'wheel_prize_extraction_id' : string,
+}
+export type RetryWheelPrizeExtractionPayoutsResponse = { 'ok' : null } |
+  { 'err' : Err };
+export interface RevokeInviteCodeRequest { 'invite_code_id' : string }
+export type RevokeInviteCodeResponse = { 'ok' : null } |
+  { 'err' : Err };
 export type SetDefaultWheelAssetsResponse = { 'ok' : null } |
   { 'err' : Err };
+export interface SetEventSelfSpinCodeRequest {
+  'self_spin_code' : [] | [string],
+  'max_failed_attempts' : [] | [number],
+  'max_spins' : [] | [number],
+  'event_id' : string,
+}
+export type SetEventSelfSpinCodeResponse = { 'ok' : null } |
+  { 'err' : Err };
+export interface SetManualTransferLimitRequest {
+  'approval_threshold' : [] | [bigint],
+  'ledger_canister_id' : Principal,
+  'daily_limit' : [] | [bigint],
+}
+export type SetManualTransferLimitResponse = { 'ok' : ManualTransferLimit } |
+  { 'err' : Err };
+export interface StaleWheelAssetPrice {
+  'last_fetched_at' : string,
+  'resolution' : StaleWheelAssetPriceResolution,
+  'wheel_asset_id' : string,
+}
+export type StaleWheelAssetPriceResolution = {
+    'refreshed' : { 'usd_price' : number }
+  } |
+  { 'excluded' : { 'error' : Err } };
+export type TransferTokenOutcome = {
+    'completed' : { 'block_index' : bigint }
+  } |
+  { 'pending_approval' : { 'manual_transfer_id' : string } };
 export interface TransferTokenRequest {
-  'to' : Principal,
+  'to' : [] | [Principal],
+  'from_subaccount' : [] | [Uint8Array | number[]],
+  'to_account' : [] | [RecipientAccount],
   'ledger_canister_id' : Principal,
   'amount' : bigint,
+  'reason' : [] | [string],
 }
-export type TransferTokenResponse = { 'ok' : bigint } |
+export type TransferTokenResponse = { 'ok' : TransferTokenOutcome } |
   { 'err' : Err };
 export interface UpdateCustomDomainRecordRequest {
   'id' : string,
@@ -112,6 +440,15 @@ export interface UpdateCustomDomainRecordRequest {
 }
 export type UpdateCustomDomainRecordResponse = { 'ok' : null } |
   { 'err' : Err };
+export interface UpdateEventRequest {
+  'id' : string,
+  'active' : [] | [boolean],
+  'name' : [] | [string],
+  'end_date' : [] | [string],
+  'start_date' : [] | [string],
+}
+export type UpdateEventResponse = { 'ok' : null } |
+  { 'err' : Err };
 export interface UpdateMyUserProfileRequest { 'username' : [] | [string] }
 export type UpdateMyUserProfileResponse = { 'ok' : null } |
   { 'err' : Err };
@@ -148,19 +485,42 @@ export interface UpdateWheelAssetRequest {
   'name' : [] | [string],
   'state' : [] | [WheelAssetState],
   'wheel_ui_settings' : [] | [WheelAssetUiSettings],
+  'draw_weight' : [] | [WheelAssetDrawWeight],
 }
 export type UpdateWheelAssetResponse = { 'ok' : null } |
   { 'err' : Err };
 export type UpdateWheelAssetTypeConfig = {
     'token' : {
+      'funding_source' : [] | [WheelAssetTokenFundingSource],
       'exchange_rate_symbol' : [] | [string],
       'prize_usd_amount' : [] | [number],
+      'prize_token_amount' : [] | [bigint],
       'ledger_config' : [] | [UpdateWheelAssetTypeLedgerConfig],
     }
   } |
   { 'jackpot' : { 'wheel_asset_ids' : Array<string> } } |
-  { 'gadget' : { 'article_type' : [] | [string] } };
+  {
+    'gadget' : {
+      'variants' : [] | [Array<WheelAssetGadgetVariantConfig>],
+      'article_type' : [] | [string],
+    }
+  };
 export interface UpdateWheelAssetTypeLedgerConfig { 'decimals' : [] | [number] }
+export interface UpdateWheelPrizeExtractionPolicyRequest {
+  'max_wins_per_principal' : [] | [number],
+  'allowed_principals' : [] | [Array<Principal>],
+  'self_spin_max_failed_attempts' : [] | [number],
+  'max_token_price_age_seconds' : [] | [bigint],
+  'invite_code_required' : [] | [boolean],
+  'spin_cooldown_seconds' : bigint,
+  'denied_principals' : Array<Principal>,
+  'max_wins_per_principal_per_day' : [] | [number],
+  'failed_spin_cooldown_seconds' : bigint,
+  'self_spin_enabled' : [] | [boolean],
+  'max_wins_per_day' : [] | [number],
+}
+export type UpdateWheelPrizeExtractionPolicyResponse = { 'ok' : null } |
+  { 'err' : Err };
 export interface UpdateWheelPrizesOrderRequest {
   'wheel_asset_ids' : Array<string>,
 }
@@ -184,8 +544,26 @@ export interface WheelAsset {
   'wheel_image_path' : [] | [string],
   'state' : WheelAssetState,
   'available_amount' : number,
+  'event_id' : [] | [string],
   'wheel_ui_settings' : WheelAssetUiSettings,
   'modal_image_path' : [] | [string],
+  'draw_weight' : WheelAssetDrawWeight,
+}
+export type WheelAssetDrawWeight = { 'fixed' : { 'weight' : number } } |
+  { 'available_quantity' : null };
+export interface WheelAssetGadgetVariant {
+  'total_amount' : number,
+  'name' : string,
+  'color' : [] | [string],
+  'size' : [] | [string],
+  'available_amount' : number,
+  'handed_over_amount' : number,
+}
+export interface WheelAssetGadgetVariantConfig {
+  'total_amount' : number,
+  'name' : string,
+  'color' : [] | [string],
+  'size' : [] | [string],
 }
 export type WheelAssetState = { 'disabled' : null } |
   { 'enabled' : null };
@@ -193,9 +571,18 @@ export interface WheelAssetTokenBalance {
   'balance' : bigint,
   'last_fetched_at' : string,
 }
+export interface WheelAssetTokenFetchStatus {
+  'last_error' : Err,
+  'failed_attempts' : number,
+  'last_failed_at' : string,
+}
+export type WheelAssetTokenFundingSource = { 'canister_account' : null } |
+  { 'sponsor_allowance' : { 'sponsor_account' : Account } };
 export interface WheelAssetTokenLedgerConfig {
   'decimals' : number,
+  'name' : [] | [string],
   'ledger_canister_id' : Principal,
+  'symbol' : [] | [string],
 }
 export interface WheelAssetTokenPrice {
   'usd_price' : number,
@@ -205,47 +592,146 @@ export type WheelAssetType = {
     'token' : {
       'usd_price' : [] | [WheelAssetTokenPrice],
       'balance' : [] | [WheelAssetTokenBalance],
+      'funding_source' : WheelAssetTokenFundingSource,
+      'usd_price_fetch_status' : [] | [WheelAssetTokenFetchStatus],
+      'transfer_fee' : [] | [bigint],
+      'deposit_account' : Account,
+      'deposit_account_text' : string,
       'exchange_rate_symbol' : [] | [string],
       'prize_usd_amount' : number,
       'available_draws_count' : number,
+      'balance_fetch_status' : [] | [WheelAssetTokenFetchStatus],
+      'prize_token_amount' : [] | [bigint],
       'ledger_config' : WheelAssetTokenLedgerConfig,
     }
   } |
   { 'jackpot' : { 'wheel_asset_ids' : Array<string> } } |
-  { 'gadget' : { 'article_type' : [] | [string] } };
+  {
+    'gadget' : {
+      'variants' : Array<WheelAssetGadgetVariant>,
+      'article_type' : [] | [string],
+    }
+  };
 export interface WheelAssetUiSettings { 'background_color_hex' : string }
 export interface WheelPrize {
   'name' : string,
   'wheel_image_path' : [] | [string],
   'prize_usd_amount' : [] | [number],
+  'draw_probability' : number,
   'wheel_ui_settings' : WheelAssetUiSettings,
   'wheel_asset_id' : string,
   'modal_image_path' : [] | [string],
 }
+export interface WheelPrizeDrawCandidate {
+  'weight' : number,
+  'wheel_asset_id' : string,
+}
+export interface WheelPrizeDrawProof {
+  'chosen_index' : number,
+  'seed' : Uint8Array | number[],
+  'excluded_draws' : Array<WheelPrizeExcludedDraw>,
+  'candidates' : Array<WheelPrizeDrawCandidate>,
+}
+export interface WheelPrizeExcludedDraw {
+  'chosen_index' : number,
+  'candidates' : Array<WheelPrizeDrawCandidate>,
+}
 export interface WheelPrizeExtraction {
   'id' : string,
   'updated_at' : string,
+  'extracted_for_subaccount' : [] | [Uint8Array | number[]],
   'extracted_for_principal' : Principal,
   'created_at' : string,
+  'stale_prices' : Array<StaleWheelAssetPrice>,
   'state' : WheelPrizeExtractionState,
+  'self_spin' : [] | [WheelPrizeSelfSpin],
   'extracted_by_user_id' : string,
+  'event_id' : [] | [string],
   'wheel_asset_id' : [] | [string],
+  'invite_code_id' : [] | [string],
+  'payouts' : Array<WheelPrizePayout>,
+  'gadget_claim' : [] | [WheelPrizeGadgetClaim],
+}
+export interface WheelPrizeExtractionPolicy {
+  'updated_at' : string,
+  'max_wins_per_principal' : [] | [number],
+  'allowed_principals' : [] | [Array<Principal>],
+  'self_spin_max_failed_attempts' : number,
+  'max_token_price_age_seconds' : bigint,
+  'invite_code_required' : boolean,
+  'spin_cooldown_seconds' : bigint,
+  'denied_principals' : Array<Principal>,
+  'max_wins_per_principal_per_day' : [] | [number],
+  'failed_spin_cooldown_seconds' : bigint,
+  'self_spin_enabled' : boolean,
+  'max_wins_per_day' : [] | [number],
 }
 export type WheelPrizeExtractionState = {
     'completed' : { 'prize_usd_amount' : [] | [number] }
   } |
+  {
+    'partially_completed' : {
+      'error' : Err,
+      'prize_usd_amount' : [] | [number],
+    }
+  } |
   { 'processing' : null } |
   { 'failed' : { 'error' : Err } };
+export type WheelPrizeExtractionStateFilter = { 'completed' : null } |
+  { 'partially_completed' : null } |
+  { 'processing' : null } |
+  { 'failed' : null };
+export interface WheelPrizeExtractionsPage {
+  'wheel_prize_extractions' : Array<WheelPrizeExtraction>,
+  'next_start_after' : [] | [string],
+}
 export interface WheelPrizeExtractionsStats {
   'total_completed_extractions' : number,
   'total_spent_usd' : number,
 }
+export type WheelPrizeGadgetClaim = { 'won' : null } |
+  {
+    'handed_over' : {
+      'handed_over_at' : string,
+      'gadget_variant_name' : [] | [string],
+      'handed_over_by_user_id' : string,
+    }
+  };
+export interface WheelPrizePayout {
+  'memo' : Uint8Array | number[],
+  'state' : WheelPrizePayoutState,
+  'from_account' : [] | [Account],
+  'ledger_canister_id' : Principal,
+  'created_at_time' : bigint,
+  'wheel_asset_id' : string,
+  'amount' : bigint,
+}
+export type WheelPrizePayoutState = { 'pending' : null } |
+  { 'completed' : { 'block_index' : bigint } } |
+  { 'failed' : { 'error' : Err } };
+export type WheelPrizeSelfSpin = {
+    'invite_code' : { 'invite_code_id' : string }
+  } |
+  { 'event_code' : null };
 export interface _SERVICE {
+  'approve_manual_transfer' : ActorMethod<
+    [ApproveManualTransferRequest],
+    ApproveManualTransferResponse
+  >,
   'create_custom_domain_record' : ActorMethod<
     [CreateCustomDomainRecordRequest],
     CreateCustomDomainRecordResponse
   >,
+  'create_event' : ActorMethod<[CreateEventRequest], CreateEventResponse>,
   'create_my_user_profile' : ActorMethod<[], CreateMyUserProfileResponse>,
+  'create_my_wheel_prize_extraction' : ActorMethod<
+    [CreateMyWheelPrizeExtractionRequest],
+    CreateMyWheelPrizeExtractionResponse
+  >,
+  'create_self_spin_invite' : ActorMethod<
+    [CreateSelfSpinInviteRequest],
+    CreateSelfSpinInviteResponse
+  >,
   'create_wheel_asset' : ActorMethod<
     [CreateWheelAssetRequest],
     CreateWheelAssetResponse
@@ -258,6 +744,7 @@ export interface _SERVICE {
     [DeleteCustomDomainRecordRequest],
     DeleteCustomDomainRecordResponse
   >,
+  'delete_event' : ActorMethod<[DeleteEventRequest], DeleteEventResponse>,
   'delete_user_profile' : ActorMethod<
     [DeleteUserProfileRequest],
     DeleteUserProfileResponse
@@ -266,25 +753,72 @@ export interface _SERVICE {
     [DeleteWheelAssetRequest],
     DeleteWheelAssetResponse
   >,
+  'export_invite_codes' : ActorMethod<
+    [ListInviteCodesRequest],
+    ExportInviteCodesResponse
+  >,
   'fetch_tokens_data' : ActorMethod<[], FetchTokensDataResponse>,
+  'generate_invite_codes' : ActorMethod<
+    [GenerateInviteCodesRequest],
+    GenerateInviteCodesResponse
+  >,
+  'get_active_event' : ActorMethod<[], GetActiveEventResponse>,
   'get_last_wheel_prize_extraction' : ActorMethod<
     [],
     GetLastWheelPrizeExtractionResponse
   >,
   'get_my_user_profile' : ActorMethod<[], GetMyUserProfileResponse>,
+  'get_my_wheel_prize_extractions' : ActorMethod<
+    [],
+    GetMyWheelPrizeExtractionsResponse
+  >,
+  'get_my_wheel_prize_redemption_qr_code' : ActorMethod<
+    [GetMyWheelPrizeRedemptionQrCodeRequest],
+    GetMyWheelPrizeRedemptionQrCodeResponse
+  >,
+  'get_wheel_prize_draw_proof' : ActorMethod<
+    [GetWheelPrizeDrawProofRequest],
+    GetWheelPrizeDrawProofResponse
+  >,
   'get_wheel_prize_extraction' : ActorMethod<
     [GetWheelPrizeExtractionRequest],
     GetWheelPrizeExtractionResponse
   >,
-  'get_wheel_prize_extractions_stats' : ActorMethod<
+  'get_wheel_prize_extraction_policy' : ActorMethod<
     [],
+    GetWheelPrizeExtractionPolicyResponse
+  >,
+  'get_wheel_prize_extractions_stats' : ActorMethod<
+    [[] | [GetWheelPrizeExtractionsStatsRequest]],
     GetWheelPrizeExtractionsStatsResponse
   >,
+  'hand_over_wheel_prize_gadget' : ActorMethod<
+    [HandOverWheelPrizeGadgetRequest],
+    HandOverWheelPrizeGadgetResponse
+  >,
   'http_request' : ActorMethod<[HttpRequest], HttpResponse>,
   'list_custom_domain_records' : ActorMethod<
     [],
     ListCustomDomainRecordsResponse
   >,
+  'list_events' : ActorMethod<[], ListEventsResponse>,
+  'list_invite_codes' : ActorMethod<
+    [ListInviteCodesRequest],
+    ListInviteCodesResponse
+  >,
+  'list_ledger_transfers' : ActorMethod<
+    [ListLedgerTransfersRequest],
+    ListLedgerTransfersResponse
+  >,
+  'list_manual_transfer_limits' : ActorMethod<
+    [],
+    ListManualTransferLimitsResponse
+  >,
+  'list_manual_transfers' : ActorMethod<[], ListManualTransfersResponse>,
+  'list_unclaimed_wheel_prize_gadgets' : ActorMethod<
+    [ListUnclaimedWheelPrizeGadgetsRequest],
+    ListUnclaimedWheelPrizeGadgetsResponse
+  >,
   'list_users' : ActorMethod<[], ListUsersResponse>,
   'list_wheel_assets' : ActorMethod<
     [ListWheelAssetsRequest],
@@ -294,13 +828,43 @@ export interface _SERVICE {
     [],
     ListWheelPrizeExtractionsResponse
   >,
+  'list_wheel_prize_extractions_page' : ActorMethod<
+    [ListWheelPrizeExtractionsPageRequest],
+    ListWheelPrizeExtractionsPageResponse
+  >,
   'list_wheel_prizes' : ActorMethod<[], ListWheelPrizesResponse>,
+  'redeem_prize' : ActorMethod<[RedeemPrizeRequest], RedeemPrizeResponse>,
+  'reject_manual_transfer' : ActorMethod<
+    [RejectManualTransferRequest],
+    RejectManualTransferResponse
+  >,
+  'retire_wheel_asset' : ActorMethod<
+    [RetireWheelAssetRequest],
+    RetireWheelAssetResponse
+  >,
+  'retry_wheel_prize_extraction_payouts' : ActorMethod<
+    [RetryWheelPrizeExtractionPayoutsRequest],
+    RetryWheelPrizeExtractionPayoutsResponse
+  >,
+  'revoke_invite_code' : ActorMethod<
+    [RevokeInviteCodeRequest],
+    RevokeInviteCodeResponse
+  >,
   'set_default_wheel_assets' : ActorMethod<[], SetDefaultWheelAssetsResponse>,
+  'set_event_self_spin_code' : ActorMethod<
+    [SetEventSelfSpinCodeRequest],
+    SetEventSelfSpinCodeResponse
+  >,
+  'set_manual_transfer_limit' : ActorMethod<
+    [SetManualTransferLimitRequest],
+    SetManualTransferLimitResponse
+  >,
   'transfer_token' : ActorMethod<[TransferTokenRequest], TransferTokenResponse>,
   'update_custom_domain_record' : ActorMethod<
     [UpdateCustomDomainRecordRequest],
     UpdateCustomDomainRecordResponse
   >,
+  'update_event' : ActorMethod<[UpdateEventRequest], UpdateEventResponse>,
   'update_my_user_profile' : ActorMethod<
     [UpdateMyUserProfileRequest],
     UpdateMyUserProfileResponse
@@ -317,6 +881,10 @@ export interface _SERVICE {
     [UpdateWheelAssetImageRequest],
     UpdateWheelAssetImageResponse
   >,
+  'update_wheel_prize_extraction_policy' : ActorMethod<
+    [UpdateWheelPrizeExtractionPolicyRequest],
+    UpdateWheelPrizeExtractionPolicyResponse
+  >,
   'update_wheel_prizes_order' : ActorMethod<
     [UpdateWheelPrizesOrderRequest],
     UpdateWheelPrizesOrderResponse
@@ -1,4 +1,49 @@
 export const idlFactory = ({ IDL }) => {
+  const ApproveManualTransferRequest = IDL.Record({
+    'manual_transfer_id' : IDL.Text,
+  });
+  const ExtractionPolicyRejection = IDL.Variant({
+    'max_wins_per_principal' : IDL.Record({ 'max_wins' : IDL.Nat32 }),
+    'failed_spin_cooldown' : IDL.Null,
+    'principal_not_allowed' : IDL.Null,
+    'principal_denied' : IDL.Null,
+    'max_wins_per_principal_per_day' : IDL.Record({ 'max_wins' : IDL.Nat32 }),
+    'spin_cooldown' : IDL.Null,
+    'max_wins_per_day' : IDL.Record({ 'max_wins' : IDL.Nat32 }),
+  });
+  const LedgerTransferRejection = IDL.Variant({
+    'insufficient_funds' : IDL.Record({ 'balance' : IDL.Nat }),
+    'created_in_future' : IDL.Record({ 'ledger_time' : IDL.Nat64 }),
+    'generic_error' : IDL.Record({
+      'message' : IDL.Text,
+      'error_code' : IDL.Nat,
+    }),
+    'temporarily_unavailable' : IDL.Null,
+    'too_old' : IDL.Null,
+    'bad_fee' : IDL.Record({ 'expected_fee' : IDL.Nat }),
+    'bad_burn' : IDL.Record({ 'min_burn_amount' : IDL.Nat }),
+    'insufficient_allowance' : IDL.Record({ 'allowance' : IDL.Nat }),
+  });
+  const ErrKind = IDL.Variant({
+    'extraction_policy_rejected' : IDL.Record({
+      'rejection' : ExtractionPolicyRejection,
+      'retry_after_seconds' : IDL.Opt(IDL.Nat64),
+    }),
+    'ledger_transfer_rejected' : IDL.Record({
+      'rejection' : LedgerTransferRejection,
+      'ledger_canister_id' : IDL.Principal,
+    }),
+    'ledger_call_failed' : IDL.Record({ 'ledger_canister_id' : IDL.Principal }),
+  });
+  const Err = IDL.Record({
+    'code' : IDL.Nat16,
+    'kind' : IDL.Opt(ErrKind),
+    'message' : IDL.Text,
+  });
+  const ApproveManualTransferResponse = IDL.Variant({
+    'ok' : IDL.Nat,
+    'err' : Err,
+  });
   const CreateCustomDomainRecordRequest = IDL.Record({
     'domain_name' : IDL.Text,
   });
@@ -18,11 +63,29 @@ export const idlFactory = ({ IDL }) => {
     'created_at' : IDL.Text,
     'bn_registration_state' : CustomDomainRecordBnRegistrationState,
   });
-  const Err = IDL.Record({ 'code' : IDL.Nat16, 'message' : IDL.Text });
   const CreateCustomDomainRecordResponse = IDL.Variant({
     'ok' : CustomDomainRecord,
     'err' : Err,
   });
+  const CreateEventRequest = IDL.Record({
+    'name' : IDL.Text,
+    'end_date' : IDL.Text,
+    'start_date' : IDL.Text,
+  });
+  const Event = IDL.Record({
+    'id' : IDL.Text,
+    'has_self_spin_code' : IDL.Bool,
+    'updated_at' : IDL.Text,
+    'active' : IDL.Bool,
+    'self_spin_max_failed_attempts' : IDL.Nat32,
+    'name' : IDL.Text,
+    'self_spin_code_max_spins' : IDL.Opt(IDL.Nat32),
+    'end_date' : IDL.Text,
+    'created_at' : IDL.Text,
+    'start_date' : IDL.Text,
+    'self_spin_failed_attempts' : IDL.Nat32,
+  });
+  const CreateEventResponse = IDL.Variant({ 'ok' : Event, 'err' : Err });
   const UserRole = IDL.Variant({
     'admin' : IDL.Null,
     'scanner' : IDL.Null,
@@ -38,27 +101,75 @@ export const idlFactory = ({ IDL }) => {
     'ok' : UserProfile,
     'err' : Err,
   });
-  const WheelAssetTokenLedgerConfig = IDL.Record({
-    'decimals' : IDL.Nat8,
+  const CreateMyWheelPrizeExtractionRequest = IDL.Record({
+    'self_spin_code' : IDL.Text,
+  });
+  const CreateMyWheelPrizeExtractionResponse = IDL.Variant({
+    'ok' : IDL.Null,
+    'err' : Err,
+  });
+  const CreateSelfSpinInviteRequest = IDL.Record({
+    'principal' : IDL.Opt(IDL.Principal),
+    'event_id' : IDL.Opt(IDL.Text),
+  });
+  const CreateSelfSpinInviteResponse = IDL.Variant({
+    'ok' : IDL.Record({
+      'invite_code' : IDL.Text,
+      'invite_code_id' : IDL.Text,
+    }),
+    'err' : Err,
+  });
+  const Account = IDL.Record({
+    'owner' : IDL.Principal,
+    'subaccount' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+  });
+  const WheelAssetTokenFundingSource = IDL.Variant({
+    'canister_account' : IDL.Null,
+    'sponsor_allowance' : IDL.Record({ 'sponsor_account' : Account }),
+  });
+  const CreateWheelAssetTokenLedgerConfig = IDL.Record({
+    'decimals' : IDL.Opt(IDL.Nat8),
     'ledger_canister_id' : IDL.Principal,
   });
+  const WheelAssetGadgetVariantConfig = IDL.Record({
+    'total_amount' : IDL.Nat32,
+    'name' : IDL.Text,
+    'color' : IDL.Opt(IDL.Text),
+    'size' : IDL.Opt(IDL.Text),
+  });
   const CreateWheelAssetTypeConfig = IDL.Variant({
     'token' : IDL.Record({
+      'funding_source' : IDL.Opt(WheelAssetTokenFundingSource),
       'exchange_rate_symbol' : IDL.Opt(IDL.Text),
       'prize_usd_amount' : IDL.Float64,
-      'ledger_config' : WheelAssetTokenLedgerConfig,
+      'ledger_config' : CreateWheelAssetTokenLedgerConfig,
     }),
     'jackpot' : IDL.Record({ 'wheel_asset_ids' : IDL.Vec(IDL.Text) }),
-    'gadget' : IDL.Record({ 'article_type' : IDL.Opt(IDL.Text) }),
+    'gadget' : IDL.Record({
+      'variants' : IDL.Opt(IDL.Vec(WheelAssetGadgetVariantConfig)),
+      'article_type' : IDL.Opt(IDL.Text),
+    }),
+    'token_fixed_amount' : IDL.Record({
+      'funding_source' : IDL.Opt(WheelAssetTokenFundingSource),
+      'exchange_rate_symbol' : IDL.Opt(IDL.Text),
+      'prize_token_amount' : IDL.Nat,
+      'ledger_config' : CreateWheelAssetTokenLedgerConfig,
+    }),
   });
   const WheelAssetUiSettings = IDL.Record({
     'background_color_hex' : IDL.Text,
   });
+  const WheelAssetDrawWeight = IDL.Variant({
+    'fixed' : IDL.Record({ 'weight' : IDL.Nat32 }),
+    'available_quantity' : IDL.Null,
+  });
   const CreateWheelAssetRequest = IDL.Record({
     'total_amount' : IDL.Nat32,
     'asset_type_config' : CreateWheelAssetTypeConfig,
     'name' : IDL.Text,
+    'event_id' : IDL.Opt(IDL.Text),
     'wheel_ui_settings' : IDL.Opt(WheelAssetUiSettings),
+    'draw_weight' : IDL.Opt(WheelAssetDrawWeight),
   });
   const WheelAssetTokenPrice = IDL.Record({
     'usd_price' : IDL.Float64,
@@ -68,17 +179,46 @@ export const idlFactory = ({ IDL }) => {
     'balance' : IDL.Nat,
     'last_fetched_at' : IDL.Text,
   });
+  const WheelAssetTokenFetchStatus = IDL.Record({
+    'last_error' : Err,
+    'failed_attempts' : IDL.Nat32,
+    'last_failed_at' : IDL.Text,
+  });
+  const WheelAssetTokenLedgerConfig = IDL.Record({
+    'decimals' : IDL.Nat8,
+    'name' : IDL.Opt(IDL.Text),
+    'ledger_canister_id' : IDL.Principal,
+    'symbol' : IDL.Opt(IDL.Text),
+  });
+  const WheelAssetGadgetVariant = IDL.Record({
+    'total_amount' : IDL.Nat32,
+    'name' : IDL.Text,
+    'color' : IDL.Opt(IDL.Text),
+    'size' : IDL.Opt(IDL.Text),
+    'available_amount' : IDL.Nat32,
+    'handed_over_amount' : IDL.Nat32,
+  });
   const WheelAssetType = IDL.Variant({
     'token' : IDL.Record({
       'usd_price' : IDL.Opt(WheelAssetTokenPrice),
       'balance' : IDL.Opt(WheelAssetTokenBalance),
+      'funding_source' : WheelAssetTokenFundingSource,
+      'usd_price_fetch_status' : IDL.Opt(WheelAssetTokenFetchStatus),
+      'transfer_fee' : IDL.Opt(IDL.Nat),
+      'deposit_account' : Account,
+      'deposit_account_text' : IDL.Text,
       'exchange_rate_symbol' : IDL.Opt(IDL.Text),
       'prize_usd_amount' : IDL.Float64,
       'available_draws_count' : IDL.Nat32,
+      'balance_fetch_status' : IDL.Opt(WheelAssetTokenFetchStatus),
+      'prize_token_amount' : IDL.Opt(IDL.Nat),
       'ledger_config' : WheelAssetTokenLedgerConfig,
     }),
     'jackpot' : IDL.Record({ 'wheel_asset_ids' : IDL.Vec(IDL.Text) }),
-    'gadget' : IDL.Record({ 'article_type' : IDL.Opt(IDL.Text) }),
+    'gadget' : IDL.Record({
+      'variants' : IDL.Vec(WheelAssetGadgetVariant),
+      'article_type' : IDL.Opt(IDL.Text),
+    }),
   });
   const WheelAssetState = IDL.Variant({
     'disabled' : IDL.Null,
@@ -93,15 +233,23 @@ export const idlFactory = ({ IDL }) => {
     'wheel_image_path' : IDL.Opt(IDL.Text),
     'state' : WheelAssetState,
     'available_amount' : IDL.Nat32,
+    'event_id' : IDL.Opt(IDL.Text),
     'wheel_ui_settings' : WheelAssetUiSettings,
     'modal_image_path' : IDL.Opt(IDL.Text),
+    'draw_weight' : WheelAssetDrawWeight,
   });
   const CreateWheelAssetResponse = IDL.Variant({
     'ok' : WheelAsset,
     'err' : Err,
   });
+  const RecipientAccount = IDL.Variant({
+    'text' : IDL.Text,
+    'account' : Account,
+  });
   const CreateWheelPrizeExtractionRequest = IDL.Record({
-    'extract_for_principal' : IDL.Principal,
+    'invite_code' : IDL.Opt(IDL.Text),
+    'extract_for_principal' : IDL.Opt(IDL.Principal),
+    'extract_for_account' : IDL.Opt(RecipientAccount),
   });
   const CreateWheelPrizeExtractionResponse = IDL.Variant({
     'ok' : IDL.Null,
@@ -112,6 +260,8 @@ export const idlFactory = ({ IDL }) => {
     'ok' : IDL.Null,
     'err' : Err,
   });
+  const DeleteEventRequest = IDL.Record({ 'id' : IDL.Text });
+  const DeleteEventResponse = IDL.Variant({ 'ok' : IDL.Null, 'err' : Err });
   const DeleteUserProfileRequest = IDL.Record({ 'user_id' : IDL.Text });
   const DeleteUserProfileResponse = IDL.Variant({
     'ok' : IDL.Null,
@@ -122,20 +272,96 @@ export const idlFactory = ({ IDL }) => {
     'ok' : IDL.Null,
     'err' : Err,
   });
+  const InviteCodePurpose = IDL.Variant({
+    'attendance' : IDL.Null,
+    'self_spin' : IDL.Null,
+  });
+  const ListInviteCodesRequest = IDL.Record({
+    'event_id' : IDL.Opt(IDL.Text),
+    'purpose' : IDL.Opt(InviteCodePurpose),
+  });
+  const ExportInviteCodesResponse = IDL.Variant({
+    'ok' : IDL.Text,
+    'err' : Err,
+  });
   const FetchTokensDataResponse = IDL.Variant({ 'ok' : IDL.Null, 'err' : Err });
+  const GenerateInviteCodesRequest = IDL.Record({
+    'principal' : IDL.Opt(IDL.Principal),
+    'count' : IDL.Nat32,
+    'event_id' : IDL.Opt(IDL.Text),
+    'purpose' : IDL.Opt(InviteCodePurpose),
+  });
+  const GeneratedInviteCode = IDL.Record({
+    'invite_code' : IDL.Text,
+    'invite_code_id' : IDL.Text,
+  });
+  const GenerateInviteCodesResponse = IDL.Variant({
+    'ok' : IDL.Vec(GeneratedInviteCode),
+    'err' : Err,
+  });
+  const GetActiveEventResponse = IDL.Variant({
+    'ok' : IDL.Opt(Event),
+    'err' : Err,
+  });
+  const StaleWheelAssetPriceResolution = IDL.Variant({
+    'refreshed' : IDL.Record({ 'usd_price' : IDL.Float64 }),
+    'excluded' : IDL.Record({ 'error' : Err }),
+  });
+  const StaleWheelAssetPrice = IDL.Record({
+    'last_fetched_at' : IDL.Text,
+    'resolution' : StaleWheelAssetPriceResolution,
+    'wheel_asset_id' : IDL.Text,
+  });
   const WheelPrizeExtractionState = IDL.Variant({
     'completed' : IDL.Record({ 'prize_usd_amount' : IDL.Opt(IDL.Float64) }),
+    'partially_completed' : IDL.Record({
+      'error' : Err,
+      'prize_usd_amount' : IDL.Opt(IDL.Float64),
+    }),
     'processing' : IDL.Null,
     'failed' : IDL.Record({ 'error' : Err }),
   });
+  const WheelPrizeSelfSpin = IDL.Variant({
+    'invite_code' : IDL.Record({ 'invite_code_id' : IDL.Text }),
+    'event_code' : IDL.Null,
+  });
+  const WheelPrizePayoutState = IDL.Variant({
+    'pending' : IDL.Null,
+    'completed' : IDL.Record({ 'block_index' : IDL.Nat }),
+    'failed' : IDL.Record({ 'error' : Err }),
+  });
+  const WheelPrizePayout = IDL.Record({
+    'memo' : IDL.Vec(IDL.Nat8),
+    'state' : WheelPrizePayoutState,
+    'from_account' : IDL.Opt(Account),
+    'ledger_canister_id' : IDL.Principal,
+    'created_at_time' : IDL.Nat64,
+    'wheel_asset_id' : IDL.Text,
+    'amount' : IDL.Nat,
+  });
+  const WheelPrizeGadgetClaim = IDL.Variant({
+    'won' : IDL.Null,
+    'handed_over' : IDL.Record({
+      'handed_over_at' : IDL.Text,
+      'gadget_variant_name' : IDL.Opt(IDL.Text),
+      'handed_over_by_user_id' : IDL.Text,
+    }),
+  });
   const WheelPrizeExtraction = IDL.Record({
     'id' : IDL.Text,
     'updated_at' : IDL.Text,
+    'extracted_for_subaccount' : IDL.Opt(IDL.Vec(IDL.Nat8)),
     'extracted_for_principal' : IDL.Principal,
     'created_at' : IDL.Text,
+    'stale_prices' : IDL.Vec(StaleWheelAssetPrice),
     'state' : WheelPrizeExtractionState,
+    'self_spin' : IDL.Opt(WheelPrizeSelfSpin),
     'extracted_by_user_id' : IDL.Text,
+    'event_id' : IDL.Opt(IDL.Text),
     'wheel_asset_id' : IDL.Opt(IDL.Text),
+    'invite_code_id' : IDL.Opt(IDL.Text),
+    'payouts' : IDL.Vec(WheelPrizePayout),
+    'gadget_claim' : IDL.Opt(WheelPrizeGadgetClaim),
   });
   const GetLastWheelPrizeExtractionResponse = IDL.Variant({
     'ok' : IDL.Opt(WheelPrizeExtraction),
@@ -145,6 +371,53 @@ export const idlFactory = ({ IDL }) => {
     'ok' : UserProfile,
     'err' : Err,
   });
+  const MyWheelPrizePayout = IDL.Record({
+    'block_index' : IDL.Opt(IDL.Nat),
+    'ledger_canister_id' : IDL.Principal,
+    'amount' : IDL.Nat,
+  });
+  const MyWheelPrizeExtraction = IDL.Record({
+    'id' : IDL.Text,
+    'extracted_for_subaccount' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'created_at' : IDL.Text,
+    'state' : WheelPrizeExtractionState,
+    'prize_name' : IDL.Opt(IDL.Text),
+    'wheel_asset_id' : IDL.Opt(IDL.Text),
+    'payouts' : IDL.Vec(MyWheelPrizePayout),
+    'gadget_claim' : IDL.Opt(WheelPrizeGadgetClaim),
+  });
+  const GetMyWheelPrizeExtractionsResponse = IDL.Variant({
+    'ok' : IDL.Vec(MyWheelPrizeExtraction),
+    'err' : Err,
+  });
+  const GetMyWheelPrizeRedemptionQrCodeRequest = IDL.Record({
+    'wheel_prize_extraction_id' : IDL.Text,
+  });
+  const GetMyWheelPrizeRedemptionQrCodeResponse = IDL.Variant({
+    'ok' : IDL.Text,
+    'err' : Err,
+  });
+  const GetWheelPrizeDrawProofRequest = IDL.Record({
+    'wheel_prize_extraction_id' : IDL.Text,
+  });
+  const WheelPrizeDrawCandidate = IDL.Record({
+    'weight' : IDL.Nat32,
+    'wheel_asset_id' : IDL.Text,
+  });
+  const WheelPrizeExcludedDraw = IDL.Record({
+    'chosen_index' : IDL.Nat32,
+    'candidates' : IDL.Vec(WheelPrizeDrawCandidate),
+  });
+  const WheelPrizeDrawProof = IDL.Record({
+    'chosen_index' : IDL.Nat32,
+    'seed' : IDL.Vec(IDL.Nat8),
+    'excluded_draws' : IDL.Vec(WheelPrizeExcludedDraw),
+    'candidates' : IDL.Vec(WheelPrizeDrawCandidate),
+  });
+  const GetWheelPrizeDrawProofResponse = IDL.Variant({
+    'ok' : WheelPrizeDrawProof,
+    'err' : Err,
+  });
   const GetWheelPrizeExtractionRequest = IDL.Record({
     'wheel_prize_extraction_id' : IDL.Text,
   });
@@ -152,6 +425,27 @@ export const idlFactory = ({ IDL }) => {
     'ok' : WheelPrizeExtraction,
     'err' : Err,
   });
+  const WheelPrizeExtractionPolicy = IDL.Record({
+    'updated_at' : IDL.Text,
+    'max_wins_per_principal' : IDL.Opt(IDL.Nat32),
+    'allowed_principals' : IDL.Opt(IDL.Vec(IDL.Principal)),
+    'self_spin_max_failed_attempts' : IDL.Nat32,
+    'max_token_price_age_seconds' : IDL.Nat64,
+    'invite_code_required' : IDL.Bool,
+    'spin_cooldown_seconds' : IDL.Nat64,
+    'denied_principals' : IDL.Vec(IDL.Principal),
+    'max_wins_per_principal_per_day' : IDL.Opt(IDL.Nat32),
+    'failed_spin_cooldown_seconds' : IDL.Nat64,
+    'self_spin_enabled' : IDL.Bool,
+    'max_wins_per_day' : IDL.Opt(IDL.Nat32),
+  });
+  const GetWheelPrizeExtractionPolicyResponse = IDL.Variant({
+    'ok' : WheelPrizeExtractionPolicy,
+    'err' : Err,
+  });
+  const GetWheelPrizeExtractionsStatsRequest = IDL.Record({
+    'event_id' : IDL.Opt(IDL.Text),
+  });
   const WheelPrizeExtractionsStats = IDL.Record({
     'total_completed_extractions' : IDL.Nat32,
     'total_spent_usd' : IDL.Float64,
@@ -160,6 +454,14 @@ export const idlFactory = ({ IDL }) => {
     'ok' : WheelPrizeExtractionsStats,
     'err' : Err,
   });
+  const HandOverWheelPrizeGadgetRequest = IDL.Record({
+    'wheel_prize_extraction_id' : IDL.Text,
+    'gadget_variant_name' : IDL.Opt(IDL.Text),
+  });
+  const HandOverWheelPrizeGadgetResponse = IDL.Variant({
+    'ok' : IDL.Null,
+    'err' : Err,
+  });
   const HeaderField = IDL.Tuple(IDL.Text, IDL.Text);
   const HttpRequest = IDL.Record({
     'url' : IDL.Text,
@@ -177,6 +479,109 @@ export const idlFactory = ({ IDL }) => {
     'ok' : IDL.Vec(CustomDomainRecord),
     'err' : Err,
   });
+  const ListEventsResponse = IDL.Variant({
+    'ok' : IDL.Vec(Event),
+    'err' : Err,
+  });
+  const InviteCodeState = IDL.Variant({
+    'revoked' : IDL.Record({
+      'revoked_at' : IDL.Text,
+      'revoked_by_user_id' : IDL.Text,
+    }),
+    'used' : IDL.Record({
+      'principal' : IDL.Principal,
+      'wheel_prize_extraction_id' : IDL.Text,
+      'used_at' : IDL.Text,
+    }),
+    'available' : IDL.Null,
+  });
+  const InviteCode = IDL.Record({
+    'id' : IDL.Text,
+    'updated_at' : IDL.Text,
+    'principal' : IDL.Opt(IDL.Principal),
+    'created_at' : IDL.Text,
+    'state' : InviteCodeState,
+    'created_by_user_id' : IDL.Text,
+    'event_id' : IDL.Opt(IDL.Text),
+    'purpose' : IDL.Opt(InviteCodePurpose),
+  });
+  const ListInviteCodesResponse = IDL.Variant({
+    'ok' : IDL.Vec(InviteCode),
+    'err' : Err,
+  });
+  const ListLedgerTransfersRequest = IDL.Record({
+    'principal' : IDL.Opt(IDL.Principal),
+    'wheel_prize_extraction_id' : IDL.Opt(IDL.Text),
+    'start_after' : IDL.Opt(IDL.Text),
+    'limit' : IDL.Opt(IDL.Nat32),
+    'ledger_canister_id' : IDL.Opt(IDL.Principal),
+  });
+  const LedgerTransfer = IDL.Record({
+    'id' : IDL.Text,
+    'to' : Account,
+    'fee' : IDL.Opt(IDL.Nat),
+    'wheel_prize_extraction_id' : IDL.Opt(IDL.Text),
+    'block_index' : IDL.Nat,
+    'from' : Account,
+    'memo' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'initiated_by_user_id' : IDL.Opt(IDL.Text),
+    'created_at' : IDL.Text,
+    'ledger_canister_id' : IDL.Principal,
+    'wheel_asset_id' : IDL.Opt(IDL.Text),
+    'amount' : IDL.Nat,
+  });
+  const LedgerTransfersPage = IDL.Record({
+    'transfers' : IDL.Vec(LedgerTransfer),
+    'next_start_after' : IDL.Opt(IDL.Text),
+  });
+  const ListLedgerTransfersResponse = IDL.Variant({
+    'ok' : LedgerTransfersPage,
+    'err' : Err,
+  });
+  const ManualTransferLimit = IDL.Record({
+    'updated_at' : IDL.Text,
+    'approval_threshold' : IDL.Opt(IDL.Nat),
+    'ledger_canister_id' : IDL.Principal,
+    'daily_limit' : IDL.Opt(IDL.Nat),
+  });
+  const ListManualTransferLimitsResponse = IDL.Variant({
+    'ok' : IDL.Vec(ManualTransferLimit),
+    'err' : Err,
+  });
+  const ManualTransferState = IDL.Variant({
+    'completed' : IDL.Record({ 'block_index' : IDL.Nat }),
+    'pending_approval' : IDL.Null,
+    'denied' : IDL.Record({ 'error' : Err }),
+    'rejected' : IDL.Record({ 'rejected_by_user_id' : IDL.Text }),
+    'processing' : IDL.Null,
+    'failed' : IDL.Record({ 'error' : Err }),
+  });
+  const ManualTransfer = IDL.Record({
+    'id' : IDL.Text,
+    'to' : IDL.Principal,
+    'updated_at' : IDL.Text,
+    'to_subaccount' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'requested_by_user_id' : IDL.Text,
+    'approved_by_user_id' : IDL.Opt(IDL.Text),
+    'created_at' : IDL.Text,
+    'from_subaccount' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'state' : ManualTransferState,
+    'ledger_canister_id' : IDL.Principal,
+    'created_at_time' : IDL.Opt(IDL.Nat64),
+    'amount' : IDL.Nat,
+    'reason' : IDL.Text,
+  });
+  const ListManualTransfersResponse = IDL.Variant({
+    'ok' : IDL.Vec(ManualTransfer),
+    'err' : Err,
+  });
+  const ListUnclaimedWheelPrizeGadgetsRequest = IDL.Record({
+    'event_id' : IDL.Opt(IDL.Text),
+  });
+  const ListUnclaimedWheelPrizeGadgetsResponse = IDL.Variant({
+    'ok' : IDL.Vec(WheelPrizeExtraction),
+    'err' : Err,
+  });
   const ListUsersResponse = IDL.Variant({
     'ok' : IDL.Vec(UserProfile),
     'err' : Err,
@@ -192,10 +597,34 @@ export const idlFactory = ({ IDL }) => {
     'ok' : IDL.Vec(WheelPrizeExtraction),
     'err' : Err,
   });
+  const WheelPrizeExtractionStateFilter = IDL.Variant({
+    'completed' : IDL.Null,
+    'partially_completed' : IDL.Null,
+    'processing' : IDL.Null,
+    'failed' : IDL.Null,
+  });
+  const ListWheelPrizeExtractionsPageRequest = IDL.Record({
+    'start_after' : IDL.Opt(IDL.Text),
+    'limit' : IDL.Opt(IDL.Nat32),
+    'state' : IDL.Opt(WheelPrizeExtractionStateFilter),
+    'extracted_by_user_id' : IDL.Opt(IDL.Text),
+    'wheel_asset_id' : IDL.Opt(IDL.Text),
+    'created_before' : IDL.Opt(IDL.Text),
+    'created_from' : IDL.Opt(IDL.Text),
+  });
+  const WheelPrizeExtractionsPage = IDL.Record({
+    'wheel_prize_extractions' : IDL.Vec(WheelPrizeExtraction),
+    'next_start_after' : IDL.Opt(IDL.Text),
+  });
+  const ListWheelPrizeExtractionsPageResponse = IDL.Variant({
+    'ok' : WheelPrizeExtractionsPage,
+    'err' : Err,
+  });
   const WheelPrize = IDL.Record({
     'name' : IDL.Text,
     'wheel_image_path' : IDL.Opt(IDL.Text),
     'prize_usd_amount' : IDL.Opt(IDL.Float64),
+    'draw_probability' : IDL.Float64,
     'wheel_ui_settings' : WheelAssetUiSettings,
     'wheel_asset_id' : IDL.Text,
     'modal_image_path' : IDL.Opt(IDL.Text),
@@ -204,16 +633,78 @@ export const idlFactory = ({ IDL }) => {
     'ok' : IDL.Vec(WheelPrize),
     'err' : Err,
   });
+  const RedeemPrizeRequest = IDL.Record({
+    'wheel_prize_extraction_id' : IDL.Text,
+    'redemption_code' : IDL.Text,
+    'gadget_variant_name' : IDL.Opt(IDL.Text),
+  });
+  const RedeemPrizeResponse = IDL.Variant({ 'ok' : IDL.Null, 'err' : Err });
+  const RejectManualTransferRequest = IDL.Record({
+    'manual_transfer_id' : IDL.Text,
+  });
+  const RejectManualTransferResponse = IDL.Variant({
+    'ok' : IDL.Null,
+    'err' : Err,
+  });
+  const RetireWheelAssetRequest = IDL.Record({
+    'id' : IDL.Text,
+    'to' : Account,
+  });
+  const RetireWheelAssetResponse = IDL.Variant({
+    'ok' : IDL.Record({ 'block_index' : IDL.Opt(IDL.Nat) }),
+    'err' : Err,
+  });
+  const RetryWheelPrizeExtractionPayoutsRequest = IDL.Record({
+    'wheel_prize_extraction_id' : IDL.Text,
+  });
+  const RetryWheelPrizeExtractionPayoutsResponse = IDL.Variant({
+    'ok' : IDL.Null,
+    'err' : Err,
+  });
+  const RevokeInviteCodeRequest = IDL.Record({ 'invite_code_id' : IDL.Text });
+  const RevokeInviteCodeResponse = IDL.Variant({
+    'ok' : IDL.Null,
+    'err' : Err,
+  });
   const SetDefaultWheelAssetsResponse = IDL.Variant({
     'ok' : IDL.Null,
     'err' : Err,
   });
+  const SetEventSelfSpinCodeRequest = IDL.Record({
+    'self_spin_code' : IDL.Opt(IDL.Text),
+    'max_failed_attempts' : IDL.Opt(IDL.Nat32),
+    'max_spins' : IDL.Opt(IDL.Nat32),
+    'event_id' : IDL.Text,
+  });
+  const SetEventSelfSpinCodeResponse = IDL.Variant({
+    'ok' : IDL.Null,
+    'err' : Err,
+  });
+  const SetManualTransferLimitRequest = IDL.Record({
+    'approval_threshold' : IDL.Opt(IDL.Nat),
+    'ledger_canister_id' : IDL.Principal,
+    'daily_limit' : IDL.Opt(IDL.Nat),
+  });
+  const SetManualTransferLimitResponse = IDL.Variant({
+    'ok' : ManualTransferLimit,
+    'err' : Err,
+  });
   const TransferTokenRequest = IDL.Record({
-    'to' : IDL.Principal,
+    'to' : IDL.Opt(IDL.Principal),
+    'from_subaccount' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'to_account' : IDL.Opt(RecipientAccount),
     'ledger_canister_id' : IDL.Principal,
     'amount' : IDL.Nat,
+    'reason' : IDL.Opt(IDL.Text),
+  });
+  const TransferTokenOutcome = IDL.Variant({
+    'completed' : IDL.Record({ 'block_index' : IDL.Nat }),
+    'pending_approval' : IDL.Record({ 'manual_transfer_id' : IDL.Text }),
+  });
+  const TransferTokenResponse = IDL.Variant({
+    'ok' : TransferTokenOutcome,
+    'err' : Err,
   });
-  const TransferTokenResponse = IDL.Variant({ 'ok' : IDL.Nat, 'err' : Err });
   const UpdateCustomDomainRecordRequest = IDL.Record({
     'id' : IDL.Text,
     'bn_registration_state' : CustomDomainRecordBnRegistrationState,
@@ -222,6 +713,14 @@ export const idlFactory = ({ IDL }) => {
     'ok' : IDL.Null,
     'err' : Err,
   });
+  const UpdateEventRequest = IDL.Record({
+    'id' : IDL.Text,
+    'active' : IDL.Opt(IDL.Bool),
+    'name' : IDL.Opt(IDL.Text),
+    'end_date' : IDL.Opt(IDL.Text),
+    'start_date' : IDL.Opt(IDL.Text),
+  });
+  const UpdateEventResponse = IDL.Variant({ 'ok' : IDL.Null, 'err' : Err });
   const UpdateMyUserProfileRequest = IDL.Record({
     'username' : IDL.Opt(IDL.Text),
   });
@@ -239,12 +738,17 @@ export const idlFactory = ({ IDL }) => {
   });
   const UpdateWheelAssetTypeConfig = IDL.Variant({
     'token' : IDL.Record({
+      'funding_source' : IDL.Opt(WheelAssetTokenFundingSource),
       'exchange_rate_symbol' : IDL.Opt(IDL.Text),
       'prize_usd_amount' : IDL.Opt(IDL.Float64),
+      'prize_token_amount' : IDL.Opt(IDL.Nat),
       'ledger_config' : IDL.Opt(UpdateWheelAssetTypeLedgerConfig),
     }),
     'jackpot' : IDL.Record({ 'wheel_asset_ids' : IDL.Vec(IDL.Text) }),
-    'gadget' : IDL.Record({ 'article_type' : IDL.Opt(IDL.Text) }),
+    'gadget' : IDL.Record({
+      'variants' : IDL.Opt(IDL.Vec(WheelAssetGadgetVariantConfig)),
+      'article_type' : IDL.Opt(IDL.Text),
+    }),
   });
   const UpdateWheelAssetRequest = IDL.Record({
     'id' : IDL.Text,
@@ -254,6 +758,7 @@ export const idlFactory = ({ IDL }) => {
     'name' : IDL.Opt(IDL.Text),
     'state' : IDL.Opt(WheelAssetState),
     'wheel_ui_settings' : IDL.Opt(WheelAssetUiSettings),
+    'draw_weight' : IDL.Opt(WheelAssetDrawWeight),
   });
   const UpdateWheelAssetResponse = IDL.Variant({
     'ok' : IDL.Null,
@@ -277,6 +782,23 @@ export const idlFactory = ({ IDL }) => {
     'ok' : IDL.Null,
     'err' : Err,
   });
+  const UpdateWheelPrizeExtractionPolicyRequest = IDL.Record({
+    'max_wins_per_principal' : IDL.Opt(IDL.Nat32),
+    'allowed_principals' : IDL.Opt(IDL.Vec(IDL.Principal)),
+    'self_spin_max_failed_attempts' : IDL.Opt(IDL.Nat32),
+    'max_token_price_age_seconds' : IDL.Opt(IDL.Nat64),
+    'invite_code_required' : IDL.Opt(IDL.Bool),
+    'spin_cooldown_seconds' : IDL.Nat64,
+    'denied_principals' : IDL.Vec(IDL.Principal),
+    'max_wins_per_principal_per_day' : IDL.Opt(IDL.Nat32),
+    'failed_spin_cooldown_seconds' : IDL.Nat64,
+    'self_spin_enabled' : IDL.Opt(IDL.Bool),
+    'max_wins_per_day' : IDL.Opt(IDL.Nat32),
+  });
+  const UpdateWheelPrizeExtractionPolicyResponse = IDL.Variant({
+    'ok' : IDL.Null,
+    'err' : Err,
+  });
   const UpdateWheelPrizesOrderRequest = IDL.Record({
     'wheel_asset_ids' : IDL.Vec(IDL.Text),
   });
@@ -285,12 +807,28 @@ export const idlFactory = ({ IDL }) => {
     'err' : Err,
   });
   return IDL.Service({
+    'approve_manual_transfer' : IDL.Func(
+        [ApproveManualTransferRequest],
+        [ApproveManualTransferResponse],
+        [],
+      ),
     'create_custom_domain_record' : IDL.Func(
         [CreateCustomDomainRecordRequest],
         [CreateCustomDomainRecordResponse],
         [],
       ),
+    'create_event' : IDL.Func([CreateEventRequest], [CreateEventResponse], []),
     'create_my_user_profile' : IDL.Func([], [CreateMyUserProfileResponse], []),
+    'create_my_wheel_prize_extraction' : IDL.Func(
+        [CreateMyWheelPrizeExtractionRequest],
+        [CreateMyWheelPrizeExtractionResponse],
+        [],
+      ),
+    'create_self_spin_invite' : IDL.Func(
+        [CreateSelfSpinInviteRequest],
+        [CreateSelfSpinInviteResponse],
+        [],
+      ),
     'create_wheel_asset' : IDL.Func(
         [CreateWheelAssetRequest],
         [CreateWheelAssetResponse],
@@ -306,6 +844,7 @@ export const idlFactory = ({ IDL }) => {
         [DeleteCustomDomainRecordResponse],
         [],
       ),
+    'delete_event' : IDL.Func([DeleteEventRequest], [DeleteEventResponse], []),
     'delete_user_profile' : IDL.Func(
         [DeleteUserProfileRequest],
         [DeleteUserProfileResponse],
@@ -316,29 +855,91 @@ export const idlFactory = ({ IDL }) => {
         [DeleteWheelAssetResponse],
         [],
       ),
+    'export_invite_codes' : IDL.Func(
+        [ListInviteCodesRequest],
+        [ExportInviteCodesResponse],
+        ['query'],
+      ),
     'fetch_tokens_data' : IDL.Func([], [FetchTokensDataResponse], []),
+    'generate_invite_codes' : IDL.Func(
+        [GenerateInviteCodesRequest],
+        [GenerateInviteCodesResponse],
+        [],
+      ),
+    'get_active_event' : IDL.Func([], [GetActiveEventResponse], ['query']),
     'get_last_wheel_prize_extraction' : IDL.Func(
         [],
         [GetLastWheelPrizeExtractionResponse],
         ['query'],
       ),
     'get_my_user_profile' : IDL.Func([], [GetMyUserProfileResponse], ['query']),
+    'get_my_wheel_prize_extractions' : IDL.Func(
+        [],
+        [GetMyWheelPrizeExtractionsResponse],
+        ['query'],
+      ),
+    'get_my_wheel_prize_redemption_qr_code' : IDL.Func(
+        [GetMyWheelPrizeRedemptionQrCodeRequest],
+        [GetMyWheelPrizeRedemptionQrCodeResponse],
+        ['query'],
+      ),
+    'get_wheel_prize_draw_proof' : IDL.Func(
+        [GetWheelPrizeDrawProofRequest],
+        [GetWheelPrizeDrawProofResponse],
+        ['query'],
+      ),
     'get_wheel_prize_extraction' : IDL.Func(
         [GetWheelPrizeExtractionRequest],
         [GetWheelPrizeExtractionResponse],
         ['query'],
       ),
-    'get_wheel_prize_extractions_stats' : IDL.Func(
+    'get_wheel_prize_extraction_policy' : IDL.Func(
         [],
+        [GetWheelPrizeExtractionPolicyResponse],
+        ['query'],
+      ),
+    'get_wheel_prize_extractions_stats' : IDL.Func(
+        [IDL.Opt(GetWheelPrizeExtractionsStatsRequest)],
         [GetWheelPrizeExtractionsStatsResponse],
         ['query'],
       ),
+    'hand_over_wheel_prize_gadget' : IDL.Func(
+        [HandOverWheelPrizeGadgetRequest],
+        [HandOverWheelPrizeGadgetResponse],
+        [],
+      ),
     'http_request' : IDL.Func([HttpRequest], [HttpResponse], ['query']),
     'list_custom_domain_records' : IDL.Func(
         [],
         [ListCustomDomainRecordsResponse],
         ['query'],
       ),
+    'list_events' : IDL.Func([], [ListEventsResponse], ['query']),
+    'list_invite_codes' : IDL.Func(
+        [ListInviteCodesRequest],
+        [ListInviteCodesResponse],
+        ['query'],
+      ),
+    'list_ledger_transfers' : IDL.Func(
+        [ListLedgerTransfersRequest],
+        [ListLedgerTransfersResponse],
+        ['query'],
+      ),
+    'list_manual_transfer_limits' : IDL.Func(
+        [],
+        [ListManualTransferLimitsResponse],
+        ['query'],
+      ),
+    'list_manual_transfers' : IDL.Func(
+        [],
+        [ListManualTransfersResponse],
+        ['query'],
+      ),
+    'list_unclaimed_wheel_prize_gadgets' : IDL.Func(
+        [ListUnclaimedWheelPrizeGadgetsRequest],
+        [ListUnclaimedWheelPrizeGadgetsResponse],
+        ['query'],
+      ),
     'list_users' : IDL.Func([], [ListUsersResponse], ['query']),
     'list_wheel_assets' : IDL.Func(
         [ListWheelAssetsRequest],
@@ -350,12 +951,48 @@ export const idlFactory = ({ IDL }) => {
         [ListWheelPrizeExtractionsResponse],
         ['query'],
       ),
+    'list_wheel_prize_extractions_page' : IDL.Func(
+        [ListWheelPrizeExtractionsPageRequest],
+        [ListWheelPrizeExtractionsPageResponse],
+        ['query'],
+      ),
     'list_wheel_prizes' : IDL.Func([], [ListWheelPrizesResponse], ['query']),
+    'redeem_prize' : IDL.Func([RedeemPrizeRequest], [RedeemPrizeResponse], []),
+    'reject_manual_transfer' : IDL.Func(
+        [RejectManualTransferRequest],
+        [RejectManualTransferResponse],
+        [],
+      ),
+    'retire_wheel_asset' : IDL.Func(
+        [RetireWheelAssetRequest],
+        [RetireWheelAssetResponse],
+        [],
+      ),
+    'retry_wheel_prize_extraction_payouts' : IDL.Func(
+        [RetryWheelPrizeExtractionPayoutsRequest],
+        [RetryWheelPrizeExtractionPayoutsResponse],
+        [],
+      ),
+    'revoke_invite_code' : IDL.Func(
+        [RevokeInviteCodeRequest],
+        [RevokeInviteCodeResponse],
+        [],
+      ),
     'set_default_wheel_assets' : IDL.Func(
         [],
         [SetDefaultWheelAssetsResponse],
         [],
       ),
+    'set_event_self_spin_code' : IDL.Func(
+        [SetEventSelfSpinCodeRequest],
+        [SetEventSelfSpinCodeResponse],
+        [],
+      ),
+    'set_manual_transfer_limit' : IDL.Func(
+        [SetManualTransferLimitRequest],
+        [SetManualTransferLimitResponse],
+        [],
+      ),
     'transfer_token' : IDL.Func(
         [TransferTokenRequest],
         [TransferTokenResponse],
@@ -366,6 +1003,7 @@ export const idlFactory = ({ IDL }) => {
         [UpdateCustomDomainRecordResponse],
         [],
       ),
+    'update_event' : IDL.Func([UpdateEventRequest], [UpdateEventResponse], []),
     'update_my_user_profile' : IDL.Func(
         [UpdateMyUserProfileRequest],
         [UpdateMyUserProfileResponse],
@@ -386,6 +1024,11 @@ export const idlFactory = ({ IDL }) => {
         [UpdateWheelAssetImageResponse],
         [],
       ),
+    'update_wheel_prize_extraction_policy' : IDL.Func(
+        [UpdateWheelPrizeExtractionPolicyRequest],
+        [UpdateWheelPrizeExtractionPolicyResponse],
+        [],
+      ),
     'update_wheel_prizes_order' : IDL.Func(
         [UpdateWheelPrizesOrderRequest],
         [UpdateWheelPrizesOrderResponse],
@@ -2,17 +2,26 @@ import { useMutation } from '@tanstack/react-query';
 import { Principal } from '@icp-sdk/core/principal';
 import { useAuth } from '@/hooks/use-auth';
 import { extractOk } from '@/lib/api';
+import { candidOpt } from '@/lib/utils';
 import { useEffect, useRef } from 'react';
 
 const EXTRACTION_RESULT_RESET_TIMEOUT_MS = 20_000;
 
+type CreateWheelPrizeExtractionParams = {
+  principal: Principal;
+  inviteCode?: string;
+};
+
 export function useCreateWheelPrizeExtraction() {
   const { actor } = useAuth();
   const isExtractingRef = useRef(false);
   const resetTimeoutRef = useRef<NodeJS.Timeout | null>(null);
 
   const mutation = useMutation({
-    mutationFn: async (principal: Principal) => {
+    mutationFn: async ({
+      principal,
+      inviteCode,
+    }: CreateWheelPrizeExtractionParams) => {
       if (isExtractingRef.current) {
         return;
       }
@@ -20,7 +29,9 @@ export function useCreateWheelPrizeExtraction() {
       isExtractingRef.current = true;
 
       const result = await actor.create_wheel_prize_extraction({
-        extract_for_principal: principal,
+        extract_for_principal: [principal],
+        extract_for_account: [],
+        invite_code: candidOpt(inviteCode || null),
       });
       return extractOk(result);
     },
@@ -15,27 +15,17 @@ export const useWheelPrizesWithProbability = <T extends WheelPrize>({
   prizes,
 }: UseWheelPrizesProbabilityParams<T>): UseWheelPrizesProbabilityReturn =>
   useMemo(() => {
+    // the same prize can appear multiple times on the wheel,
+    // but its draw probability is computed by the backend for the prize itself
     const prizesWithProbability = prizes
-      .reduce(
-        (acc, prize) => {
-          const prizeCount = acc.prizesCount.get(prize.wheel_asset_id) || 0;
-          const newCount = prizeCount + 1;
-          acc.prizesWithProbability.set(prize.wheel_asset_id, {
-            ...prize,
-            drawProbability: newCount / prizes.length,
-          });
-          acc.prizesCount.set(prize.wheel_asset_id, newCount);
-          return acc;
-        },
-        { prizesWithProbability: new Map(), prizesCount: new Map() } as {
-          prizesWithProbability: Map<
-            string,
-            WheelPrize & { drawProbability: number }
-          >;
-          prizesCount: Map<string, number>;
-        },
-      )
-      .prizesWithProbability.values();
+      .reduce((acc, prize) => {
+        acc.set(prize.wheel_asset_id, {
+          ...prize,
+          drawProbability: prize.draw_probability,
+        });
+        return acc;
+      }, new Map<string, WheelPrize & { drawProbability: number }>())
+      .values();
 
     return Array.from(prizesWithProbability).sort(
       (a, b) => b.drawProbability - a.drawProbability,
@@ -2,6 +2,8 @@ import { createFileRoute } from '@tanstack/react-router';
 import { PageLayout } from '@/components/layouts';
 import { Alert, AlertDescription, AlertTitle } from '@/components/ui/alert';
 import { Card, CardContent } from '@/components/ui/card';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
 import { Principal } from '@icp-sdk/core/principal';
 import { Scanner } from '@yudiel/react-qr-scanner';
 import { Volume2 } from 'lucide-react';
@@ -14,6 +16,7 @@ export const Route = createFileRoute('/(authenticated)/admin/scanner')({
 
 function RouteComponent() {
   const [scanError, setScanError] = useState<string | null>(null);
+  const [inviteCode, setInviteCode] = useState('');
   const createWheelPrizeExtractionMutation = useCreateWheelPrizeExtraction();
 
   const handleScan: React.ComponentProps<typeof Scanner>['onScan'] =
@@ -29,16 +32,37 @@ function RouteComponent() {
             setScanError('Invalid principal');
             return;
           }
-          await createWheelPrizeExtractionMutation.mutateAsync(principal);
+          const result = await createWheelPrizeExtractionMutation.mutateAsync({
+            principal,
+            inviteCode: inviteCode.trim(),
+          });
+          // the invite code is consumed by the extraction,
+          // unless the scan is ignored because another extraction is running
+          if (result !== undefined) {
+            setInviteCode('');
+          }
         }
       },
-      [createWheelPrizeExtractionMutation],
+      [createWheelPrizeExtractionMutation, inviteCode],
     );
 
   return (
     <PageLayout>
       <Card className="col-span-full md:col-span-8 md:col-start-3">
         <CardContent className="flex flex-col items-center gap-2 p-3 md:p-6">
+          <div className="w-full space-y-2">
+            <Label htmlFor="invite-code">Invite code</Label>
+            <Input
+              id="invite-code"
+              placeholder="The invite code of the winner"
+              value={inviteCode}
+              onChange={e => setInviteCode(e.target.value)}
+            />
+            <p className="text-muted-foreground text-sm">
+              Required if the extraction policy requires invite codes. Enter it
+              before scanning the winner&apos;s QR code.
+            </p>
+          </div>
           <Scanner
             allowMultiple
             constraints={{